@@ -9,9 +9,7 @@ fn concat (
     snd: char_p::Ref<'_>,
 ) -> char_p::Box
 {
-    format!("{}{}\0", fst.to_str(), snd.to_str())
-        .try_into()
-        .unwrap()
+    char_p::new(format_args!("{}{}", fst.to_str(), snd.to_str()))
 }
 
 /// Frees a string created by `concat`.
@@ -93,10 +91,11 @@ fn generate_headers ()
     if ::std::env::var("HEADERS_TO_STDOUT").ok().map_or(false, |it| it == "1") {
         builder
             .to_writer(::std::io::stdout())
-            .generate()
+            .generate()?;
     } else {
         builder
             .to_file(&"generated.h".to_string())?
-            .generate()
+            .generate()?;
     }
+    Ok(())
 }
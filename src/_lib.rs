@@ -56,10 +56,11 @@ cfg_proc_macros! {
 #[path = "layout/_mod.rs"]
 pub mod layout;
 
-__cfg_headers__! {
-    #[doc(hidden)] pub
-    use ::inventory;
+#[cfg(any(feature = "headers", feature = "profile-ffi"))]
+#[doc(hidden)] pub
+use ::inventory;
 
+__cfg_headers__! {
     #[cfg_attr(feature = "nightly",
         doc(cfg(feature = "headers")),
     )]
@@ -92,23 +93,88 @@ use self::c_char_module::c_char;
 #[path = "c_char.rs"]
 mod c_char_module;
 
+pub
+mod atomic_flag;
+
+pub
+mod bounded;
+
+pub
+mod boundary;
+
 pub
 mod char_p;
 
 pub
 mod closure;
 
+cfg_std! {
+    pub
+    mod events;
+
+    pub
+    mod fatal;
+
+    pub
+    mod generator;
+
+    pub
+    mod intern;
+
+    pub
+    mod pool;
+}
+
+pub
+mod endian;
+
+pub
+mod features;
+
+#[doc(inline)]
+pub use error::Error;
+mod error;
+
+pub
+mod float;
+
+pub
+mod fn_ptr;
+
+#[cfg(feature = "hot-reload")]
+#[cfg_attr(all(docs, feature = "nightly"),
+    doc(cfg(feature = "hot-reload"))
+)]
+pub
+mod hot_reload;
+
 const _: () = {
     #[path = "ffi_export.rs"]
     mod ffi_export;
 };
 
+#[cfg(feature = "profile-ffi")]
+#[cfg_attr(all(docs, feature = "nightly"),
+    doc(cfg(feature = "profile-ffi"))
+)]
+pub
+mod profile;
+
 pub
 mod ptr;
 
+pub
+mod realtime;
+
+pub
+mod simd;
+
 pub
 mod slice;
 
+pub
+mod progress;
+
 #[path = "string/_mod.rs"]
 pub
 mod string;
@@ -117,6 +183,23 @@ mod string;
 pub
 use tuple::*;
 
+cfg_std! {
+    pub
+    mod os_str;
+
+    pub
+    mod stream;
+
+    pub
+    mod sync;
+
+    pub
+    mod timeout;
+
+    pub
+    mod triple_buffer;
+}
+
 pub
 mod tuple;
 
@@ -154,6 +237,17 @@ cfg_std! {
 #[doc(hidden)] pub
 struct NotZeroSized;
 
+/// `#[ffi_export(used)]`: the address of an exported function, stored in a
+/// `#[used]` static.
+#[allow(missing_debug_implementations)]
+#[doc(hidden)] pub
+struct __Used(pub *const ());
+
+unsafe // Safety: the address is never dereferenced.
+impl Sync
+    for __Used
+{}
+
 pub
 mod prelude {
     #[doc(no_inline)]
@@ -190,12 +284,26 @@ mod prelude {
             pub use crate::slice::slice_boxed as Box;
         }
     }
+    cfg_std! {
+        pub
+        mod os_str {
+            #[doc(no_inline)]
+            pub use crate::os_str::os_str_ref as Ref;
+        }
+        pub
+        mod os_string {
+            #[doc(no_inline)]
+            pub use crate::os_str::os_str_boxed as Box;
+        }
+    }
     pub
     mod repr_c {
+        #[doc(no_inline)]
+        pub use crate::fn_ptr::{CatchingFnPtr, FnPtr};
         cfg_alloc! {
             #[doc(no_inline)]
             pub use crate::{
-                boxed::Box,
+                boxed::{Box, PinBox},
                 string::String,
                 vec::Vec,
             };
@@ -260,10 +368,22 @@ struct __PanicOnDrop__; impl Drop for __PanicOnDrop__ {
     }
 }
 
+/// Called by `__abort_with_msg__!` right before aborting: see
+/// [`fatal`][`crate::fatal`].
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __on_fatal__ (message: ::core::fmt::Arguments<'_>)
+{
+    #[cfg(feature = "std")]
+    fatal::on_fatal(&message.to_string());
+    #[cfg(not(feature = "std"))]
+    let _ = message;
+}
+
 #[cfg(feature = "log")]
 #[doc(hidden)] /** Not part of the public API **/ #[macro_export]
 macro_rules! __abort_with_msg__ { ($($tt:tt)*) => ({
     $crate::log::error!($($tt)*);
+    $crate::__on_fatal__($crate::core::format_args!($($tt)*));
     let _panic_on_drop = $crate::__PanicOnDrop__;
     $crate::core::panic!($($tt)*);
 })}
@@ -274,6 +394,7 @@ macro_rules! __abort_with_msg__ { ($($tt:tt)*) => ({
 #[doc(hidden)] /** Not part of the public API **/ #[macro_export]
 macro_rules! __abort_with_msg__ { ($($tt:tt)*) => ({
     $crate::std::eprintln!($($tt)*);
+    $crate::__on_fatal__($crate::core::format_args!($($tt)*));
     $crate::std::process::abort();
 })}
 #[cfg(all(
@@ -0,0 +1,227 @@
+//! Boolean flag shared between Rust and C, such as a "request stop" flag
+//! polled by a C loop.
+//!
+//! Signaling through a hand-rolled `volatile int` is a data race: `volatile`
+//! neither makes the accesses atomic nor orders them with respect to the
+//! other memory accesses. An [`FfiAtomicFlag`] is opaque to C, which
+//! accesses it through exported functions with documented memory orderings:
+//!
+//!   - setting (or clearing) the flag is a _release_ operation,
+//!
+//!   - testing the flag is an _acquire_ operation,
+//!
+//! so that whatever was written before setting the flag is visible to
+//! whoever sees it set.
+//!
+//! The `new / set / clear / test / free` functions are generated with the
+//! [`ffi_export_atomic_flag!`] macro.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ::safer_ffi::{prelude::*, atomic_flag::FfiAtomicFlag};
+//!
+//! ::safer_ffi::ffi_export_atomic_flag! {
+//!     new: stop_flag_new,
+//!     set: stop_flag_set,
+//!     clear: stop_flag_clear,
+//!     test: stop_flag_test,
+//!     free: stop_flag_free,
+//! }
+//!
+//! #[ffi_export]
+//! fn run (stop: &'_ FfiAtomicFlag)
+//! {
+//!     while !stop.is_set() {
+//!         // ...
+//!         # break;
+//!     }
+//! }
+//!
+//! # fn main () {
+//! let stop = FfiAtomicFlag::new();
+//! stop.set();
+//! assert!(stop.is_set());
+//! # }
+//! ```
+//!
+//! which, C-wise, is used as:
+//!
+//! ```C
+//! FfiAtomicFlag_t * stop = stop_flag_new();
+//!
+//! /* Worker thread */
+//! run(stop);
+//!
+//! /* UI thread */
+//! stop_flag_set(stop);
+//! ```
+
+use_prelude!();
+use ::core::sync::atomic::{self, AtomicBool};
+
+ReprC! {
+    #[ReprC::opaque("FfiAtomicFlag")]
+    /// Boolean flag, to be shared between Rust and C, whose accesses are
+    /// atomic, and ordered: see [the module docs][`crate::atomic_flag`].
+    ///
+    /// It is an opaque type from the point of view of C.
+    pub
+    struct FfiAtomicFlag {
+        set: AtomicBool,
+    }
+}
+
+impl FfiAtomicFlag {
+    /// A new (cleared) flag.
+    pub
+    const
+    fn new ()
+      -> FfiAtomicFlag
+    {
+        FfiAtomicFlag { set: AtomicBool::new(false) }
+    }
+
+    /// Sets the flag (with _release_ ordering).
+    #[inline]
+    pub
+    fn set (self: &'_ Self)
+    {
+        self.set.store(true, atomic::Ordering::Release)
+    }
+
+    /// Clears the flag (with _release_ ordering).
+    #[inline]
+    pub
+    fn clear (self: &'_ Self)
+    {
+        self.set.store(false, atomic::Ordering::Release)
+    }
+
+    /// Whether the flag is set (with _acquire_ ordering).
+    #[inline]
+    pub
+    fn is_set (self: &'_ Self)
+      -> bool
+    {
+        self.set.load(atomic::Ordering::Acquire)
+    }
+}
+
+impl Default
+    for FfiAtomicFlag
+{
+    #[inline]
+    fn default ()
+      -> FfiAtomicFlag
+    {
+        FfiAtomicFlag::new()
+    }
+}
+
+impl fmt::Debug
+    for FfiAtomicFlag
+{
+    fn fmt (self: &'_ Self, fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        fmt .debug_struct("FfiAtomicFlag")
+            .field("set", &self.is_set())
+            .finish()
+    }
+}
+
+/// Exports the `new`, `set`, `clear`, `test` and `free` functions of an
+/// [`FfiAtomicFlag`][`crate::atomic_flag::FfiAtomicFlag`].
+///
+/// ```rust,ignore
+/// ::safer_ffi::ffi_export_atomic_flag! {
+///     new: stop_flag_new,
+///     set: stop_flag_set,
+///     clear: stop_flag_clear,
+///     test: stop_flag_test,
+///     free: stop_flag_free,
+/// }
+/// ```
+///
+/// exports:
+///
+/// ```C
+/// FfiAtomicFlag_t * stop_flag_new (void);
+///
+/// void stop_flag_set (FfiAtomicFlag_t const * flag);
+///
+/// void stop_flag_clear (FfiAtomicFlag_t const * flag);
+///
+/// bool stop_flag_test (FfiAtomicFlag_t const * flag);
+///
+/// void stop_flag_free (FfiAtomicFlag_t * flag);
+/// ```
+///
+/// whose doc comments, in the headers, spell out their memory ordering:
+/// `set` and `clear` are _release_ operations, and `test`, an _acquire_
+/// one.
+#[macro_export]
+macro_rules! ffi_export_atomic_flag {(
+    new: $new:ident,
+    set: $set:ident,
+    clear: $clear:ident,
+    test: $test:ident,
+    free: $free:ident $(,)?
+) => (
+    #[$crate::ffi_export]
+    /// Creates a (cleared) flag.
+    pub
+    fn $new ()
+      -> $crate::boxed::Box<$crate::atomic_flag::FfiAtomicFlag>
+    {
+        $crate::boxed::Box::new($crate::atomic_flag::FfiAtomicFlag::new())
+    }
+
+    #[$crate::ffi_export]
+    /// Sets the flag.
+    ///
+    /// This is a _release_ operation: the writes which happen before it
+    /// (in this thread) are visible to a thread which then sees the flag
+    /// set.
+    pub
+    fn $set (flag: &'_ $crate::atomic_flag::FfiAtomicFlag)
+    {
+        flag.set()
+    }
+
+    #[$crate::ffi_export]
+    /// Clears the flag.
+    ///
+    /// This is a _release_ operation: the writes which happen before it
+    /// (in this thread) are visible to a thread which then sees the flag
+    /// cleared.
+    pub
+    fn $clear (flag: &'_ $crate::atomic_flag::FfiAtomicFlag)
+    {
+        flag.clear()
+    }
+
+    #[$crate::ffi_export]
+    /// Whether the flag is set.
+    ///
+    /// This is an _acquire_ operation: the writes which happen before the
+    /// flag was set (resp. cleared), in the setting (resp. clearing)
+    /// thread, are visible after it.
+    pub
+    fn $test (flag: &'_ $crate::atomic_flag::FfiAtomicFlag)
+      -> bool
+    {
+        flag.is_set()
+    }
+
+    #[$crate::ffi_export]
+    /// Destroys the flag.
+    ///
+    /// No other thread may be accessing it anymore.
+    pub
+    fn $free (flag: Option<$crate::boxed::Box<$crate::atomic_flag::FfiAtomicFlag>>)
+    {
+        $crate::core::mem::drop(flag)
+    }
+)}
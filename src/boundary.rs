@@ -0,0 +1,146 @@
+//! What the `#[ffi_export]`ed functions do when given invalid arguments.
+//!
+//! The arguments of the exported functions are checked (see
+//! [`ReprC::is_valid`]) in debug builds, and, in all builds, as soon as a
+//! policy other than [`BoundaryPolicy::Abort`] has been [set][`set_policy`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use ::safer_ffi::{boundary::{self, BoundaryPolicy}, prelude::*};
+//!
+//! #[ffi_export]
+//! /// Initializes the plugin: aborting the host process on a buggy call
+//! /// would be unacceptable.
+//! fn plugin_init ()
+//! {
+//!     boundary::set_policy(BoundaryPolicy::ReturnError);
+//! }
+//! ```
+
+use_prelude!();
+use ::core::sync::atomic::{self, AtomicU8};
+
+/// What to do when an `#[ffi_export]`ed function is given an invalid
+/// argument (_e.g._, a `bool` which is neither `0` nor `1`, or an
+/// out-of-range [`Bounded`][`crate::bounded::Bounded`] integer).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub
+enum BoundaryPolicy {
+    /// Abort the process (the default).
+    Abort,
+
+    /// Log the invalid argument, and return early, without running the body
+    /// of the function, with the all-zeros value of the return type (`0`,
+    /// `NULL`, `false`, _etc._) as the error value.
+    ///
+    /// If the all-zeros value is not a valid instance of the return type
+    /// (_e.g._, a non-nullable pointer), the process is aborted.
+    ReturnError,
+
+    /// Log the invalid argument, and [clamp][`ReprC::clamp`] it into a
+    /// valid value, such as the nearest bound of a
+    /// [`Bounded`][`crate::bounded::Bounded`] integer.
+    ///
+    /// The arguments which cannot be clamped are handled as with
+    /// [`BoundaryPolicy::ReturnError`].
+    LogAndClamp,
+}
+
+static POLICY: AtomicU8 = AtomicU8::new(BoundaryPolicy::Abort as u8);
+
+/// Sets the (process-wide) [`BoundaryPolicy`], typically from the
+/// initialization function of the library.
+pub
+fn set_policy (policy: BoundaryPolicy)
+{
+    POLICY.store(policy as u8, atomic::Ordering::Relaxed);
+}
+
+/// The current [`BoundaryPolicy`].
+pub
+fn policy ()
+  -> BoundaryPolicy
+{
+    match POLICY.load(atomic::Ordering::Relaxed) {
+        | 0 => BoundaryPolicy::Abort,
+        | 1 => BoundaryPolicy::ReturnError,
+        | _ => BoundaryPolicy::LogAndClamp,
+    }
+}
+
+/// Checks the argument `arg_name` of the exported function `fname`, applying
+/// the [`BoundaryPolicy`] if it is invalid: `None` means that the
+/// function has to return [`__error_value__`] right away.
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __check_arg__<T : ReprC> (
+    fname: &'static str,
+    arg_name: &'static str,
+    mut arg: T::CLayout,
+) -> Option<T::CLayout>
+{
+    let policy = policy();
+    if (cfg!(debug_assertions) || policy != BoundaryPolicy::Abort).not()
+    || T::is_valid(&arg)
+    {
+        return Some(arg);
+    }
+    let clamped =
+        policy == BoundaryPolicy::LogAndClamp
+        && T::clamp(&mut arg)
+        && T::is_valid(&arg)
+    ;
+    match policy {
+        | BoundaryPolicy::Abort => crate::__abort_with_msg__!(
+            "Error, `{}()` was given an invalid `{}` (of type `{}`). \
+            Aborting for soundness.",
+            fname, arg_name, ::core::any::type_name::<T>(),
+        ),
+        | _ => log(format_args!(
+            "`{}()` was given an invalid `{}` (of type `{}`): {}.",
+            fname, arg_name, ::core::any::type_name::<T>(),
+            if clamped { "clamped it" } else { "returning an error" },
+        )),
+    }
+    if clamped { Some(arg) } else { None }
+}
+
+/// The error value returned by the exported function `fname` under the
+/// [`BoundaryPolicy::ReturnError`] policy: the all-zeros `Ret`, provided it
+/// is valid.
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __error_value__<Ret : ReprC> (fname: &'static str)
+  -> Ret
+{
+    if mem::size_of::<Ret>() == 0 {
+        // `-> ()`, whose `CLayout` cannot be checked.
+        return unsafe {
+            // Safety: `()` (or another such inhabited ZST).
+            mem::zeroed()
+        };
+    }
+    let zeroed: Ret::CLayout = unsafe {
+        // Safety: the `CLayout`s are C types (integers, raw pointers, and
+        // aggregates thereof), for which the all-zeros bit-pattern is valid.
+        mem::zeroed()
+    };
+    unsafe {
+        // Safety: `from_raw` checks that the bit-pattern is a valid `Ret`.
+        crate::layout::from_raw::<Ret>(zeroed)
+    }.unwrap_or_else(|| crate::__abort_with_msg__!(
+        "Error, `{}()` was given an invalid argument, but cannot return \
+        an error value (the all-zeros `{}` is not valid). \
+        Aborting for soundness.",
+        fname, ::core::any::type_name::<Ret>(),
+    ))
+}
+
+fn log (message: fmt::Arguments<'_>)
+{
+    #[cfg(feature = "log")]
+    ::log::warn!("{}", message);
+    #[cfg(all(not(feature = "log"), feature = "std"))]
+    ::std::eprintln!("{}", message);
+    #[cfg(all(not(feature = "log"), not(feature = "std")))]
+    let _ = message;
+}
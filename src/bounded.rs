@@ -0,0 +1,240 @@
+//! Integers constrained to a range of values.
+//!
+//! Since the range is part of the validity invariant of a [`Bounded`]
+//! integer, out-of-range values received from C are rejected at the FFI
+//! boundary (as any other invalid bit-pattern, see [`ReprC::is_valid`])
+//! rather than causing panics deep inside the Rust code, and the range is
+//! documented in the headers.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ::safer_ffi::bounded::Bounded;
+//!
+//! ::safer_ffi::bounds! {
+//!     /// Worker thread count.
+//!     pub WorkerCount: u32 = [1, 64];
+//! }
+//!
+//! assert!(Bounded::<WorkerCount>::new(8).is_some());
+//! assert!(Bounded::<WorkerCount>::new(0).is_none());
+//! ```
+//!
+//! where a `Bounded<WorkerCount>` is, C-wise:
+//!
+//! ```C
+//! /** \brief
+//!  *  `uint32_t` within `[1, 64]` (inclusive).
+//!  */
+//! typedef uint32_t WorkerCount_t;
+//! ```
+
+use_prelude!();
+
+/// The range of values of a [`Bounded`] integer: see [`bounds!`][
+/// `crate::bounds`].
+pub
+trait Bounds : 'static {
+    type Int : CType + ReprC + PartialOrd + fmt::Debug + fmt::Display;
+
+    /// The smallest allowed value.
+    const MIN: Self::Int;
+
+    /// The greatest allowed value.
+    const MAX: Self::Int;
+
+    /// The name of the C `typedef`.
+    const C_NAME: &'static str;
+}
+
+/// An integer within the [`Bounds`] `B` (inclusive).
+#[repr(transparent)]
+pub
+struct Bounded<B : Bounds>(B::Int);
+
+impl<B : Bounds> Bounded<B> {
+    /// Returns `None` if `value` is out of bounds.
+    #[inline]
+    pub
+    fn new (value: B::Int)
+      -> Option<Self>
+    {
+        if B::MIN <= value && value <= B::MAX {
+            Some(Bounded(value))
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub
+    fn get (self: Self)
+      -> B::Int
+    {
+        self.0
+    }
+}
+
+impl<B : Bounds> Copy
+    for Bounded<B>
+{}
+
+impl<B : Bounds> Clone
+    for Bounded<B>
+{
+    #[inline]
+    fn clone (self: &'_ Self)
+      -> Self
+    {
+        *self
+    }
+}
+
+impl<B : Bounds> PartialEq
+    for Bounded<B>
+{
+    #[inline]
+    fn eq (self: &'_ Self, other: &'_ Self)
+      -> bool
+    {
+        self.0 == other.0
+    }
+}
+
+impl<B : Bounds> fmt::Debug
+    for Bounded<B>
+{
+    fn fmt (self: &'_ Self, fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        fmt::Debug::fmt(&self.0, fmt)
+    }
+}
+
+unsafe // Safety: `#[repr(transparent)]` wrapper.
+impl<B : Bounds> CType
+    for Bounded<B>
+{ __cfg_headers__! {
+    fn c_short_name_fmt (fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        fmt.write_str(B::C_NAME)
+    }
+
+    fn c_define_self (definer: &'_ mut dyn crate::headers::Definer)
+      -> io::Result<()>
+    {
+        definer.define_once(B::C_NAME, &mut |definer| {
+            B::Int::c_define_self(definer)?;
+            write!(definer.out(),
+                concat!(
+                    "/** \\brief\n",
+                    " *  `{}` within `[{}, {}]` (inclusive).\n",
+                    " */\n",
+                    "typedef {};\n\n",
+                ),
+                B::Int::c_var(""),
+                B::MIN, B::MAX,
+                B::Int::c_var(&crate::headers::__type_name__(&B::C_NAME)),
+            )
+        })
+    }
+
+    fn c_var_fmt (
+        fmt: &'_ mut fmt::Formatter<'_>,
+        var_name: &'_ str,
+    ) -> fmt::Result
+    {
+        write!(fmt,
+            "{}{sep}{}",
+            crate::headers::__type_name__(&B::C_NAME),
+            var_name,
+            sep = if var_name.is_empty() { "" } else { " " },
+        )
+    }
+
+    fn binding_ty ()
+      -> rust::String
+    {
+        <B::Int as CType>::binding_ty()
+    }
+
+    fn binding_define_self (definer: &'_ mut dyn crate::headers::Definer)
+      -> io::Result<()>
+    {
+        <B::Int as CType>::binding_define_self(definer)
+    }
+} type OPAQUE_KIND = OpaqueKind::Concrete; }
+
+unsafe // Safety: `#[repr(transparent)]` wrapper, with a stricter validity.
+impl<B : Bounds> ReprC
+    for Bounded<B>
+{
+    type CLayout = Self;
+
+    #[inline]
+    fn is_valid (it: &'_ Self)
+      -> bool
+    {
+        B::MIN <= it.0 && it.0 <= B::MAX
+    }
+
+    #[inline]
+    fn clamp (it: &'_ mut Self)
+      -> bool
+    {
+        it.0 = if it.0 < B::MIN { B::MIN } else { B::MAX };
+        true
+    }
+}
+
+__cfg_headers__! {
+    impl<B : Bounds> crate::headers::CLiteral
+        for Bounded<B>
+    where
+        B::Int : crate::headers::CLiteral,
+    {
+        fn c_literal (self: &'_ Self)
+          -> Option<rust::String>
+        {
+            self.0.c_literal()
+        }
+    }
+}
+
+/// Defines [`Bounds`][`crate::bounded::Bounds`], _i.e._, the range of values
+/// of a [`Bounded`][`crate::bounded::Bounded`] integer.
+///
+/// ```rust,ignore
+/// ::safer_ffi::bounds! {
+///     /// Percentage.
+///     pub Percent: u8 = [0, 100];
+/// }
+/// ```
+///
+/// The (inclusive) bounds are `const` expressions, and the name of the
+/// marker type is the one of the C `typedef` (`Percent_t`).
+#[macro_export]
+macro_rules! bounds {(
+    $(
+        $(#[$meta:meta])*
+        $pub:vis
+        $Name:ident : $Int:ty = [$MIN:expr, $MAX:expr $(,)?];
+    )*
+) => (
+    $(
+        $(#[$meta])*
+        #[allow(missing_copy_implementations, missing_debug_implementations)]
+        $pub
+        enum $Name {}
+
+        impl $crate::bounded::Bounds
+            for $Name
+        {
+            type Int = $Int;
+            const MIN: $Int = $MIN;
+            const MAX: $Int = $MAX;
+            const C_NAME: &'static str = $crate::core::stringify!($Name);
+        }
+    )*
+)}
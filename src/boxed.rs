@@ -112,6 +112,177 @@ impl<T : fmt::Debug> fmt::Debug
     }
 }
 
+ReprC! {
+    #[repr(transparent)]
+    /// Same as [`Pin`][`::core::pin::Pin`]`<`[`Box<T>`][`rust::Box`]`>`, for
+    /// the values which must not move once created, such as the
+    /// self-referential state machines of the `async` blocks, or the nodes of
+    /// intrusive data structures.
+    ///
+    /// It has the same `#[repr(C)]` layout as [`Box`] (a `T *` in the
+    /// headers), and since C can only move the pointer around, the pinning
+    /// guarantee holds across the FFI boundary:
+    ///
+    ///   - the value is only ever moved before being pinned (_i.e._, before
+    ///     being returned by the exported "create" function);
+    ///
+    ///   - it is then only accessible through a [`Pin<&mut T>`][
+    ///     `PinBox::as_mut`] (or a plain `&mut T` if `T : Unpin`);
+    ///
+    ///   - and it is dropped in place (by the exported "free" function,
+    ///     taking the `PinBox<T>` back).
+    ///
+    /// The functions taking or returning a `PinBox` also mention it in their
+    /// doc comment in the headers, for the C side not to `memcpy` the value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ::core::{future::Future, pin::Pin};
+    /// use ::safer_ffi::prelude::*;
+    ///
+    /// #[derive_ReprC]
+    /// #[ReprC::opaque]
+    /// pub
+    /// struct Task {
+    ///     future: Pin<Box<dyn Future<Output = i32> + Send>>,
+    /// }
+    ///
+    /// #[ffi_export]
+    /// fn task_new (x: i32)
+    ///   -> repr_c::PinBox<Task>
+    /// {
+    ///     repr_c::PinBox::new(Task {
+    ///         future: Box::pin(async move { x + 1 }),
+    ///     })
+    /// }
+    ///
+    /// #[ffi_export]
+    /// fn task_free (task: repr_c::PinBox<Task>)
+    /// {
+    ///     drop(task)
+    /// }
+    /// ```
+    ///
+    /// which generates:
+    ///
+    /// ```c
+    /// typedef struct Task Task_t;
+    ///
+    /// /** \remark
+    ///  *  The returned pointer points to a pinned value, which must not be
+    ///  *  moved (_e.g._, `memcpy`-ed) until it is freed.
+    ///  */
+    /// Task_t * task_new (
+    ///     int32_t x);
+    ///
+    /// /** \remark
+    ///  *  `task` points to a pinned value, which must not be moved (_e.g._,
+    ///  *  `memcpy`-ed) until it is freed.
+    ///  */
+    /// void task_free (
+    ///     Task_t * task);
+    /// ```
+    pub
+    struct PinBox[T] (
+        Box<T>,
+    );
+}
+
+impl<T> From<::core::pin::Pin<rust::Box<T>>>
+    for PinBox<T>
+{
+    #[inline]
+    fn from (pinned: ::core::pin::Pin<rust::Box<T>>)
+      -> PinBox<T>
+    {
+        Self(unsafe {
+            // Safety: the value keeps being pinned, as per the API of
+            // `PinBox`.
+            ::core::pin::Pin::into_inner_unchecked(pinned)
+        }.into())
+    }
+}
+
+impl<T> PinBox<T> {
+    #[inline]
+    pub
+    fn new (value: T)
+      -> Self
+    {
+        rust::Box::pin(value)
+            .into()
+    }
+
+    #[inline]
+    pub
+    fn into (self: PinBox<T>)
+      -> ::core::pin::Pin<rust::Box<T>>
+    {
+        unsafe {
+            // Safety: the value has been pinned since the `PinBox` creation.
+            ::core::pin::Pin::new_unchecked(self.0.into())
+        }
+    }
+
+    #[inline]
+    pub
+    fn as_ref (self: &'_ PinBox<T>)
+      -> ::core::pin::Pin<&'_ T>
+    {
+        unsafe {
+            // Safety: idem.
+            ::core::pin::Pin::new_unchecked(&*self.0)
+        }
+    }
+
+    #[inline]
+    pub
+    fn as_mut (self: &'_ mut PinBox<T>)
+      -> ::core::pin::Pin<&'_ mut T>
+    {
+        unsafe {
+            // Safety: idem.
+            ::core::pin::Pin::new_unchecked(&mut *self.0)
+        }
+    }
+}
+
+impl<T> Deref
+    for PinBox<T>
+{
+    type Target = T;
+
+    #[inline]
+    fn deref (self: &'_ PinBox<T>)
+      -> &'_ T
+    {
+        &self.0
+    }
+}
+
+/// Only for the values which do not care about being moved.
+impl<T : Unpin> DerefMut
+    for PinBox<T>
+{
+    #[inline]
+    fn deref_mut (self: &'_ mut PinBox<T>)
+      -> &'_ mut T
+    {
+        &mut self.0
+    }
+}
+
+impl<T : fmt::Debug> fmt::Debug
+    for PinBox<T>
+{
+    fn fmt (self: &'_ Self, fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        T::fmt(self, fmt)
+    }
+}
+
 #[doc(no_inline)]
 pub use crate::slice::slice_boxed;
 
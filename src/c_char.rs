@@ -57,6 +57,18 @@ impl CType
             sep = if var_name.is_empty() { "" } else { " " },
         )
     }
+
+    fn binding_ty ()
+      -> rust::String
+    {
+        crate::headers::__backend__().primitive_ty("char").into()
+    }
+
+    fn binding_define_self (_: &'_ mut dyn crate::headers::Definer)
+      -> io::Result<()>
+    {
+        Ok(())
+    }
 } type OPAQUE_KIND = crate::layout::OpaqueKind::Concrete; }
 
 from_CType_impl_ReprC! {
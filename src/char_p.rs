@@ -45,6 +45,20 @@ impl<'lt> char_p_ref<'lt> {
     }
 }
 
+unsafe // Safety: equivalent to that of the `where` bound
+    impl<'lt> Send
+        for char_p_ref<'lt>
+    where
+        &'lt str : Send,
+    {}
+
+unsafe // Safety: equivalent to that of the `where` bound
+    impl<'lt> Sync
+        for char_p_ref<'lt>
+    where
+        &'lt str : Sync,
+    {}
+
 impl fmt::Debug
     for char_p_ref<'_>
 {
@@ -70,6 +84,11 @@ struct InvalidNulTerminator<Payload> (
     pub Payload,
 );
 
+/// Error of the string constructors that append the nul terminator
+/// themselves, when the string contains an interior nul byte.
+pub
+type InteriorNul = InvalidNulTerminator<()>;
+
 impl<T> fmt::Display
     for InvalidNulTerminator<T>
 {
@@ -136,6 +155,59 @@ cfg_std! {
             }
         }
     }
+
+    impl<'lt> From<char_p_ref<'lt>>
+        for &'lt ::std::ffi::CStr
+    {
+        #[inline]
+        fn from (s: char_p_ref<'lt>)
+          -> &'lt ::std::ffi::CStr
+        {
+            s.to_c_str()
+        }
+    }
+
+    impl AsRef<::std::ffi::CStr>
+        for char_p_ref<'_>
+    {
+        #[inline]
+        fn as_ref (self: &'_ Self)
+          -> &'_ ::std::ffi::CStr
+        {
+            self.to_c_str()
+        }
+    }
+
+    impl<'lt> char_p_ref<'lt> {
+        /// Non-panicking version of the `From<&CStr>` conversion.
+        #[inline]
+        pub
+        fn try_from_c_str (s: &'lt ::std::ffi::CStr)
+          -> Result<char_p_ref<'lt>, ::core::str::Utf8Error>
+        {
+            ::core::str::from_utf8(s.to_bytes())?;
+            Ok(unsafe {
+                Self::from_ptr_unchecked(
+                    ptr::NonNull::new(s.as_ptr() as _)
+                        .unwrap()
+                )
+            })
+        }
+
+        /// Zero-cost conversion to a `&CStr`.
+        #[inline]
+        pub
+        fn to_c_str (self: char_p_ref<'lt>)
+          -> &'lt ::std::ffi::CStr
+        {
+            unsafe {
+                // Safety: single null terminator, at the end.
+                ::std::ffi::CStr::from_bytes_with_nul_unchecked(
+                    self.to_bytes_with_null()
+                )
+            }
+        }
+    }
 }
 
 impl<'lt> char_p_ref<'lt> {
@@ -239,6 +311,50 @@ impl<'lt> PartialEq for char_p_ref<'lt> {
         *self.to_str() == *other.to_str()
     }
 }
+impl PartialEq<str>
+    for char_p_ref<'_>
+{
+    #[inline]
+    fn eq (self: &'_ Self, other: &'_ str)
+      -> bool
+    {
+        *self.to_str() == *other
+    }
+}
+impl PartialEq<&'_ str>
+    for char_p_ref<'_>
+{
+    #[inline]
+    fn eq (self: &'_ Self, other: &'_ &'_ str)
+      -> bool
+    {
+        *self.to_str() == **other
+    }
+}
+impl PartialEq<char_p_ref<'_>>
+    for str
+{
+    #[inline]
+    fn eq (self: &'_ Self, other: &'_ char_p_ref<'_>)
+      -> bool
+    {
+        *self == *other.to_str()
+    }
+}
+
+impl<'lt> char_p_ref<'lt> {
+    /// Borrows an already nul-terminated `s`, such as `"Hello, World!\0"`
+    /// (see also the `c!` macro).
+    ///
+    /// Same as the `TryFrom<&str>` conversion.
+    #[inline]
+    pub
+    fn from_str_with_nul (s: &'lt str)
+      -> Result<char_p_ref<'lt>, InvalidNulTerminator<()>>
+    {
+        s.try_into()
+    }
+}
 
 ReprC! {
     #[repr(transparent)]
@@ -428,6 +544,17 @@ cfg_alloc! {
         rust::String => rust::String,
         String => rust::String,
     }
+    /// `char_p::new(format_args!(...))`
+    impl<'lt> MyFrom<fmt::Arguments<'lt>>
+        for char_p_boxed
+    {
+        #[inline]
+        fn my_from (args: fmt::Arguments<'lt>)
+          -> char_p_boxed
+        {
+            MyFrom::my_from(::alloc::fmt::format(args))
+        }
+    }
     cfg_std! {
         derive_MyFrom_from! {
             @for['lt] &'lt ::std::ffi::CStr => ::std::ffi::CString,
@@ -470,6 +597,88 @@ cfg_alloc! {
         }
     }
 
+    impl char_p_boxed {
+        /// Same as [`.parse()`][`str::parse`]-ing a string into a
+        /// [`char_p_boxed`], but for the formatted `format_args!(...)`, thus
+        /// avoiding the `format!("...\0", ...)` idiom.
+        ///
+        /// ```rust
+        /// use ::safer_ffi::prelude::*;
+        ///
+        /// let x = 42;
+        /// let s = char_p::Box::from_fmt(format_args!("x = {}", x)).unwrap();
+        /// assert_eq!(s.to_str(), "x = 42");
+        /// ```
+        #[inline]
+        pub
+        fn from_fmt (args: fmt::Arguments<'_>)
+          -> Result<char_p_boxed, InvalidNulTerminator<rust::String>>
+        {
+            ::alloc::fmt::format(args).try_into()
+        }
+
+        /// Infallible version of its [`FromStr`][`::core::str::FromStr`] impl,
+        /// which replaces interior nul bytes with
+        /// `U+FFFD REPLACEMENT CHARACTER`.
+        pub
+        fn from_str_lossy (s: &'_ str)
+          -> char_p_boxed
+        {
+            // A trailing nul byte is the terminator, not an interior one.
+            let s = match s.as_bytes().last() {
+                | Some(&NUL) => &s[.. s.len() - 1],
+                | _ => s,
+            };
+            if s.as_bytes().contains(&NUL) {
+                s.replace('\0', "\u{FFFD}")
+            } else {
+                s.to_owned()
+            }
+            .try_into()
+            .unwrap_or_else(|_| unreachable!("Replaced all the nul bytes"))
+        }
+    }
+
+    impl char_p_boxed {
+        /// Infallible conversion from bytes, which replaces both invalid UTF-8
+        /// sequences and interior nul bytes with
+        /// `U+FFFD REPLACEMENT CHARACTER`.
+        #[inline]
+        pub
+        fn from_utf8_lossy (bytes: &'_ [u8])
+          -> char_p_boxed
+        {
+            char_p_boxed::from_str_lossy(&rust::String::from_utf8_lossy(bytes))
+        }
+    }
+
+    /// Copies `s` into a new nul-terminated string.
+    ///
+    /// `s` does not need to be nul-terminated (if it is, the terminator is
+    /// not duplicated), but it cannot contain interior nul bytes.
+    ///
+    /// ```rust
+    /// use ::safer_ffi::prelude::*;
+    ///
+    /// let s: char_p::Box = "Hello, World!".parse().unwrap();
+    /// assert_eq!(s.to_str(), "Hello, World!");
+    /// assert!("Hello,\0World!".parse::<char_p::Box>().is_err());
+    /// ```
+    impl ::core::str::FromStr
+        for char_p_boxed
+    {
+        type Err = InteriorNul;
+
+        #[inline]
+        fn from_str (s: &'_ str)
+          -> Result<char_p_boxed, InteriorNul>
+        {
+            s   .to_owned()
+                .try_into()
+                .map_err(|_| InvalidNulTerminator(()))
+        }
+    }
+
     impl Drop for char_p_boxed {
         fn drop (self: &'_ mut char_p_boxed)
         {
@@ -591,6 +800,57 @@ cfg_alloc! {
         }
     }
 
+    impl PartialEq<str>
+        for char_p_boxed
+    {
+        #[inline]
+        fn eq (self: &'_ Self, other: &'_ str)
+          -> bool
+        {
+            *self.to_str() == *other
+        }
+    }
+    impl PartialEq<&'_ str>
+        for char_p_boxed
+    {
+        #[inline]
+        fn eq (self: &'_ Self, other: &'_ &'_ str)
+          -> bool
+        {
+            *self.to_str() == **other
+        }
+    }
+    impl PartialEq<char_p_boxed>
+        for str
+    {
+        #[inline]
+        fn eq (self: &'_ Self, other: &'_ char_p_boxed)
+          -> bool
+        {
+            *self == *other.to_str()
+        }
+    }
+    impl PartialEq<char_p_ref<'_>>
+        for char_p_boxed
+    {
+        #[inline]
+        fn eq (self: &'_ Self, other: &'_ char_p_ref<'_>)
+          -> bool
+        {
+            self.as_ref() == *other
+        }
+    }
+    impl PartialEq<char_p_boxed>
+        for char_p_ref<'_>
+    {
+        #[inline]
+        fn eq (self: &'_ Self, other: &'_ char_p_boxed)
+          -> bool
+        {
+            *self == other.as_ref()
+        }
+    }
+
     impl Clone
         for char_p_boxed
     {
@@ -627,4 +887,67 @@ cfg_std! {
             }
         }
     }
+
+    impl char_p_boxed {
+        /// Non-panicking version of the `From<CString>` conversion, which does
+        /// not copy the string either.
+        ///
+        /// On failure, the original `CString` can be recovered with
+        /// [`IntoStringError::into_cstring`][
+        /// `::std::ffi::IntoStringError::into_cstring`].
+        pub
+        fn try_from_c_string (s: ::std::ffi::CString)
+          -> Result<char_p_boxed, ::std::ffi::IntoStringError>
+        {
+            let s = s.into_string()?;
+            Ok(s.try_into().unwrap_or_else(|_| unreachable!(
+                "A `CString` has no inner nul bytes"
+            )))
+        }
+
+        /// Conversion to a `CString`, which does not copy the string (unless
+        /// it is empty).
+        pub
+        fn into_c_string (self: char_p_boxed)
+          -> ::std::ffi::CString
+        {
+            unsafe {
+                // Safety: no inner nul bytes.
+                // The `Vec` has room for the nul terminator, so no
+                // reallocation happens.
+                ::std::ffi::CString::from_vec_unchecked(self.into_vec())
+            }
+        }
+
+        /// Zero-cost conversion to a `&CStr`.
+        #[inline]
+        pub
+        fn as_c_str (self: &'_ char_p_boxed)
+          -> &'_ ::std::ffi::CStr
+        {
+            self.as_ref().to_c_str()
+        }
+    }
+
+    impl From<char_p_boxed>
+        for ::std::ffi::CString
+    {
+        #[inline]
+        fn from (s: char_p_boxed)
+          -> ::std::ffi::CString
+        {
+            s.into_c_string()
+        }
+    }
+
+    impl AsRef<::std::ffi::CStr>
+        for char_p_boxed
+    {
+        #[inline]
+        fn as_ref (self: &'_ Self)
+          -> &'_ ::std::ffi::CStr
+        {
+            self.as_c_str()
+        }
+    }
 }
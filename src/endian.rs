@@ -0,0 +1,260 @@
+//! Integers stored in a fixed byte order, for `struct`s mirroring network
+//! protocols or file formats shared with C parsers.
+//!
+//! [`Be`]`<Int>` (resp. [`Le`]`<Int>`) stores its bytes in big-endian (resp.
+//! little-endian) order regardless of the platform, converting on access,
+//! and documents the byte order in the headers:
+//!
+//! ```C
+//! /** \brief
+//!  *  A `uint32_t` stored in big-endian (network) byte order.
+//!  */
+//! typedef uint32_t uint32_be_t;
+//! ```
+//!
+//! # Example
+//!
+//! ```rust
+//! use ::safer_ffi::endian::Be;
+//!
+//! let len = Be::new(0x0102_0304_u32);
+//! assert_eq!(len.as_bytes(), [0x01, 0x02, 0x03, 0x04]);
+//! assert_eq!(len.get(), 0x0102_0304);
+//! ```
+
+use_prelude!();
+
+/// The integer types usable with [`Be`] and [`Le`].
+pub
+trait Int : Copy + Eq + 'static + ::core::hash::Hash + Default + fmt::Debug
+    + seal::Sealed
+{
+    #[doc(hidden)]
+    fn __to_be__ (self: Self) -> Self;
+
+    #[doc(hidden)]
+    fn __from_be__ (it: Self) -> Self;
+
+    #[doc(hidden)]
+    fn __to_le__ (self: Self) -> Self;
+
+    #[doc(hidden)]
+    fn __from_le__ (it: Self) -> Self;
+}
+
+mod seal {
+    pub
+    trait Sealed {}
+}
+
+macro_rules! endian_types {(
+    $(
+        $(#[$meta:meta])*
+        $Endian:ident ($to:ident, $from:ident) => $suffix:literal, $order:literal;
+    )*
+) => (
+    $(
+        $(#[$meta])*
+        #[repr(transparent)]
+        #[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+        pub
+        struct $Endian<Int>(Int);
+
+        impl<Int : self::Int> $Endian<Int> {
+            #[inline]
+            pub
+            fn new (value: Int)
+              -> Self
+            {
+                $Endian(value.$to())
+            }
+
+            /// The value, in the native byte order.
+            #[inline]
+            pub
+            fn get (self: Self)
+              -> Int
+            {
+                Int::$from(self.0)
+            }
+
+            #[inline]
+            pub
+            fn set (self: &'_ mut Self, value: Int)
+            {
+                *self = Self::new(value);
+            }
+
+            /// The bytes, as stored.
+            #[inline]
+            pub
+            fn as_bytes (self: &'_ Self)
+              -> &'_ [u8]
+            {
+                unsafe {
+                    // Safety: plain integer.
+                    ::core::slice::from_raw_parts(
+                        &self.0 as *const Int as *const u8,
+                        mem::size_of::<Int>(),
+                    )
+                }
+            }
+        }
+
+        impl<Int : self::Int> From<Int>
+            for $Endian<Int>
+        {
+            #[inline]
+            fn from (value: Int)
+              -> Self
+            {
+                Self::new(value)
+            }
+        }
+
+        impl<Int : self::Int> fmt::Debug
+            for $Endian<Int>
+        {
+            fn fmt (self: &'_ Self, fmt: &'_ mut fmt::Formatter<'_>)
+              -> fmt::Result
+            {
+                fmt .debug_tuple(stringify!($Endian))
+                    .field(&self.get())
+                    .finish()
+            }
+        }
+
+        endian_types! {
+            @impls $Endian => $suffix, $order;
+            u16 => "uint16", u32 => "uint32", u64 => "uint64",
+            i16 => "int16", i32 => "int32", i64 => "int64",
+        }
+    )*
+);
+
+(
+    @impls $Endian:ident => $suffix:literal, $order:literal;
+    $(
+        $Int:ident => $CInt:literal,
+    )*
+) => (
+    $(
+        impl From<$Endian<$Int>>
+            for $Int
+        {
+            #[inline]
+            fn from (it: $Endian<$Int>)
+              -> $Int
+            {
+                it.get()
+            }
+        }
+
+        unsafe // Safety: `#[repr(transparent)]` wrapper.
+        impl CType
+            for $Endian<$Int>
+        { __cfg_headers__! {
+            fn c_short_name_fmt (fmt: &'_ mut fmt::Formatter<'_>)
+              -> fmt::Result
+            {
+                fmt.write_str(concat!($CInt, "_", $suffix))
+            }
+
+            fn c_define_self (definer: &'_ mut dyn crate::headers::Definer)
+              -> io::Result<()>
+            {
+                let me = concat!($CInt, "_", $suffix);
+                definer.define_once(me, &mut |definer| {
+                    <$Int as CType>::c_define_self(definer)?;
+                    write!(definer.out(),
+                        concat!(
+                            "/** \\brief\n",
+                            " *  A `", $CInt, "_t` stored in ", $order,
+                            " byte order.\n",
+                            " */\n",
+                            "typedef ", $CInt, "_t {};\n\n",
+                        ),
+                        crate::headers::__type_name__(&me),
+                    )
+                })
+            }
+
+            fn c_var_fmt (
+                fmt: &'_ mut fmt::Formatter<'_>,
+                var_name: &'_ str,
+            ) -> fmt::Result
+            {
+                write!(fmt,
+                    "{}{sep}{}",
+                    crate::headers::__type_name__(&concat!($CInt, "_", $suffix)),
+                    var_name,
+                    sep = if var_name.is_empty() { "" } else { " " },
+                )
+            }
+
+            fn binding_ty ()
+              -> rust::String
+            {
+                <$Int as CType>::binding_ty()
+            }
+
+            fn binding_define_self (definer: &'_ mut dyn crate::headers::Definer)
+              -> io::Result<()>
+            {
+                <$Int as CType>::binding_define_self(definer)
+            }
+        } type OPAQUE_KIND = OpaqueKind::Concrete; }
+
+        from_CType_impl_ReprC! { $Endian<$Int> }
+
+        __cfg_headers__! {
+            impl crate::headers::CLiteral
+                for $Endian<$Int>
+            {
+                /// The value as stored, _i.e._, as C reads it.
+                fn c_literal (self: &'_ Self)
+                  -> Option<rust::String>
+                {
+                    self.0.c_literal()
+                }
+            }
+        }
+    )*
+)}
+
+macro_rules! impl_Int {(
+    $($Int:ident),* $(,)?
+) => (
+    $(
+        impl seal::Sealed for $Int {}
+
+        impl Int
+            for $Int
+        {
+            #[inline]
+            fn __to_be__ (self: Self) -> Self { self.to_be() }
+
+            #[inline]
+            fn __from_be__ (it: Self) -> Self { $Int::from_be(it) }
+
+            #[inline]
+            fn __to_le__ (self: Self) -> Self { self.to_le() }
+
+            #[inline]
+            fn __from_le__ (it: Self) -> Self { $Int::from_le(it) }
+        }
+    )*
+)}
+
+impl_Int! {
+    u16, u32, u64,
+    i16, i32, i64,
+}
+
+endian_types! {
+    /// An integer stored in big-endian (network) byte order.
+    Be (__to_be__, __from_be__) => "be", "big-endian (network)";
+
+    /// An integer stored in little-endian byte order.
+    Le (__to_le__, __from_le__) => "le", "little-endian";
+}
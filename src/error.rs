@@ -0,0 +1,163 @@
+//! [`Error`], the conversion errors of the crate, unified.
+
+use_prelude!();
+
+/// The errors of the (fallible) conversions of the crate, unified, so that
+/// an exported function can `?` any of them into its own error type, with
+/// a single `From<::safer_ffi::Error>` impl.
+///
+/// ```rust
+/// use ::safer_ffi::prelude::*;
+///
+/// #[derive_ReprC]
+/// #[repr(i8)]
+/// enum Status {
+///     Ok = 0,
+///     InvalidString = -1,
+///     InvalidValue = -2,
+/// }
+///
+/// impl From<::safer_ffi::Error> for Status {
+///     fn from (err: ::safer_ffi::Error)
+///       -> Status
+///     {
+///         match err {
+///             | ::safer_ffi::Error::InvalidBitPattern(_) => Status::InvalidValue,
+///             | _ => Status::InvalidString,
+///         }
+///     }
+/// }
+///
+/// fn to_name (bytes: Vec<u8>)
+///   -> Result<String, ::safer_ffi::Error>
+/// {
+///     let name = ::std::ffi::CString::new(bytes)?; // interior nul
+///     Ok(name.into_string()?) // invalid UTF-8
+/// }
+///
+/// fn greet (bytes: Vec<u8>)
+///   -> Result<(), Status>
+/// {
+///     let name = to_name(bytes)?;
+///     println!("Hello, {}!", name);
+///     Ok(())
+/// }
+/// # assert!(greet(b"\xff".to_vec()).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub
+enum Error {
+    /// A string with a nul byte elsewhere than at its terminating position
+    /// (or without a nul terminator), _e.g._, when converted to a
+    /// [`char_p::Ref`][`crate::prelude::char_p::Ref`].
+    InvalidNulTerminator,
+
+    /// A string (or C string) which is not valid UTF-8.
+    InvalidUtf8(::core::str::Utf8Error),
+
+    /// A `CLayout` value which is not a valid bit-pattern for its `ReprC`
+    /// type: see [`layout::try_from_raw`][`crate::layout::try_from_raw`].
+    InvalidBitPattern(crate::layout::InvalidBitPattern),
+}
+
+impl fmt::Display
+    for Error
+{
+    fn fmt (self: &'_ Self, fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        match *self {
+            | Error::InvalidNulTerminator => {
+                fmt::Display::fmt(&crate::char_p::InvalidNulTerminator(()), fmt)
+            },
+            | Error::InvalidUtf8(ref err) => fmt::Display::fmt(err, fmt),
+            | Error::InvalidBitPattern(ref err) => fmt::Display::fmt(err, fmt),
+        }
+    }
+}
+
+cfg_std! {
+    impl ::std::error::Error
+        for Error
+    {
+        fn source (self: &'_ Self)
+          -> Option<&'_ (dyn ::std::error::Error + 'static)>
+        {
+            match *self {
+                | Error::InvalidNulTerminator => None,
+                | Error::InvalidUtf8(ref err) => Some(err),
+                | Error::InvalidBitPattern(ref err) => Some(err),
+            }
+        }
+    }
+}
+
+impl<Payload> From<crate::char_p::InvalidNulTerminator<Payload>>
+    for Error
+{
+    #[inline]
+    fn from (_: crate::char_p::InvalidNulTerminator<Payload>)
+      -> Error
+    {
+        Error::InvalidNulTerminator
+    }
+}
+
+impl From<::core::str::Utf8Error>
+    for Error
+{
+    #[inline]
+    fn from (err: ::core::str::Utf8Error)
+      -> Error
+    {
+        Error::InvalidUtf8(err)
+    }
+}
+
+impl From<crate::layout::InvalidBitPattern>
+    for Error
+{
+    #[inline]
+    fn from (err: crate::layout::InvalidBitPattern)
+      -> Error
+    {
+        Error::InvalidBitPattern(err)
+    }
+}
+
+cfg_alloc! {
+    impl From<::alloc::string::FromUtf8Error>
+        for Error
+    {
+        #[inline]
+        fn from (err: ::alloc::string::FromUtf8Error)
+          -> Error
+        {
+            Error::InvalidUtf8(err.utf8_error())
+        }
+    }
+}
+
+cfg_std! {
+    impl From<::std::ffi::NulError>
+        for Error
+    {
+        #[inline]
+        fn from (_: ::std::ffi::NulError)
+          -> Error
+        {
+            Error::InvalidNulTerminator
+        }
+    }
+
+    impl From<::std::ffi::IntoStringError>
+        for Error
+    {
+        #[inline]
+        fn from (err: ::std::ffi::IntoStringError)
+          -> Error
+        {
+            Error::InvalidUtf8(err.utf8_error())
+        }
+    }
+}
@@ -0,0 +1,275 @@
+//! Thread-safe publish / subscribe of typed events, with C subscribers.
+//!
+//! The Rust side [`publish`][`EventBus::publish`]es events on a (`static`)
+//! [`EventBus`], and the C side subscribes to them through the functions
+//! exported by [`ffi_export_events!`][`crate::ffi_export_events`]: the topic
+//! `enum` and the payload `struct` thus end up in the generated headers.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ::safer_ffi::{prelude::*, events::EventBus};
+//!
+//! #[derive_ReprC]
+//! #[repr(u8)]
+//! #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+//! pub
+//! enum Topic {
+//!     Connected,
+//!     Progress,
+//! }
+//!
+//! #[derive_ReprC]
+//! #[repr(C)]
+//! pub
+//! struct Payload {
+//!     value: u32,
+//! }
+//!
+//! static EVENTS: EventBus<Topic, Payload> = EventBus::new();
+//!
+//! ::safer_ffi::ffi_export_events! {
+//!     EVENTS: EventBus<Topic, Payload> => {
+//!         subscribe: events_subscribe,
+//!         unsubscribe: events_unsubscribe,
+//!     }
+//! }
+//!
+//! # fn main () {
+//! // Rust side:
+//! EVENTS.publish(Topic::Progress, &Payload { value: 42 }).unwrap();
+//! # }
+//! ```
+//!
+//! exports:
+//!
+//! ```C
+//! typedef void (*void_void_ptr_Topic_Payload_const_ptr_fptr_t)(void *, Topic_t, Payload_t const *);
+//!
+//! uint64_t events_subscribe (
+//!     Topic_t topic,
+//!     void_void_ptr_Topic_Payload_const_ptr_fptr_t callback,
+//!     void * ctx);
+//!
+//! bool events_unsubscribe (
+//!     uint64_t subscription);
+//! ```
+
+use_prelude!();
+use ::core::sync::atomic::{self, AtomicU64};
+use crate::sync::{AccessError, FfiMutex};
+
+/// Signature of the (C) subscribers of an [`EventBus`]`<Topic, Payload>`:
+/// they are called with the `ctx` given when subscribing, the topic, and a
+/// pointer to the payload (only valid for the duration of the call).
+pub
+type EventCallback<Topic, Payload> =
+    unsafe extern "C" fn(ctx: *mut c_void, topic: Topic, payload: *const Payload)
+;
+
+struct Subscriber<Topic, Payload> {
+    id: u64,
+    topic: Topic,
+    callback: EventCallback<Topic, Payload>,
+    ctx: *mut c_void,
+}
+
+impl<Topic : Copy, Payload> Copy
+    for Subscriber<Topic, Payload>
+{}
+
+impl<Topic : Copy, Payload> Clone
+    for Subscriber<Topic, Payload>
+{
+    #[inline]
+    fn clone (self: &'_ Self)
+      -> Self
+    {
+        *self
+    }
+}
+
+unsafe // Safety: `ctx` is declared usable from any thread by `subscribe`.
+impl<Topic : Send, Payload> Send
+    for Subscriber<Topic, Payload>
+{}
+
+/// A thread-safe set of subscriptions to events, usable as a `static`.
+///
+/// Subscribers are called from the thread publishing the event, and outside
+/// the internal lock (so that they can, for instance, unsubscribe).
+pub
+struct EventBus<Topic, Payload> {
+    subscribers: FfiMutex<rust::Vec<Subscriber<Topic, Payload>>>,
+    last_id: AtomicU64,
+}
+
+impl<Topic, Payload> EventBus<Topic, Payload> {
+    /// An `EventBus` without subscribers.
+    pub
+    const
+    fn new ()
+      -> Self
+    {
+        EventBus {
+            subscribers: FfiMutex::new(rust::Vec::new()),
+            last_id: AtomicU64::new(0),
+        }
+    }
+}
+
+impl<Topic, Payload> EventBus<Topic, Payload>
+where
+    Topic : Copy + PartialEq + Send,
+{
+    /// Registers `callback` to be called with `ctx` on each `topic` event.
+    ///
+    /// Returns the (non-zero) identifier of the subscription.
+    ///
+    /// # Safety
+    ///
+    /// `ctx` must be usable from any thread publishing events, until the
+    /// subscription is [`unsubscribe`][`EventBus::unsubscribe`]d.
+    pub
+    unsafe
+    fn subscribe (
+        self: &'_ Self,
+        topic: Topic,
+        callback: EventCallback<Topic, Payload>,
+        ctx: *mut c_void,
+    ) -> Result<u64, AccessError>
+    {
+        let mut subscribers = self.subscribers.lock()?;
+        let id = self.last_id.fetch_add(1, atomic::Ordering::Relaxed) + 1;
+        subscribers.push(Subscriber { id, topic, callback, ctx });
+        Ok(id)
+    }
+
+    /// Cancels the subscription, returning whether it existed.
+    pub
+    fn unsubscribe (self: &'_ Self, id: u64)
+      -> Result<bool, AccessError>
+    {
+        let mut subscribers = self.subscribers.lock()?;
+        let count = subscribers.len();
+        subscribers.retain(|it| it.id != id);
+        Ok(subscribers.len() < count)
+    }
+
+    /// Calls the subscribers of `topic` with the `payload`.
+    ///
+    /// Returns the number of subscribers called.
+    pub
+    fn publish (self: &'_ Self, topic: Topic, payload: &'_ Payload)
+      -> Result<usize, AccessError>
+    {
+        let subscribers: rust::Vec<_> =
+            self.subscribers
+                .lock()?
+                .iter()
+                .filter(|it| it.topic == topic)
+                .copied()
+                .collect()
+        ;
+        subscribers.iter().for_each(|it| unsafe {
+            // Safety: upheld by the caller of `subscribe`.
+            (it.callback)(it.ctx, topic, payload)
+        });
+        Ok(subscribers.len())
+    }
+}
+
+impl<Topic, Payload> Default
+    for EventBus<Topic, Payload>
+{
+    #[inline]
+    fn default ()
+      -> Self
+    {
+        Self::new()
+    }
+}
+
+impl<Topic, Payload> fmt::Debug
+    for EventBus<Topic, Payload>
+{
+    fn fmt (self: &'_ Self, fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        fmt .debug_struct("EventBus")
+            .field("subscribers", &self.subscribers)
+            .finish()
+    }
+}
+
+/// Exports the `subscribe` and `unsubscribe` functions of a `static`
+/// [`EventBus`][`crate::events::EventBus`].
+///
+/// ```rust,ignore
+/// ::safer_ffi::ffi_export_events! {
+///     EVENTS: EventBus<Topic, Payload> => {
+///         subscribe: my_events_subscribe,
+///         unsubscribe: my_events_unsubscribe,
+///     }
+/// }
+/// ```
+///
+/// exports:
+///
+/// ```C
+/// typedef void (*void_void_ptr_Topic_Payload_const_ptr_fptr_t)(void *, Topic_t, Payload_t const *);
+///
+/// uint64_t my_events_subscribe (
+///     Topic_t topic,
+///     void_void_ptr_Topic_Payload_const_ptr_fptr_t callback,
+///     void * ctx);
+///
+/// bool my_events_unsubscribe (uint64_t subscription);
+/// ```
+///
+///   - `subscribe` returns the identifier of the subscription, or `0` on
+///     error (_e.g._, a `NULL` `callback`);
+///
+///   - `unsubscribe` returns whether the subscription existed.
+#[macro_export]
+macro_rules! ffi_export_events {(
+    $BUS:path : EventBus<$Topic:ty, $Payload:ty> => {
+        subscribe: $subscribe:ident,
+        unsubscribe: $unsubscribe:ident $(,)?
+    } $(;)?
+) => (
+    #[$crate::ffi_export]
+    /// Calls `callback` with `ctx` on each `topic` event, from the thread
+    /// publishing it, until unsubscribed.
+    ///
+    /// Returns the identifier of the subscription, or `0` on error.
+    pub
+    fn $subscribe (
+        topic: $Topic,
+        callback: $crate::fn_ptr::FnPtr<(
+            *mut $crate::core::ffi::c_void,
+            $Topic,
+            *const $Payload,
+        )>,
+        ctx: *mut $crate::core::ffi::c_void,
+    ) -> u64
+    {
+        let callback = match callback.get() {
+            | Some(it) => it,
+            | None => return 0,
+        };
+        unsafe {
+            // Safety: upheld by the C caller.
+            $BUS.subscribe(topic, callback, ctx)
+        }.unwrap_or(0)
+    }
+
+    #[$crate::ffi_export]
+    /// Cancels the subscription, returning whether it existed.
+    pub
+    fn $unsubscribe (subscription: u64)
+      -> bool
+    {
+        $BUS.unsubscribe(subscription).unwrap_or(false)
+    }
+)}
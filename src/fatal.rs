@@ -0,0 +1,229 @@
+//! Host-provided handler for the fatal errors of the library.
+//!
+//! When the library aborts the process (such as when a panic would otherwise
+//! unwind across the FFI boundary, or on any panic when compiled with
+//! `panic = "abort"`), the host application gets no chance to log what
+//! happened, nor to flush its own state.
+//!
+//! The [`ffi_export_fatal_handler!`][`crate::ffi_export_fatal_handler`] macro
+//! exports a function for the host to register a [`FatalHandler`] with, which
+//! is then called with the error message right before aborting:
+//!
+//! ```rust
+//! ::safer_ffi::ffi_export_fatal_handler!(mylib_set_fatal_handler);
+//! ```
+//!
+//! exports:
+//!
+//! ```C
+//! typedef struct {
+//!     void * ctx;
+//!     void (*on_fatal)(void *, char const *, char const *);
+//! } FatalHandler_t;
+//!
+//! void mylib_set_fatal_handler (
+//!     FatalHandler_t handler);
+//! ```
+//!
+//! The process is aborted once the handler returns (if it does).
+//!
+//! # Backtraces
+//!
+//! With the `backtrace` feature of `::safer_ffi`, the handler is also given
+//! the (symbolized) backtrace of the error: that of the panic which led to
+//! the abort, if any. Otherwise, it is given `NULL`.
+//!
+//! Note that, once a handler is registered, the backtrace of every panic is
+//! then captured (since it may end up aborting), which makes panicking (even
+//! when caught) notably slower.
+
+use_prelude!();
+use crate::sync::FfiMutex;
+use rust::String;
+
+ReprC! {
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    /// Handler of the fatal errors of the library, called right before it
+    /// aborts the process.
+    ///
+    /// The `on_fatal` function is called with the `ctx` pointer, a
+    /// human-readable description of the error, and its backtrace (`NULL`
+    /// unless the library is compiled with the `backtrace` feature of
+    /// `::safer_ffi`), from whichever thread hit the error. Both strings are
+    /// only valid for the duration of the call.
+    ///
+    /// A `NULL` `on_fatal` function unregisters the handler.
+    pub
+    struct FatalHandler {
+        /// Opaque context, forwarded as is to `on_fatal`.
+        pub
+        ctx: *mut c_void,
+
+        /// Called right before aborting the process.
+        pub
+        on_fatal: Option<
+            unsafe extern "C"
+            fn (
+                ctx: *mut c_void,
+                message: char_p::Raw,
+                backtrace: Option<char_p::Raw>,
+            )
+        >,
+    }
+}
+
+unsafe // Safety: the C caller registering the handler vouches for `ctx` to
+       // be usable from any thread.
+impl Send
+    for FatalHandler
+{}
+
+impl fmt::Debug
+    for FatalHandler
+{
+    fn fmt (self: &'_ Self, fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        fmt .debug_struct("FatalHandler")
+            .field("ctx", &self.ctx)
+            .field("on_fatal", &self.on_fatal)
+            .finish()
+    }
+}
+
+static HANDLER: FfiMutex<Option<FatalHandler>> = FfiMutex::new(None);
+
+/// Registers the `handler` of the fatal errors of the library (replacing
+/// the previous one, if any).
+///
+/// When compiled with `panic = "abort"` (or with the `backtrace` feature),
+/// this also installs a panic hook (chained with the previous one) calling
+/// the handler (or capturing the backtrace of the panic, respectively).
+pub
+fn set_fatal_handler (handler: FatalHandler)
+{
+    HANDLER.clear_poison();
+    if let Ok(mut current) = HANDLER.lock() {
+        *current = handler.on_fatal.map(|_| handler);
+    }
+    if cfg!(panic = "abort") || cfg!(feature = "backtrace") {
+        static HOOK: ::std::sync::Once = ::std::sync::Once::new();
+        HOOK.call_once(|| {
+            let prev_hook = ::std::panic::take_hook();
+            ::std::panic::set_hook(::std::boxed::Box::new(move |info| {
+                prev_hook(info);
+                let handler = match current_handler() {
+                    | Some(it) => it,
+                    | None => return,
+                };
+                let backtrace = capture_backtrace();
+                if cfg!(panic = "abort") {
+                    call_handler(handler, &info.to_string(), backtrace);
+                } else {
+                    // The panic may still be caught: keep the backtrace
+                    // around in case it ends up aborting.
+                    PANIC_BACKTRACE.with(|it| *it.borrow_mut() = backtrace);
+                }
+            }));
+        });
+    }
+}
+
+::std::thread_local! {
+    /// The backtrace of the last panic of the thread.
+    // `const { … }` `thread_local!` initializers require Rust 1.59.
+    #[allow(clippy::missing_const_for_thread_local)]
+    static PANIC_BACKTRACE: ::core::cell::RefCell<Option<String>> =
+        ::core::cell::RefCell::new(None)
+    ;
+}
+
+fn current_handler ()
+  -> Option<FatalHandler>
+{
+    // Being called while panicking, this poisons the lock: since the handler
+    // is only ever replaced as a whole, poisoning is ignored.
+    HANDLER.clear_poison();
+    match HANDLER.lock() {
+        | Ok(it) => *it,
+        | Err(_) => None,
+    }
+}
+
+#[cfg(feature = "backtrace")]
+fn capture_backtrace ()
+  -> Option<String>
+{
+    Some(format!("{:?}", ::backtrace_crate::Backtrace::new()))
+}
+
+#[cfg(not(feature = "backtrace"))]
+fn capture_backtrace ()
+  -> Option<String>
+{
+    None
+}
+
+/// Calls the registered handler, if any, with `message` (and the backtrace
+/// of the panic which led to it, if any).
+pub(in crate)
+fn on_fatal (message: &'_ str)
+{
+    let handler = match current_handler() {
+        | Some(it) => it,
+        | None => return,
+    };
+    let backtrace =
+        PANIC_BACKTRACE
+            .with(|it| it.borrow_mut().take())
+            .or_else(capture_backtrace)
+    ;
+    call_handler(handler, message, backtrace);
+}
+
+fn call_handler (
+    handler: FatalHandler,
+    message: &'_ str,
+    backtrace: Option<String>,
+)
+{
+    let to_c_string = |s: &'_ str| {
+        ::std::ffi::CString::new(s.replace('\0', "\\0"))
+            .unwrap_or_default()
+    };
+    if let FatalHandler { ctx, on_fatal: Some(on_fatal) } = handler {
+        let message = to_c_string(message);
+        let backtrace = backtrace.as_ref().map(|it| to_c_string(it));
+        unsafe {
+            // Safety: upheld by the C caller that registered the handler.
+            on_fatal(
+                ctx,
+                char_p::Ref::from(&*message).into(),
+                backtrace.as_ref().map(|it| char_p::Ref::from(&**it).into()),
+            );
+        }
+    }
+}
+
+/// Exports a `void <name> (FatalHandler_t handler)` function registering the
+/// [`FatalHandler`] of the library (see [the `fatal` module][`crate::fatal`]).
+///
+/// ```rust,ignore
+/// ::safer_ffi::ffi_export_fatal_handler!(mylib_set_fatal_handler);
+/// ```
+#[macro_export]
+macro_rules! ffi_export_fatal_handler {(
+    $name:ident $(,)?
+) => (
+    #[$crate::ffi_export]
+    /// Registers the handler called (with a description of the error) right
+    /// before the library aborts the process.
+    ///
+    /// A `NULL` `on_fatal` function unregisters the handler.
+    pub
+    fn $name (handler: $crate::fatal::FatalHandler)
+    {
+        $crate::fatal::set_fatal_handler(handler);
+    }
+)}
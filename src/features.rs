@@ -0,0 +1,76 @@
+//! Runtime detection of the optional features compiled into the library.
+//!
+//! A single shipped binary may have been compiled with or without some
+//! optional subsystems (Cargo features); C hosts can query which ones through
+//! a function exported with the [`ffi_export_has_feature!`] macro.
+//!
+//! Only the features listed in the invocation (an allowlist) are reported,
+//! so that internal features are not part of the API.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ::safer_ffi::prelude::*;
+//!
+//! ::safer_ffi::ffi_export_has_feature!(mylib_has_feature, [
+//!     "compression",
+//!     "tls",
+//! ]);
+//!
+//! # fn main () {
+//! // (This crate was compiled without these features.)
+//! assert!(!mylib_has_feature(c!("tls")));
+//! assert!(!mylib_has_feature(c!("unknown")));
+//! # }
+//! ```
+//!
+//! which, C-wise, is used as:
+//!
+//! ```C
+//! if (mylib_has_feature("tls")) {
+//!     /* ... */
+//! }
+//! ```
+
+use_prelude!();
+
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __has_feature__ (name: char_p::Ref<'_>, features: &'_ [(&'_ str, bool)])
+  -> bool
+{
+    let name = name.to_bytes();
+    features
+        .iter()
+        .any(|&(feature, enabled)| enabled && feature.as_bytes() == name)
+}
+
+/// Exports a `bool <name> (char const * name)` function telling whether the
+/// given (allowlisted) Cargo feature of the crate was enabled when compiling
+/// it (see [the `features` module][`crate::features`]).
+///
+/// ```rust,ignore
+/// ::safer_ffi::ffi_export_has_feature!(mylib_has_feature, [
+///     "compression",
+///     "tls",
+/// ]);
+/// ```
+///
+/// Unknown names (and the features not listed) are reported as disabled.
+#[macro_export]
+macro_rules! ffi_export_has_feature {(
+    $fname:ident, [
+        $($feature:literal),* $(,)?
+    ] $(,)?
+) => (
+    #[$crate::ffi_export]
+    /// Whether the optional feature of the library named `name` was compiled
+    /// in.
+    pub
+    fn $fname (name: $crate::char_p::char_p_ref<'_>)
+      -> bool
+    {
+        $crate::features::__has_feature__(name, &[$(
+            ($feature, $crate::core::cfg!(feature = $feature)),
+        )*])
+    }
+)}
@@ -1,5 +1,14 @@
 #[doc(hidden)] #[macro_export]
 macro_rules! __ffi_export__ {(
+    @abi[$abi:tt]
+    $(@debug_only[$debug_cfg:meta])?
+    $(@inline[$inline:meta])?
+    $(@used[$used:ident])?
+    $(@system_abi[$system_fname:ident])?
+    $(@group[$group:literal])?
+    $(@doc_group[$doc_group:literal])?
+    $(@batched[$batch_fname:ident])?
+    $(@deprecated[$deprecated_note:literal, $deprecated:meta])?
     $($(#[doc = $doc:expr])+)?
     // $(#[$meta:meta])*
     $pub:vis
@@ -17,9 +26,12 @@ macro_rules! __ffi_export__ {(
 ) => (
     $($(#[doc = $doc])+)?
     // $(#[$meta])*
+    $(#[$deprecated])?
+    $(#[cfg($debug_cfg)])?
+    $(#[$inline])?
     $pub
     $(unsafe $(@$hack@)?)?
-    extern "C"
+    extern $abi
     fn $fname $(<$($lt $(: $sup_lt)?),*>)? (
         $(
             $arg_name : $arg_ty,
@@ -31,42 +43,58 @@ macro_rules! __ffi_export__ {(
     )?
         $body
 
-    #[allow(dead_code, nonstandard_style, unused_parens)]
+    $crate::paste::item! {
+        /// The `#[ffi_export]`ed function, for `c_fn!` and
+        /// `register_exports_in!` to refer to.
+        #[doc(hidden)]
+        #[allow(dead_code, nonstandard_style)]
+        $pub
+        struct [< __ $fname _safer_ffi_export >] {}
+    }
+
+    #[allow(dead_code, deprecated, nonstandard_style, unused_parens)]
+    $(#[cfg($debug_cfg)])?
     const _: () = {
-        $($(#[doc = $doc])+)?
-        #[no_mangle]
-        pub
-        $(unsafe $(@$hack@)?)? /* Safety: function is not visible but to the linker */
-        extern "C"
-        fn $fname $(<$($lt $(: $sup_lt)?),*>)? (
-            $(
-                $arg_name : <$arg_ty as $crate::layout::ReprC>::CLayout,
-            )*
-        ) $(-> $Ret)?
-        where
-            $( $($bounds)* )?
-        {{
-            let body = /* #[inline(always)] */ || {
+        $crate::paste::item! {
+            type __Export__ = [< __ $fname _safer_ffi_export >];
+        }
+
+        impl __Export__ {
+            $($(#[doc = $doc])+)?
+            #[no_mangle]
+            $(#[$inline])?
+            pub
+            $(unsafe $(@$hack@)?)? /* Safety: function is not visible but to the linker */
+            extern $abi
+            fn $fname $(<$($lt $(: $sup_lt)?),*>)? (
                 $(
-                    {
-                        fn __return_type__<T> (_: T)
-                        where
-                            T : $crate::layout::ReprC,
-                            <T as $crate::layout::ReprC>::CLayout
-                            :
-                            $crate::layout::CType<
-                                OPAQUE_KIND = $crate::layout::OpaqueKind::Concrete,
-                            >,
-                        {}
-                        let _ = __return_type__::<$Ret>;
-                    }
-                    let _: <$Ret as $crate::layout::ReprC>::CLayout;
-                )?
+                    $arg_name : <$arg_ty as $crate::layout::ReprC>::CLayout,
+                )*
+            ) $(-> $Ret)?
+            where
+                $( $($bounds)* )?
+            {{
+                #[allow(unused_variables)]
+                let error_value = || {
+                    $crate::boundary::__error_value__::<($($Ret)?)>(
+                        $crate::core::stringify!($fname),
+                    )
+                };
                 $(
+                    let $arg_name = match $crate::boundary::__check_arg__::<$arg_ty>(
+                        $crate::core::stringify!($fname),
+                        $crate::core::stringify!($arg_name),
+                        $arg_name,
+                    )
                     {
-                        mod __parameter__ {
-                            pub(in super)
-                            fn $arg_name<T> (_: T)
+                        | $crate::core::option::Option::Some(it) => it,
+                        | $crate::core::option::Option::None => return error_value(),
+                    };
+                )*
+                let body = /* #[inline(always)] */ || {
+                    $(
+                        {
+                            fn __return_type__<T> (_: T)
                             where
                                 T : $crate::layout::ReprC,
                                 <T as $crate::layout::ReprC>::CLayout
@@ -75,141 +103,1215 @@ macro_rules! __ffi_export__ {(
                                     OPAQUE_KIND = $crate::layout::OpaqueKind::Concrete,
                                 >,
                             {}
+                            let _ = __return_type__::<$Ret>;
                         }
-                        let _ = __parameter__::$arg_name::<$arg_ty>;
-                    }
-                    #[allow(unused_unsafe)]
-                    let $arg_name: $arg_ty = unsafe {
-                        $crate::layout::from_raw_unchecked::<$arg_ty>(
-                            $arg_name,
-                        )
-                    };
+                        let _: <$Ret as $crate::layout::ReprC>::CLayout;
+                    )?
+                    $(
+                        {
+                            mod __parameter__ {
+                                pub(in super)
+                                fn $arg_name<T> (_: T)
+                                where
+                                    T : $crate::layout::ReprC,
+                                    <T as $crate::layout::ReprC>::CLayout
+                                    :
+                                    $crate::layout::CType<
+                                        OPAQUE_KIND = $crate::layout::OpaqueKind::Concrete,
+                                    >,
+                                {}
+                            }
+                            let _ = __parameter__::$arg_name::<$arg_ty>;
+                        }
+                        #[allow(unused_unsafe)]
+                        let $arg_name: $arg_ty = unsafe {
+                            $crate::layout::from_raw_unchecked::<$arg_ty>(
+                                $arg_name,
+                            )
+                        };
+                    )*
+                    $body
+                };
+                $crate::__ffi_export_profiled__!($fname
+                    $crate::__ffi_export_call__!($abi $fname body)
+                )
+            }}
+
+            #[inline]
+            pub
+            fn __erased__ ()
+              -> $crate::fn_ptr::ErasedFnPtr
+            {
+                unsafe {
+                    // Safety: non-null function pointer, and `ErasedFnPtr`
+                    // is a `#[repr(transparent)]` function pointer.
+                    $crate::core::mem::transmute::<
+                        *const (),
+                        $crate::fn_ptr::ErasedFnPtr,
+                    >(
+                        Self::$fname as *const ()
+                    )
+                }
+            }
+        }
+
+        $(
+            /// Keeps the exported function when linking as a `staticlib`.
+            #[$used]
+            static __USED__: $crate::__Used = $crate::__Used(
+                __Export__::$fname as *const ()
+            );
+        )?
+
+        $crate::__ffi_export_system_abi__! {
+            $([$system_fname])?
+            $(@used[$used])?
+            __Export__::$fname [$(<$($lt $(: $sup_lt)?),*>)?] (
+                $(
+                    $arg_name : <$arg_ty as $crate::layout::ReprC>::CLayout,
                 )*
-                $body
-            };
-            let guard = {
-                struct $fname;
-                impl $crate::core::ops::Drop
-                    for $fname
+            ) [$(-> $Ret)?]
+            where [$( $($bounds)* )?]
+        }
+    };
+
+    $crate::__cfg_headers__! {
+        #[allow(dead_code, deprecated, nonstandard_style)]
+        const _: () = {
+            $crate::paste::item! {
+                type __Export__ = [< __ $fname _safer_ffi_export >];
+            }
+
+            impl __Export__ {
+                // Also called by the function generated by `register_exports_in!`.
+                #[doc(hidden)] /** Not part of the public API **/ pub
+                fn __define_self__ (
+                    definer: &'_ mut dyn $crate::headers::Definer,
+                ) -> $crate::std::io::Result<()>
                 {
-                    fn drop (self: &'_ mut Self)
-                    {
-                        $crate::__abort_with_msg__!($crate::core::concat!(
-                            "Error, attempted to panic across the FFI ",
-                            "boundary of `",
+                    #[allow(unused_parens)]
+                    fn typedef $(<$($lt $(: $sup_lt)?),*>)? (
+                        definer: &'_ mut dyn $crate::headers::Definer,
+                    ) -> $crate::std::io::Result<()>
+                    {Ok({
+                        if ! $crate::headers::__in_group__(
+                            $crate::core::module_path!(),
+                            None $(.or(Some($group)))?,
+                        )
+                        {
+                            return $crate::core::result::Result::Ok(());
+                        }
+                        // FIXME: this merges the value namespace with the type
+                        // namespace...
+                        if ! definer.insert($crate::core::stringify!($fname)) {
+                            return $crate::core::result::Result::Err(
+                                $crate::std::io::Error::new(
+                                    $crate::std::io::ErrorKind::AlreadyExists,
+                                    $crate::core::concat!(
+                                        "Error, attempted to declare `",
+                                        $crate::core::stringify!($fname),
+                                        "` while another declaration already exists",
+                                    ),
+                                )
+                            );
+                        }
+                        $crate::headers::__lint_item__(
+                            definer,
                             $crate::core::stringify!($fname),
-                            "()`, ",
-                            "which is Undefined Behavior.\n",
-                            "Aborting for soundness.",
-                        ));
-                    }
+                            Some(!$crate::core::stringify!($($($doc)+)?).is_empty()),
+                            &[
+                                $crate::core::stringify!($fname),
+                                $($crate::core::stringify!($arg_name),)*
+                            ],
+                        );
+                        if $crate::headers::__language__()
+                            != $crate::headers::Language::C
+                        {
+                            return $crate::headers::__declare_fn__(
+                                definer,
+                                &[$($($doc),+)?],
+                                $crate::core::stringify!($fname),
+                                $crate::headers::TypeDesc::of::<
+                                    <($($Ret)?) as $crate::layout::ReprC>::CLayout
+                                >(),
+                                &[$(
+                                    (
+                                        $crate::core::stringify!($arg_name),
+                                        $crate::headers::TypeDesc::of::<
+                                            <$arg_ty as $crate::layout::ReprC>::CLayout
+                                        >(),
+                                    ),
+                                )*],
+                            );
+                        }
+                        $(
+                            <
+                                <$arg_ty as $crate::layout::ReprC>::CLayout
+                                as
+                                $crate::layout::CType
+                            >::c_define_self(definer)?;
+                        )*
+                        $(
+                            <
+                                <$Ret as $crate::layout::ReprC>::CLayout
+                                as
+                                $crate::layout::CType
+                            >::c_define_self(definer)?;
+                        )?
+                        // The `extern "system"` variant, if any.
+                        let system_fname: Option<&'static str> = None $(.or(Some(
+                            $crate::core::stringify!($system_fname),
+                        )))?;
+                        if let Some(system_fname) = system_fname {
+                            if ! definer.insert(system_fname) {
+                                return $crate::core::result::Result::Err(
+                                    $crate::std::io::Error::new(
+                                        $crate::std::io::ErrorKind::AlreadyExists,
+                                        $crate::std::format!(
+                                            "Error, attempted to declare `{}` \
+                                            while another declaration already exists",
+                                            system_fname,
+                                        ),
+                                    )
+                                );
+                            }
+                            $crate::headers::__define_system_abi__(definer)?;
+                        }
+                        let doc_group: Option<&'static str> =
+                            None $(.or(Some($doc_group)))?
+                        ;
+                        if let Some(doc_group) = doc_group {
+                            $crate::headers::__define_doc_group__(definer, doc_group)?;
+                        }
+                        let deprecated: Option<&'static str> =
+                            None $(.or(Some($deprecated_note)))?
+                        ;
+                        if deprecated.is_some() {
+                            $crate::headers::__define_deprecated__(definer)?;
+                        }
+                        let abi = $crate::headers::__abi_annotation__(definer, $abi)?;
+                        // The type definitions are over: what follows is the
+                        // declaration itself.
+                        definer.set_symbol_kind(
+                            $crate::core::stringify!($fname),
+                            $crate::headers::SymbolKind::Function,
+                        );
+                        $crate::headers::__report_destructor__(
+                            definer,
+                            $crate::core::stringify!($fname),
+                            &[$(
+                                {
+                                    use $crate::headers::__owned_probe__::*;
+                                    (
+                                        (&__OwnedProbe__::<$arg_ty>(
+                                            $crate::core::marker::PhantomData,
+                                        ))
+                                            .__owned_kind__()
+                                        ,
+                                        $crate::std::string::ToString::to_string(&<
+                                            <$arg_ty as $crate::layout::ReprC>::CLayout
+                                            as
+                                            $crate::layout::CType
+                                        >::c_var("")),
+                                    )
+                                },
+                            )*],
+                            !$crate::core::stringify!($($Ret)?).is_empty(),
+                        );
+                        $crate::headers::__report_signature__(
+                            definer,
+                            $crate::core::stringify!($fname),
+                            &[$(
+                                (
+                                    $crate::core::stringify!($arg_name),
+                                    $crate::std::string::ToString::to_string(&<
+                                        <$arg_ty as $crate::layout::ReprC>::CLayout
+                                        as
+                                        $crate::layout::CType
+                                    >::c_var("")),
+                                ),
+                            )*],
+                            $crate::std::string::ToString::to_string(&<
+                                <($($Ret)?) as $crate::layout::ReprC>::CLayout
+                                as
+                                $crate::layout::CType
+                            >::c_var("")),
+                        );
+                        if let Some(system_fname) = system_fname {
+                            definer.set_symbol_kind(
+                                system_fname,
+                                $crate::headers::SymbolKind::Function,
+                            );
+                        }
+                        if !$crate::core::stringify!($($used)?).is_empty() {
+                            definer.set_used($crate::core::stringify!($fname));
+                            if let Some(system_fname) = system_fname {
+                                definer.set_used(system_fname);
+                            }
+                        }
+                        let out = definer.out();
+                        // Whether the function is only compiled in debug builds.
+                        let debug_only = false $(|| {
+                            let _ = $crate::core::stringify!($debug_cfg);
+                            true
+                        })?;
+                        if debug_only {
+                            $crate::std::io::Write::write_all(out,
+                                b"#ifndef NDEBUG\n",
+                            )?;
+                        }
+                        // The preconditions of the function, if documented.
+                        let mut contracts = $crate::headers::__contracts__(&[$(
+                            (
+                                $crate::core::stringify!($arg_name),
+                                $crate::headers::__is_nonnull__::<$arg_ty>(),
+                            ),
+                        )*]);
+                        // As well as the pinning of the `PinBox`es.
+                        contracts.extend({
+                            use $crate::headers::__owned_probe__::*;
+                            $crate::headers::__pinned__(
+                                &[$(
+                                    (
+                                        $crate::core::stringify!($arg_name),
+                                        (&__OwnedProbe__::<$arg_ty>(
+                                            $crate::core::marker::PhantomData,
+                                        ))
+                                            .__owned_kind__()
+                                        ,
+                                    ),
+                                )*],
+                                (&__OwnedProbe__::<($($Ret)?)>(
+                                    $crate::core::marker::PhantomData,
+                                ))
+                                    .__owned_kind__()
+                                ,
+                            )
+                        });
+                        let docs = $crate::headers::__doc_lines__(
+                            $crate::core::stringify!($fname),
+                            &[$($($doc),+)?],
+                        );
+                        if !docs.is_empty() {
+                            $crate::std::io::Write::write_all(out,
+                                b"/** \\brief\n",
+                            )?;
+                            for line in &docs {
+                                $crate::core::write!(out, " * {}\n", line)?;
+                            }
+                            if !contracts.is_empty() {
+                                $crate::std::io::Write::write_all(out, b" * \n")?;
+                                for contract in &contracts {
+                                    $crate::core::write!(out, " *  {}\n", contract)?;
+                                }
+                            }
+                            if let Some(doc_group) = doc_group {
+                                $crate::core::write!(out,
+                                    " * \n *  \\ingroup {}\n", doc_group,
+                                )?;
+                            }
+                            $crate::std::io::Write::write_all(out,
+                                b" */\n",
+                            )?;
+                        } else if !contracts.is_empty() {
+                            $crate::std::io::Write::write_all(out, b"/**\n")?;
+                            for contract in &contracts {
+                                $crate::core::write!(out, " *  {}\n", contract)?;
+                            }
+                            if let Some(doc_group) = doc_group {
+                                $crate::core::write!(out,
+                                    " * \n *  \\ingroup {}\n", doc_group,
+                                )?;
+                            }
+                            $crate::std::io::Write::write_all(out, b" */\n")?;
+                        } else if let Some(doc_group) = doc_group {
+                            $crate::core::write!(out,
+                                "/** \\ingroup {} */\n", doc_group,
+                            )?;
+                        }
+
+                        let declare = |
+                            out: &'_ mut dyn $crate::std::io::Write,
+                            fname: &'_ str,
+                            abi: &'_ str,
+                        | -> $crate::std::io::Result<()>
+                        {
+                            $crate::core::write!(out,
+                                "{}", $crate::headers::__deprecated__(deprecated),
+                            )?;
+                            if let $crate::core::option::Option::Some(export_macro) =
+                                $crate::headers::__export_macro__()
+                            {
+                                $crate::core::write!(out, "{} ", export_macro)?;
+                            }
+                            $crate::core::write!(out,
+                                "{} (",
+                                <
+                                    <($($Ret)?) as $crate::layout::ReprC>::CLayout
+                                    as
+                                    $crate::layout::CType
+                                >::c_var(&$crate::std::format!("{}{}{}",
+                                    $crate::headers::__nullability__::<($($Ret)?)>(),
+                                    abi,
+                                    fname,
+                                )),
+                            )?;
+                            let mut has_args = false; has_args = has_args;
+                            $(
+                                $crate::core::write!(out,
+                                    "{comma}\n    {arg}",
+                                    comma = if has_args { "," } else { "" },
+                                    arg = <
+                                            <$arg_ty as $crate::layout::ReprC>::CLayout
+                                            as
+                                            $crate::layout::CType
+                                        >::c_var($crate::std::format!("{}{}",
+                                            $crate::headers::__nullability__::<$arg_ty>(),
+                                            {
+                                                let it = stringify!($arg_name);
+                                                $crate::headers::__c_escape__(
+                                                    if it == "_" { "" } else { it }
+                                                )
+                                            },
+                                        ).trim_end())
+                                    ,
+                                )?;
+                                has_args |= true;
+                            )*
+                            if !has_args {
+                                out.write_all(b"void")?;
+                            }
+                            drop(has_args);
+                            $crate::core::write!(out,
+                                "){};\n",
+                                $crate::headers::__swift_name__(
+                                    fname,
+                                    &[$($crate::core::stringify!($arg_name)),*],
+                                ),
+                            )?;
+                            let c_name = $crate::headers::__fn_name__(fname);
+                            if c_name != fname {
+                                $crate::core::write!(out,
+                                    "#define {} {}\n",
+                                    c_name, fname,
+                                )?;
+                            }
+                            Ok(())
+                        };
+                        declare(out, $crate::core::stringify!($fname), abi)?;
+                        if let Some(system_fname) = system_fname {
+                            $crate::core::write!(out,
+                                "\n/** \\brief\n \
+                                *  `{}`, with the `system` calling convention.\n \
+                                */\n",
+                                $crate::core::stringify!($fname),
+                            )?;
+                            declare(out, system_fname, "SAFER_FFI_SYSTEM_ABI ")?;
+                        }
+                        if debug_only {
+                            out.write_all(b"#endif /* NDEBUG */\n")?;
+                        }
+                        out.write_all(b"\n")?;
+                    })}
+                    typedef(definer)
+                }
+            }
+
+            $crate::inventory::submit! {
+                #![crate = $crate]
+                $crate::FfiExport(<__Export__>::__define_self__)
+            }
+        };
+    }
+    $crate::__ffi_export_batched__! {
+        $([$batch_fname])?
+        @abi[$abi]
+        $(@debug_only[$debug_cfg])?
+        $(@used[$used])?
+        $(@group[$group])?
+        $(@doc_group[$doc_group])?
+        $(@deprecated[$deprecated_note, $deprecated])?
+        [$(unsafe $(@$hack@)?)?]
+        $fname [$($($lt $(: $sup_lt)?),*)?] (
+            $(
+                $arg_name : $arg_ty,
+            )*
+        ) [$(-> $Ret)?]
+        where [$( $($bounds)* )?]
+    }
+)}
+
+/// `#[ffi_export(transparent_wrapper)]`: check that the exported wrapper is
+/// a no-op, _i.e._, that the types of the signature are their own `CLayout`
+/// (so that no conversion nor validity check is involved), and make it
+/// `#[inline]` (or `#[inline(always)]`, _etc._, as specified).
+#[doc(hidden)] #[macro_export]
+macro_rules! __ffi_export_transparent__ {(
+    @abi[$abi:tt]
+    $(@debug_only[$debug_cfg:meta])?
+    @inline[$inline:meta]
+    $(@used[$used:ident])?
+    $(@system_abi[$system_fname:ident])?
+    $(@group[$group:literal])?
+    $(@doc_group[$doc_group:literal])?
+    $(@batched[$batch_fname:ident])?
+    $(@deprecated[$deprecated_note:literal, $deprecated:meta])?
+    $($(#[doc = $doc:expr])+)?
+    $pub:vis
+    $(unsafe $(@$hack:ident@)?)?
+    $(extern $("C")?)?
+    fn $fname:ident $(<$($lt:lifetime $(: $sup_lt:lifetime)?),* $(,)?>)? (
+        $(
+            $arg_name:ident : $arg_ty:ty
+        ),* $(,)?
+    ) $(-> $Ret:ty)?
+        $body:block
+) => (
+    #[allow(dead_code, deprecated, nonstandard_style)]
+    const _: () = {
+        fn transparent<T : $crate::layout::ReprC<CLayout = T>> ()
+        {}
+
+        fn $fname $(<$($lt $(: $sup_lt)?),*>)? ()
+        {
+            $(
+                transparent::<$arg_ty>();
+            )*
+            $(
+                transparent::<$Ret>();
+            )?
+        }
+    };
+
+    $crate::__ffi_export__! {
+        @abi[$abi]
+        $(@debug_only[$debug_cfg])?
+        @inline[$inline]
+        $(@used[$used])?
+        $(@system_abi[$system_fname])?
+        $(@group[$group])?
+        $(@doc_group[$doc_group])?
+        $(@batched[$batch_fname])?
+        $(@deprecated[$deprecated_note, $deprecated])?
+        $($(#[doc = $doc])+)?
+        $pub
+        $(unsafe $(@$hack@)?)?
+        fn $fname $(<$($lt $(: $sup_lt)?),*>)? (
+            $(
+                $arg_name : $arg_ty,
+            )*
+        ) $(-> $Ret)?
+            $body
+    }
+)}
+
+/// Calls the `body` of an exported function: with the `"C"` (or `"system"`)
+/// ABI, aborting the process should it panic (unwinding across the FFI
+/// boundary being Undefined Behavior), and, with the `"C-unwind"` one,
+/// letting the panic unwind into the caller.
+#[doc(hidden)] #[macro_export]
+macro_rules! __ffi_export_call__ {
+    (
+        "C" $fname:ident $body:ident
+    ) => ({
+        let guard = {
+            struct $fname;
+            impl $crate::core::ops::Drop
+                for $fname
+            {
+                fn drop (self: &'_ mut Self)
+                {
+                    $crate::__abort_with_msg__!($crate::core::concat!(
+                        "Error, attempted to panic across the FFI ",
+                        "boundary of `",
+                        $crate::core::stringify!($fname),
+                        "()`, ",
+                        "which is Undefined Behavior.\n",
+                        "Aborting for soundness.",
+                    ));
+                }
+            }
+            $fname
+        };
+        let ret = $body();
+        $crate::core::mem::forget(guard);
+        ret
+    });
+
+    (
+        "system" $fname:ident $body:ident
+    ) => (
+        $crate::__ffi_export_call__!("C" $fname $body)
+    );
+
+    (
+        "C-unwind" $fname:ident $body:ident
+    ) => (
+        $body()
+    );
+}
+
+/// With the `profile-ffi` feature, records the call (see
+/// [`profile`][`crate::profile`]) of the exported function `$fname`.
+#[cfg(feature = "profile-ffi")]
+#[doc(hidden)] #[macro_export]
+macro_rules! __ffi_export_profiled__ {(
+    $fname:ident $call:expr
+) => ({
+    static STATS: $crate::profile::FnStats = $crate::profile::FnStats::new(
+        $crate::core::concat!($crate::core::stringify!($fname), "\0"),
+    );
+    $crate::inventory::submit! {
+        #![crate = $crate]
+        $crate::profile::Registered(&STATS)
+    }
+    let start = $crate::std::time::Instant::now();
+    let ret = $call;
+    STATS.record(start.elapsed());
+    ret
+})}
+#[cfg(not(feature = "profile-ffi"))]
+#[doc(hidden)] #[macro_export]
+macro_rules! __ffi_export_profiled__ {(
+    $fname:ident $call:expr
+) => (
+    $call
+)}
+
+/// `a::b::f => suffix...` becomes `a::b::__f_safer_ffi_export suffix...`:
+/// the path to the type through which `#[ffi_export]` exposes `f`.
+#[doc(hidden)] #[macro_export]
+macro_rules! __ffi_export_path__ {
+    (
+        [$($prefix:tt)*] $fname:ident => @fname
+    ) => (
+        $crate::paste::expr! {
+            $($prefix)* [< __ $fname _safer_ffi_export >]::$fname
+        }
+    );
+
+    (
+        [$($prefix:tt)*] $fname:ident => $($suffix:tt)*
+    ) => (
+        $crate::paste::expr! {
+            $($prefix)* [< __ $fname _safer_ffi_export >] $($suffix)*
+        }
+    );
+
+    (
+        [$($prefix:tt)*] $module:ident :: $($rest:tt)*
+    ) => (
+        $crate::__ffi_export_path__!([$($prefix)* $module ::] $($rest)*)
+    );
+}
+
+/// `#[ffi_export(system_abi)]`: the `extern "system"` variant of the exported
+/// function, forwarding to the `extern "C"` one (at path `$fname`).
+#[doc(hidden)] #[macro_export]
+macro_rules! __ffi_export_system_abi__ {
+    (
+        $(@used[$used:ident])?
+        $fname:path [$($generics:tt)*] $params:tt [$($ret:tt)*]
+        where [$($bounds:tt)*]
+    ) => (
+        // No `system_abi`.
+    );
+
+    (
+        [$system_fname:ident]
+        $(@used[$used:ident])?
+        $fname:path [$($generics:tt)*] (
+            $(
+                $arg_name:ident : $arg_ty:ty,
+            )*
+        ) [$($ret:tt)*]
+        where [$($bounds:tt)*]
+    ) => (
+        #[no_mangle]
+        pub
+        extern "system"
+        fn $system_fname $($generics)* (
+            $(
+                $arg_name : $arg_ty,
+            )*
+        ) $($ret)*
+        where
+            $($bounds)*
+        {
+            #[allow(unused_unsafe)]
+            unsafe {
+                // Safety: same contract as the function it forwards to.
+                $fname($($arg_name),*)
+            }
+        }
+
+        $(
+            /// Keeps the exported function when linking as a `staticlib`.
+            #[$used]
+            static __USED_SYSTEM_ABI__: $crate::__Used = $crate::__Used(
+                $system_fname as *const ()
+            );
+        )?
+    );
+}
+
+/// `#[ffi_export(batched)]`: the `_batch` variant of the exported function,
+/// taking a slice per parameter (and a slice for the results), and calling
+/// the function on each element, on the Rust side.
+#[doc(hidden)] #[macro_export]
+macro_rules! __ffi_export_batched__ {
+    (
+        @abi $($rest:tt)*
+    ) => (
+        // No `batched`.
+    );
+
+    (
+        [$batch_fname:ident]
+        @abi[$abi:tt]
+        $(@$prefix:ident[$($arg:tt)*])*
+        [$($unsafe:tt)*]
+        $fname:ident [$($generics:tt)*] () [] where $bounds:tt
+    ) => (
+        $crate::core::compile_error!(
+            "`batched` requires parameters or a return value"
+        );
+    );
+
+    (
+        [$batch_fname:ident]
+        @abi[$abi:tt]
+        $(@debug_only[$debug_cfg:meta])?
+        $(@used[$used:ident])?
+        $(@group[$group:literal])?
+        $(@doc_group[$doc_group:literal])?
+        $(@deprecated[$deprecated_note:literal, $deprecated:meta])?
+        [$(unsafe $(@$hack:ident@)?)?]
+        $fname:ident [$($lt:lifetime $(: $sup_lt:lifetime)?),*] (
+            $(
+                $arg_name:ident : $arg_ty:ty,
+            )*
+        ) [$(-> $Ret:ty)?]
+        where [$($bounds:tt)*]
+    ) => (
+        $crate::__ffi_export__! {
+            @abi[$abi]
+            $(@debug_only[$debug_cfg])?
+            $(@used[$used])?
+            $(@group[$group])?
+            $(@doc_group[$doc_group])?
+            $(@deprecated[$deprecated_note, $deprecated])?
+            #[doc = $crate::core::concat!(
+                "`", $crate::core::stringify!($fname), "`, batched: calls it ",
+                "with the elements of the given slices",
+            )]
+            #[doc = " (writing the results to `outputs`), and returns the number of calls,"]
+            #[doc = " _i.e._, the length of the shortest slice."]
+            pub
+            $(unsafe $(@$hack@)?)?
+            fn $batch_fname <'__batch, $($lt : '__batch),*> (
+                $(
+                    $arg_name : $crate::slice::slice_ref<'__batch, $arg_ty>,
+                )*
+                $(
+                    outputs : $crate::slice::slice_mut<'__batch, $Ret>,
+                )?
+            ) -> usize
+            where {
+                $($($lt : $sup_lt,)?)*
+                $($bounds)*
+            }
+            {
+                fn __copy__<T : $crate::core::marker::Copy> ()
+                {}
+                let len = $crate::core::usize::MAX;
+                $(
+                    __copy__::<$arg_ty>();
+                    let len = len.min($arg_name.len());
+                )*
+                $(
+                    __copy__::<$Ret>();
+                    let mut outputs = outputs;
+                    let len = len.min(outputs.len());
+                )?
+                for i in 0 .. len {
+                    #[allow(deprecated, unused_unsafe, unused_variables)]
+                    let ret = unsafe {
+                        // Safety: same contract as the batched function.
+                        $fname($($arg_name[i]),*)
+                    };
+                    $(
+                        let ret: $Ret = ret;
+                        outputs[i] = ret;
+                    )?
                 }
-                $fname
-            };
-            let ret = body();
-            $crate::core::mem::forget(guard);
-            ret
-        }}
+                len
+            }
+        }
+    );
+}
+
+/// `#[ffi_export]` on a `static`: export it as a (`const`) C symbol,
+/// declared `extern` in the headers.
+#[doc(hidden)] #[macro_export]
+macro_rules! __ffi_export_static__ {(
+    $(@used[$used:ident])?
+    $(@group[$group:literal])?
+    $(@doc_group[$doc_group:literal])?
+    $(@deprecated[$deprecated_note:literal, $deprecated:meta])?
+    $($(#[doc = $doc:expr])+)?
+    $pub:vis
+    static $NAME:ident : $T:ty = $value:expr ;
+) => (
+    $($(#[doc = $doc])+)?
+    $(#[$deprecated])?
+    #[no_mangle]
+    $(#[$used])?
+    $pub
+    static $NAME : $T = $value;
+
+    #[allow(dead_code, deprecated, nonstandard_style)]
+    const _: () = {
+        fn __static_type__<T> ()
+        where
+            T : $crate::layout::ReprC + $crate::core::marker::Sync,
+            <T as $crate::layout::ReprC>::CLayout
+            :
+            $crate::layout::CType<
+                OPAQUE_KIND = $crate::layout::OpaqueKind::Concrete,
+            >,
+        {}
+        let _ = __static_type__::<$T>;
     };
 
     $crate::__cfg_headers__! {
         $crate::inventory::submit! {
             #![crate = $crate]
             $crate::FfiExport({
-                #[allow(unused_parens)]
-                fn typedef $(<$($lt $(: $sup_lt)?),*>)? (
+                #[allow(deprecated)]
+                fn typedef (
                     definer: &'_ mut dyn $crate::headers::Definer,
                 ) -> $crate::std::io::Result<()>
                 {Ok({
-                    // FIXME: this merges the value namespace with the type
-                    // namespace...
-                    if ! definer.insert($crate::core::stringify!($fname)) {
+                    if ! $crate::headers::__in_group__(
+                        $crate::core::module_path!(),
+                        None $(.or(Some($group)))?,
+                    )
+                    {
+                        return $crate::core::result::Result::Ok(());
+                    }
+                    if ! definer.insert($crate::core::stringify!($NAME)) {
                         return $crate::core::result::Result::Err(
                             $crate::std::io::Error::new(
                                 $crate::std::io::ErrorKind::AlreadyExists,
                                 $crate::core::concat!(
                                     "Error, attempted to declare `",
-                                    $crate::core::stringify!($fname),
+                                    $crate::core::stringify!($NAME),
                                     "` while another declaration already exists",
                                 ),
                             )
                         );
                     }
-                    $(
-                        <
-                            <$arg_ty as $crate::layout::ReprC>::CLayout
-                            as
-                            $crate::layout::CType
-                        >::c_define_self(definer)?;
-                    )*
-                    $(
+                    $crate::headers::__lint_item__(
+                        definer,
+                        $crate::core::stringify!($NAME),
+                        Some(!$crate::core::stringify!($($($doc)+)?).is_empty()),
+                        &[$crate::core::stringify!($NAME)],
+                    );
+                    if $crate::headers::__language__()
+                        != $crate::headers::Language::C
+                    {
+                        return $crate::headers::__declare_static__(
+                            definer,
+                            &[$($($doc),+)?],
+                            $crate::core::stringify!($NAME),
+                            $crate::headers::TypeDesc::of::<
+                                <$T as $crate::layout::ReprC>::CLayout
+                            >(),
+                        );
+                    }
+                    <
+                        <$T as $crate::layout::ReprC>::CLayout
+                        as
+                        $crate::layout::CType
+                    >::c_define_self(definer)?;
+                    let doc_group: Option<&'static str> =
+                        None $(.or(Some($doc_group)))?
+                    ;
+                    if let Some(doc_group) = doc_group {
+                        $crate::headers::__define_doc_group__(definer, doc_group)?;
+                    }
+                    let deprecated: Option<&'static str> =
+                        None $(.or(Some($deprecated_note)))?
+                    ;
+                    if deprecated.is_some() {
+                        $crate::headers::__define_deprecated__(definer)?;
+                    }
+                    definer.set_symbol_kind(
+                        $crate::core::stringify!($NAME),
+                        $crate::headers::SymbolKind::Static,
+                    );
+                    if !$crate::core::stringify!($($used)?).is_empty() {
+                        definer.set_used($crate::core::stringify!($NAME));
+                    }
+                    let out = definer.out();
+                    let docs = $crate::headers::__doc_lines__(
+                        $crate::core::stringify!($NAME),
+                        &[$($($doc),+)?],
+                    );
+                    if !docs.is_empty() {
+                        $crate::std::io::Write::write_all(out,
+                            b"/** \\brief\n",
+                        )?;
+                        for line in &docs {
+                            $crate::core::write!(out, " * {}\n", line)?;
+                        }
+                        if let Some(doc_group) = doc_group {
+                            $crate::core::write!(out,
+                                " * \n *  \\ingroup {}\n", doc_group,
+                            )?;
+                        }
+                        $crate::std::io::Write::write_all(out,
+                            b" */\n",
+                        )?;
+                    } else if let Some(doc_group) = doc_group {
+                        $crate::core::write!(out,
+                            "/** \\ingroup {} */\n", doc_group,
+                        )?;
+                    }
+                    $crate::core::write!(out,
+                        "{}extern {}{};\n\n",
+                        $crate::headers::__deprecated__(deprecated),
+                        $crate::headers::__export_macro__()
+                            .map_or($crate::std::string::String::new(), |it| {
+                                $crate::std::format!("{} ", it)
+                            })
+                        ,
                         <
-                            <$Ret as $crate::layout::ReprC>::CLayout
+                            <$T as $crate::layout::ReprC>::CLayout
                             as
                             $crate::layout::CType
-                        >::c_define_self(definer)?;
-                    )?
+                        >::c_var($crate::core::concat!(
+                            "const ", $crate::core::stringify!($NAME),
+                        )),
+                    )?;
+                })}
+                typedef
+            })
+        }
+    }
+)}
+
+/// `#[ffi_export]` on a `const`: `#define` it in the headers, as its
+/// [`CLiteral`][`crate::headers::CLiteral`].
+#[doc(hidden)] #[macro_export]
+macro_rules! __ffi_export_const__ {(
+    $(@group[$group:literal])?
+    $(@doc_group[$doc_group:literal])?
+    $(@deprecated[$deprecated_note:literal, $deprecated:meta])?
+    $($(#[doc = $doc:expr])+)?
+    $pub:vis
+    const $NAME:ident : $T:ty = $value:expr ;
+) => (
+    $($(#[doc = $doc])+)?
+    $(#[$deprecated])?
+    $pub
+    const $NAME : $T = $value;
+
+    $crate::__cfg_headers__! {
+        $crate::inventory::submit! {
+            #![crate = $crate]
+            $crate::FfiExport({
+                #[allow(deprecated)]
+                fn typedef (
+                    definer: &'_ mut dyn $crate::headers::Definer,
+                ) -> $crate::std::io::Result<()>
+                {Ok({
+                    if ! $crate::headers::__in_group__(
+                        $crate::core::module_path!(),
+                        None $(.or(Some($group)))?,
+                    )
+                    {
+                        return $crate::core::result::Result::Ok(());
+                    }
+                    if ! definer.insert($crate::core::stringify!($NAME)) {
+                        return $crate::core::result::Result::Err(
+                            $crate::std::io::Error::new(
+                                $crate::std::io::ErrorKind::AlreadyExists,
+                                $crate::core::concat!(
+                                    "Error, attempted to declare `",
+                                    $crate::core::stringify!($NAME),
+                                    "` while another declaration already exists",
+                                ),
+                            )
+                        );
+                    }
+                    $crate::headers::__lint_item__(
+                        definer,
+                        $crate::core::stringify!($NAME),
+                        Some(!$crate::core::stringify!($($($doc)+)?).is_empty()),
+                        &[$crate::core::stringify!($NAME)],
+                    );
+                    if $crate::headers::__language__()
+                        != $crate::headers::Language::C
+                    {
+                        return $crate::headers::__declare_const__(
+                            definer,
+                            $crate::core::stringify!($NAME),
+                        );
+                    }
+                    let literal =
+                        $crate::headers::CLiteral::c_literal(&$NAME)
+                            .ok_or_else(|| $crate::std::io::Error::new(
+                                $crate::std::io::ErrorKind::InvalidData,
+                                $crate::core::concat!(
+                                    "Error, the value of `",
+                                    $crate::core::stringify!($NAME),
+                                    "` cannot be written as a C literal",
+                                ),
+                            ))?
+                    ;
+                    let doc_group: Option<&'static str> =
+                        None $(.or(Some($doc_group)))?
+                    ;
+                    if let Some(doc_group) = doc_group {
+                        $crate::headers::__define_doc_group__(definer, doc_group)?;
+                    }
+                    definer.set_symbol_kind(
+                        $crate::core::stringify!($NAME),
+                        $crate::headers::SymbolKind::Macro,
+                    );
                     let out = definer.out();
-                    $(
+                    let docs = $crate::headers::__doc_lines__(
+                        $crate::core::stringify!($NAME),
+                        &[$($($doc),+)?],
+                    );
+                    if !docs.is_empty() {
                         $crate::std::io::Write::write_all(out,
                             b"/** \\brief\n",
                         )?;
-                        $(
+                        for line in &docs {
+                            $crate::core::write!(out, " * {}\n", line)?;
+                        }
+                        if let Some(doc_group) = doc_group {
                             $crate::core::write!(out,
-                                " * {}\n", $doc,
+                                " * \n *  \\ingroup {}\n", doc_group,
                             )?;
-                        )+
+                        }
                         $crate::std::io::Write::write_all(out,
                             b" */\n",
                         )?;
-                    )?
-
+                    } else if let Some(doc_group) = doc_group {
+                        $crate::core::write!(out,
+                            "/** \\ingroup {} */\n", doc_group,
+                        )?;
+                    }
                     $crate::core::write!(out,
-                        "{} (",
+                        "#define {} {}\n\n",
+                        $crate::core::stringify!($NAME),
+                        // Negative numbers need to be parenthesized.
+                        if literal.starts_with('-') {
+                            $crate::std::format!("({})", literal)
+                        } else {
+                            literal
+                        },
+                    )?;
+                })}
+                typedef
+            })
+        }
+    }
+)}
+
+/// `#[derive_ReprC(optional_fields)]`: emit, for each `#[optional]` field,
+/// a `#define STRUCT_NAME_FIELD_PRESENT` bit (of the `present` field) and a
+/// `#define STRUCT_NAME_HAS_FIELD(it)` accessor in the headers.
+#[doc(hidden)] #[macro_export]
+macro_rules! __ffi_export_presence__ {(
+    $StructName:ident [$($field_name:ident),* $(,)?]
+) => (
+    $crate::__cfg_headers__! {
+        $crate::inventory::submit! {
+            #![crate = $crate]
+            $crate::FfiExport({
+                fn typedef (
+                    definer: &'_ mut dyn $crate::headers::Definer,
+                ) -> $crate::std::io::Result<()>
+                {
+                    if $crate::headers::__language__()
+                        != $crate::headers::Language::C
+                    || ! $crate::headers::__in_group__(
+                        $crate::core::module_path!(),
+                        None,
+                    )
+                    {
+                        return $crate::core::result::Result::Ok(());
+                    }
+                    <
+                        <$StructName as $crate::layout::ReprC>::CLayout
+                        as
+                        $crate::layout::CType
+                    >::c_define_self(definer)?;
+                    let me = &
                         <
-                            <($($Ret)?) as $crate::layout::ReprC>::CLayout
+                            <$StructName as $crate::layout::ReprC>::CLayout
                             as
                             $crate::layout::CType
-                        >::c_var($crate::core::stringify!($fname)),
-                    )?;
-                    // $crate::std::io::Write::write_all(out,
-                    //     $crate::core::concat!($crate::core::stringify!($fname), " (")
-                    //         .as_bytes()
-                    //     ,
-                    // )?;
-                    let mut has_args = false; has_args = has_args;
+                        >::c_short_name().to_string()
+                    ;
+                    let mut bit = 0_u32;
                     $(
-                        $crate::core::write!(out,
-                            "{comma}\n    {arg}",
-                            comma = if has_args { "," } else { "" },
-                            arg = <
-                                    <$arg_ty as $crate::layout::ReprC>::CLayout
-                                    as
-                                    $crate::layout::CType
-                                >::c_var({
-                                    let it = stringify!($arg_name);
-                                    if it == "_" { "" } else { it }
-                                })
-                            ,
-                        )?;
-                        has_args |= true;
+                        let field_name = $crate::core::stringify!($field_name);
+                        let present = &$crate::std::string::ToString::to_string(
+                            &$crate::__utils__::screaming_case(
+                                me,
+                                &$crate::std::format!("_{}_present", field_name),
+                            ),
+                        );
+                        let has = &$crate::std::string::ToString::to_string(
+                            &$crate::__utils__::screaming_case(
+                                me,
+                                &$crate::std::format!("Has_{}", field_name),
+                            ),
+                        );
+                        definer.define_once(present, &mut |definer| {
+                            definer.set_symbol_kind(
+                                present,
+                                $crate::headers::SymbolKind::Macro,
+                            );
+                            $crate::core::write!(definer.out(),
+                                $crate::core::concat!(
+                                    "/** \\brief\n",
+                                    " *  Bit of `{ty}.present` telling ",
+                                    "whether `{field}` is provided.\n",
+                                    " */\n",
+                                    "#define {present} (UINT64_C(1) << {bit})\n",
+                                    "\n",
+                                    "#define {has}(it) ",
+                                    "((((it).present) & {present}) != 0)\n",
+                                    "\n",
+                                ),
+                                ty = $crate::headers::__type_name__(me),
+                                field = field_name,
+                                present = present,
+                                has = has,
+                                bit = bit,
+                            )
+                        })?;
+                        bit += 1;
                     )*
-                    if has_args.not() {
-                        out.write_all(b"void")?;
+                    let _ = bit;
+                    $crate::core::result::Result::Ok(())
+                }
+                typedef
+            })
+        }
+    }
+)}
+
+/// `#[derive_ReprC(default_init)]`: emit a `#define STRUCT_NAME_DEFAULT`
+/// initializer macro (after the definition of the struct) in the headers.
+#[doc(hidden)] #[macro_export]
+macro_rules! __ffi_export_c_default__ {(
+    $StructName:ident
+) => (
+    $crate::__cfg_headers__! {
+        $crate::inventory::submit! {
+            #![crate = $crate]
+            $crate::FfiExport({
+                fn typedef (
+                    definer: &'_ mut dyn $crate::headers::Definer,
+                ) -> $crate::std::io::Result<()>
+                {
+                    if $crate::headers::__language__()
+                        != $crate::headers::Language::C
+                    || ! $crate::headers::__in_group__(
+                        $crate::core::module_path!(),
+                        None,
+                    )
+                    {
+                        return $crate::core::result::Result::Ok(());
                     }
-                    drop(has_args);
-                    $crate::std::io::Write::write_all(out,
-                        ");\n\n"
-                            .as_bytes()
-                        ,
-                    )?;
-                })};
+                    <
+                        <$StructName as $crate::layout::ReprC>::CLayout
+                        as
+                        $crate::layout::CType
+                    >::c_define_self(definer)?;
+                    let me = &
+                        <
+                            <$StructName as $crate::layout::ReprC>::CLayout
+                            as
+                            $crate::layout::CType
+                        >::c_short_name().to_string()
+                    ;
+                    let macro_name = &$crate::std::string::ToString::to_string(
+                        &$crate::__utils__::screaming_case(me, "Default"),
+                    );
+                    let default: $StructName =
+                        $crate::core::default::Default::default()
+                    ;
+                    let literal =
+                        $crate::headers::CLiteral::c_literal(&default)
+                            .ok_or_else(|| $crate::std::io::Error::new(
+                                $crate::std::io::ErrorKind::InvalidData,
+                                $crate::std::format!(
+                                    "Error, the default value of `{}` cannot \
+                                    be written as a C literal: use \
+                                    `#[c_default = \"...\"]` on the \
+                                    offending fields",
+                                    me,
+                                ),
+                            ))?
+                    ;
+                    definer.define_once(macro_name, &mut |definer| {
+                        definer.set_symbol_kind(
+                            macro_name,
+                            $crate::headers::SymbolKind::Macro,
+                        );
+                        $crate::core::write!(definer.out(),
+                            "#define {} {}\n\n",
+                            macro_name,
+                            literal,
+                        )
+                    })
+                }
                 typedef
             })
         }
     }
 )}
 
+/// Exports a `char const * <name> (void)` function returning the version of
+/// the library, _i.e._, the `CARGO_PKG_VERSION` of the crate invoking the
+/// macro (_e.g._, `"1.2.3"`).
+///
+/// C consumers can then check it against the `MYLIB_VERSION` macro of the
+/// headers (see [`Builder::with_version_macros`](
+/// /safer_ffi/headers/struct.Builder.html#method.with_version_macros)), to
+/// detect a mismatch between the headers and the library actually loaded at
+/// runtime.
+///
+/// ```rust,ignore
+/// ::safer_ffi::ffi_export_version!(mylib_version);
+/// ```
+#[macro_export]
+macro_rules! ffi_export_version {(
+    $name:ident $(,)?
+) => (
+    #[$crate::ffi_export]
+    /// Returns the version of the library, _e.g._, `"1.2.3"`.
+    ///
+    /// The returned string is static, and must not be freed.
+    pub
+    fn $name ()
+      -> $crate::prelude::char_p::Ref<'static>
+    {
+        $crate::prelude::char_p::Ref::from_str_with_nul(
+            $crate::core::concat!($crate::core::env!("CARGO_PKG_VERSION"), "\0")
+        ).unwrap()
+    }
+)}
+
 // __ffi_export__! {
 //     /// Concatenate two strings
 //     fn concat (
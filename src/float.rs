@@ -0,0 +1,161 @@
+//! Floating-point types with stricter contracts.
+//!
+//! See also [`headers::FloatTypes`][`crate::headers::FloatTypes`] (with the
+//! `headers` feature) to map `f32` and `f64` to `_Float32` and `_Float64`.
+
+use_prelude!();
+
+/// A `f32` or `f64` which is never NaN.
+///
+/// In the headers, it is a dedicated `typedef` documenting that invariant:
+///
+/// ```C
+/// /** \brief
+///  *  A `float` which is never NaN.
+///  */
+/// typedef float float_not_nan_t;
+/// ```
+///
+/// and it is enforced at the FFI boundary: a NaN value received from C is
+/// rejected as an invalid bit-pattern (see [`ReprC::is_valid`]).
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub
+struct NotNan<F>(F);
+
+/// `CLayout` of a [`NotNan`], emitted as a `typedef` in the headers.
+#[doc(hidden)] /** Not part of the public API **/
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy)]
+#[allow(nonstandard_style)]
+pub
+struct NotNan_Layout<F>(F);
+
+macro_rules! impl_NotNan {(
+    $(
+        $fN:ident => $short_name:literal,
+    )*
+) => (
+    $(
+        impl NotNan<$fN> {
+            /// Returns `None` if `value` is NaN.
+            #[inline]
+            pub
+            fn new (value: $fN)
+              -> Option<Self>
+            {
+                if value.is_nan() { None } else { Some(NotNan(value)) }
+            }
+
+            #[inline]
+            pub
+            fn get (self: Self)
+              -> $fN
+            {
+                self.0
+            }
+        }
+
+        impl From<NotNan<$fN>>
+            for $fN
+        {
+            #[inline]
+            fn from (it: NotNan<$fN>)
+              -> $fN
+            {
+                it.0
+            }
+        }
+
+        impl Eq
+            for NotNan<$fN>
+        {}
+
+        unsafe // Safety: `#[repr(transparent)]` wrapper.
+        impl CType
+            for NotNan_Layout<$fN>
+        { __cfg_headers__! {
+            fn c_short_name_fmt (fmt: &'_ mut fmt::Formatter<'_>)
+              -> fmt::Result
+            {
+                fmt.write_str($short_name)
+            }
+
+            fn c_define_self (definer: &'_ mut dyn crate::headers::Definer)
+              -> io::Result<()>
+            {
+                definer.define_once($short_name, &mut |definer| {
+                    <$fN as CType>::c_define_self(definer)?;
+                    write!(definer.out(),
+                        concat!(
+                            "/** \\brief\n",
+                            " *  A `{}` which is never NaN.\n",
+                            " */\n",
+                            "typedef {};\n\n",
+                        ),
+                        <$fN as CType>::c_var(""),
+                        <$fN as CType>::c_var(
+                            &crate::headers::__type_name__(&$short_name),
+                        ),
+                    )
+                })
+            }
+
+            fn c_var_fmt (
+                fmt: &'_ mut fmt::Formatter<'_>,
+                var_name: &'_ str,
+            ) -> fmt::Result
+            {
+                write!(fmt,
+                    "{}{sep}{}",
+                    crate::headers::__type_name__(&$short_name),
+                    var_name,
+                    sep = if var_name.is_empty() { "" } else { " " },
+                )
+            }
+
+            fn binding_ty ()
+              -> rust::String
+            {
+                <$fN as CType>::binding_ty()
+            }
+
+            fn binding_define_self (definer: &'_ mut dyn crate::headers::Definer)
+              -> io::Result<()>
+            {
+                <$fN as CType>::binding_define_self(definer)
+            }
+        } type OPAQUE_KIND = OpaqueKind::Concrete; }
+
+        __cfg_headers__! {
+            impl crate::headers::CLiteral
+                for NotNan<$fN>
+            {
+                fn c_literal (self: &'_ Self)
+                  -> Option<rust::String>
+                {
+                    self.0.c_literal()
+                }
+            }
+        }
+
+        unsafe // Safety: `#[repr(transparent)]` wrapper, and NaNs are invalid.
+        impl ReprC
+            for NotNan<$fN>
+        {
+            type CLayout = NotNan_Layout<$fN>;
+
+            #[inline]
+            fn is_valid (it: &'_ NotNan_Layout<$fN>)
+              -> bool
+            {
+                it.0.is_nan().not()
+            }
+        }
+    )*
+)}
+
+impl_NotNan! {
+    f32 => "float_not_nan",
+    f64 => "double_not_nan",
+}
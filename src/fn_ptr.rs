@@ -0,0 +1,987 @@
+//! Function pointers, such as the ones of `#[ffi_export]`ed functions.
+
+use_prelude!();
+
+/// A (non-null) type-erased function pointer: `void (*)(void)`.
+///
+/// Obtained through the [`c_fn!`][`crate::c_fn`]`(erased ...)` macro, it is
+/// meant to be registered into C APIs that store generic callbacks, or to be
+/// cast back to the actual signature of the function.
+#[repr(transparent)]
+#[derive(Clone, Copy)]
+pub
+struct ErasedFnPtr (
+    unsafe extern "C" fn(),
+);
+
+unsafe // Safety: `#[repr(transparent)]` non-null function pointer.
+impl ReprC
+    for ErasedFnPtr
+{
+    type CLayout = Option<unsafe extern "C" fn() -> crate::tuple::CVoid>;
+
+    #[inline]
+    fn is_valid (it: &'_ Self::CLayout)
+      -> bool
+    {
+        it.is_some()
+    }
+}
+
+unsafe
+impl crate::layout::__HasNiche__
+    for ErasedFnPtr
+{
+    #[inline]
+    fn is_niche (it: &'_ Self::CLayout)
+      -> bool
+    {
+        it.is_none()
+    }
+}
+
+impl ErasedFnPtr {
+    /// The type-erased function pointer itself.
+    #[inline]
+    pub
+    fn as_raw (self: ErasedFnPtr)
+      -> unsafe extern "C" fn()
+    {
+        self.0
+    }
+
+    /// Casts the function pointer back to its actual type `F`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `F` does not have the size of a function pointer.
+    ///
+    /// # Safety
+    ///
+    /// `F` must be a function pointer type (or a `#[repr(transparent)]`
+    /// wrapper thereof), with the actual ABI and signature of the function.
+    #[inline]
+    pub
+    unsafe
+    fn cast<F : Copy> (self: ErasedFnPtr)
+      -> F
+    {
+        assert_eq!(
+            mem::size_of::<F>(),
+            mem::size_of::<unsafe extern "C" fn()>(),
+            "`ErasedFnPtr::cast()` target is not a function pointer",
+        );
+        mem::transmute_copy(&self.0)
+    }
+}
+
+impl fmt::Debug
+    for ErasedFnPtr
+{
+    fn fmt (self: &'_ Self, fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        fmt .debug_tuple("ErasedFnPtr")
+            .field(&(self.0 as *const ()))
+            .finish()
+    }
+}
+
+/// Obtains the C function of an `#[ffi_export]`ed function, that is, the
+/// function the C code calls: the one protected against panics unwinding
+/// across the FFI boundary (contrary to the Rust function of the same name).
+///
+/// It coerces to its `extern "C" fn(<Args as ReprC>::CLayout...) -> Ret`
+/// function pointer type, so that it can be registered as a callback into C
+/// APIs:
+///
+/// ```rust
+/// mod exports {
+///     use ::safer_ffi::prelude::*;
+///
+///     #[ffi_export]
+///     pub
+///     fn on_event (code: i32)
+///     {
+///         drop(code);
+///     }
+/// }
+///
+/// let on_event: extern "C" fn(i32) = ::safer_ffi::c_fn!(exports::on_event);
+/// on_event(42);
+/// ```
+///
+/// or, prefixed with `erased`, to obtain its type-erased
+/// [`ErasedFnPtr`][`crate::fn_ptr::ErasedFnPtr`], for C APIs storing generic
+/// `void (*)(void)` callbacks:
+///
+/// ```rust
+/// # mod exports {
+/// #     use ::safer_ffi::prelude::*;
+/// #     #[ffi_export]
+/// #     pub fn on_event (code: i32) { drop(code); }
+/// # }
+/// let callback = ::safer_ffi::c_fn!(erased exports::on_event);
+/// ```
+#[macro_export]
+macro_rules! c_fn {
+    (
+        erased $($path:ident)::+
+    ) => (
+        $crate::__ffi_export_path__!([] $($path)::+ => ::__erased__())
+    );
+
+    (
+        $($path:ident)::+
+    ) => (
+        $crate::__ffi_export_path__!([] $($path)::+ => @fname)
+    );
+}
+
+/// A nullable C function pointer: `Ret (*)(Args...)`, with `Args` a tuple of
+/// the parameter types.
+///
+/// Contrary to a raw `Option<unsafe extern "C" fn(...) -> Ret>` field, it can
+/// be [`.call()`][`FnPtr::call`]-ed without `unsafe` nor `.unwrap()`, a
+/// `NULL` function pointer being reported as an [`Err`]or, and it is
+/// `typedef`-ed in the generated headers.
+///
+/// ```rust
+/// use ::safer_ffi::prelude::*;
+///
+/// #[derive_ReprC]
+/// #[repr(C)]
+/// pub
+/// struct Handlers {
+///     on_data: repr_c::FnPtr<(i32, bool), u8>,
+///     on_done: repr_c::FnPtr<()>,
+/// }
+///
+/// extern "C" fn on_data (x: i32, flag: bool) -> u8 { (x as u8) ^ (flag as u8) }
+///
+/// # fn main () {
+/// let handlers = Handlers {
+///     on_data: repr_c::FnPtr::from(on_data as extern "C" fn(_, _) -> _),
+///     on_done: repr_c::FnPtr::NULL,
+/// };
+/// assert_eq!(handlers.on_data.call((42, true)), Ok(43));
+/// assert!(handlers.on_done.call(()).is_err());
+/// # }
+/// ```
+#[repr(transparent)]
+pub
+struct FnPtr<Args, Ret = ()>
+where
+    Args : FnArgs<Ret>,
+    Ret : ReprC,
+{
+    ptr: Option<Args::FnPtr>,
+}
+
+/// Error returned when [`.call()`][`FnPtr::call`]-ing a `NULL` [`FnPtr`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub
+struct NullFnPtr;
+
+impl fmt::Display
+    for NullFnPtr
+{
+    fn fmt (self: &'_ Self, fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        fmt.write_str("called a NULL function pointer")
+    }
+}
+
+cfg_std! {
+    impl ::std::error::Error
+        for NullFnPtr
+    {}
+}
+
+/// Error returned by the `validate()` method of a
+/// `#[derive_ReprC(callback_table)]` struct: the name of the first required
+/// callback found to be `NULL`.
+///
+/// ```rust
+/// use ::safer_ffi::{prelude::*, fn_ptr::MissingCallback};
+///
+/// #[derive_ReprC(callback_table)]
+/// #[repr(C)]
+/// pub
+/// struct Ops {
+///     pub add: repr_c::FnPtr<(i32, i32), i32>,
+///     #[optional]
+///     pub log: repr_c::FnPtr<(i32,)>,
+/// }
+///
+/// extern "C" fn add (x: i32, y: i32) -> i32 { x + y }
+///
+/// # fn main () {
+/// let mut ops = Ops {
+///     add: repr_c::FnPtr::NULL,
+///     log: repr_c::FnPtr::NULL,
+/// };
+/// assert_eq!(ops.validate(), Err(MissingCallback("add")));
+/// ops.add = repr_c::FnPtr::from(add as extern "C" fn(_, _) -> _);
+/// assert_eq!(ops.validate(), Ok(()));
+/// assert_eq!(ops.add(40, 2), Ok(42));
+/// assert!(ops.log(42).is_err());
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub
+struct MissingCallback (
+    pub &'static str,
+);
+
+impl fmt::Display
+    for MissingCallback
+{
+    fn fmt (self: &'_ Self, fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        write!(fmt, "missing required callback `{}`", self.0)
+    }
+}
+
+cfg_std! {
+    impl ::std::error::Error
+        for MissingCallback
+    {}
+}
+
+/// Tuples of (up to 9) [`ReprC`] function parameters: `Args` in
+/// [`FnPtr`]`<Args, Ret>`.
+pub
+trait FnArgs<Ret : ReprC> : Sized {
+    /// `unsafe extern "C" fn(Args...) -> Ret`
+    type FnPtr : Copy;
+
+    /// `Option<unsafe extern "C" fn(Args::CLayout...) -> Ret::CLayout>`
+    #[doc(hidden)] /** Not part of the public API **/
+    type CLayout : CType;
+
+    /// Calls `f` with the tuple of `args`.
+    ///
+    /// # Safety
+    ///
+    /// That of calling the foreign function `f`.
+    unsafe
+    fn call_with (f: Self::FnPtr, args: Self)
+      -> Ret
+    ;
+}
+
+macro_rules! impl_FnArgs {(
+    $(
+        $An:ident $(,
+        $Ai:ident)* $(,)?
+    )?
+) => (
+    // recurse
+    $(
+        impl_FnArgs! {
+            $($Ai ,)*
+        }
+    )?
+
+    impl<
+        Ret : ReprC, $(
+        $An : ReprC, $(
+        $Ai : ReprC,
+    )*)?> FnArgs<Ret>
+        for ($($An, $($Ai ,)*)?)
+    {
+        type FnPtr = unsafe extern "C" fn ($($An, $($Ai ,)*)?) -> Ret;
+
+        type CLayout = Option<
+            unsafe extern "C"
+            fn ($($An::CLayout, $($Ai::CLayout ,)*)?) -> Ret::CLayout
+        >;
+
+        #[inline]
+        #[allow(nonstandard_style)]
+        unsafe
+        fn call_with (
+            f: unsafe extern "C" fn ($($An, $($Ai ,)*)?) -> Ret,
+            ($($An, $($Ai ,)*)?): ($($An, $($Ai ,)*)?),
+        ) -> Ret
+        {
+            f($($An, $($Ai ,)*)?)
+        }
+    }
+
+    impl<
+        Ret : ReprC, $(
+        $An : ReprC, $(
+        $Ai : ReprC,
+    )*)?> From<extern "C" fn ($($An, $($Ai ,)*)?) -> Ret>
+        for FnPtr<($($An, $($Ai ,)*)?), Ret>
+    {
+        #[inline]
+        fn from (f: extern "C" fn ($($An, $($Ai ,)*)?) -> Ret)
+          -> Self
+        {
+            unsafe {
+                // Safety: `f` is not `unsafe` to call.
+                FnPtr::new(f as unsafe extern "C" fn ($($An, $($Ai ,)*)?) -> Ret)
+            }
+        }
+    }
+)}
+
+impl_FnArgs! {
+    A9, A8, A7, A6, A5, A4, A3, A2, A1,
+}
+
+impl<Args, Ret> FnPtr<Args, Ret>
+where
+    Args : FnArgs<Ret>,
+    Ret : ReprC,
+{
+    /// The `NULL` function pointer.
+    pub
+    const NULL: Self = FnPtr { ptr: None };
+
+    /// # Safety
+    ///
+    /// Calling `f` with any (valid) `Args` must be sound, since
+    /// [`.call()`][`FnPtr::call`] is not `unsafe`.
+    #[inline]
+    pub
+    unsafe
+    fn new (f: Args::FnPtr)
+      -> Self
+    {
+        FnPtr { ptr: Some(f) }
+    }
+
+    #[inline]
+    pub
+    fn is_null (self: &'_ Self)
+      -> bool
+    {
+        self.ptr.is_none()
+    }
+
+    /// The function pointer itself, if not `NULL`.
+    #[inline]
+    pub
+    fn get (self: &'_ Self)
+      -> Option<Args::FnPtr>
+    {
+        self.ptr
+    }
+
+    /// Calls the function with the tuple of `args`, unless it is `NULL`.
+    #[inline]
+    pub
+    fn call (self: &'_ Self, args: Args)
+      -> Result<Ret, NullFnPtr>
+    {
+        let f = self.ptr.ok_or(NullFnPtr)?;
+        Ok(unsafe {
+            // Safety: upheld by the constructor (or by the C code that
+            // provided the function pointer).
+            Args::call_with(f, args)
+        })
+    }
+}
+
+impl<Args, Ret> Default
+    for FnPtr<Args, Ret>
+where
+    Args : FnArgs<Ret>,
+    Ret : ReprC,
+{
+    #[inline]
+    fn default ()
+      -> Self
+    {
+        Self::NULL
+    }
+}
+
+impl<Args, Ret> Copy
+    for FnPtr<Args, Ret>
+where
+    Args : FnArgs<Ret>,
+    Ret : ReprC,
+{}
+
+impl<Args, Ret> Clone
+    for FnPtr<Args, Ret>
+where
+    Args : FnArgs<Ret>,
+    Ret : ReprC,
+{
+    #[inline]
+    fn clone (self: &'_ Self)
+      -> Self
+    {
+        *self
+    }
+}
+
+impl<Args, Ret> fmt::Debug
+    for FnPtr<Args, Ret>
+where
+    Args : FnArgs<Ret>,
+    Ret : ReprC,
+{
+    fn fmt (self: &'_ Self, fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        let ptr = self.ptr.map_or(ptr::null(), |f| unsafe {
+            mem::transmute_copy::<Args::FnPtr, *const ()>(&f)
+        });
+        fmt .debug_tuple("FnPtr")
+            .field(&ptr)
+            .finish()
+    }
+}
+
+unsafe // Safety: same layout as `Option<unsafe extern "C" fn(...) -> Ret>`.
+impl<Args, Ret> ReprC
+    for FnPtr<Args, Ret>
+where
+    Args : FnArgs<Ret>,
+    Ret : ReprC,
+{
+    type CLayout = FnPtr_Layout<Args, Ret>;
+
+    #[inline]
+    fn is_valid (_: &'_ Self::CLayout)
+      -> bool
+    {
+        true
+    }
+}
+
+/// `CLayout` of a [`FnPtr`], emitted as a `typedef` in the headers.
+#[doc(hidden)] /** Not part of the public API **/
+#[repr(transparent)]
+#[allow(nonstandard_style)]
+pub
+struct FnPtr_Layout<Args, Ret>
+where
+    Args : FnArgs<Ret>,
+    Ret : ReprC,
+{
+    ptr: Args::CLayout,
+}
+
+impl<Args, Ret> Copy
+    for FnPtr_Layout<Args, Ret>
+where
+    Args : FnArgs<Ret>,
+    Ret : ReprC,
+{}
+
+impl<Args, Ret> Clone
+    for FnPtr_Layout<Args, Ret>
+where
+    Args : FnArgs<Ret>,
+    Ret : ReprC,
+{
+    #[inline]
+    fn clone (self: &'_ Self)
+      -> Self
+    {
+        *self
+    }
+}
+
+impl<Args, Ret> fmt::Debug
+    for FnPtr_Layout<Args, Ret>
+where
+    Args : FnArgs<Ret>,
+    Ret : ReprC,
+{
+    fn fmt (self: &'_ Self, fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        fmt .debug_struct("FnPtr_Layout")
+            .finish()
+    }
+}
+
+unsafe // Safety: `#[repr(transparent)]` wrapper.
+impl<Args, Ret> CType
+    for FnPtr_Layout<Args, Ret>
+where
+    Args : FnArgs<Ret>,
+    Ret : ReprC,
+{ __cfg_headers__! {
+    fn c_short_name_fmt (fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        // ret_t_arg1_t_arg2_t_fptr
+        Args::CLayout::c_short_name_fmt(fmt)
+    }
+
+    fn c_define_self (definer: &'_ mut dyn crate::headers::Definer)
+      -> io::Result<()>
+    {
+        let me = &Self::c_short_name().to_string();
+        definer.define_once(me, &mut |definer| {
+            Args::CLayout::c_define_self(definer)?;
+            write!(definer.out(),
+                "typedef {};\n\n",
+                Args::CLayout::c_var(&crate::headers::__type_name__(me)),
+            )
+        })
+    }
+
+    fn c_var_fmt (
+        fmt: &'_ mut fmt::Formatter<'_>,
+        var_name: &'_ str,
+    ) -> fmt::Result
+    {
+        write!(fmt,
+            "{}{sep}{}",
+            crate::headers::__type_name__(&Self::c_short_name()),
+            var_name,
+            sep = if var_name.is_empty() { "" } else { " " },
+        )
+    }
+
+    fn binding_ty ()
+      -> rust::String
+    {
+        Args::CLayout::binding_ty()
+    }
+
+    fn binding_define_self (definer: &'_ mut dyn crate::headers::Definer)
+      -> io::Result<()>
+    {
+        Args::CLayout::binding_define_self(definer)
+    }
+} type OPAQUE_KIND = OpaqueKind::Concrete; }
+
+/// A nullable C callback reporting whether it succeeded: `int32_t (*)(Ret *
+/// out, Args...)`, with `Args` a tuple of (up to 8) parameter types.
+///
+/// It is meant for callbacks implemented in C++, which may throw: the C++
+/// section of the generated headers provides a
+/// `safer_ffi::catching<Signature, &function>::call` shim, catching the
+/// exceptions at the C boundary (rather than letting them unwind into Rust,
+/// which is Undefined Behavior), and returning them as a non-zero status:
+///
+///   - `SAFER_FFI_CALLBACK_OK` (`0`): the value has been written to `out`;
+///
+///   - `SAFER_FFI_CALLBACK_EXCEPTION` (`1`): a `std::exception` was thrown;
+///
+///   - `SAFER_FFI_CALLBACK_UNKNOWN_EXCEPTION` (`2`): something else was
+///     thrown.
+///
+/// Calling it from Rust then yields a [`CallbackError::Failed`] with that
+/// status.
+///
+/// ```rust
+/// use ::safer_ffi::{prelude::*, fn_ptr::{CallbackError, CatchingFnPtr}};
+///
+/// #[ffi_export]
+/// fn parse_with (parse: CatchingFnPtr<(i32,), u8>)
+///   -> i32
+/// {
+///     match parse.call((42,)) {
+///         | Ok(byte) => byte.into(),
+///         | Err(CallbackError::Failed(status)) => -status,
+///         | Err(CallbackError::Null) => -100,
+///     }
+/// }
+/// ```
+///
+/// ```cpp
+/// uint8_t parse (int32_t x)
+/// {
+///     if (x > 255) throw std::out_of_range("x");
+///     return x;
+/// }
+///
+/// parse_with(safer_ffi::catching<uint8_t (int32_t), &parse>::call);
+/// ```
+#[repr(transparent)]
+pub
+struct CatchingFnPtr<Args, Ret = ()>
+where
+    Args : CatchingFnArgs<Ret>,
+    Ret : ReprC,
+{
+    ptr: FnPtr<Args::CArgs, i32>,
+}
+
+/// Error returned when [`.call()`][`CatchingFnPtr::call`]-ing a
+/// [`CatchingFnPtr`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub
+enum CallbackError {
+    /// The callback is `NULL`.
+    Null,
+
+    /// The callback returned the given (non-zero) status, _e.g._, because
+    /// the C++ shim caught an exception.
+    Failed(i32),
+}
+
+impl fmt::Display
+    for CallbackError
+{
+    fn fmt (self: &'_ Self, fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        match *self {
+            | CallbackError::Null => fmt::Display::fmt(&NullFnPtr, fmt),
+            | CallbackError::Failed(status) => write!(fmt,
+                "the callback failed with status {}", status,
+            ),
+        }
+    }
+}
+
+cfg_std! {
+    impl ::std::error::Error
+        for CallbackError
+    {}
+}
+
+/// Tuples of (up to 8) [`ReprC`] function parameters: `Args` in
+/// [`CatchingFnPtr`]`<Args, Ret>`.
+pub
+trait CatchingFnArgs<Ret : ReprC> : Sized {
+    /// `(*mut Ret, Args...)`, the parameters of the C callback.
+    type CArgs : FnArgs<i32>;
+
+    /// Calls `f` with `out` and the tuple of `args`.
+    ///
+    /// # Safety
+    ///
+    /// That of calling the foreign function `f`.
+    unsafe
+    fn call_catching (
+        f: <Self::CArgs as FnArgs<i32>>::FnPtr,
+        out: *mut Ret,
+        args: Self,
+    ) -> i32
+    ;
+}
+
+macro_rules! impl_CatchingFnArgs {(
+    $(
+        $An:ident $(,
+        $Ai:ident)* $(,)?
+    )?
+) => (
+    // recurse
+    $(
+        impl_CatchingFnArgs! {
+            $($Ai ,)*
+        }
+    )?
+
+    impl<
+        Ret : ReprC, $(
+        $An : ReprC, $(
+        $Ai : ReprC,
+    )*)?> CatchingFnArgs<Ret>
+        for ($($An, $($Ai ,)*)?)
+    {
+        type CArgs = (*mut Ret, $($An, $($Ai ,)*)?);
+
+        #[inline]
+        #[allow(nonstandard_style)]
+        unsafe
+        fn call_catching (
+            f: unsafe extern "C" fn (*mut Ret, $($An, $($Ai ,)*)?) -> i32,
+            out: *mut Ret,
+            ($($An, $($Ai ,)*)?): ($($An, $($Ai ,)*)?),
+        ) -> i32
+        {
+            f(out, $($An, $($Ai ,)*)?)
+        }
+    }
+)}
+
+impl_CatchingFnArgs! {
+    A8, A7, A6, A5, A4, A3, A2, A1,
+}
+
+impl<Args, Ret> CatchingFnPtr<Args, Ret>
+where
+    Args : CatchingFnArgs<Ret>,
+    Ret : ReprC,
+{
+    /// The `NULL` callback.
+    pub
+    const NULL: Self = CatchingFnPtr { ptr: FnPtr::NULL };
+
+    /// # Safety
+    ///
+    /// Calling `f` with any (valid) `Args` must be sound, and, when it
+    /// returns `0`, it must have written a valid `Ret` to `out`.
+    #[inline]
+    pub
+    unsafe
+    fn new (f: <Args::CArgs as FnArgs<i32>>::FnPtr)
+      -> Self
+    {
+        CatchingFnPtr { ptr: FnPtr::new(f) }
+    }
+
+    #[inline]
+    pub
+    fn is_null (self: &'_ Self)
+      -> bool
+    {
+        self.ptr.is_null()
+    }
+
+    /// Calls the callback with the tuple of `args`, unless it is `NULL`,
+    /// and returns the value it has written, unless it has returned a
+    /// non-zero status.
+    pub
+    fn call (self: &'_ Self, args: Args)
+      -> Result<Ret, CallbackError>
+    {
+        let f = self.ptr.get().ok_or(CallbackError::Null)?;
+        let mut out = mem::MaybeUninit::<Ret>::uninit();
+        match unsafe {
+            // Safety: upheld by the constructor (or by the C code that
+            // provided the function pointer).
+            Args::call_catching(f, out.as_mut_ptr(), args)
+        }
+        {
+            | 0 => Ok(unsafe {
+                // Safety: a `0` status means `out` has been written to.
+                out.assume_init()
+            }),
+            | status => Err(CallbackError::Failed(status)),
+        }
+    }
+}
+
+impl<Args, Ret> Default
+    for CatchingFnPtr<Args, Ret>
+where
+    Args : CatchingFnArgs<Ret>,
+    Ret : ReprC,
+{
+    #[inline]
+    fn default ()
+      -> Self
+    {
+        Self::NULL
+    }
+}
+
+impl<Args, Ret> Copy
+    for CatchingFnPtr<Args, Ret>
+where
+    Args : CatchingFnArgs<Ret>,
+    Ret : ReprC,
+{}
+
+impl<Args, Ret> Clone
+    for CatchingFnPtr<Args, Ret>
+where
+    Args : CatchingFnArgs<Ret>,
+    Ret : ReprC,
+{
+    #[inline]
+    fn clone (self: &'_ Self)
+      -> Self
+    {
+        *self
+    }
+}
+
+impl<Args, Ret> fmt::Debug
+    for CatchingFnPtr<Args, Ret>
+where
+    Args : CatchingFnArgs<Ret>,
+    Ret : ReprC,
+{
+    fn fmt (self: &'_ Self, fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        fmt .debug_tuple("CatchingFnPtr")
+            .field(&self.ptr)
+            .finish()
+    }
+}
+
+unsafe // Safety: `#[repr(transparent)]` wrapper around a `FnPtr`.
+impl<Args, Ret> ReprC
+    for CatchingFnPtr<Args, Ret>
+where
+    Args : CatchingFnArgs<Ret>,
+    Ret : ReprC,
+{
+    type CLayout = CatchingFnPtr_Layout<Args, Ret>;
+
+    #[inline]
+    fn is_valid (_: &'_ Self::CLayout)
+      -> bool
+    {
+        true
+    }
+}
+
+/// `CLayout` of a [`CatchingFnPtr`]: that of the inner [`FnPtr`], also
+/// emitting the C++ `safer_ffi::catching` shim in the headers.
+#[doc(hidden)] /** Not part of the public API **/
+#[repr(transparent)]
+#[allow(nonstandard_style)]
+pub
+struct CatchingFnPtr_Layout<Args, Ret>
+where
+    Args : CatchingFnArgs<Ret>,
+    Ret : ReprC,
+{
+    ptr: FnPtr_Layout<Args::CArgs, i32>,
+}
+
+impl<Args, Ret> Copy
+    for CatchingFnPtr_Layout<Args, Ret>
+where
+    Args : CatchingFnArgs<Ret>,
+    Ret : ReprC,
+{}
+
+impl<Args, Ret> Clone
+    for CatchingFnPtr_Layout<Args, Ret>
+where
+    Args : CatchingFnArgs<Ret>,
+    Ret : ReprC,
+{
+    #[inline]
+    fn clone (self: &'_ Self)
+      -> Self
+    {
+        *self
+    }
+}
+
+impl<Args, Ret> fmt::Debug
+    for CatchingFnPtr_Layout<Args, Ret>
+where
+    Args : CatchingFnArgs<Ret>,
+    Ret : ReprC,
+{
+    fn fmt (self: &'_ Self, fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        fmt .debug_struct("CatchingFnPtr_Layout")
+            .finish()
+    }
+}
+
+unsafe // Safety: `#[repr(transparent)]` wrapper.
+impl<Args, Ret> CType
+    for CatchingFnPtr_Layout<Args, Ret>
+where
+    Args : CatchingFnArgs<Ret>,
+    Ret : ReprC,
+{ __cfg_headers__! {
+    fn c_short_name_fmt (fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        FnPtr_Layout::<Args::CArgs, i32>::c_short_name_fmt(fmt)
+    }
+
+    fn c_define_self (definer: &'_ mut dyn crate::headers::Definer)
+      -> io::Result<()>
+    {
+        FnPtr_Layout::<Args::CArgs, i32>::c_define_self(definer)?;
+        definer.define_once("__catching_shim__", &mut |definer| {
+            definer.set_symbol_kind(
+                "__catching_shim__",
+                crate::headers::SymbolKind::Macro,
+            );
+            definer.out().write_all(CATCHING_SHIM.as_bytes())
+        })
+    }
+
+    fn c_var_fmt (
+        fmt: &'_ mut fmt::Formatter<'_>,
+        var_name: &'_ str,
+    ) -> fmt::Result
+    {
+        FnPtr_Layout::<Args::CArgs, i32>::c_var_fmt(fmt, var_name)
+    }
+
+    fn binding_ty ()
+      -> rust::String
+    {
+        FnPtr_Layout::<Args::CArgs, i32>::binding_ty()
+    }
+
+    fn binding_define_self (definer: &'_ mut dyn crate::headers::Definer)
+      -> io::Result<()>
+    {
+        FnPtr_Layout::<Args::CArgs, i32>::binding_define_self(definer)
+    }
+} type OPAQUE_KIND = OpaqueKind::Concrete; }
+
+/// The status codes of the [`CatchingFnPtr`] callbacks, and the C++ shim
+/// catching the exceptions of the wrapped functions.
+#[cfg(feature = "headers")]
+const CATCHING_SHIM: &str = r#"#define SAFER_FFI_CALLBACK_OK 0
+
+#define SAFER_FFI_CALLBACK_EXCEPTION 1
+
+#define SAFER_FFI_CALLBACK_UNKNOWN_EXCEPTION 2
+
+#ifdef __cplusplus
+} /* extern "C" */
+
+#include <exception>
+
+namespace safer_ffi {
+
+/** \brief
+ *  `catching<Ret (Args...), &f>::call` is a `int32_t (*)(Ret *, Args...)`
+ *  callback calling `f`, and catching the exceptions it throws, rather than
+ *  letting them unwind into Rust.
+ */
+template<typename Signature, Signature * F>
+struct catching;
+
+template<typename Ret, typename... Args, Ret (*F)(Args...)>
+struct catching<Ret (Args...), F> {
+    static int32_t call (Ret * out, Args... args) noexcept
+    {
+        try {
+            *out = F(args...);
+            return SAFER_FFI_CALLBACK_OK;
+        } catch (std::exception const &) {
+            return SAFER_FFI_CALLBACK_EXCEPTION;
+        } catch (...) {
+            return SAFER_FFI_CALLBACK_UNKNOWN_EXCEPTION;
+        }
+    }
+};
+
+template<typename... Args, void (*F)(Args...)>
+struct catching<void (Args...), F> {
+    static int32_t call (void *, Args... args) noexcept
+    {
+        try {
+            F(args...);
+            return SAFER_FFI_CALLBACK_OK;
+        } catch (std::exception const &) {
+            return SAFER_FFI_CALLBACK_EXCEPTION;
+        } catch (...) {
+            return SAFER_FFI_CALLBACK_UNKNOWN_EXCEPTION;
+        }
+    }
+};
+
+} /* namespace safer_ffi */
+
+extern "C" {
+#endif
+
+"#;
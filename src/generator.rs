@@ -0,0 +1,290 @@
+//! Synchronous generators (_a.k.a._ coroutines) that can be driven from C.
+//!
+//! Some APIs, such as streaming parsers, need to hand out a sequence of values
+//! without buffering all of them in a [`Vec`][`crate::Vec`] beforehand.
+//!
+//! A [`Generator`] runs its body on a dedicated thread, which only makes
+//! progress when the consumer asks for the next value: it thus behaves like a
+//! coroutine, where each [`Yielder::yield_`] suspends the body until the next
+//! call to [`Generator::next`].
+//!
+//! Exposed to C, this leads to the classic `create / next / destroy` triplet:
+//!
+//!   - the `create` function is a classic `#[ffi_export]`ed function returning
+//!     a [`repr_c::Box`][`crate::boxed::Box`]`<Generator<Item>>`;
+//!
+//!   - the `next` and `destroy` functions can be generated with the
+//!     [`ffi_export_generator!`] macro.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ::safer_ffi::{prelude::*, generator::Generator};
+//!
+//! /// Yields the lengths of the words of `text`, one at a time.
+//! #[ffi_export]
+//! fn word_lengths (text: char_p::Ref<'_>) -> repr_c::Box<Generator<usize>>
+//! {
+//!     let text = text.to_string();
+//!     repr_c::Box::new(Generator::new(move |yielder| {
+//!         for word in text.split_whitespace() {
+//!             if yielder.yield_(word.len()).is_err() {
+//!                 // The generator has been dropped: stop early.
+//!                 return;
+//!             }
+//!         }
+//!     }))
+//! }
+//!
+//! ::safer_ffi::ffi_export_generator! {
+//!     usize => {
+//!         next: word_lengths_next,
+//!         free: word_lengths_free,
+//!     }
+//! }
+//! ```
+//!
+//! which, C-wise, is used as:
+//!
+//! ```C
+//! Generator_size_t * it = word_lengths("Hello, World!");
+//! size_t len;
+//! while (word_lengths_next(it, &len)) {
+//!     printf("%zu\n", len);
+//! }
+//! word_lengths_free(it);
+//! ```
+
+use_prelude!();
+use ::std::{
+    sync::mpsc,
+    thread,
+};
+
+ReprC! {
+    #[ReprC::opaque(
+        format!("Generator_{}", <T::CLayout as CType>::c_short_name())
+    )]
+    /// Handle to a generator of `T`s, driven through [`Generator::next`].
+    ///
+    /// It is an opaque type from the point of view of C.
+    pub
+    struct Generator[T]
+    where {
+        T : ReprC,
+    }
+    {
+        resume: Option<mpsc::SyncSender<()>>,
+        values: Option<mpsc::Receiver<T>>,
+        thread: Option<thread::JoinHandle<()>>,
+    }
+}
+
+/// Handle given to the body of a [`Generator`], to yield values with.
+pub
+struct Yielder<T> {
+    values: mpsc::SyncSender<T>,
+    resume: mpsc::Receiver<()>,
+}
+
+/// Error returned by [`Yielder::yield_`] when the [`Generator`] has been
+/// dropped, meaning that the body should stop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub
+struct Cancelled;
+
+impl fmt::Display
+    for Cancelled
+{
+    fn fmt (self: &'_ Self, fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        fmt::Display::fmt("the generator has been dropped", fmt)
+    }
+}
+
+impl ::std::error::Error
+    for Cancelled
+{}
+
+impl<T> Yielder<T> {
+    /// Hands `value` to the consumer, and suspends the body until the next
+    /// value is requested.
+    ///
+    /// Returns `Err(Cancelled)` when the [`Generator`] has been dropped in the
+    /// meantime, in which case the body is expected to return early.
+    pub
+    fn yield_ (self: &'_ Self, value: T)
+      -> Result<(), Cancelled>
+    {
+        self.values.send(value).map_err(|_| Cancelled)?;
+        self.resume.recv().map_err(|_| Cancelled)
+    }
+}
+
+impl<T> fmt::Debug
+    for Yielder<T>
+{
+    fn fmt (self: &'_ Self, fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        fmt .debug_struct("Yielder")
+            .finish()
+    }
+}
+
+impl<T : ReprC> Generator<T> {
+    /// Spawns the (suspended) `body` of the generator.
+    ///
+    /// The body does not start running until the first call to
+    /// [`.next()`][`Generator::next`].
+    pub
+    fn new (body: impl 'static + Send + FnOnce(Yielder<T>))
+      -> Generator<T>
+    where
+        T : 'static + Send,
+    {
+        let (resume, resume_rx) = mpsc::sync_channel(0);
+        let (values_tx, values) = mpsc::sync_channel(0);
+        let thread = thread::spawn(move || {
+            if resume_rx.recv().is_err() {
+                // Dropped before the first `.next()`.
+                return;
+            }
+            body(Yielder { values: values_tx, resume: resume_rx });
+        });
+        Generator {
+            resume: Some(resume),
+            values: Some(values),
+            thread: Some(thread),
+        }
+    }
+}
+
+impl<T : ReprC> Iterator
+    for Generator<T>
+{
+    type Item = T;
+
+    /// Resumes the body until it yields its next value, or returns.
+    fn next (self: &'_ mut Generator<T>)
+      -> Option<T>
+    {
+        let value =
+            self.resume.as_ref()?.send(()).ok()
+                .and_then(|()| self.values.as_ref()?.recv().ok())
+        ;
+        if value.is_none() {
+            // The body has returned: the generator is exhausted.
+            self.resume = None;
+            self.values = None;
+        }
+        value
+    }
+}
+
+impl<T : ReprC> Drop
+    for Generator<T>
+{
+    fn drop (self: &'_ mut Generator<T>)
+    {
+        // Makes the pending (or next) `yield_` fail with `Cancelled`.
+        drop(self.resume.take());
+        // Makes a body that ignores `Cancelled` and keeps yielding fail its
+        // `send`, rather than blocking forever (and thus the `join` below).
+        drop(self.values.take());
+        if let Some(thread) = self.thread.take() {
+            // A panic in the body has already been reported by the panic hook,
+            // and it must not propagate out of this `drop` (which may be
+            // running within an `#[ffi_export]`ed function).
+            let _ = thread.join();
+        }
+    }
+}
+
+impl<T : ReprC> fmt::Debug
+    for Generator<T>
+{
+    fn fmt (self: &'_ Self, fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        fmt .debug_struct("Generator")
+            .field("exhausted", &self.resume.is_none())
+            .finish()
+    }
+}
+
+/// Exports the `next` and `free` functions of a
+/// [`Generator`][`crate::generator::Generator`]`<Item>`.
+///
+/// ```rust,ignore
+/// ::safer_ffi::ffi_export_generator! {
+///     Item => {
+///         next: my_generator_next,
+///         free: my_generator_free,
+///     }
+/// }
+/// ```
+///
+/// exports:
+///
+/// ```C
+/// bool my_generator_next (Generator_Item_t * generator, Item_t * out);
+///
+/// void my_generator_free (Generator_Item_t * generator);
+/// ```
+///
+///   - `next` writes the next yielded value to `out` and returns `true`, or
+///     returns `false` (leaving `out` untouched) once the generator is
+///     exhausted. It is an `unsafe fn` on the Rust side: `out` must be `NULL`
+///     or valid for a write;
+///
+///   - `free` destroys the generator (`NULL` is allowed), cancelling its body
+///     if it had not returned yet.
+#[macro_export]
+macro_rules! ffi_export_generator {(
+    $Item:ty => {
+        next: $next:ident,
+        free: $free:ident $(,)?
+    } $(;)?
+) => (
+    #[$crate::ffi_export]
+    /// Advances the generator, writing the yielded value to `out`.
+    ///
+    /// Returns `false`, leaving `out` untouched, once the generator is
+    /// exhausted (or if `out` is `NULL`).
+    ///
+    /// # Safety
+    ///
+    /// `out` must be `NULL` or valid for (and properly aligned for) a write
+    /// of an item.
+    pub
+    unsafe
+    fn $next (
+        generator: &'_ mut $crate::generator::Generator<$Item>,
+        out: *mut $Item,
+    ) -> bool
+    {
+        if out.is_null() {
+            return false;
+        }
+        match $crate::core::iter::Iterator::next(generator) {
+            | Some(value) => {
+                // Safety: non-null out-pointer, valid as per the contract.
+                out.write(value);
+                true
+            },
+            | None => false,
+        }
+    }
+
+    #[$crate::ffi_export]
+    /// Destroys the generator, cancelling it if it was not exhausted yet.
+    pub
+    fn $free (
+        generator: Option<$crate::boxed::Box<$crate::generator::Generator<$Item>>>,
+    )
+    {
+        $crate::core::mem::drop(generator)
+    }
+)}
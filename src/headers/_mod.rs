@@ -28,9 +28,8 @@
 //! fn rust_concat (fst: char_p::Ref<'_>, snd: char_p::Ref<'_>)
 //!   -> char_p::Box
 //! {
-//!     let s: String = format!("{}{}\0", fst, snd);
-//!     s   .try_into() // Try to convert to a boxed `char *` pointer
-//!         .unwrap()   // Only fails if there is an inner nul byte.
+//!     // Appends the nul terminator, and only panics on an inner nul byte.
+//!     char_p::new(format_args!("{}{}", fst, snd))
 //! }
 //!
 //! /// Frees a pointer obtained by calling `rust_concat`.
@@ -50,7 +49,8 @@
 //!     ::safer_ffi::headers::builder()
 //!         .with_guard("__ASGARD__")
 //!         .to_file("filename.h")?
-//!         .generate()
+//!         .generate()?;
+//!     Ok(())
 //! }
 // //! # generate_c_header().unwrap();
 //! ```
@@ -105,7 +105,7 @@ use ::std::{
     env,
     fs,
     io,
-    path::Path,
+    path::{Path, PathBuf},
 };
 
 use_prelude!();
@@ -114,6 +114,129 @@ use rust::{String, Vec};
 pub use definer::{Definer, HashSetDefiner};
 mod definer;
 
+pub use atomic_file::AtomicFile;
+mod atomic_file;
+
+pub use report::{
+    Destructor, OwnedKind, Report, Signature, Symbol, SymbolKind,
+};
+#[doc(hidden)]
+pub use report::__report_signature__;
+mod report;
+
+pub use diagnostics::Diagnostic;
+#[doc(hidden)]
+pub use diagnostics::{__lint_item__, __record_since__, __since__};
+mod diagnostics;
+
+mod keywords;
+
+#[doc(hidden)]
+pub use backend::{
+    Backend, Field, TypeDesc, Variant, __backend__, __declare_const__,
+    __declare_fn__, __declare_static__, __define_enum__, __define_opaque__,
+    __define_struct__, __define_union__,
+};
+mod backend;
+
+mod c;
+mod csharp;
+mod go;
+mod java;
+mod zig;
+
+pub use context::{
+    Case, FloatTypes, FnPtrTypedefs, KeywordEscaping, Language,
+    NamingConvention, SimdTypes, VariantOrder,
+};
+#[doc(hidden)]
+pub use context::{
+    __c_escape__, __export_macro__, __fn_name__, __fn_ptr_typedef__,
+    __language__, __type_name__,
+};
+pub(in crate) mod context;
+
+pub use dialect::CDialect;
+mod dialect;
+
+pub use c_literal::CLiteral;
+mod c_literal;
+
+mod cffi;
+
+mod markdown;
+
+mod check;
+
+#[doc(hidden)]
+pub use cxx::{__owned_probe__, __report_destructor__};
+mod cxx;
+
+mod kotlin;
+
+pub use determinism::assert_deterministic;
+mod determinism;
+
+mod license;
+
+#[doc(hidden)]
+pub use manifest::__describe_api__;
+mod manifest;
+
+mod visibility;
+
+mod version;
+
+mod config;
+
+#[doc(hidden)]
+pub use system_abi::{__abi_annotation__, __define_system_abi__};
+mod system_abi;
+
+#[doc(hidden)]
+pub use deprecated::{__define_deprecated__, __deprecated__};
+mod deprecated;
+
+#[doc(hidden)]
+pub use variants::__output_variants__;
+mod variants;
+
+pub use collisions::NameCollisions;
+#[doc(hidden)]
+pub use collisions::__c_short_name__;
+mod collisions;
+
+#[doc(hidden)]
+pub use doc_group::__define_doc_group__;
+mod doc_group;
+
+#[doc(hidden)]
+pub use code_blocks::__doc_lines__;
+mod code_blocks;
+
+pub use translations::DocTranslations;
+#[doc(hidden)]
+pub use translations::__output_translated_docs__;
+mod translations;
+
+#[doc(hidden)]
+pub use swift::{__nullability__, __swift_name__};
+mod swift;
+
+#[doc(hidden)]
+pub use contracts::{__contracts__, __is_nonnull__, __pinned__};
+mod contracts;
+
+pub use split::Grouping;
+#[doc(hidden)]
+pub use split::__in_group__;
+mod split;
+
+pub use registration::registered_exports;
+#[doc(hidden)]
+pub use registration::__register_export__;
+mod registration;
+
 macro_rules! with_optional_fields {(
     $(
         $(#[$field_meta:meta])*
@@ -161,11 +284,28 @@ macro_rules! with_optional_fields {(
         }
     }
 
+    impl<'__> Builder<'__, WhereTo> {
+        /// A copy of the configuration (all the options being `Copy`).
+        fn copy (self: &'_ Self)
+          -> Self
+        {
+            Builder {
+                target: WhereTo, $(
+                $field: self.$field, )*
+            }
+        }
+    }
+
     impl<'__> Builder<'__, WhereTo> {
         /// Specify the path to the file to be generated.
         ///
         /// **⚠️ If it already exists, its contents will be overwritten ⚠️**
         ///
+        /// The file is only written to once the headers have been
+        /// successfully generated, and atomically so (see [`AtomicFile`]).
+        /// Since it is not opened beforehand, errors regarding its path (such
+        /// as a missing parent directory) are reported by [`.generate()`].
+        ///
         /// There is no default value here, either `.to_file()` or [`.to_writer()`]
         /// need to be called to be able to [`.generate()`] the headers.
         ///
@@ -178,7 +318,7 @@ macro_rules! with_optional_fields {(
         /// # fn main () -> ::std::io::Result<()> { Ok({
         /// ::safer_ffi::headers::builder()
         ///     .to_file("my_header.h")?
-        ///     .generate()?
+        ///     .generate()?;
         /// # })}
         /// ```
         ///
@@ -188,12 +328,47 @@ macro_rules! with_optional_fields {(
         fn to_file (
             self: Self,
             filename: impl AsRef<Path>,
+        ) -> io::Result<Builder<'__, AtomicFile>>
+        {
+            Ok(self.to_writer(
+                AtomicFile::new(filename.as_ref().to_owned())
+            ))
+        }
+
+        /// Like [`.to_file()`][`Builder::to_file`], but appending to the file
+        /// (if it already exists) rather than overwriting it.
+        ///
+        /// This allows several generation passes (_e.g._, the core exports,
+        /// and then the exports of an optional feature) to target the same
+        /// header file, provided only one of them emits the banner and the
+        /// include guard (see [`.with_emit_guard()`]). When the passes run
+        /// within the same process, they can also share one [`Definer`]
+        /// (through [`.generate_with_definer()`]`(&mut definer)`), so that
+        /// the types defined by one pass are not redefined by the others.
+        ///
+        /// # Example
+        ///
+        /// ```rust,no_run
+        /// # fn main () -> ::std::io::Result<()> { Ok({
+        /// ::safer_ffi::headers::builder()
+        ///     .with_banner("/* Optional feature exports */")
+        ///     .with_emit_guard(false)
+        ///     .to_file_append("my_header.h")?
+        ///     .generate()?;
+        /// # })}
+        /// ```
+        ///
+        /// [`.with_emit_guard()`]: `Builder::with_emit_guard`
+        /// [`.generate_with_definer()`]: `Builder::generate_with_definer`
+        pub
+        fn to_file_append (
+            self: Self,
+            filename: impl AsRef<Path>,
         ) -> io::Result<Builder<'__, fs::File>>
         {
             Ok(self.to_writer(
                 fs::OpenOptions::new()
-                    .create(true)/*or*/.truncate(true)
-                    .write(true)
+                    .create(true)/*or*/.append(true)
                     .open(filename)?
             ))
         }
@@ -209,7 +384,7 @@ macro_rules! with_optional_fields {(
         /// # fn main () -> ::std::io::Result<()> { Ok({
         /// ::safer_ffi::headers::builder()
         ///     .to_writer(::std::io::stdout())
-        ///     .generate()?
+        ///     .generate()?;
         /// # })}
         /// ```
         pub
@@ -234,18 +409,24 @@ macro_rules! with_optional_fields {(
 
     impl<'__, W : io::Write> Builder<'__, W> {
         /// Generate the C header file.
+        ///
+        /// The "output stream" is [`flush`][`io::Write::flush`]ed once the
+        /// generation has succeeded, and a [`Report`] of the emitted items
+        /// is returned.
         pub
         fn generate (self)
-          -> io::Result<()>
+          -> io::Result<Report>
         {
             let Self { mut target, $($field ,)* } = self;
-            Builder {
+            let report = Builder {
                 target: WhereTo, $(
                 $field, )*
             }.generate_with_definer(HashSetDefiner {
                 out: &mut target,
                 defines_set: Default::default(),
-            })
+            })?;
+            target.flush()?;
+            Ok(report)
         }
 
         // pub
@@ -299,73 +480,837 @@ with_optional_fields! {
     /// <span style="color:#3f7f8f; ">&nbsp;*                                         *</span>
     /// <span style="color:#3f7f8f; ">&nbsp;*******************************************/</span>
     /// </pre>
+    ///
+    /// or, for [`Language::Zig`] (which has no `/* ... */` comments), to the
+    /// same text, as `//` comments.
     banner: &'__ str,
+
+    /// Sets up a license header, emitted at the very top of the generated
+    /// file (before the banner).
+    ///
+    /// It can either be an [SPDX] license expression, such as
+    /// `"MIT OR Apache-2.0"`, which is emitted as:
+    ///
+    /// ```C
+    /// /* SPDX-License-Identifier: MIT OR Apache-2.0 */
+    /// ```
+    ///
+    /// or the (multi-line) text of a license notice, which is emitted within
+    /// a comment as is.
+    ///
+    /// [SPDX]: https://spdx.org/licenses/
+    license_header: &'__ str,
+
+    /// Shuffles the order in which the `#[ffi_export]`ed items are defined,
+    /// using the given seed.
+    ///
+    /// This is only useful to check that the generated headers do not depend
+    /// on the (link-time) order of the definitions: see
+    /// [`assert_deterministic`]. The shuffling happens before the
+    /// [stable ordering pass][`Builder::with_stable_order`], if any.
+    shuffle_seed: u64,
+
+    /// Whether to sort the `#[ffi_export]`ed items by name (defaults to
+    /// `true`), rather than emitting them in the order in which they have
+    /// been registered, which depends on the link order, and may thus
+    /// change from one build to another.
+    ///
+    /// Each type is still defined before the first item using it (and after
+    /// the types it depends on), so that the headers end up ordered
+    /// topologically with regard to the type dependencies, and alphabetically
+    /// otherwise.
+    stable_order: bool,
+
+    /// Only emits the `#[ffi_export]`ed functions and `static`s whose (C)
+    /// name is accepted by the given predicate, as well as the types these
+    /// depend on.
+    ///
+    /// This makes it possible to generate, for instance, a public header
+    /// with part of the API, and an internal one with the rest:
+    ///
+    /// ```rust,no_run
+    /// # fn main () -> ::std::io::Result<()> { Ok({
+    /// let is_internal = |name: &'_ str| name.starts_with("mylib_internal_");
+    ///
+    /// ::safer_ffi::headers::builder()
+    ///     .with_filter(&|name| is_internal(name) == false)
+    ///     .to_file("include/mylib.h")?
+    ///     .generate()?;
+    /// ::safer_ffi::headers::builder()
+    ///     .with_filter(&is_internal)
+    ///     .with_guard("__RUST_MYLIB_INTERNAL__")
+    ///     .to_file("include/mylib_internal.h")?
+    ///     .generate()?;
+    /// # })}
+    /// ```
+    ///
+    /// Note that the types used by both sides are defined in both headers,
+    /// which are thus not meant to be `#include`d together (see
+    /// [`.generate_split()`][`Builder::generate_split`] for that).
+    filter: &'__ dyn Fn(&str) -> bool,
+
+    /// Whether to emit the include guard around the generated definitions
+    /// (defaults to `true`).
+    ///
+    /// Disabling it is useful when several generation passes target the same
+    /// file: see [`.to_file_append()`][`Builder::to_file_append`].
+    emit_guard: bool,
+
+    /// Sets up extra `#include`s, emitted right after the banner (and the
+    /// include guard), outside the `extern "C"` block, for the headers some
+    /// hand-written declarations (such as callback typedefs) depend on.
+    ///
+    /// Each one is either a `<system>` or a `"local"` header; a bare path is
+    /// taken as a local header.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main () -> ::std::io::Result<()> { Ok({
+    /// ::safer_ffi::headers::builder()
+    ///     .with_includes(&["<stdarg.h>", "\"my_common.h\""])
+    ///     .to_file("my_header.h")?
+    ///     .generate()?;
+    /// # })}
+    /// ```
+    includes: &'__ [&'__ str],
+
+    /// Sets up a hand-written snippet (_e.g._, helper macros, compatibility
+    /// shims, or extra declarations) emitted verbatim within the include
+    /// guard, right after the opening of the `extern "C"` block, before the
+    /// generated definitions.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main () -> ::std::io::Result<()> { Ok({
+    /// ::safer_ffi::headers::builder()
+    ///     .with_prologue("#define MYLIB_MAX_PATH 4096\n")
+    ///     .with_epilogue("static inline int mylib_is_ok (int status) { return status == 0; }\n")
+    ///     .to_file("my_header.h")?
+    ///     .generate()?;
+    /// # })}
+    /// ```
+    prologue: &'__ str,
+
+    /// Sets up a hand-written snippet emitted verbatim within the include
+    /// guard, after the generated definitions, right before the closing of
+    /// the `extern "C"` block (see
+    /// [`.with_prologue()`][`Builder::with_prologue`]).
+    epilogue: &'__ str,
+
+    /// Sets up the (C++) namespace, such as `"mylib::ffi"`, within which the
+    /// declarations are put when the header is `#include`d from C++ (in
+    /// addition to the `extern "C"` block), so as not to pollute the global
+    /// namespace.
+    ///
+    /// The symbols keep their (unmangled) C names, and the C side is left
+    /// untouched.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main () -> ::std::io::Result<()> { Ok({
+    /// ::safer_ffi::headers::builder()
+    ///     .with_cxx_namespace("mylib::ffi")
+    ///     .to_file("my_header.h")?
+    ///     .generate()?;
+    /// # })}
+    /// ```
+    ///
+    /// yields:
+    ///
+    /// ```C
+    /// #ifdef __cplusplus
+    /// #include <stddef.h>
+    /// #include <stdint.h>
+    ///
+    /// namespace mylib {
+    /// namespace ffi {
+    /// extern "C" {
+    /// #endif
+    ///
+    /// /* ... */
+    ///
+    /// #ifdef __cplusplus
+    /// } /* extern "C" */
+    /// } /* namespace ffi */
+    /// } /* namespace mylib */
+    /// #endif
+    /// ```
+    cxx_namespace: &'__ str,
+
+    /// Sets up the style of the include guard (when
+    /// [emitted][`Builder::with_emit_guard`]).
+    ///
+    /// It defaults to [`GuardStyle::Ifndef`].
+    guard_style: GuardStyle,
+
+    /// Sets up a callback to be called with each suspicious construct
+    /// spotted while generating the headers (see [`Diagnostic`]).
+    ///
+    /// These are ignored by default (but are still listed in the
+    /// [`Report`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main () -> ::std::io::Result<()> { Ok({
+    /// ::safer_ffi::headers::builder()
+    ///     .with_on_warning(|diagnostic| eprintln!("warning: {}", diagnostic))
+    ///     .to_file("my_header.h")?
+    ///     .generate()?;
+    /// # })}
+    /// ```
+    on_warning: fn(Diagnostic),
+
+    /// Sets up how the fields and parameters named after a C (or C++)
+    /// keyword get renamed.
+    ///
+    /// It defaults to [`KeywordEscaping::Suffix`]`("_")`: a field named
+    /// `class` is emitted as `class_`.
+    keyword_escaping: KeywordEscaping,
+
+    /// Sets up what to do when two distinct Rust types (_e.g._, two `Point`
+    /// structs, in two different modules) would be given the same C name.
+    ///
+    /// It defaults to [`NameCollisions::Error`], rather than only defining
+    /// the first of them.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main () -> ::std::io::Result<()> { Ok({
+    /// use ::safer_ffi::headers::NameCollisions;
+    ///
+    /// ::safer_ffi::headers::builder()
+    ///     // `geometry::Point` and `ui::Point` become `Point_t` and
+    ///     // `ui_Point_t`.
+    ///     .with_name_collisions(NameCollisions::ModulePrefix)
+    ///     .to_file("my_header.h")?
+    ///     .generate()?;
+    /// # })}
+    /// ```
+    name_collisions: NameCollisions,
+
+    /// Sets up where the type definitions are emitted, relative to the
+    /// function (and `static`) declarations.
+    ///
+    /// It defaults to [`TypePlacement::BeforeFirstUse`].
+    type_placement: TypePlacement,
+
+    /// Sets up the C types `f32` and `f64` are mapped to.
+    ///
+    /// It defaults to [`FloatTypes::Standard`]: `float` and `double`.
+    float_types: FloatTypes,
+
+    /// Sets up the C types the [`crate::simd`] vectors are mapped to.
+    ///
+    /// It defaults to [`SimdTypes::AlignedArrays`].
+    simd_types: SimdTypes,
+
+    /// Sets up how the function pointer types (_e.g._, of the callback
+    /// parameters) are emitted.
+    ///
+    /// It defaults to [`FnPtrTypedefs::Inline`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main () -> ::std::io::Result<()> { Ok({
+    /// use ::safer_ffi::headers::FnPtrTypedefs;
+    ///
+    /// ::safer_ffi::headers::builder()
+    ///     .with_fn_ptr_typedefs(FnPtrTypedefs::Named)
+    ///     .to_file("my_header.h")?
+    ///     .generate()?;
+    /// # })}
+    /// ```
+    ///
+    /// yields, for a `fn on_event (cb: extern "C" fn(i32))` export:
+    ///
+    /// ```c
+    /// typedef void (*void_int32_fptr_t)(int32_t);
+    ///
+    /// void on_event (
+    ///     void_int32_fptr_t cb);
+    /// ```
+    fn_ptr_typedefs: FnPtrTypedefs,
+
+    /// Sets up the order in which the constants of the enums are listed.
+    ///
+    /// It defaults to [`VariantOrder::Declaration`], the fields of the
+    /// structs and unions being always emitted in declaration order as
+    /// well, so that the headers only change along with the Rust
+    /// definitions.
+    variant_order: VariantOrder,
+
+    /// Sets up the C standard the generated headers are written against,
+    /// such as [`CDialect::C89`] for headers meant for old toolchains.
+    ///
+    /// It defaults to [`CDialect::C11`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main () -> ::std::io::Result<()> { Ok({
+    /// use ::safer_ffi::headers::{self, CDialect};
+    ///
+    /// headers::builder()
+    ///     .with_c_dialect(CDialect::C89)
+    ///     .to_file("my_header.h")?
+    ///     .generate()?;
+    /// # })}
+    /// ```
+    c_dialect: CDialect,
+
+    /// Annotates the function and `static` declarations with the given
+    /// macro, defined (unless already defined) as `__declspec(dllexport)`
+    /// or `__declspec(dllimport)` on Windows (depending on whether the
+    /// [`dllexport` define][`Builder::with_dllexport_define`] is defined),
+    /// and as `__attribute__((visibility("default")))` with GCC and Clang
+    /// elsewhere:
+    ///
+    /// ```C
+    /// #ifndef MYLIB_API
+    /// #  if defined(_WIN32) || defined(__CYGWIN__)
+    /// #    ifdef MYLIB_API_BUILD
+    /// #      define MYLIB_API __declspec(dllexport)
+    /// #    else
+    /// #      define MYLIB_API __declspec(dllimport)
+    /// #    endif
+    /// #  elif defined(__GNUC__) || defined(__clang__)
+    /// #    define MYLIB_API __attribute__((visibility("default")))
+    /// #  else
+    /// #    define MYLIB_API
+    /// #  endif
+    /// #endif
+    ///
+    /// MYLIB_API void mylib_init (void);
+    /// ```
+    ///
+    /// The same header can thus be used both to consume the library as a
+    /// DLL (such as the `cdylib` of the crate, whose exported `static`s
+    /// require `dllimport`), and to build one (such as a DLL embedding the
+    /// `staticlib` of the crate, compiled with `-DMYLIB_API_BUILD`).
+    /// Consumers linking the `staticlib` directly can `#define` the macro
+    /// (as empty) beforehand.
+    ///
+    /// Elsewhere, this is meant for C code compiled with
+    /// `-fvisibility=hidden` (such as a shared library embedding the
+    /// `staticlib` of the crate), so that the exported API is the only one
+    /// visible. To also hide the symbols of the `staticlib` itself
+    /// (including the ones of the Rust standard library), link the shared
+    /// library with the [version script][`Report::version_script`] of the
+    /// exports.
+    export_macro: &'static str,
+
+    /// Sets up the macro which, when defined, makes the [export macro][
+    /// `Builder::with_export_macro`] expand to `__declspec(dllexport)`
+    /// (rather than `__declspec(dllimport)`) on Windows, _i.e._, when
+    /// building the DLL.
+    ///
+    /// It defaults to the name of the export macro followed by `_BUILD`
+    /// (_e.g._, `MYLIB_API_BUILD`).
+    dllexport_define: &'__ str,
+
+    /// Emits, with the given prefix (_e.g._, `MYLIB`), the
+    /// `MYLIB_VERSION_MAJOR`, `MYLIB_VERSION_MINOR`, `MYLIB_VERSION_PATCH`
+    /// and `MYLIB_VERSION` (_e.g._, `"1.2.3"`) macros, for C consumers to
+    /// check the version of the headers at compile time.
+    ///
+    /// The version is the one of the crate generating the headers, _i.e._,
+    /// its `CARGO_PKG_VERSION`, as set by Cargo when running the binary (or
+    /// test) generating them.
+    ///
+    /// See also [`ffi_export_version!`](/safer_ffi/macro.ffi_export_version.html),
+    /// to check it against the version of the library, at runtime.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main () -> ::std::io::Result<()> { Ok({
+    /// ::safer_ffi::headers::builder()
+    ///     .with_version_macros("MYLIB")
+    ///     .to_file("mylib.h")?
+    ///     .generate()?;
+    /// # })}
+    /// ```
+    ///
+    /// yields, for a `1.2.3` crate:
+    ///
+    /// ```c
+    /// #define MYLIB_VERSION_MAJOR 1
+    /// #define MYLIB_VERSION_MINOR 2
+    /// #define MYLIB_VERSION_PATCH 3
+    /// #define MYLIB_VERSION "1.2.3"
+    /// ```
+    version_macros: &'__ str,
+
+    /// Sets up the language of the generated bindings.
+    ///
+    /// It defaults to [`Language::C`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main () -> ::std::io::Result<()> { Ok({
+    /// use ::safer_ffi::headers::{self, Language};
+    ///
+    /// headers::builder()
+    ///     .with_language(Language::CSharp)
+    ///     .to_file("MyLib.cs")?
+    ///     .generate()?;
+    /// # })}
+    /// ```
+    language: Language,
+
+    /// Sets up the name of the native library the C# `[DllImport]`s, the
+    /// Java `Native.load()`, the Go `#cgo LDFLAGS`, or the Kotlin/Native
+    /// `linkerOpts`, refer to (only relevant for [`Language::CSharp`],
+    /// [`Language::Java`] and [`Language::Go`], and for
+    /// [`.generate_kotlin_def()`][`Builder::generate_kotlin_def`]).
+    ///
+    /// It defaults to the name of the crate (with `-`s replaced by `_`s).
+    dll_name: &'__ str,
+
+    /// Sets up the `package` of the generated `Ffi` interface (only relevant
+    /// for [`Language::Java`]).
+    ///
+    /// It defaults to none (the unnamed package).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main () -> ::std::io::Result<()> { Ok({
+    /// use ::safer_ffi::headers::{self, Language};
+    ///
+    /// headers::builder()
+    ///     .with_language(Language::Java)
+    ///     .with_java_package("com.example.mylib")
+    ///     .to_file("src/main/java/com/example/mylib/Ffi.java")?
+    ///     .generate()?;
+    /// # })}
+    /// ```
+    java_package: &'__ str,
+
+    /// Sets up the `package` of the generated Go file (only relevant for
+    /// [`Language::Go`]).
+    ///
+    /// It defaults to the name of the crate (with `-`s replaced by `_`s).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main () -> ::std::io::Result<()> { Ok({
+    /// use ::safer_ffi::headers::{self, Language};
+    ///
+    /// headers::builder()
+    ///     .with_language(Language::Go)
+    ///     .with_go_package("mylib")
+    ///     .to_file("mylib/mylib.go")?
+    ///     .generate()?;
+    /// # })}
+    /// ```
+    go_package: &'__ str,
+
+    /// Sets up the `package` of the Kotlin/Native bindings generated by the
+    /// `cinterop` tool (see
+    /// [`.generate_kotlin_def()`][`Builder::generate_kotlin_def`]).
+    ///
+    /// It defaults to the name of the crate (with `-`s replaced by `_`s).
+    kotlin_package: &'__ str,
+
+    /// Sets up how the names of the emitted types and functions are derived
+    /// from the Rust ones (without renaming the Rust items).
+    ///
+    /// The convention applies to the whole name of a type (_e.g._,
+    /// `slice_ref_Point` for a `c_slice::Ref<'_, Point>`), before the `_t`
+    /// suffix is appended. Since the symbols of the exported functions are
+    /// fixed when compiling the library, a renamed function is declared
+    /// under its actual symbol, and then aliased with a `#define`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main () -> ::std::io::Result<()> { Ok({
+    /// use ::safer_ffi::headers::{self, Case, NamingConvention};
+    ///
+    /// // `fn do_thing` and `struct Point` become `MyLib_DoThing` and
+    /// // `MyLib_Point_t`.
+    /// headers::builder()
+    ///     .with_naming_convention(NamingConvention {
+    ///         prefix: "MyLib_",
+    ///         case: Case::PascalCase,
+    ///     })
+    ///     .to_file("my_header.h")?
+    ///     .generate()?;
+    /// # })}
+    /// ```
+    naming_convention: NamingConvention,
+
+    /// Whether to annotate the function declarations for Swift (defaults
+    /// to `false`): with the nullability of their pointers (`_Nullable` for
+    /// the ones which can be `NULL`, such as an `Option<&T>`, and
+    /// `_Nonnull` for the others), and with a `swift_name` attribute naming
+    /// them in `lowerCamelCase`:
+    ///
+    /// ```C
+    /// Point_t * _Nonnull mylib_point_new (
+    ///     int32_t x,
+    ///     int32_t y) SAFER_FFI_SWIFT_NAME("mylibPointNew(x:y:)");
+    /// ```
+    ///
+    /// The annotations are ignored by the compilers other than Clang.
+    ///
+    /// See also [`.generate_swift_module()`][
+    /// `Builder::generate_swift_module`].
+    swift_annotations: bool,
+
+    /// Emits, with the given prefix, the `<PREFIX>_REQUIRE(cond)` and
+    /// `<PREFIX>_REQUIRE_NONNULL(p)` macros (which `assert` unless `NDEBUG`
+    /// is defined, and which can be `#define`d beforehand), and documents
+    /// the preconditions of each function in terms of these:
+    ///
+    /// ```C
+    /// /** \brief
+    ///  *  Returns the length of the string.
+    ///  *
+    ///  *  \pre `s` is not `NULL`: `MYLIB_REQUIRE_NONNULL(s)`.
+    ///  */
+    /// size_t mylib_strlen (
+    ///     char const * s);
+    /// ```
+    ///
+    /// so that C callers can uniformly check the arguments in their debug
+    /// builds. For now, these preconditions are the non-nullability of the
+    /// pointers (other than the `Option<...>` ones).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main () -> ::std::io::Result<()> { Ok({
+    /// ::safer_ffi::headers::builder()
+    ///     .with_contract_macros("MYLIB")
+    ///     .to_file("my_header.h")?
+    ///     .generate()?;
+    /// # })}
+    /// ```
+    contract_macros: &'static str,
+
+    /// Replaces the doc comments of the items with the given translations
+    /// (keyed by the Rust name of the item), so as to generate localized
+    /// headers (_e.g._, one per language, from one translations file each).
+    ///
+    /// This applies to the exported functions, `static`s and `const`s, and to
+    /// the structs, unions and enums (and to the fields of the structs and
+    /// unions, as `Struct.field`). The items without a translation keep
+    /// their Rust documentation.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main () -> ::std::io::Result<()> { Ok({
+    /// use ::safer_ffi::headers::DocTranslations;
+    ///
+    /// ::safer_ffi::headers::builder()
+    ///     .with_doc_translations(&DocTranslations::from_file("docs/ja.txt")?)
+    ///     .to_file("include/ja/mylib.h")?
+    ///     .generate()?;
+    /// # })}
+    /// ```
+    ///
+    /// See [`DocTranslations::parse`] for the format of the file.
+    doc_translations: &'__ DocTranslations,
+}
+
+/// Opening marker of the auto-generated region of a header file: see
+/// [`Builder::update_file_section`].
+pub
+const SECTION_BEGIN: &str = "/* BEGIN SAFER-FFI */";
+
+/// Closing marker of the auto-generated region of a header file: see
+/// [`Builder::update_file_section`].
+pub
+const SECTION_END: &str = "/* END SAFER-FFI */";
+
+/// The default [banner][`Builder::with_banner`].
+const DEFAULT_BANNER: &str = concat!(
+    "/*! \\file */\n",
+    "/*******************************************\n",
+    " *                                         *\n",
+    " *  File auto-generated by `::safer_ffi`.  *\n",
+    " *                                         *\n",
+    " *  Do not manually edit this file.        *\n",
+    " *                                         *\n",
+    " *******************************************/",
+);
+
+/// Where the type definitions are emitted in the generated headers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub
+enum TypePlacement {
+    /// Each type is defined right above the first declaration using it: a
+    /// type used by exactly one function is thus defined right above the
+    /// prototype of that function.
+    BeforeFirstUse,
+
+    /// All the types are defined first (in the same relative order), and
+    /// then come all the function (and `static`) declarations.
+    Hoisted,
+}
+
+/// The style of the include guard of the generated headers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub
+enum GuardStyle {
+    /// `#ifndef GUARD` / `#define GUARD` ... `#endif /* GUARD */`.
+    Ifndef,
+
+    /// `#pragma once`, which is not standard C, but is supported by all the
+    /// major compilers.
+    PragmaOnce,
+
+    /// `#pragma once`, followed by the `#ifndef` guard, for the compilers
+    /// which do not support the former.
+    Both,
+}
+
+impl GuardStyle {
+    /// What goes before the generated definitions.
+    pub(in crate)
+    fn open (self: Self, guard: &'_ str)
+      -> String
+    {
+        let pragma = "#pragma once\n\n";
+        let ifndef = format!("#ifndef {0}\n#define {0}\n\n", guard);
+        match self {
+            | GuardStyle::Ifndef => ifndef,
+            | GuardStyle::PragmaOnce => pragma.into(),
+            | GuardStyle::Both => format!("{}{}", pragma, ifndef),
+        }
+    }
+
+    /// What goes after the generated definitions.
+    pub(in crate)
+    fn close (self: Self, guard: &'_ str)
+      -> String
+    {
+        match self {
+            | GuardStyle::PragmaOnce => String::new(),
+            | GuardStyle::Ifndef
+            | GuardStyle::Both
+            => format!("\n#endif /* {} */\n", guard),
+        }
+    }
+}
+
+/// The `#include` of a `<system>` or a `"local"` header, a bare path being
+/// taken as a local header.
+fn include_directive (include: &'_ str)
+  -> String
+{
+    if include.starts_with(&['<', '"'][..]) {
+        format!("#include {}\n", include)
+    } else {
+        format!("#include \"{}\"\n", include)
+    }
+}
+
+/// Whether `s` is a valid C (and C++) identifier.
+fn is_c_identifier (s: &'_ str)
+  -> bool
+{
+    matches!(s.chars().next(), Some(c) if c == '_' || c.is_ascii_alphabetic())
+    && s.chars().all(|c| c == '_' || c.is_ascii_alphanumeric())
+}
+
+/// Writes a hand-written [prologue][`Builder::with_prologue`] or
+/// [epilogue][`Builder::with_epilogue`], newline-terminated.
+fn write_snippet (out: &'_ mut dyn io::Write, snippet: &'_ str)
+  -> io::Result<()>
+{
+    out.write_all(snippet.as_bytes())?;
+    if !snippet.ends_with('\n') {
+        out.write_all(b"\n")?;
+    }
+    Ok(())
 }
 
 impl Builder<'_, WhereTo> {
+    /// Alternative to [`.to_file()`][`Builder::to_file`]`.generate()` which
+    /// only replaces the region between the [`SECTION_BEGIN`] and
+    /// [`SECTION_END`] markers of an existing file, leaving the
+    /// (human-maintained) rest of the file untouched.
+    ///
+    /// If the file does not exist, it is created with only the generated
+    /// region (markers included). If it exists but lacks the markers, an
+    /// error of kind [`InvalidData`][`io::ErrorKind::InvalidData`] is
+    /// returned and the file is left as is.
+    ///
+    /// # Example
+    ///
+    /// Given a `my_header.h` file such as:
+    ///
+    /// ```C
+    /// #include "my_platform.h"
+    ///
+    /// /* BEGIN SAFER-FFI */
+    /// /* END SAFER-FFI */
+    ///
+    /// static inline void my_helper (void) { /* ... */ }
+    /// ```
+    ///
+    /// then
+    ///
+    /// ```rust,no_run
+    /// # fn main () -> ::std::io::Result<()> { Ok({
+    /// ::safer_ffi::headers::builder()
+    ///     .update_file_section("my_header.h")?;
+    /// # })}
+    /// ```
+    ///
+    /// fills the region between the markers with the generated headers.
+    pub
+    fn update_file_section (self, filename: impl AsRef<Path>)
+      -> io::Result<Report>
+    {
+        let filename = filename.as_ref();
+        let mut generated = Vec::new();
+        let report = self.to_writer(&mut generated).generate()?;
+        let generated = String::from_utf8(generated).map_err(|err| {
+            io::Error::new(io::ErrorKind::InvalidData, err)
+        })?;
+        let section = format!("{}\n{}\n{}",
+            SECTION_BEGIN, generated.trim_end(), SECTION_END,
+        );
+        let contents = match fs::read_to_string(filename) {
+            | Err(ref err) if err.kind() == io::ErrorKind::NotFound => {
+                section + "\n"
+            },
+            | Err(err) => return Err(err),
+            | Ok(existing) => {
+                let begin = existing.find(SECTION_BEGIN);
+                let end = begin.and_then(|begin| {
+                    existing[begin ..]
+                        .find(SECTION_END)
+                        .map(|end| begin + end + SECTION_END.len())
+                });
+                match (begin, end) {
+                    | (Some(begin), Some(end)) => format!("{}{}{}",
+                        &existing[.. begin], section, &existing[end ..],
+                    ),
+                    | _ => return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!(
+                            "`{}` lacks the `{}` ... `{}` markers",
+                            filename.display(), SECTION_BEGIN, SECTION_END,
+                        ),
+                    )),
+                }
+            },
+        };
+        atomic_file::write_atomically(filename, contents.as_bytes())?;
+        Ok(report)
+    }
+
     /// More customizable version of [`.generate()`][`Builder::generate].
     ///
     /// With this call, one can provide a custom implementation of a [`Definer`],
     /// which can be useful for mock tests, mainly.
     pub
-    fn generate_with_definer (self, mut definer: impl Definer)
-      -> io::Result<()>
+    fn generate_with_definer (self, definer: impl Definer)
+      -> io::Result<Report>
     {
-        let s;
+        let mut definer = report::ReportingDefiner::new(definer);
+        definer.on_warning = self.on_warning;
         let config = self;
-        let guard: &'_ str =
-            if let Some(it) = config.guard { it } else {
-                s = format!("__RUST_{}__",
-                    env::var("CARGO_PKG_NAME")
-                        .unwrap()
-                        .to_ascii_uppercase()
-                );
-                &*s
-            }
-        ;
-        let banner: &'_ str = config.banner.unwrap_or(concat!(
-            "/*! \\file */\n",
-            "/*******************************************\n",
-            " *                                         *\n",
-            " *  File auto-generated by `::safer_ffi`.  *\n",
-            " *                                         *\n",
-            " *  Do not manually edit this file.        *\n",
-            " *                                         *\n",
-            " *******************************************/",
-        ));
-
-        write!(definer.out(),
-            concat!(
-                "{banner}\n\n",
-                "#ifndef {guard}\n",
-                "#define {guard}\n",
-                "\n",
-                "#ifdef __cplusplus\n",
-                "extern \"C\" {{\n",
-                "#endif\n\n",
-            ),
-            guard = guard,
-            banner = banner,
-        )?;
-        crate::inventory::iter
-            .into_iter()
-            // Iterate in reverse fashion to more closely match
-            // the Rust definition order.
-            .collect::<Vec<_>>().into_iter().rev()
-            .try_for_each(|crate::FfiExport(define)| define(&mut definer))
-            ?
+        let default_ctx = context::Context::default();
+        let language = config.language.unwrap_or(default_ctx.language);
+        let backend = language.backend();
+
+        if let Some(license) = config.license_header {
+            let lines = license::lines(license);
+            writeln!(definer.out(), "{}", backend.comment(&lines))?;
+        }
+        let banner = config.banner.unwrap_or(backend.banner());
+        write!(definer.out(), "{}\n\n", banner)?;
+        backend.prologue(&mut definer, &config)?;
+        let mut exports = registration::exports();
+        if let Some(seed) = config.shuffle_seed {
+            determinism::shuffle(&mut exports, seed);
+        }
+        let ctx = context::Context {
+            keyword_escaping:
+                config.keyword_escaping
+                    .unwrap_or(default_ctx.keyword_escaping)
+            ,
+            float_types:
+                config.float_types
+                    .unwrap_or(default_ctx.float_types)
+            ,
+            simd_types:
+                config.simd_types
+                    .unwrap_or(default_ctx.simd_types)
+            ,
+            variant_order:
+                config.variant_order
+                    .unwrap_or(default_ctx.variant_order)
+            ,
+            export_macro: config.export_macro,
+            language,
+            naming_convention:
+                config.naming_convention
+                    .unwrap_or(default_ctx.naming_convention)
+            ,
+            c_dialect:
+                config.c_dialect
+                    .unwrap_or(default_ctx.c_dialect)
+            ,
+            swift_annotations:
+                config.swift_annotations
+                    .unwrap_or(default_ctx.swift_annotations)
+            ,
+            contract_macros: config.contract_macros,
+            fn_ptr_typedefs:
+                config.fn_ptr_typedefs
+                    .unwrap_or(default_ctx.fn_ptr_typedefs)
+            ,
+        };
+        let hoist = config.type_placement == Some(TypePlacement::Hoisted);
+        if hoist {
+            definer.start_hoisting();
+        }
+        let stable_order = config.stable_order.unwrap_or(true);
+        let translations = config.doc_translations.cloned();
+        let name_collisions =
+            config.name_collisions
+                .unwrap_or(NameCollisions::Error)
         ;
-        write!(definer.out(),
-            concat!(
-                "\n",
-                "#ifdef __cplusplus\n",
-                "}} /* extern \"C\" */\n",
-                "#endif\n",
-                "\n",
-                "#endif /* {} */\n",
-            ),
-            guard,
-        )?;
-        Ok(())
+        let generate = || {
+            if let Some(filter) = config.filter {
+                exports.retain(|define| {
+                    match determinism::declared_name(define) {
+                        | Some(name) => filter(&name),
+                        | None => true,
+                    }
+                });
+            }
+            if stable_order {
+                determinism::sort(&mut exports);
+            }
+            exports
+                .into_iter()
+                .try_for_each(|define| define(&mut definer))
+        };
+        context::with_context(ctx, || translations::with_translations(translations, || {
+            collisions::with_name_collisions(name_collisions, generate)
+        }))?;
+        definer.end_hoisting()?;
+        backend.epilogue(&mut definer, &config)?;
+        let symbols = mem::take(&mut definer.report.symbols);
+        diagnostics::lint_case_collisions(&mut definer, &symbols);
+        definer.report.symbols = symbols;
+        Ok(definer.report)
     }
 }
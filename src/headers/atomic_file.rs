@@ -0,0 +1,96 @@
+use super::*;
+
+/// The output "stream" of [`.to_file()`][`Builder::to_file`]: the contents
+/// are buffered in memory, and only written to the file once the generation
+/// has succeeded, _atomically_ (through a temporary file which is then
+/// renamed over the target one).
+///
+/// This way, a failed (or panicking) generation never leaves a truncated
+/// header file behind.
+pub
+struct AtomicFile {
+    path: PathBuf,
+    contents: Vec<u8>,
+    skip_unchanged: bool,
+}
+
+impl AtomicFile {
+    pub(in super)
+    fn new (path: PathBuf)
+      -> AtomicFile
+    {
+        AtomicFile { path, contents: Vec::new(), skip_unchanged: false }
+    }
+}
+
+impl<'__> Builder<'__, AtomicFile> {
+    /// Do not touch the file (thus preserving its modification time, which
+    /// avoids spurious rebuilds) when its contents would be unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main () -> ::std::io::Result<()> { Ok({
+    /// ::safer_ffi::headers::builder()
+    ///     .to_file("my_header.h")?
+    ///     .with_skip_unchanged(true)
+    ///     .generate()?;
+    /// # })}
+    /// ```
+    pub
+    fn with_skip_unchanged (mut self: Self, skip_unchanged: bool)
+      -> Self
+    {
+        self.target.skip_unchanged = skip_unchanged;
+        self
+    }
+}
+
+impl io::Write
+    for AtomicFile
+{
+    #[inline]
+    fn write (self: &'_ mut Self, buf: &'_ [u8])
+      -> io::Result<usize>
+    {
+        self.contents.write(buf)
+    }
+
+    /// (Atomically) writes the buffered contents to the file.
+    fn flush (self: &'_ mut Self)
+      -> io::Result<()>
+    {
+        if self.skip_unchanged {
+            if let Ok(existing) = fs::read(&self.path) {
+                if existing == self.contents {
+                    return Ok(());
+                }
+            }
+        }
+        write_atomically(&self.path, &self.contents)
+    }
+}
+
+/// Writes `contents` to a temporary file next to `path`, and then renames it
+/// over `path`.
+pub(in super)
+fn write_atomically (path: &'_ Path, contents: &'_ [u8])
+  -> io::Result<()>
+{
+    let file_name = path.file_name().ok_or_else(|| io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("`{}` is not a file path", path.display()),
+    ))?;
+    let mut tmp_name = ::std::ffi::OsString::from(".");
+    tmp_name.push(file_name);
+    tmp_name.push(format!(".{}.tmp", ::std::process::id()));
+    let tmp_path = path.with_file_name(tmp_name);
+    let ret =
+        fs::write(&tmp_path, contents)
+            .and_then(|()| fs::rename(&tmp_path, path))
+    ;
+    if ret.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    ret
+}
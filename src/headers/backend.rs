@@ -0,0 +1,495 @@
+//! The generated bindings: each [`Language`] is a [`Backend`], which frames
+//! the generated file, which the [`CType`]s describe their shape to, and
+//! which the exported items are declared through.
+
+use super::*;
+
+/// The emission of the bindings of a language.
+///
+/// The types only tell it what they are (see
+/// [`CType::binding_ty`]`()` and [`CType::binding_define_self`]`()`), and
+/// the exported items, what they are called and what their types are, so
+/// that supporting another language is a matter of implementing this trait.
+#[doc(hidden)] /** Not part of the public API **/ pub
+trait Backend {
+    /// The default [banner][`Builder::with_banner`]: by default, a
+    /// `/* ... */` one.
+    fn banner (self: &'_ Self)
+      -> &'static str
+    {
+        DEFAULT_BANNER
+    }
+
+    /// The `lines` (of the [license header][`Builder::with_license_header`])
+    /// as a comment: by default, a `/* ... */` one.
+    fn comment (self: &'_ Self, lines: &'_ [String])
+      -> String
+    {
+        license::c_comment(lines)
+    }
+
+    /// What goes between the banner and the exported items: by default,
+    /// nothing.
+    fn prologue (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        config: &'_ Builder<'_, WhereTo>,
+    ) -> io::Result<()>
+    {
+        let _ = (definer, config);
+        Ok(())
+    }
+
+    /// What goes after the exported items: by default, nothing.
+    fn epilogue (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        config: &'_ Builder<'_, WhereTo>,
+    ) -> io::Result<()>
+    {
+        let _ = (definer, config);
+        Ok(())
+    }
+
+    /// The counterpart of `ty`: by default, its
+    /// [`CType::binding_ty`]`()`.
+    fn type_name (self: &'_ Self, ty: &'_ TypeDesc)
+      -> String
+    {
+        (ty.binding_ty)()
+    }
+
+    /// Defines what [`Backend::type_name`]`()` refers to: by default,
+    /// through [`CType::binding_define_self`]`()`.
+    fn define_type (self: &'_ Self, definer: &'_ mut dyn Definer, ty: &'_ TypeDesc)
+      -> io::Result<()>
+    {
+        (ty.binding_define)(definer)
+    }
+
+    /// The counterpart of a primitive C type, given its short name
+    /// (`"uint8"`, `"size"`, `"double"`, `"bool"`, `"char"`, `"void"`, ...).
+    fn primitive_ty (self: &'_ Self, c_short_name: &'_ str)
+      -> &'static str
+    ;
+
+    /// The counterpart of a (`mut`able or not) pointer to `pointee`.
+    fn pointer_ty (self: &'_ Self, pointee: &'_ TypeDesc, mutable: bool)
+      -> String
+    ;
+
+    /// Defines what [`Backend::pointer_ty`]`()` refers to: by default, the
+    /// pointee.
+    fn define_pointer (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        pointee: &'_ TypeDesc,
+    ) -> io::Result<()>
+    {
+        pointee.define(definer)
+    }
+
+    /// The counterpart of a (nullable) function pointer, of the `"C"` ABI,
+    /// or, when `system_abi`, of the `"system"` one.
+    fn fn_ptr_ty (
+        self: &'_ Self,
+        ret: &'_ TypeDesc,
+        params: &'_ [TypeDesc],
+        system_abi: bool,
+    ) -> String
+    ;
+
+    /// Defines what [`Backend::fn_ptr_ty`]`()` refers to: by default,
+    /// nothing.
+    fn define_fn_ptr (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        ret: &'_ TypeDesc,
+        params: &'_ [TypeDesc],
+        system_abi: bool,
+    ) -> io::Result<()>
+    {
+        let _ = (definer, ret, params, system_abi);
+        Ok(())
+    }
+
+    /// The counterpart of an array of `len` `item`s, `name`d after its C
+    /// `typedef`, which it defaults to.
+    fn array_ty (self: &'_ Self, name: &'_ str, item: &'_ TypeDesc, len: usize)
+      -> String
+    {
+        let _ = (item, len);
+        name.into()
+    }
+
+    /// Defines what [`Backend::array_ty`]`()` refers to: by default, a type
+    /// with the same layout as the array `me`
+    /// (see [`Backend::define_bytes`]`()`).
+    fn define_array (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        me: &'_ TypeDesc,
+        item: &'_ TypeDesc,
+        len: usize,
+    ) -> io::Result<()>
+    {
+        let _ = (item, len);
+        self.define_bytes(definer, me)
+    }
+
+    /// Defines the type named `me.ty()` with the same size and alignment as
+    /// `me` (but no visible fields), so that it can at least be passed
+    /// around by value.
+    ///
+    /// That is the default definition of the types
+    /// (see [`CType::binding_define_self`]`()`).
+    fn define_bytes (self: &'_ Self, definer: &'_ mut dyn Definer, me: &'_ TypeDesc)
+      -> io::Result<()>
+    ;
+
+    /// Defines `me`, a struct, given its doc lines and its (non-zero-sized)
+    /// fields, whose types have already been defined.
+    fn define_struct (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        me: &'_ TypeDesc,
+        docs: &'_ [&'_ str],
+        fields: &'_ [Field<'_>],
+    ) -> io::Result<()>
+    ;
+
+    /// Defines `me`, a union, given its doc lines and its (non-zero-sized)
+    /// fields, whose types have already been defined.
+    fn define_union (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        me: &'_ TypeDesc,
+        docs: &'_ [&'_ str],
+        fields: &'_ [Field<'_>],
+    ) -> io::Result<()>
+    ;
+
+    /// The counterpart of a C-like enum, with `int` as its integer type,
+    /// `name`d after its C `typedef`, which it defaults to.
+    fn enum_ty (self: &'_ Self, name: &'_ str, int: &'_ TypeDesc)
+      -> String
+    {
+        let _ = int;
+        name.into()
+    }
+
+    /// Defines the enum `me`, given its doc lines, its integer type, and
+    /// its variants.
+    fn define_enum (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        me: &'_ TypeDesc,
+        docs: &'_ [&'_ str],
+        int: &'_ TypeDesc,
+        variants: &'_ mut [Variant<'_>],
+    ) -> io::Result<()>
+    ;
+
+    /// Defines `me`, an opaque type (only ever used behind a pointer).
+    fn define_opaque (self: &'_ Self, definer: &'_ mut dyn Definer, me: &'_ TypeDesc)
+      -> io::Result<()>
+    ;
+
+    /// Declares the exported function `name`, given its doc lines, and the
+    /// types of its return value and of its parameters, which have already
+    /// been defined.
+    fn declare_fn (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        docs: &'_ [&'_ str],
+        name: &'_ str,
+        ret: &'_ TypeDesc,
+        params: &'_ [(&'_ str, TypeDesc)],
+    ) -> io::Result<()>
+    ;
+
+    /// Declares the exported `static` `name`, of type `ty`, given its doc
+    /// lines.
+    fn declare_static (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        docs: &'_ [&'_ str],
+        name: &'_ str,
+        ty: &'_ TypeDesc,
+    ) -> io::Result<()>
+    ;
+
+    /// Declares the exported constant `name`: by default, as a comment
+    /// pointing at the C headers, which are the only ones to emit them.
+    fn declare_const (self: &'_ Self, definer: &'_ mut dyn Definer, name: &'_ str)
+      -> io::Result<()>
+    {
+        write!(definer.out(),
+            "/* `{}`: exported constants are only emitted in the C headers */\n\n",
+            name,
+        )
+    }
+}
+
+/// A [`CType`], as seen by a [`Backend`].
+#[derive(Clone, Copy)]
+#[doc(hidden)] /** Not part of the public API **/ pub
+struct TypeDesc {
+    pub size: usize,
+    pub align: usize,
+    c_short_name: fn() -> String,
+    c_var: fn(&str) -> String,
+    c_define: fn(&mut dyn Definer) -> io::Result<()>,
+    binding_ty: fn() -> String,
+    binding_define: fn(&mut dyn Definer) -> io::Result<()>,
+}
+
+impl TypeDesc {
+    pub
+    fn of<T : CType> ()
+      -> Self
+    {
+        TypeDesc {
+            size: T::SIZE,
+            align: T::ALIGN,
+            c_short_name: || T::c_short_name().to_string(),
+            c_var: |var_name| T::c_var(var_name).to_string(),
+            c_define: T::c_define_self,
+            binding_ty: T::binding_ty,
+            binding_define: T::binding_define_self,
+        }
+    }
+
+    /// [`CType::c_short_name`]`()`.
+    pub
+    fn c_short_name (self: &'_ Self)
+      -> String
+    {
+        (self.c_short_name)()
+    }
+
+    /// [`CType::c_var`]`("")`, _i.e._, the C type.
+    pub
+    fn c_var (self: &'_ Self)
+      -> String
+    {
+        (self.c_var)("")
+    }
+
+    /// [`CType::c_var`]`(var_name)`, _i.e._, the C declaration of
+    /// `var_name`.
+    pub
+    fn c_var_named (self: &'_ Self, var_name: &'_ str)
+      -> String
+    {
+        (self.c_var)(var_name)
+    }
+
+    /// [`CType::c_define_self`]`()`.
+    pub
+    fn c_define (self: &'_ Self, definer: &'_ mut dyn Definer)
+      -> io::Result<()>
+    {
+        (self.c_define)(definer)
+    }
+
+    /// The counterpart of the type in the bindings (see
+    /// [`Backend::type_name`]).
+    pub
+    fn ty (self: &'_ Self)
+      -> String
+    {
+        __backend__().type_name(self)
+    }
+
+    /// Defines the counterpart of the type in the bindings (see
+    /// [`Backend::define_type`]).
+    pub
+    fn define (self: &'_ Self, definer: &'_ mut dyn Definer)
+      -> io::Result<()>
+    {
+        __backend__().define_type(definer, self)
+    }
+}
+
+/// A field of a struct or of a union.
+#[derive(Clone, Copy)]
+#[doc(hidden)] /** Not part of the public API **/ pub
+struct Field<'__> {
+    pub name: &'__ str,
+    pub docs: &'__ [&'__ str],
+    pub ty: TypeDesc,
+}
+
+/// A variant of a C-like enum: its value, its name, its doc lines, and its
+/// explicit discriminant, if any.
+#[doc(hidden)] /** Not part of the public API **/ pub
+struct Variant<'__> {
+    pub value: i128,
+    pub name: &'__ str,
+    pub docs: &'__ [&'__ str],
+    pub discriminant: Option<&'__ dyn fmt::Display>,
+}
+
+impl Language {
+    /// The [`Backend`] emitting the bindings of the language.
+    pub(in super)
+    fn backend (self: Self)
+      -> &'static dyn Backend
+    {
+        match self {
+            | Language::C => &c::C,
+            | Language::CSharp => &csharp::CSharp,
+            | Language::Java => &java::Java,
+            | Language::Go => &go::Go,
+            | Language::Zig => &zig::Zig,
+        }
+    }
+}
+
+/// The [`Backend`] of the ongoing generation.
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __backend__ ()
+  -> &'static dyn Backend
+{
+    __language__().backend()
+}
+
+/// The name of the native library the bindings load (see
+/// [`Builder::with_dll_name`]): by default, the name of the crate.
+pub(in super)
+fn dll_name (config: &'_ Builder<'_, WhereTo>)
+  -> String
+{
+    match config.dll_name {
+        | Some(it) => it.into(),
+        | None => env::var("CARGO_PKG_NAME").unwrap().replace('-', "_"),
+    }
+}
+
+/// Declares an exported function, after defining the types of its
+/// parameters and of its return value.
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __declare_fn__ (
+    definer: &'_ mut dyn Definer,
+    docs: &'_ [&'_ str],
+    name: &'_ str,
+    ret: TypeDesc,
+    params: &'_ [(&'_ str, TypeDesc)],
+) -> io::Result<()>
+{
+    for (_, ty) in params {
+        ty.define(definer)?;
+    }
+    ret.define(definer)?;
+    definer.set_symbol_kind(name, SymbolKind::Function);
+    __backend__().declare_fn(definer, docs, name, &ret, params)
+}
+
+/// Declares an exported `static`.
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __declare_static__ (
+    definer: &'_ mut dyn Definer,
+    docs: &'_ [&'_ str],
+    name: &'_ str,
+    ty: TypeDesc,
+) -> io::Result<()>
+{
+    __backend__().declare_static(definer, docs, name, &ty)
+}
+
+/// Declares an exported constant.
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __declare_const__ (definer: &'_ mut dyn Definer, name: &'_ str)
+  -> io::Result<()>
+{
+    __backend__().declare_const(definer, name)
+}
+
+/// Defines the struct `me` (once), after the types of its fields.
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __define_struct__ (
+    definer: &'_ mut dyn Definer,
+    me: TypeDesc,
+    docs: &'_ [&'_ str],
+    fields: &'_ [Field<'_>],
+) -> io::Result<()>
+{
+    definer.define_once(&me.c_short_name(), &mut |definer| {
+        let fields = define_fields(definer, fields)?;
+        __backend__().define_struct(definer, &me, docs, &fields)
+    })
+}
+
+/// Defines the union `me` (once), after the types of its fields.
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __define_union__ (
+    definer: &'_ mut dyn Definer,
+    me: TypeDesc,
+    docs: &'_ [&'_ str],
+    fields: &'_ [Field<'_>],
+) -> io::Result<()>
+{
+    definer.define_once(&me.c_short_name(), &mut |definer| {
+        let fields = define_fields(definer, fields)?;
+        __backend__().define_union(definer, &me, docs, &fields)
+    })
+}
+
+/// Defines the types of the `fields`, and yields the non-zero-sized ones,
+/// the only ones to be spelled out.
+fn define_fields<'fields> (
+    definer: &'_ mut dyn Definer,
+    fields: &'_ [Field<'fields>],
+) -> io::Result<Vec<Field<'fields>>>
+{
+    for field in fields {
+        field.ty.define(definer)?;
+    }
+    Ok(fields.iter().filter(|field| field.ty.size > 0).copied().collect())
+}
+
+/// Defines the enum `me` (once).
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __define_enum__ (
+    definer: &'_ mut dyn Definer,
+    me: TypeDesc,
+    docs: &'_ [&'_ str],
+    int: TypeDesc,
+    variants: &'_ mut [Variant<'_>],
+) -> io::Result<()>
+{
+    definer.define_once(&me.c_short_name(), &mut |definer| {
+        __backend__().define_enum(definer, &me, docs, &int, variants)
+    })
+}
+
+/// Defines the opaque type `me` (once).
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __define_opaque__ (definer: &'_ mut dyn Definer, me: TypeDesc)
+  -> io::Result<()>
+{
+    definer.define_once(&me.c_short_name(), &mut |definer| {
+        __backend__().define_opaque(definer, &me)
+    })
+}
+
+/// Emits the `docs` (lines) as a `/** \brief ... */` doc comment, as the C
+/// headers do.
+pub(in super)
+fn c_docs (
+    out: &'_ mut dyn io::Write,
+    pad: &'_ str,
+    docs: &'_ [&'_ str],
+) -> io::Result<()>
+{
+    let (first, rest) = match docs.split_first() {
+        | Some(it) => it,
+        | None => return Ok(()),
+    };
+    writeln!(out, "{pad}/** \\brief\n{pad} * {}", first, pad = pad)?;
+    for line in rest {
+        writeln!(out, "{pad} * {}", line, pad = pad)?;
+    }
+    writeln!(out, "{pad} */", pad = pad)
+}
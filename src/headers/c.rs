@@ -0,0 +1,324 @@
+//! The C backend: see [`Language::C`].
+
+use super::*;
+
+/// The C [`Backend`].
+///
+/// The C bindings of a type are its C definition: the type-level hooks thus
+/// forward to the [`CType`]s themselves (_i.e._, to [`CType::c_var`]`()` and
+/// [`CType::c_define_self`]`()`).
+pub(in super)
+struct C;
+
+impl Backend for C {
+    fn type_name (self: &'_ Self, ty: &'_ TypeDesc)
+      -> String
+    {
+        ty.c_var()
+    }
+
+    fn define_type (self: &'_ Self, definer: &'_ mut dyn Definer, ty: &'_ TypeDesc)
+      -> io::Result<()>
+    {
+        ty.c_define(definer)
+    }
+
+    fn primitive_ty (self: &'_ Self, c_short_name: &'_ str)
+      -> &'static str
+    {
+        match c_short_name {
+            | "uint8" => "uint8_t",
+            | "uint16" => "uint16_t",
+            | "uint32" => "uint32_t",
+            | "uint64" => "uint64_t",
+            | "size" => "size_t",
+            | "int8" => "int8_t",
+            | "int16" => "int16_t",
+            | "int32" => "int32_t",
+            | "int64" => "int64_t",
+            | "ssize" => "ssize_t",
+            | "float" => "float",
+            | "double" => "double",
+            | "bool" => "bool",
+            | "char" => "char",
+            | "void" => "void",
+            | _ => unreachable!("`{}` is not a primitive type", c_short_name),
+        }
+    }
+
+    fn pointer_ty (self: &'_ Self, pointee: &'_ TypeDesc, mutable: bool)
+      -> String
+    {
+        format!("{}{} *", pointee.ty(), if mutable { "" } else { " const" })
+    }
+
+    fn fn_ptr_ty (
+        self: &'_ Self,
+        ret: &'_ TypeDesc,
+        params: &'_ [TypeDesc],
+        system_abi: bool,
+    ) -> String
+    {
+        format!("{} ({}*)({})",
+            ret.ty(),
+            if system_abi { "SAFER_FFI_SYSTEM_ABI " } else { "" },
+            c_params(params.iter().map(TypeDesc::ty)),
+        )
+    }
+
+    fn define_fn_ptr (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        ret: &'_ TypeDesc,
+        params: &'_ [TypeDesc],
+        system_abi: bool,
+    ) -> io::Result<()>
+    {
+        ret.define(definer)?;
+        for param in params {
+            param.define(definer)?;
+        }
+        if system_abi {
+            __define_system_abi__(definer)?;
+        }
+        Ok(())
+    }
+
+    fn define_bytes (self: &'_ Self, definer: &'_ mut dyn Definer, me: &'_ TypeDesc)
+      -> io::Result<()>
+    {
+        me.c_define(definer)
+    }
+
+    fn define_struct (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        me: &'_ TypeDesc,
+        _: &'_ [&'_ str],
+        _: &'_ [Field<'_>],
+    ) -> io::Result<()>
+    {
+        me.c_define(definer)
+    }
+
+    fn define_union (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        me: &'_ TypeDesc,
+        _: &'_ [&'_ str],
+        _: &'_ [Field<'_>],
+    ) -> io::Result<()>
+    {
+        me.c_define(definer)
+    }
+
+    fn define_enum (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        me: &'_ TypeDesc,
+        _: &'_ [&'_ str],
+        _: &'_ TypeDesc,
+        _: &'_ mut [Variant<'_>],
+    ) -> io::Result<()>
+    {
+        me.c_define(definer)
+    }
+
+    fn define_opaque (self: &'_ Self, definer: &'_ mut dyn Definer, me: &'_ TypeDesc)
+      -> io::Result<()>
+    {
+        me.c_define(definer)
+    }
+
+    fn declare_fn (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        docs: &'_ [&'_ str],
+        name: &'_ str,
+        ret: &'_ TypeDesc,
+        params: &'_ [(&'_ str, TypeDesc)],
+    ) -> io::Result<()>
+    {
+        let out = definer.out();
+        backend::c_docs(out, "", docs)?;
+        write!(out, "{} (", ret.c_var_named(name))?;
+        if params.is_empty() {
+            out.write_all(b"void")?;
+        }
+        for (i, &(param_name, ref param_ty)) in params.iter().enumerate() {
+            write!(out,
+                "{comma}\n    {}",
+                param_ty.c_var_named(&__c_escape__(param_name)),
+                comma = if i == 0 { "" } else { "," },
+            )?;
+        }
+        out.write_all(b");\n\n")
+    }
+
+    fn declare_static (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        docs: &'_ [&'_ str],
+        name: &'_ str,
+        ty: &'_ TypeDesc,
+    ) -> io::Result<()>
+    {
+        ty.define(definer)?;
+        definer.set_symbol_kind(name, SymbolKind::Static);
+        let out = definer.out();
+        backend::c_docs(out, "", docs)?;
+        write!(out, "extern {};\n\n", ty.c_var_named(&format!("const {}", name)))
+    }
+
+    fn declare_const (self: &'_ Self, _: &'_ mut dyn Definer, _: &'_ str)
+      -> io::Result<()>
+    {
+        // Their `#define` needs their value, which only `#[ffi_export]` has:
+        // it thus emits it itself.
+        Ok(())
+    }
+
+    fn prologue (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        config: &'_ Builder<'_, WhereTo>,
+    ) -> io::Result<()>
+    {
+        if config.emit_guard.unwrap_or(true) {
+            let guard = guard(config);
+            definer.out().write_all(guard_style(config).open(&guard).as_bytes())?;
+        }
+        if let Some(includes) = config.includes.filter(|it| !it.is_empty()) {
+            for &include in includes {
+                definer.out().write_all(include_directive(include).as_bytes())?;
+            }
+            writeln!(definer.out())?;
+        }
+        if let Some(export_macro) = config.export_macro {
+            let default_define;
+            let dllexport_define = match config.dllexport_define {
+                | Some(it) => it,
+                | None => {
+                    default_define = format!("{}_BUILD", export_macro);
+                    &default_define
+                },
+            };
+            visibility::define_export_macro(
+                definer.out(),
+                export_macro,
+                dllexport_define,
+            )?;
+        }
+        if let Some(prefix) = config.version_macros {
+            version::define_version_macros(definer.out(), prefix)?;
+        }
+        if let Some(prefix) = config.contract_macros {
+            contracts::define_contract_macros(definer.out(), prefix)?;
+        }
+        let namespaces = namespaces(config)?;
+        writeln!(definer.out(), "#ifdef __cplusplus")?;
+        if !namespaces.is_empty() {
+            // So that these are not `#include`d within the namespace.
+            write!(definer.out(),
+                "#include <stddef.h>\n#include <stdint.h>\n\n",
+            )?;
+        }
+        for namespace in &namespaces {
+            writeln!(definer.out(), "namespace {} {{", namespace)?;
+        }
+        write!(definer.out(),
+            concat!(
+                "extern \"C\" {{\n",
+                "#endif\n\n",
+            ),
+        )?;
+        if config.swift_annotations.unwrap_or(false) {
+            definer.out().write_all(swift::PROLOGUE.as_bytes())?;
+        }
+        if let Some(prologue) = config.prologue {
+            write_snippet(definer.out(), prologue)?;
+        }
+        Ok(())
+    }
+
+    fn epilogue (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        config: &'_ Builder<'_, WhereTo>,
+    ) -> io::Result<()>
+    {
+        if let Some(epilogue) = config.epilogue {
+            write_snippet(definer.out(), epilogue)?;
+        }
+        if config.swift_annotations.unwrap_or(false) {
+            definer.out().write_all(swift::EPILOGUE.as_bytes())?;
+        }
+        write!(definer.out(),
+            concat!(
+                "\n",
+                "#ifdef __cplusplus\n",
+                "}} /* extern \"C\" */\n",
+            ),
+        )?;
+        for namespace in namespaces(config)?.iter().rev() {
+            writeln!(definer.out(), "}} /* namespace {} */", namespace)?;
+        }
+        writeln!(definer.out(), "#endif")?;
+        if config.emit_guard.unwrap_or(true) {
+            let guard = guard(config);
+            definer.out().write_all(guard_style(config).close(&guard).as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+/// The parameters of a C function (type), `void` if none.
+fn c_params (params: impl Iterator<Item = String>)
+  -> String
+{
+    let params = params.collect::<Vec<_>>();
+    if params.is_empty() {
+        "void".into()
+    } else {
+        params.join(", ")
+    }
+}
+
+/// The name of the include guard (see [`Builder::with_guard`]).
+fn guard (config: &'_ Builder<'_, WhereTo>)
+  -> String
+{
+    match config.guard {
+        | Some(it) => it.into(),
+        | None => format!("__RUST_{}__",
+            env::var("CARGO_PKG_NAME")
+                .unwrap()
+                .to_ascii_uppercase()
+        ),
+    }
+}
+
+fn guard_style (config: &'_ Builder<'_, WhereTo>)
+  -> GuardStyle
+{
+    config.guard_style.unwrap_or(GuardStyle::Ifndef)
+}
+
+/// The (validated) components of the C++ namespace the declarations are
+/// wrapped in, if any (see [`Builder::with_cxx_namespace`]).
+fn namespaces<'config> (config: &'_ Builder<'config, WhereTo>)
+  -> io::Result<Vec<&'config str>>
+{
+    let namespace = match config.cxx_namespace {
+        | Some(it) => it,
+        | None => return Ok(vec![]),
+    };
+    let namespaces: Vec<_> = namespace.split("::").collect();
+    if !namespaces.iter().all(|it| is_c_identifier(it)) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Invalid C++ namespace: `{}`", namespace),
+        ));
+    }
+    Ok(namespaces)
+}
@@ -0,0 +1,149 @@
+use super::*;
+
+/// Values that can be written as a C constant expression, such as the
+/// field values of a `MYSTRUCT_DEFAULT` initializer macro, or the values of
+/// the `#[ffi_export]`ed `const`s.
+///
+/// Returns `None` when the value has no meaningful C literal (_e.g._, a
+/// non-`NULL` pointer).
+pub
+trait CLiteral {
+    fn c_literal (self: &'_ Self)
+      -> Option<String>
+    ;
+}
+
+macro_rules! impl_CLiterals {(
+    $(
+        $T:ty => $suffix:expr
+    ),* $(,)?
+) => (
+    $(
+        impl CLiteral
+            for $T
+        {
+            fn c_literal (self: &'_ Self)
+              -> Option<String>
+            {
+                Some(format!("{}{}", self, $suffix))
+            }
+        }
+    )*
+)}
+
+impl_CLiterals! {
+    u8 => "u", u16 => "u", u32 => "u", u64 => "ull", usize => "ull",
+    i8 => "", i16 => "", i32 => "", i64 => "ll", isize => "ll",
+}
+
+impl CLiteral
+    for bool
+{
+    fn c_literal (self: &'_ Self)
+      -> Option<String>
+    {
+        Some(if *self { "true" } else { "false" }.into())
+    }
+}
+
+macro_rules! impl_float_CLiterals {(
+    $(
+        $f:ty => $suffix:expr
+    ),* $(,)?
+) => (
+    $(
+        impl CLiteral
+            for $f
+        {
+            fn c_literal (self: &'_ Self)
+              -> Option<String>
+            {
+                Some(if self.is_nan() {
+                    format!("(0.0{s} / 0.0{s})", s = $suffix)
+                } else if self.is_infinite() {
+                    format!(
+                        "({}1.0{s} / 0.0{s})",
+                        if *self < 0.0 { "-" } else { "" },
+                        s = $suffix,
+                    )
+                } else {
+                    // `Debug` always yields a decimal point or an exponent.
+                    format!("{:?}{}", self, $suffix)
+                })
+            }
+        }
+    )*
+)}
+
+impl_float_CLiterals! {
+    f32 => "f",
+    f64 => "",
+}
+
+impl CLiteral
+    for &'_ str
+{
+    fn c_literal (self: &'_ Self)
+      -> Option<String>
+    {
+        let mut ret = String::from("\"");
+        for &b in self.as_bytes() {
+            match b {
+                | b'"' => ret.push_str("\\\""),
+                | b'\\' => ret.push_str("\\\\"),
+                | b'\n' => ret.push_str("\\n"),
+                | b'\r' => ret.push_str("\\r"),
+                | b'\t' => ret.push_str("\\t"),
+                | b' ' ..= b'~' => ret.push(b as char),
+                // Unlike the hexadecimal ones, octal escapes have a bounded
+                // length, so the following characters cannot be swallowed.
+                | _ => ret.push_str(&format!("\\{:03o}", b)),
+            }
+        }
+        ret.push('"');
+        Some(ret)
+    }
+}
+
+impl CLiteral
+    for crate::char_p::char_p_ref<'_>
+{
+    fn c_literal (self: &'_ Self)
+      -> Option<String>
+    {
+        self.to_str().c_literal()
+    }
+}
+
+impl<T> CLiteral
+    for *const T
+{
+    fn c_literal (self: &'_ Self)
+      -> Option<String>
+    {
+        if self.is_null() { Some("NULL".into()) } else { None }
+    }
+}
+
+impl<T> CLiteral
+    for *mut T
+{
+    fn c_literal (self: &'_ Self)
+      -> Option<String>
+    {
+        if self.is_null() { Some("NULL".into()) } else { None }
+    }
+}
+
+impl<Args, Ret> CLiteral
+    for crate::fn_ptr::FnPtr<Args, Ret>
+where
+    Args : crate::fn_ptr::FnArgs<Ret>,
+    Ret : ReprC,
+{
+    fn c_literal (self: &'_ Self)
+      -> Option<String>
+    {
+        if self.is_null() { Some("NULL".into()) } else { None }
+    }
+}
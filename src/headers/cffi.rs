@@ -0,0 +1,213 @@
+//! The Python [`cffi`](https://cffi.readthedocs.io) output: the C header,
+//! stripped from what `ffi.cdef()` cannot parse.
+
+use super::*;
+
+impl<'__> Builder<'__, WhereTo> {
+    /// Generates the headers in memory, and returns them as a string that
+    /// can be fed to Python's `cffi.FFI().cdef()` as is.
+    ///
+    /// Since `cdef()` does not run the C preprocessor, the preprocessor
+    /// directives are evaluated (`DOXYGEN` and `__cplusplus` being undefined)
+    /// and stripped, except for the `#define`s of integer constants (which
+    /// `cdef()` supports), as well as the `#[deprecated]` annotations. The doc
+    /// comments are kept.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main () -> ::std::io::Result<()> { Ok({
+    /// let cdef = ::safer_ffi::headers::builder().generate_cffi_cdef()?;
+    /// # drop(cdef);
+    /// # })}
+    /// ```
+    pub
+    fn generate_cffi_cdef (self)
+      -> io::Result<String>
+    {
+        let mut header = Vec::new();
+        Builder {
+            emit_guard: Some(false),
+            export_macro: None,
+            language: Some(Language::C),
+            swift_annotations: None,
+            contract_macros: None,
+            .. self
+        }
+            .to_writer(&mut header)
+            .generate()?
+        ;
+        let header =
+            String::from_utf8(header)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?
+        ;
+        let cdef = strip_preprocessor(&header, &|_name, value| is_int_literal(value));
+        Ok(deprecated::strip_deprecated(&cdef).0)
+    }
+
+    /// Like [`.generate_cffi_cdef()`][`Builder::generate_cffi_cdef`], but
+    /// wrapping the `cdef` within a Python module, exposing the `ffi` object
+    /// and a `load(path)` function to `dlopen()` the library with it:
+    ///
+    /// ```python
+    /// import mylib_cffi
+    ///
+    /// lib = mylib_cffi.load("target/release/libmylib.so")
+    /// ```
+    pub
+    fn generate_cffi_module (self)
+      -> io::Result<String>
+    {
+        let cdef = self.generate_cffi_cdef()?;
+        Ok(format!(
+            concat!(
+                "# File auto-generated by `::safer_ffi`.\n",
+                "# Do not manually edit this file.\n",
+                "\n",
+                "from cffi import FFI\n",
+                "\n",
+                "ffi = FFI()\n",
+                "ffi.cdef(\"\"\"\n",
+                "{}",
+                "\"\"\")\n",
+                "\n",
+                "\n",
+                "def load(path):\n",
+                "    \"\"\"Loads the library at `path` (in ABI mode).\"\"\"\n",
+                "    return ffi.dlopen(path)\n",
+            ),
+            cdef.replace('\\', "\\\\").replace("\"\"\"", "\\\"\\\"\\\""),
+        ))
+    }
+}
+
+/// Evaluates and strips the preprocessor directives of the generated header,
+/// but for the (single-line) `#define NAME value`s for which `keep_define`
+/// returns `true`.
+pub(in super)
+fn strip_preprocessor (
+    header: &'_ str,
+    keep_define: &'_ dyn Fn(&str, &str) -> bool,
+) -> String
+{
+    struct Branch {
+        parent_active: bool,
+        active: bool,
+        taken: bool,
+    }
+    let mut branches: Vec<Branch> = vec![];
+    let is_active = |branches: &'_ [Branch]| {
+        match branches.last() {
+            | Some(it) => it.active,
+            | None => true,
+        }
+    };
+    let mut ret = String::with_capacity(header.len());
+    let mut continued_directive = false;
+    let mut blank = true;
+    // Start of the doc comment right before the current line, if any, so
+    // that it can be stripped along with a stripped `#define`.
+    let mut doc_start = None;
+    let mut in_doc = false;
+    for line in header.lines() {
+        if continued_directive {
+            continued_directive = line.ends_with('\\');
+            continue;
+        }
+        let directive = line.trim_start();
+        if directive.starts_with('#').not() {
+            if is_active(&branches) {
+                // Collapse the runs of blank lines left by the directives.
+                if line.trim().is_empty() {
+                    if blank { continue; }
+                    blank = true;
+                } else {
+                    blank = false;
+                }
+                if in_doc.not() {
+                    doc_start = None;
+                    if directive.starts_with("/**") {
+                        doc_start = Some(ret.len());
+                        in_doc = true;
+                    }
+                }
+                if in_doc && line.contains("*/") {
+                    in_doc = false;
+                }
+                ret.push_str(line);
+                ret.push('\n');
+            }
+            continue;
+        }
+        continued_directive = line.ends_with('\\');
+        let mut words = directive[1 ..].split_whitespace();
+        let parent_active = is_active(&branches);
+        match words.next() {
+            // The macros tested by the generated headers (`DOXYGEN`,
+            // `__cplusplus`, the guard, and `NDEBUG`) are all undefined.
+            | Some("ifdef") | Some("if") => branches.push(Branch {
+                parent_active,
+                active: false,
+                taken: false,
+            }),
+            | Some("ifndef") => branches.push(Branch {
+                parent_active,
+                active: parent_active,
+                taken: true,
+            }),
+            | Some("elif") => if let Some(it) = branches.last_mut() {
+                it.active = false;
+            },
+            | Some("else") => if let Some(it) = branches.last_mut() {
+                it.active = it.parent_active && it.taken.not();
+                it.taken = true;
+            },
+            | Some("endif") => {
+                branches.pop();
+            },
+            | Some("define") if parent_active => {
+                let mut define =
+                    directive[1 ..].trim_start()["define".len() ..]
+                        .trim_start()
+                        .splitn(2, char::is_whitespace)
+                ;
+                let name = define.next().filter(|it| it.is_empty().not());
+                let value = define.next().unwrap_or("").trim();
+                match name {
+                    | Some(name)
+                        if continued_directive.not()
+                        && name.contains('(').not()
+                        && value.is_empty().not()
+                        && keep_define(name, value)
+                    => {
+                        ret.push_str(&format!("#define {} {}\n", name, value));
+                        blank = false;
+                    },
+                    | _ => if let Some(doc_start) = doc_start {
+                        ret.truncate(doc_start);
+                        blank = ret.is_empty() || ret.ends_with("\n\n");
+                    },
+                }
+                doc_start = None;
+            },
+            | _ => {},
+        }
+    }
+    ret
+}
+
+/// Whether `cdef()` accepts `s` as the value of a `#define`.
+fn is_int_literal (s: &'_ str)
+  -> bool
+{
+    let s = s.trim_start_matches('-');
+    let s = s.trim_end_matches(&['u', 'U', 'l', 'L'][..]);
+    let (digits, radix) =
+        if s.starts_with("0x") || s.starts_with("0X") {
+            (&s[2 ..], 16)
+        } else {
+            (s, 10)
+        }
+    ;
+    digits.is_empty().not() && digits.chars().all(|c| c.is_digit(radix))
+}
@@ -0,0 +1,126 @@
+//! Checking that a (committed) header file is up to date: see
+//! [`Builder::check_file`].
+
+use super::*;
+
+/// The number of unchanged lines shown around the changes.
+const CONTEXT: usize = 3;
+
+impl Builder<'_, WhereTo> {
+    /// Alternative to [`.to_file()`][`Builder::to_file`]`.generate()` which
+    /// leaves the file untouched, and only checks that it matches the
+    /// headers that would be generated.
+    ///
+    /// If it does not (or if it does not exist), an error is returned: for a
+    /// stale file, of kind [`InvalidData`][`io::ErrorKind::InvalidData`],
+    /// showing the (unified) diff from the file to the expected contents.
+    ///
+    /// This is meant for a test making sure that a committed header is
+    /// regenerated whenever the API changes.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # macro_rules! ignore { ($($t:tt)*) => () } ignore! {
+    /// #[::safer_ffi::cfg_headers]
+    /// #[test]
+    /// # }
+    /// fn header_is_up_to_date ()
+    /// {
+    ///     ::safer_ffi::headers::builder()
+    ///         .check_file("include/mylib.h")
+    ///         .unwrap_or_else(|err| panic!("{}", err));
+    /// }
+    /// # header_is_up_to_date();
+    /// ```
+    pub
+    fn check_file (self, filename: impl AsRef<Path>)
+      -> io::Result<Report>
+    {
+        let filename = filename.as_ref();
+        let mut expected = Vec::new();
+        let report = self.to_writer(&mut expected).generate()?;
+        let expected =
+            String::from_utf8(expected)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?
+        ;
+        let existing = fs::read_to_string(filename)?;
+        if existing != expected {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("`{}` is not up to date:\n{}",
+                    filename.display(),
+                    diff(&existing, &expected),
+                ),
+            ));
+        }
+        Ok(report)
+    }
+}
+
+/// The unified diff (as a single hunk) from `old` to `new`.
+fn diff (old: &'_ str, new: &'_ str)
+  -> String
+{
+    let old: Vec<&'_ str> = old.lines().collect();
+    let new: Vec<&'_ str> = new.lines().collect();
+    let prefix =
+        old.iter()
+            .zip(&new)
+            .take_while(|&(a, b)| a == b)
+            .count()
+    ;
+    let suffix =
+        old[prefix ..].iter().rev()
+            .zip(new[prefix ..].iter().rev())
+            .take_while(|&(a, b)| a == b)
+            .count()
+    ;
+    let removed = &old[prefix .. old.len() - suffix];
+    let added = &new[prefix .. new.len() - suffix];
+
+    // Longest common subsequence of the changed regions, `lcs[i][j]` being
+    // the one of `removed[i ..]` and `added[j ..]`.
+    let mut lcs = vec![vec![0_usize; added.len() + 1]; removed.len() + 1];
+    for i in (0 .. removed.len()).rev() {
+        for j in (0 .. added.len()).rev() {
+            lcs[i][j] = if removed[i] == added[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let start = prefix.saturating_sub(CONTEXT);
+    let trailing = suffix.min(CONTEXT);
+    let mut ret = format!("@@ -{},{} +{},{} @@\n",
+        start + 1, prefix - start + removed.len() + trailing,
+        start + 1, prefix - start + added.len() + trailing,
+    );
+    let mut line = |sign: char, s: &'_ str| {
+        ret.push(sign);
+        ret.push_str(s);
+        ret.push('\n');
+    };
+    old[start .. prefix].iter().for_each(|s| line(' ', s));
+    let (mut i, mut j) = (0, 0);
+    while i < removed.len() || j < added.len() {
+        if i < removed.len() && j < added.len() && removed[i] == added[j] {
+            line(' ', removed[i]);
+            i += 1;
+            j += 1;
+        } else if j == added.len()
+            || (i < removed.len() && lcs[i + 1][j] >= lcs[i][j + 1])
+        {
+            line('-', removed[i]);
+            i += 1;
+        } else {
+            line('+', added[j]);
+            j += 1;
+        }
+    }
+    let end = old.len() - suffix;
+    old[end .. end + trailing].iter().for_each(|s| line(' ', s));
+    ret
+}
@@ -0,0 +1,45 @@
+//! The C code examples of the doc comments, which become Doxygen code
+//! blocks.
+
+use super::*;
+use ::std::borrow::Cow;
+
+/// The doc lines of the `symbol` exported item (or [their translation][
+/// `Builder::with_doc_translations`]), as emitted in the C headers: the
+/// ```` ```c ```` fenced code blocks become `\code{.c} ... \endcode`
+/// blocks, so that the C examples written in the Rust documentation show up
+/// as such in the C one.
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __doc_lines__<'doc> (symbol: &'_ str, docs: &'_ [&'doc str])
+  -> Vec<Cow<'doc, str>>
+{
+    // `Some(is_c)` while within a fenced code block.
+    let mut fence = None;
+    translations::translated(symbol, docs)
+        .into_iter()
+        .map(|line| {
+            let trimmed = line.trim();
+            if trimmed.starts_with("```").not() {
+                return line;
+            }
+            let indent = &line[.. line.len() - line.trim_start().len()];
+            match fence.take() {
+                | None => {
+                    let is_c = match trimmed[3 ..].trim() {
+                        | "c" | "C" => true,
+                        | _ => false,
+                    };
+                    fence = Some(is_c);
+                    if is_c {
+                        return Cow::Owned(format!("{}\\code{{.c}}", indent));
+                    }
+                },
+                | Some(true) => {
+                    return Cow::Owned(format!("{}\\endcode", indent));
+                },
+                | Some(false) => {},
+            }
+            line
+        })
+        .collect()
+}
@@ -0,0 +1,171 @@
+//! Resolution of the C name collisions between distinct Rust types: see
+//! [`Builder::with_name_collisions`].
+
+use super::*;
+use ::core::cell::RefCell;
+use ::std::{borrow::Cow, collections::HashMap};
+
+/// What to do when two distinct Rust types (_e.g._, two `Point` structs
+/// defined in two different modules) would be given the same name in the
+/// generated headers, which would otherwise only define the first of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub
+enum NameCollisions {
+    /// Fail the generation, with an error naming both Rust types.
+    Error,
+
+    /// Prefix the name of the colliding type with its module path (without
+    /// the crate name, with `_` as the separator), _e.g._, `geometry_Point`
+    /// for `my_crate::geometry::Point` (the crate name being used for the
+    /// types of the crate root).
+    ///
+    /// The first type to be defined keeps its name.
+    ModulePrefix,
+
+    /// Suffix the name of the colliding type with the first number, from
+    /// `2` onwards, which makes it unique: `Point_2`, `Point_3`, _etc._
+    ///
+    /// The first type to be defined keeps its name.
+    NumericSuffix,
+}
+
+#[derive(Default)]
+struct Names {
+    /// The (Rust) type each C name has been given to.
+    owners: HashMap<String, String>,
+
+    /// The C name given to each (Rust) type.
+    names: HashMap<String, String>,
+
+    /// The C names with the pair of Rust types they have been requested for.
+    collisions: Vec<(String, String, String)>,
+}
+
+::std::thread_local! {
+    // `const { … }` `thread_local!` initializers require Rust 1.59.
+    #[allow(clippy::missing_const_for_thread_local)]
+    static NAMES: RefCell<Option<(NameCollisions, Names)>> = RefCell::new(None);
+}
+
+/// Runs `f` (the generation of the headers) with the given `strategy`, and
+/// fails, with [`NameCollisions::Error`], if some names have collided.
+pub(in super)
+fn with_name_collisions (
+    strategy: NameCollisions,
+    f: impl FnOnce() -> io::Result<()>,
+) -> io::Result<()>
+{
+    struct Restore(Option<(NameCollisions, Names)>);
+    impl Drop for Restore {
+        fn drop (self: &'_ mut Restore)
+        {
+            let prev = self.0.take();
+            NAMES.with(|it| *it.borrow_mut() = prev);
+        }
+    }
+    let prev = NAMES.with(|it| {
+        it.borrow_mut().replace((strategy, Names::default()))
+    });
+    let restore = Restore(prev);
+    f()?;
+    let collisions = NAMES.with(|it| {
+        it.borrow_mut()
+            .as_mut()
+            .map(|(_, names)| mem::take(&mut names.collisions))
+            .unwrap_or_default()
+    });
+    drop(restore);
+    match collisions.first() {
+        | None => Ok(()),
+        | Some(&(ref name, ref first, ref second)) => Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!(
+                "Error, `{}` and `{}` would both be named `{}` in the headers \
+                (see `Builder::with_name_collisions`)",
+                first, second, name,
+            ),
+        )),
+    }
+}
+
+/// Writes the C (short) name of the `name` type defined in `module_path`:
+/// `name` itself, unless it has already been given to another type during
+/// the ongoing generation.
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __c_short_name__ (
+    fmt: &'_ mut fmt::Formatter<'_>,
+    name: &'_ str,
+    module_path: &'_ str,
+) -> fmt::Result
+{
+    fmt.write_str(&c_short_name(name, module_path))
+}
+
+fn c_short_name<'name> (name: &'name str, module_path: &'_ str)
+  -> Cow<'name, str>
+{
+    NAMES.with(|it| {
+        let mut it = it.borrow_mut();
+        let (strategy, names) = match *it {
+            | Some((strategy, ref mut names)) => (strategy, names),
+            | None => return Cow::Borrowed(name),
+        };
+        // Skip the helper modules of the derives.
+        let module_path =
+            module_path
+                .split("::")
+                .filter(|it| it.ends_with("_safer_ffi_mod").not())
+                .collect::<Vec<_>>()
+                .join("::")
+        ;
+        let path = format!("{}::{}", module_path, name);
+        if let Some(c_name) = names.names.get(&path) {
+            return Cow::Owned(c_name.clone());
+        }
+        let is_free = |names: &'_ Names, c_name: &'_ str| {
+            match names.owners.get(c_name) {
+                | Some(owner) => *owner == path,
+                | None => true,
+            }
+        };
+        let c_name = if is_free(names, name) {
+            name.to_owned()
+        } else {
+            match strategy {
+                | NameCollisions::Error => {
+                    let first = names.owners[name].clone();
+                    names.collisions.push((name.into(), first, path));
+                    return Cow::Borrowed(name);
+                },
+                | NameCollisions::ModulePrefix => {
+                    let prefixed = format!(
+                        "{}_{}",
+                        match module_path.find("::") {
+                            | Some(i) => module_path[i + 2 ..].replace("::", "_"),
+                            | None => module_path.clone(),
+                        },
+                        name,
+                    );
+                    if is_free(names, &prefixed) {
+                        prefixed
+                    } else {
+                        numeric_suffix(names, &prefixed)
+                    }
+                },
+                | NameCollisions::NumericSuffix => numeric_suffix(names, name),
+            }
+        };
+        names.owners.insert(c_name.clone(), path.clone());
+        names.names.insert(path, c_name.clone());
+        Cow::Owned(c_name)
+    })
+}
+
+fn numeric_suffix (names: &'_ Names, name: &'_ str)
+  -> String
+{
+    (2 ..)
+        .map(|n| format!("{}_{}", name, n))
+        .find(|it| names.owners.contains_key(it).not())
+        .unwrap()
+}
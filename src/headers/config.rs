@@ -0,0 +1,679 @@
+//! Declarative configuration of the headers generation: see
+//! [`Builder::from_config_file`].
+
+use super::*;
+
+impl Builder<'static, WhereTo> {
+    /// Creates a builder configured by the given TOML file (typically, a
+    /// `safer-ffi.toml` at the root of the crate), so that the build script
+    /// and the tests generating the headers share a single configuration.
+    ///
+    /// Each key sets up the builder option of the same name (see the
+    /// `with_` methods), the others keeping their default value:
+    ///
+    /// ```toml
+    /// guard = "__MYLIB_H__"
+    /// emit_guard = true
+    /// guard_style = "pragma_once"   # or "ifndef", "both"
+    /// banner = """
+    /// /* My library. */
+    /// """
+    /// license_header = "MIT OR Apache-2.0"
+    /// includes = ["<stdarg.h>", "\"my_common.h\""]
+    /// prologue = "#define MYLIB_API_VERSION 2"
+    /// epilogue = ""
+    /// language = "C"                # or "C#", "Java", "Go", "Zig"
+    /// c_dialect = "C99"             # or "C89", "C11"
+    /// float_types = "standard"      # or "iec60559"
+    /// simd_types = "aligned_arrays" # or "intrinsics"
+    /// variant_order = "declaration" # or "value"
+    /// type_placement = "before_first_use" # or "hoisted"
+    /// name_collisions = "error"     # or "module_prefix", "numeric_suffix"
+    /// fn_ptr_typedefs = "inline"    # or "named"
+    /// stable_order = true
+    /// shuffle_seed = 42
+    /// export_macro = "MYLIB_API"
+    /// dllexport_define = "MYLIB_API_BUILD"
+    /// version_macros = "MYLIB"
+    /// contract_macros = "MYLIB"
+    /// cxx_namespace = "mylib"
+    /// dll_name = "mylib"
+    /// java_package = "com.example.mylib"
+    /// go_package = "mylib"
+    /// kotlin_package = "mylib"
+    /// swift_annotations = false
+    /// doc_translations = "docs/fr.txt"
+    ///
+    /// [naming]
+    /// prefix = "MyLib_"
+    /// case = "PascalCase" # or "preserve", "camelCase", "snake_case",
+    ///                     # "SCREAMING_SNAKE_CASE"
+    ///
+    /// [filter]
+    /// # The exported functions and `static`s to emit, by (C) name, with `*`
+    /// # wildcards: the ones matching some `include` pattern (all of them,
+    /// # by default), but no `exclude` one.
+    /// include = ["mylib_*"]
+    /// exclude = ["mylib_internal_*"]
+    /// ```
+    ///
+    /// Only the subset of TOML such a configuration needs is supported:
+    /// `[table]` headers, `key = value` pairs, `#` comments, and strings
+    /// (basic or literal, possibly multi-line), integers, booleans, and
+    /// arrays of strings as values. As in TOML, a key (or a table) cannot be
+    /// defined twice, and, as in the builder, unknown keys (or tables) are
+    /// errors.
+    ///
+    /// The relative paths (`doc_translations`) are relative to the current
+    /// directory, and the strings of the configuration are leaked, for the
+    /// builder to be `'static`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main () -> ::std::io::Result<()> { Ok({
+    /// ::safer_ffi::headers::Builder::from_config_file("safer-ffi.toml")?
+    ///     .to_file("mylib.h")?
+    ///     .generate()?;
+    /// # })}
+    /// ```
+    pub
+    fn from_config_file (path: impl AsRef<Path>)
+      -> io::Result<Builder<'static, WhereTo>>
+    {
+        let path = path.as_ref();
+        fs::read_to_string(path)
+            .and_then(|config| Builder::from_config_str(&config))
+            .map_err(|err| {
+                io::Error::new(err.kind(), format!("{}: {}", path.display(), err))
+            })
+    }
+
+    /// Same as [`Builder::from_config_file`], but with the contents of the
+    /// configuration file.
+    pub
+    fn from_config_str (config: &'_ str)
+      -> io::Result<Builder<'static, WhereTo>>
+    {
+        let mut ret = builder();
+        let mut included = vec![];
+        let mut excluded = vec![];
+        for (line, key, value) in parse(config)? {
+            let error = |msg: &'_ dyn fmt::Display| io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Line {}: `{}`: {}", line, key, msg),
+            );
+            let string = || match value {
+                | Value::String(ref s) => Ok(leak(s.clone())),
+                | _ => Err(error(&"expected a string")),
+            };
+            let boolean = || match value {
+                | Value::Boolean(b) => Ok(b),
+                | _ => Err(error(&"expected a boolean")),
+            };
+            let strings = || match value {
+                | Value::Array(ref strings) => Ok(strings.clone()),
+                | _ => Err(error(&"expected an array of strings")),
+            };
+            macro_rules! choice {( $($name:literal => $it:expr),* $(,)? ) => (
+                match string()? {
+                    $(
+                        | $name => $it,
+                    )*
+                    | other => return Err(error(&format_args!(
+                        "unknown value `{}`, expected one of {}",
+                        other,
+                        [$(concat!("`", $name, "`")),*].join(", "),
+                    ))),
+                }
+            )}
+            ret = match &key[..] {
+                | "guard" => ret.with_guard(string()?),
+                | "emit_guard" => ret.with_emit_guard(boolean()?),
+                | "guard_style" => ret.with_guard_style(choice! {
+                    "ifndef" => GuardStyle::Ifndef,
+                    "pragma_once" => GuardStyle::PragmaOnce,
+                    "both" => GuardStyle::Both,
+                }),
+                | "banner" => ret.with_banner(string()?),
+                | "license_header" => ret.with_license_header(string()?),
+                | "includes" => {
+                    let includes: Vec<_> =
+                        strings()?.into_iter().map(leak).collect()
+                    ;
+                    ret.with_includes(rust::Box::leak(includes.into_boxed_slice()))
+                },
+                | "prologue" => ret.with_prologue(string()?),
+                | "epilogue" => ret.with_epilogue(string()?),
+                | "language" => ret.with_language(choice! {
+                    "C" => Language::C,
+                    "C#" => Language::CSharp,
+                    "Java" => Language::Java,
+                    "Go" => Language::Go,
+                    "Zig" => Language::Zig,
+                }),
+                | "c_dialect" => ret.with_c_dialect(choice! {
+                    "C89" => CDialect::C89,
+                    "C99" => CDialect::C99,
+                    "C11" => CDialect::C11,
+                }),
+                | "float_types" => ret.with_float_types(choice! {
+                    "standard" => FloatTypes::Standard,
+                    "iec60559" => FloatTypes::Iec60559,
+                }),
+                | "simd_types" => ret.with_simd_types(choice! {
+                    "aligned_arrays" => SimdTypes::AlignedArrays,
+                    "intrinsics" => SimdTypes::Intrinsics,
+                }),
+                | "variant_order" => ret.with_variant_order(choice! {
+                    "declaration" => VariantOrder::Declaration,
+                    "value" => VariantOrder::Value,
+                }),
+                | "type_placement" => ret.with_type_placement(choice! {
+                    "before_first_use" => TypePlacement::BeforeFirstUse,
+                    "hoisted" => TypePlacement::Hoisted,
+                }),
+                | "name_collisions" => ret.with_name_collisions(choice! {
+                    "error" => NameCollisions::Error,
+                    "module_prefix" => NameCollisions::ModulePrefix,
+                    "numeric_suffix" => NameCollisions::NumericSuffix,
+                }),
+                | "fn_ptr_typedefs" => ret.with_fn_ptr_typedefs(choice! {
+                    "inline" => FnPtrTypedefs::Inline,
+                    "named" => FnPtrTypedefs::Named,
+                }),
+                | "stable_order" => ret.with_stable_order(boolean()?),
+                | "shuffle_seed" => match value {
+                    | Value::Integer(seed) if seed >= 0 => {
+                        ret.with_shuffle_seed(seed as u64)
+                    },
+                    | _ => return Err(error(&"expected a non-negative integer")),
+                },
+                | "export_macro" => ret.with_export_macro(string()?),
+                | "dllexport_define" => ret.with_dllexport_define(string()?),
+                | "version_macros" => ret.with_version_macros(string()?),
+                | "contract_macros" => ret.with_contract_macros(string()?),
+                | "cxx_namespace" => ret.with_cxx_namespace(string()?),
+                | "dll_name" => ret.with_dll_name(string()?),
+                | "java_package" => ret.with_java_package(string()?),
+                | "go_package" => ret.with_go_package(string()?),
+                | "kotlin_package" => ret.with_kotlin_package(string()?),
+                | "swift_annotations" => ret.with_swift_annotations(boolean()?),
+                | "doc_translations" => {
+                    let translations =
+                        DocTranslations::from_file(string()?)
+                            .map_err(|err| error(&err))?
+                    ;
+                    ret.with_doc_translations(
+                        rust::Box::leak(rust::Box::new(translations))
+                    )
+                },
+                | "naming.prefix" => {
+                    let naming = ret.naming_convention.unwrap_or_default();
+                    ret.with_naming_convention(NamingConvention {
+                        prefix: string()?,
+                        .. naming
+                    })
+                },
+                | "naming.case" => {
+                    let naming = ret.naming_convention.unwrap_or_default();
+                    ret.with_naming_convention(NamingConvention {
+                        case: choice! {
+                            "preserve" => Case::Preserve,
+                            "PascalCase" => Case::PascalCase,
+                            "camelCase" => Case::CamelCase,
+                            "snake_case" => Case::SnakeCase,
+                            "SCREAMING_SNAKE_CASE" => Case::ScreamingSnakeCase,
+                        },
+                        .. naming
+                    })
+                },
+                | "filter.include" => {
+                    included = strings()?;
+                    ret
+                },
+                | "filter.exclude" => {
+                    excluded = strings()?;
+                    ret
+                },
+                | _ => return Err(error(&"unknown key")),
+            };
+        }
+        if included.is_empty().not() || excluded.is_empty().not() {
+            let filter = move |name: &'_ str| {
+                (included.is_empty() || included.iter().any(|it| glob(it, name)))
+                && excluded.iter().any(|it| glob(it, name)).not()
+            };
+            ret = ret.with_filter(rust::Box::leak(rust::Box::new(filter)));
+        }
+        Ok(ret)
+    }
+}
+
+fn leak (s: String)
+  -> &'static str
+{
+    rust::Box::leak(s.into_boxed_str())
+}
+
+/// Whether `name` matches `pattern`, where `*` matches any (possibly empty)
+/// sequence of characters.
+fn glob (pattern: &'_ str, name: &'_ str)
+  -> bool
+{
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap();
+    let mut rest = match name.get(.. first.len()) {
+        | Some(head) if head == first => &name[first.len() ..],
+        | _ => return false,
+    };
+    let parts = parts.collect::<Vec<_>>();
+    let (last, middle) = match parts.split_last() {
+        | Some(it) => it,
+        | None => return rest.is_empty(),
+    };
+    for part in middle {
+        match rest.find(part) {
+            | Some(i) => rest = &rest[i + part.len() ..],
+            | None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+enum Value {
+    String(String),
+    Integer(i64),
+    Boolean(bool),
+    Array(Vec<String>),
+}
+
+/// The `[table]`s of the configuration.
+const TABLES: &[&str] = &["naming", "filter"];
+
+/// The `(line, key, value)` entries of the configuration, the keys of a
+/// `[table]` being prefixed with `table.`.
+fn parse (config: &'_ str)
+  -> io::Result<Vec<(usize, String, Value)>>
+{
+    let mut parser = Parser { s: config, pos: 0, line: 1 };
+    let mut entries = vec![];
+    let mut table = String::new();
+    let mut tables = HashSet::new();
+    let mut keys = HashSet::new();
+    loop {
+        parser.skip_whitespace(true);
+        let line = parser.line;
+        match parser.peek() {
+            | None => break,
+            | Some('[') => {
+                parser.bump();
+                parser.skip_whitespace(false);
+                let name = parser.key()?;
+                if TABLES.contains(&&*name).not() {
+                    return Err(parser.error(&format!(
+                        "unknown table `[{}]`", name,
+                    )));
+                }
+                if tables.insert(name.clone()).not() {
+                    return Err(parser.error(&format!(
+                        "table `[{}]` defined twice", name,
+                    )));
+                }
+                table = format!("{}.", name);
+                parser.skip_whitespace(false);
+                parser.expect("]")?;
+            },
+            | Some(_) => {
+                let key = format!("{}{}", table, parser.key()?);
+                if keys.insert(key.clone()).not() {
+                    return Err(parser.error(&format!(
+                        "`{}` defined twice", key,
+                    )));
+                }
+                parser.skip_whitespace(false);
+                parser.expect("=")?;
+                parser.skip_whitespace(false);
+                let value = parser.value()?;
+                entries.push((line, key, value));
+            },
+        }
+        parser.skip_whitespace(false);
+        if parser.peek_is(|c| c != '\n') {
+            return Err(parser.error("expected a new line"));
+        }
+    }
+    Ok(entries)
+}
+
+struct Parser<'s> {
+    s: &'s str,
+    pos: usize,
+    line: usize,
+}
+
+impl Parser<'_> {
+    fn peek (self: &'_ Self)
+      -> Option<char>
+    {
+        self.s[self.pos ..].chars().next()
+    }
+
+    /// Whether there is a next character, satisfying `predicate`.
+    fn peek_is (self: &'_ Self, predicate: impl FnOnce(char) -> bool)
+      -> bool
+    {
+        match self.peek() {
+            | Some(c) => predicate(c),
+            | None => false,
+        }
+    }
+
+    fn bump (self: &'_ mut Self)
+      -> Option<char>
+    {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        if c == '\n' {
+            self.line += 1;
+        }
+        Some(c)
+    }
+
+    fn eat (self: &'_ mut Self, prefix: &'_ str)
+      -> bool
+    {
+        let eaten = self.s[self.pos ..].starts_with(prefix);
+        if eaten {
+            prefix.chars().for_each(|_| { self.bump(); });
+        }
+        eaten
+    }
+
+    fn expect (self: &'_ mut Self, prefix: &'_ str)
+      -> io::Result<()>
+    {
+        if self.eat(prefix) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{}`", prefix)))
+        }
+    }
+
+    fn error (self: &'_ Self, msg: &'_ str)
+      -> io::Error
+    {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Line {}: {}", self.line, msg),
+        )
+    }
+
+    /// Skips the whitespace and the comments, as well as the new lines if
+    /// `newlines`.
+    fn skip_whitespace (self: &'_ mut Self, newlines: bool)
+    {
+        loop {
+            match self.peek() {
+                | Some(' ') | Some('\t') | Some('\r') => {},
+                | Some('\n') if newlines => {},
+                | Some('#') => {
+                    while self.peek_is(|c| c != '\n') {
+                        self.bump();
+                    }
+                    continue;
+                },
+                | _ => return,
+            }
+            self.bump();
+        }
+    }
+
+    fn key (self: &'_ mut Self)
+      -> io::Result<String>
+    {
+        let start = self.pos;
+        let is_key_char = |c: char| c.is_ascii_alphanumeric() || "_-".contains(c);
+        while self.peek_is(is_key_char) {
+            self.bump();
+        }
+        if self.pos == start {
+            return Err(self.error("expected a key"));
+        }
+        Ok(self.s[start .. self.pos].into())
+    }
+
+    fn value (self: &'_ mut Self)
+      -> io::Result<Value>
+    {
+        Ok(match self.peek() {
+            | Some('"') | Some('\'') => Value::String(self.string()?),
+            | Some('[') => {
+                self.bump();
+                let mut strings = vec![];
+                loop {
+                    self.skip_whitespace(true);
+                    if self.eat("]") {
+                        break;
+                    }
+                    match self.peek() {
+                        | Some('"') | Some('\'') => strings.push(self.string()?),
+                        | _ => return Err(self.error("expected a string")),
+                    }
+                    self.skip_whitespace(true);
+                    if self.eat(",").not() {
+                        self.skip_whitespace(true);
+                        self.expect("]")?;
+                        break;
+                    }
+                }
+                Value::Array(strings)
+            },
+            | _ if self.eat("true") => Value::Boolean(true),
+            | _ if self.eat("false") => Value::Boolean(false),
+            | _ => {
+                let start = self.pos;
+                let is_digit = |c: char| c.is_ascii_digit() || "+-_".contains(c);
+                while self.peek_is(is_digit) {
+                    self.bump();
+                }
+                self.s[start .. self.pos]
+                    .replace('_', "")
+                    .parse()
+                    .map(Value::Integer)
+                    .map_err(|_| self.error("expected a value"))?
+            },
+        })
+    }
+
+    fn string (self: &'_ mut Self)
+      -> io::Result<String>
+    {
+        let (delimiter, literal) =
+            if self.eat("\"\"\"") {
+                ("\"\"\"", false)
+            } else if self.eat("'''") {
+                ("'''", true)
+            } else if self.eat("\"") {
+                ("\"", false)
+            } else {
+                self.expect("'")?;
+                ("'", true)
+            }
+        ;
+        let multiline = delimiter.len() == 3;
+        // A new line right after the opening delimiter is trimmed.
+        if multiline && self.eat("\r\n").not() {
+            self.eat("\n");
+        }
+        let mut ret = String::new();
+        loop {
+            if self.eat(delimiter) {
+                return Ok(ret);
+            }
+            match self.bump() {
+                | None => return Err(self.error("unterminated string")),
+                | Some('\n') if multiline.not() => {
+                    return Err(self.error("unterminated string"));
+                },
+                | Some('\\') if literal.not() => ret.push(self.escape()?),
+                | Some(c) => ret.push(c),
+            }
+        }
+    }
+
+    fn escape (self: &'_ mut Self)
+      -> io::Result<char>
+    {
+        let unicode = |this: &'_ mut Self, len: usize| {
+            let digits = this.s.get(this.pos .. this.pos + len);
+            let c =
+                digits
+                    .and_then(|it| u32::from_str_radix(it, 16).ok())
+                    .and_then(::core::char::from_u32)
+                    .ok_or_else(|| this.error("invalid unicode escape"))?
+            ;
+            (0 .. len).for_each(|_| { this.bump(); });
+            Ok(c)
+        };
+        Ok(match self.bump() {
+            | Some('n') => '\n',
+            | Some('t') => '\t',
+            | Some('r') => '\r',
+            | Some('"') => '"',
+            | Some('\\') => '\\',
+            | Some('u') => return unicode(self, 4),
+            | Some('U') => return unicode(self, 8),
+            | _ => return Err(self.error("unsupported escape sequence")),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error (config: &'_ str)
+      -> String
+    {
+        match Builder::from_config_str(config) {
+            | Ok(_) => panic!("`{}` was accepted", config),
+            | Err(err) => err.to_string(),
+        }
+    }
+
+    #[test]
+    fn values ()
+    {
+        let entries = parse(r#"
+            # A comment.
+            guard = "__A_H__" # Trailing comment.
+            banner = """
+            /* "Multi-line", with an escape: é. */"""
+            prologue = '\n'
+            shuffle_seed = 1_000
+            stable_order = false
+
+            [filter]
+            include = [
+                "a_*",
+                'b_*', # Trailing comma.
+            ]
+        "#).unwrap();
+        let entries =
+            entries
+                .iter()
+                .map(|(line, key, value)| (*line, &key[..], match *value {
+                    | Value::String(ref s) => s.clone(),
+                    | Value::Integer(i) => i.to_string(),
+                    | Value::Boolean(b) => b.to_string(),
+                    | Value::Array(ref strings) => strings.join(" "),
+                }))
+                .collect::<Vec<_>>()
+        ;
+        assert_eq!(entries, [
+            (3, "guard", "__A_H__".to_owned()),
+            (4, "banner", "            /* \"Multi-line\", with an escape: é. */".to_owned()),
+            (6, "prologue", "\\n".to_owned()),
+            (7, "shuffle_seed", "1000".to_owned()),
+            (8, "stable_order", "false".to_owned()),
+            (11, "filter.include", "a_* b_*".to_owned()),
+        ]);
+    }
+
+    #[test]
+    fn builder ()
+    {
+        let builder = Builder::from_config_str(r#"
+            guard = "__A_H__"
+            language = "C#"
+
+            [naming]
+            case = "PascalCase"
+        "#).unwrap();
+        assert_eq!(builder.guard, Some("__A_H__"));
+        assert_eq!(builder.language, Some(Language::CSharp));
+        assert!(matches!(
+            builder.naming_convention.map(|it| it.case),
+            Some(Case::PascalCase)
+        ));
+    }
+
+    #[test]
+    fn duplicates ()
+    {
+        assert_eq!(
+            error("guard = \"A\"\nguard = \"B\"\n"),
+            "Line 2: `guard` defined twice",
+        );
+        assert_eq!(
+            error("[naming]\nprefix = \"a_\"\n[filter]\n[naming]\n"),
+            "Line 4: table `[naming]` defined twice",
+        );
+        // Same key, different tables.
+        assert!(Builder::from_config_str(
+            "[naming]\nprefix = \"a_\"\n[filter]\ninclude = []\n",
+        ).is_ok());
+    }
+
+    #[test]
+    fn unknowns ()
+    {
+        assert_eq!(
+            error("guard = \"A\"\n\n[nameing]\nprefix = \"a_\"\n"),
+            "Line 3: unknown table `[nameing]`",
+        );
+        assert_eq!(error("gaurd = \"A\""), "Line 1: `gaurd`: unknown key");
+        assert_eq!(
+            error("[filter]\nprefix = \"a_\""),
+            "Line 2: `filter.prefix`: unknown key",
+        );
+        assert_eq!(
+            error("language = \"Rust\""),
+            "Line 1: `language`: unknown value `Rust`, expected one of \
+            `C`, `C#`, `Java`, `Go`, `Zig`",
+        );
+    }
+
+    #[test]
+    fn syntax_errors ()
+    {
+        assert_eq!(error("guard = \"A"), "Line 1: unterminated string");
+        assert_eq!(error("guard \"A\""), "Line 1: expected `=`");
+        assert_eq!(error("guard = \"A\" \"B\""), "Line 1: expected a new line");
+        assert_eq!(error("[[naming]]"), "Line 1: expected a key");
+    }
+
+    #[test]
+    fn globs ()
+    {
+        assert!(glob("mylib_*", "mylib_new"));
+        assert!(glob("*_free", "mylib_free"));
+        assert!(glob("a*b*c", "abc"));
+        assert!(glob("a*b*c", "a_b_b_c"));
+        assert!(glob("mylib_*", "mylib_"));
+        assert!(glob("mylib_*", "other_new").not());
+        assert!(glob("exact", "exact"));
+        assert!(glob("exact", "exactly").not());
+    }
+}
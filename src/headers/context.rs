@@ -0,0 +1,458 @@
+//! Configuration of the ongoing headers generation, for the code emitting
+//! the C definitions (which only gets to see a [`Definer`]).
+
+use super::*;
+use ::core::cell::RefCell;
+use ::std::borrow::Cow;
+
+#[derive(Clone, Copy)]
+pub(in crate)
+struct Context {
+    pub(in crate)
+    keyword_escaping: KeywordEscaping,
+
+    pub(in crate)
+    float_types: FloatTypes,
+
+    pub(in crate)
+    simd_types: SimdTypes,
+
+    pub(in crate)
+    variant_order: VariantOrder,
+
+    pub(in crate)
+    export_macro: Option<&'static str>,
+
+    pub(in crate)
+    language: Language,
+
+    pub(in crate)
+    naming_convention: NamingConvention,
+
+    pub(in crate)
+    c_dialect: CDialect,
+
+    pub(in crate)
+    swift_annotations: bool,
+
+    pub(in crate)
+    contract_macros: Option<&'static str>,
+
+    pub(in crate)
+    fn_ptr_typedefs: FnPtrTypedefs,
+}
+
+impl Default
+    for Context
+{
+    fn default ()
+      -> Context
+    {
+        Context {
+            keyword_escaping: KeywordEscaping::Suffix("_"),
+            float_types: FloatTypes::Standard,
+            simd_types: SimdTypes::AlignedArrays,
+            variant_order: VariantOrder::Declaration,
+            export_macro: None,
+            language: Language::C,
+            naming_convention: NamingConvention::default(),
+            c_dialect: CDialect::C11,
+            swift_annotations: false,
+            contract_macros: None,
+            fn_ptr_typedefs: FnPtrTypedefs::Inline,
+        }
+    }
+}
+
+::std::thread_local! {
+    static CONTEXT: RefCell<Context> = RefCell::new(Context::default());
+}
+
+/// Runs `f` with `ctx` as the current [`Context`].
+pub(in super)
+fn with_context<R> (ctx: Context, f: impl FnOnce() -> R)
+  -> R
+{
+    struct Restore(Context);
+    impl Drop for Restore {
+        fn drop (self: &'_ mut Restore)
+        {
+            let prev = self.0;
+            CONTEXT.with(|it| *it.borrow_mut() = prev);
+        }
+    }
+    let prev = CONTEXT.with(|it| mem::replace(&mut *it.borrow_mut(), ctx));
+    let _restore = Restore(prev);
+    f()
+}
+
+pub(in crate)
+fn context ()
+  -> Context
+{
+    CONTEXT.with(|it| *it.borrow())
+}
+
+/// How the fields and parameters named after a keyword of the output
+/// language get renamed, for the generated headers to be valid.
+///
+/// For C headers, these are the C and C++ keywords (_e.g._, a Rust field
+/// named `new`, `class` or `template`).
+#[derive(Clone, Copy)]
+pub
+enum KeywordEscaping {
+    /// Append the given suffix: with `Suffix("_")`, the default, `class`
+    /// becomes `class_`.
+    Suffix(&'static str),
+
+    /// Prepend the given prefix: with `Prefix("_")`, `class` becomes
+    /// `_class`.
+    Prefix(&'static str),
+
+    /// Rename the keywords with the given function.
+    Custom(fn(&'_ str) -> String),
+
+    /// Leave the names as they are.
+    None,
+}
+
+impl KeywordEscaping {
+    /// Escapes `name` if `is_keyword(name)`.
+    pub
+    fn escape<'name> (
+        self: &'_ Self,
+        name: &'name str,
+        is_keyword: impl FnOnce(&'_ str) -> bool,
+    ) -> Cow<'name, str>
+    {
+        if is_keyword(name).not() {
+            return Cow::Borrowed(name);
+        }
+        match *self {
+            | KeywordEscaping::Suffix(suffix) => {
+                Cow::Owned(format!("{}{}", name, suffix))
+            },
+            | KeywordEscaping::Prefix(prefix) => {
+                Cow::Owned(format!("{}{}", prefix, name))
+            },
+            | KeywordEscaping::Custom(rename) => Cow::Owned(rename(name)),
+            | KeywordEscaping::None => Cow::Borrowed(name),
+        }
+    }
+}
+
+/// The C types `f32` and `f64` are mapped to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub
+enum FloatTypes {
+    /// `float` and `double` (the default), which are, in practice, IEEE 754
+    /// `binary32` and `binary64` on all the platforms Rust supports.
+    Standard,
+
+    /// `_Float32` and `_Float64` (C23, or ISO/IEC TS 18661-3), which are
+    /// guaranteed to be IEEE 754 `binary32` and `binary64`, for consumers
+    /// with strict floating-point contracts.
+    Iec60559,
+}
+
+/// The language of the generated bindings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub
+enum Language {
+    /// A C header (also usable from C++), the default.
+    C,
+
+    /// A C# source file, with `[DllImport]` declarations of the functions
+    /// (within a `public unsafe partial class Ffi`), and
+    /// `[StructLayout(LayoutKind.Sequential)]` definitions of the types.
+    ///
+    /// Types without a C# counterpart of their own are defined as structs
+    /// with the right size (and no fields), so as to still be passed around
+    /// by value; `bool`s are mapped to `byte`s, and function pointers, to
+    /// `IntPtr`s.
+    CSharp,
+
+    /// A Java source file, `Ffi.java`, declaring the functions as the
+    /// methods of a JNA `Library` (the `Ffi` interface, whose `INSTANCE`
+    /// loads the native library), and the structs as `Structure`s, with
+    /// their `@Structure.FieldOrder`.
+    ///
+    /// Java integers being signed, the unsigned ones are mapped to the
+    /// signed integers of the same size (and `usize` / `isize`, to `long`,
+    /// which assumes a 64-bit platform); `bool`s are mapped to `byte`s,
+    /// enums, to their integer type (their variants being the constants of
+    /// an eponymous interface), and pointers (including function pointers),
+    /// to `Pointer`s. Types without a Java counterpart of their own are
+    /// defined as `Structure`s with the right size (their bytes being their
+    /// only field), so as to still be passed around by value.
+    ///
+    /// Since JNA binds the methods to the symbols of the same name, the
+    /// [naming convention][`crate::headers::Builder::with_naming_convention`]
+    /// does not apply to the functions.
+    Java,
+
+    /// A Go source file, for a cgo package: the C header is embedded as the
+    /// cgo preamble, and followed by Go mirrors of the types, and by Go
+    /// wrappers of the functions (named after them, in `PascalCase`).
+    ///
+    /// The mirrors have the same layout as their C counterparts (which the
+    /// wrappers rely on, so as to convert from one to the other), with
+    /// capitalized field names: structs are mapped to structs, enums, to a
+    /// named integer type (and to the constants of its variants), arrays to
+    /// arrays, pointers to pointers (`unsafe.Pointer` for `void *` and
+    /// function pointers), and opaque types, to empty structs. Types without a
+    /// Go counterpart of their own are defined as structs with the right size
+    /// and (up to 8 bytes) alignment, so as to still be passed around by
+    /// value.
+    ///
+    /// Besides, the wrappers take and return the C strings (`char const *`)
+    /// as Go `string`s, and the byte slices (including `str_ref`s, which
+    /// are laid out as such) as `[]byte`s: the parameters are only borrowed
+    /// by C for the duration of the call, and the returned values are
+    /// copied.
+    Go,
+
+    /// A Zig source file, with `extern` declarations of the functions and
+    /// statics, and `extern` definitions of the types, for Zig code not to go
+    /// through `@cImport` (which loses the doc comments, and the fact that
+    /// enums only take the values of their variants).
+    ///
+    /// Structs and unions are mapped to `extern struct`s and `extern
+    /// union`s, enums, to (exhaustive) enums with an explicit integer tag
+    /// type, opaque types, to `opaque {}`s, and pointers, to optional
+    /// (single-item) pointers, such as `?*const T`, their nullability not
+    /// being known (`anyopaque` standing for `void`). Types without a Zig
+    /// counterpart of their own are defined as `extern struct`s with the
+    /// right size and alignment, so as to still be passed around by value.
+    Zig,
+}
+
+/// How the names of the emitted types and functions are derived from the
+/// Rust ones: see [`Builder::with_naming_convention`].
+///
+/// The `_t` suffix of the type names is appended afterwards.
+#[derive(Clone, Copy)]
+pub
+struct NamingConvention {
+    /// Prepended to the (case-transformed) names, _e.g._, `"MyLib_"`.
+    pub
+    prefix: &'static str,
+
+    /// The case transform applied to the names.
+    pub
+    case: Case,
+}
+
+impl Default
+    for NamingConvention
+{
+    fn default ()
+      -> NamingConvention
+    {
+        NamingConvention {
+            prefix: "",
+            case: Case::Preserve,
+        }
+    }
+}
+
+impl NamingConvention {
+    /// Renames `name` as per this convention.
+    pub
+    fn apply<'name> (self: &'_ Self, name: &'name str)
+      -> Cow<'name, str>
+    {
+        let name = self.case.apply(name);
+        if self.prefix.is_empty() {
+            name
+        } else {
+            Cow::Owned(format!("{}{}", self.prefix, name))
+        }
+    }
+}
+
+/// A case transform of identifiers, whose words are delimited by
+/// underscores and case changes (`slice_ref_Point` is made of the `slice`,
+/// `ref` and `Point` words).
+#[derive(Clone, Copy)]
+pub
+enum Case {
+    /// Leave the names as they are (the default).
+    Preserve,
+
+    /// `PascalCase`.
+    PascalCase,
+
+    /// `camelCase`.
+    CamelCase,
+
+    /// `snake_case`.
+    SnakeCase,
+
+    /// `SCREAMING_SNAKE_CASE`.
+    ScreamingSnakeCase,
+
+    /// Rename with the given function.
+    Custom(fn(&'_ str) -> String),
+}
+
+impl Case {
+    /// Applies the case transform to `name`.
+    pub
+    fn apply<'name> (self: &'_ Self, name: &'name str)
+      -> Cow<'name, str>
+    {
+        let capitalize = |word: &'_ str| {
+            let mut chars = word.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first.to_uppercase().chain(chars.flat_map(char::to_lowercase)).collect()
+            })
+        };
+        let words = || words(name);
+        Cow::Owned(match *self {
+            | Case::Preserve => return Cow::Borrowed(name),
+            | Case::PascalCase => words().map(capitalize).collect(),
+            | Case::CamelCase => {
+                words().enumerate().map(|(i, word)| {
+                    if i == 0 { word.to_lowercase() } else { capitalize(word) }
+                }).collect()
+            },
+            | Case::SnakeCase => {
+                words().map(str::to_lowercase).collect::<Vec<_>>().join("_")
+            },
+            | Case::ScreamingSnakeCase => {
+                words().map(str::to_uppercase).collect::<Vec<_>>().join("_")
+            },
+            | Case::Custom(rename) => rename(name),
+        })
+    }
+}
+
+/// The words of an identifier: `HTTPServer_new` yields `HTTP`, `Server` and
+/// `new`.
+fn words (name: &'_ str)
+  -> impl '_ + Iterator<Item = &'_ str>
+{
+    name.split('_').flat_map(|part| {
+        let chars: Vec<(usize, char)> = part.char_indices().collect();
+        let mut starts = vec![0];
+        for i in 1 .. chars.len() {
+            let (idx, c) = chars[i];
+            let prev = chars[i - 1].1;
+            let next_is_lower =
+                matches!(chars.get(i + 1), Some(&(_, it)) if it.is_lowercase())
+            ;
+            if c.is_uppercase()
+            && (prev.is_uppercase().not() || next_is_lower)
+            {
+                starts.push(idx);
+            }
+        }
+        starts.push(part.len());
+        starts
+            .windows(2)
+            .map(|it| &part[it[0] .. it[1]])
+            .filter(|it| it.is_empty().not())
+            .collect::<Vec<_>>()
+    })
+}
+
+/// The C types the [`crate::simd`] vectors are mapped to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub
+enum SimdTypes {
+    /// Portable (over-)aligned arrays (the default).
+    AlignedArrays,
+
+    /// The x86 intrinsic types with the same layout (_e.g._, `__m128` for
+    /// a [`f32x4`][`crate::simd::f32x4`]).
+    Intrinsics,
+}
+
+/// The order in which the constants of the emitted enums are listed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub
+enum VariantOrder {
+    /// The declaration order of the Rust variants (the default).
+    Declaration,
+
+    /// Sorted by (discriminant) value, the variants with the same value
+    /// remaining in declaration order. Each constant is then given its
+    /// value explicitly.
+    Value,
+}
+
+/// How the function pointer types (_e.g._, of a callback parameter) are
+/// emitted.
+#[derive(Clone, Copy)]
+pub
+enum FnPtrTypedefs {
+    /// Inline, as anonymous pointer types (the default):
+    /// `void (*cb)(int32_t)`.
+    Inline,
+
+    /// As a `typedef` per distinct signature, named after the return and
+    /// parameter types: `typedef void (*void_int32_fptr_t)(int32_t);`, then
+    /// `void_int32_fptr_t cb`.
+    Named,
+
+    /// As a `typedef` per distinct signature, named with the given function,
+    /// which is given the (short) name [`FnPtrTypedefs::Named`] would use,
+    /// such as `void_int32_fptr`.
+    Custom(fn(&'_ str) -> String),
+}
+
+/// The C name of a field or parameter.
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __c_escape__ (name: &'_ str)
+  -> Cow<'_, str>
+{
+    context().keyword_escaping.escape(name, keywords::is_c_keyword)
+}
+
+/// The name of the `typedef` of a type, given its (short) name.
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __type_name__ (short_name: &'_ dyn fmt::Display)
+  -> String
+{
+    format!("{}_t", context().naming_convention.apply(&short_name.to_string()))
+}
+
+/// The name of the `typedef` of a function pointer type, given its (short)
+/// name, unless [`FnPtrTypedefs::Inline`].
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __fn_ptr_typedef__ (short_name: &'_ dyn fmt::Display)
+  -> Option<String>
+{
+    match context().fn_ptr_typedefs {
+        | FnPtrTypedefs::Inline => None,
+        | FnPtrTypedefs::Named => Some(__type_name__(short_name)),
+        | FnPtrTypedefs::Custom(rename) => Some(rename(&short_name.to_string())),
+    }
+}
+
+/// The name an exported function is declared with (through a `#define`
+/// alias of the actual symbol, when they differ).
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __fn_name__ (symbol: &'_ str)
+  -> Cow<'_, str>
+{
+    context().naming_convention.apply(symbol)
+}
+
+/// The language of the ongoing generation.
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __language__ ()
+  -> Language
+{
+    context().language
+}
+
+/// The macro the function and `static` declarations are annotated with, if
+/// any (see [`Builder::with_export_macro`]).
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __export_macro__ ()
+  -> Option<&'static str>
+{
+    context().export_macro
+}
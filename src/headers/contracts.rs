@@ -0,0 +1,89 @@
+//! The argument-checking macros of [`Builder::with_contract_macros`], and the
+//! preconditions of the functions.
+
+use super::*;
+
+/// Emits the definitions of the `<PREFIX>_REQUIRE` and
+/// `<PREFIX>_REQUIRE_NONNULL` macros.
+pub(in super)
+fn define_contract_macros (out: &'_ mut dyn io::Write, prefix: &'_ str)
+  -> io::Result<()>
+{
+    write!(out,
+        concat!(
+            "#include <stddef.h>\n",
+            "#ifndef {0}_REQUIRE\n",
+            "#  ifdef NDEBUG\n",
+            "#    define {0}_REQUIRE(cond) ((void) 0)\n",
+            "#  else\n",
+            "#    include <assert.h>\n",
+            "#    define {0}_REQUIRE(cond) assert(cond)\n",
+            "#  endif\n",
+            "#endif\n",
+            "#ifndef {0}_REQUIRE_NONNULL\n",
+            "#  define {0}_REQUIRE_NONNULL(p) {0}_REQUIRE((p) != NULL)\n",
+            "#endif\n\n",
+        ),
+        prefix,
+    )
+}
+
+/// Whether the parameters of type `T` must not be `NULL`, _i.e._, whether
+/// `T` is a (C) pointer for which `NULL` is not a valid value (such as a
+/// `&T`, but not an `Option<&T>`).
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __is_nonnull__<T : ReprC> ()
+  -> bool
+{
+    swift::pointer_nullability::<T>() == Some(false)
+}
+
+/// The Doxygen `\pre` lines documenting the contracts of a function, given
+/// its parameters and whether each one [must not be `NULL`][
+/// `__is_nonnull__`], when [emitting the contract macros][
+/// `Builder::with_contract_macros`].
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __contracts__ (args: &'_ [(&'_ str, bool)])
+  -> Vec<String>
+{
+    let prefix = match context::context().contract_macros {
+        | Some(it) => it,
+        | None => return vec![],
+    };
+    args.iter()
+        .filter(|&&(_, nonnull)| nonnull)
+        .map(|&(arg_name, _)| {
+            let arg_name = __c_escape__(arg_name);
+            format!("\\pre `{0}` is not `NULL`: `{1}_REQUIRE_NONNULL({0})`.",
+                arg_name, prefix,
+            )
+        })
+        .collect()
+}
+
+/// The Doxygen `\remark` lines documenting that the given parameters (and
+/// the returned value) point to pinned values, _i.e._, are
+/// [`repr_c::PinBox`][`crate::prelude::repr_c::PinBox`]es, given their
+/// [`OwnedKind`].
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __pinned__ (args: &'_ [(&'_ str, Option<OwnedKind>)], ret: Option<OwnedKind>)
+  -> Vec<String>
+{
+    let mut pinned = vec![];
+    if ret == Some(OwnedKind::PinBox) {
+        pinned.push("The returned pointer".to_owned());
+    }
+    for &(arg_name, kind) in args {
+        if kind == Some(OwnedKind::PinBox) {
+            pinned.push(format!("`{}`", __c_escape__(arg_name)));
+        }
+    }
+    pinned
+        .into_iter()
+        .map(|it| format!(
+            "\\remark {} points to a pinned value, which must not be moved \
+            (_e.g._, `memcpy`-ed) until it is freed.",
+            it,
+        ))
+        .collect()
+}
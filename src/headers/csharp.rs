@@ -0,0 +1,255 @@
+//! The C# backend: see [`Language::CSharp`].
+
+use super::*;
+use ::std::borrow::Cow;
+
+/// The C# name of a field or parameter.
+fn escape (name: &'_ str)
+  -> Cow<'_, str>
+{
+    let name = name.trim_start_matches("r#");
+    if keywords::is_csharp_keyword(name) {
+        Cow::Owned(format!("@{}", name))
+    } else {
+        Cow::Borrowed(name)
+    }
+}
+
+/// The C# [`Backend`].
+pub(in super)
+struct CSharp;
+
+impl Backend for CSharp {
+    /// Emits the `using`s and the name of the native library.
+    fn prologue (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        config: &'_ Builder<'_, WhereTo>,
+    ) -> io::Result<()>
+    {
+        write!(definer.out(),
+            concat!(
+                "using System;\n",
+                "using System.Runtime.InteropServices;\n",
+                "\n",
+                "public unsafe partial class Ffi {{\n",
+                "    private const string RustLib = \"{}\";\n",
+                "}}\n",
+                "\n",
+            ),
+            backend::dll_name(config).escape_default(),
+        )
+    }
+
+    fn primitive_ty (self: &'_ Self, c_short_name: &'_ str)
+      -> &'static str
+    {
+        match c_short_name {
+            | "uint8" => "byte",
+            | "uint16" => "ushort",
+            | "uint32" => "uint",
+            | "uint64" => "ulong",
+            | "size" => "UIntPtr",
+            | "int8" => "sbyte",
+            | "int16" => "short",
+            | "int32" => "int",
+            | "int64" => "long",
+            | "ssize" => "IntPtr",
+            | "float" => "float",
+            | "double" => "double",
+            | "bool" => "byte",
+            | "char" => "byte",
+            | "void" => "void",
+            | _ => unreachable!("`{}` is not a primitive type", c_short_name),
+        }
+    }
+
+    fn pointer_ty (self: &'_ Self, pointee: &'_ TypeDesc, _: bool)
+      -> String
+    {
+        format!("{}*", pointee.ty())
+    }
+
+    fn fn_ptr_ty (self: &'_ Self, _: &'_ TypeDesc, _: &'_ [TypeDesc], _: bool)
+      -> String
+    {
+        "IntPtr".into()
+    }
+
+    fn define_bytes (self: &'_ Self, definer: &'_ mut dyn Definer, me: &'_ TypeDesc)
+      -> io::Result<()>
+    {
+        let ref name = me.ty();
+        definer.define_once(name, &mut |definer| {
+            write!(definer.out(),
+                concat!(
+                    "/** \\remark Has the same layout as `{}` **/\n",
+                    "[StructLayout(LayoutKind.Sequential, Size = {})]\n",
+                    "public unsafe struct {} {{\n",
+                    "}}\n\n",
+                ),
+                me.c_var(),
+                me.size,
+                name,
+            )
+        })
+    }
+
+    fn define_struct (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        me: &'_ TypeDesc,
+        docs: &'_ [&'_ str],
+        fields: &'_ [Field<'_>],
+    ) -> io::Result<()>
+    {
+        define_aggregate(definer.out(), me, docs, fields, "Sequential", "")
+    }
+
+    fn define_union (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        me: &'_ TypeDesc,
+        docs: &'_ [&'_ str],
+        fields: &'_ [Field<'_>],
+    ) -> io::Result<()>
+    {
+        define_aggregate(definer.out(), me, docs, fields,
+            "Explicit", "[FieldOffset(0)] ",
+        )
+    }
+
+    fn define_enum (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        me: &'_ TypeDesc,
+        docs: &'_ [&'_ str],
+        int: &'_ TypeDesc,
+        variants: &'_ mut [Variant<'_>],
+    ) -> io::Result<()>
+    {
+        let out = definer.out();
+        backend::c_docs(out, "", docs)?;
+        writeln!(out, "public enum {} : {} {{", me.ty(), int.ty())?;
+        __output_variants__(out, &mut
+            variants
+                .iter()
+                .map(|variant| (
+                    variant.value,
+                    "",
+                    variant.name,
+                    variant.discriminant,
+                ))
+                .collect::<Vec<_>>()
+        )?;
+        writeln!(out, "}}\n")
+    }
+
+    fn define_opaque (self: &'_ Self, definer: &'_ mut dyn Definer, me: &'_ TypeDesc)
+      -> io::Result<()>
+    {
+        write!(definer.out(), "public struct {} {{}}\n\n", me.ty())
+    }
+
+    fn declare_fn (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        docs: &'_ [&'_ str],
+        name: &'_ str,
+        ret: &'_ TypeDesc,
+        params: &'_ [(&'_ str, TypeDesc)],
+    ) -> io::Result<()>
+    {
+        let out = definer.out();
+        out.write_all(b"public unsafe partial class Ffi {\n")?;
+        if docs.is_empty().not() {
+            out.write_all(b"    /// <summary>\n")?;
+            for line in docs {
+                writeln!(out, "    ///{}", xml_escape(line))?;
+            }
+            out.write_all(b"    /// </summary>\n")?;
+        }
+        // Renamed functions keep on importing the actual symbol.
+        let cs_name = context::__fn_name__(name);
+        let entry_point = if cs_name != name {
+            format!(", EntryPoint = \"{}\"", name)
+        } else {
+            String::new()
+        };
+        write!(out,
+            concat!(
+                "    [DllImport(RustLib, ExactSpelling = true{})] ",
+                "public static unsafe extern\n",
+                "    {} {} (",
+            ),
+            entry_point, ret.ty(), cs_name,
+        )?;
+        for (i, &(param_name, ref param_ty)) in params.iter().enumerate() {
+            // `_` can only name one parameter.
+            let param_name = if param_name == "_" {
+                Cow::Owned(format!("_{}", i))
+            } else {
+                escape(param_name)
+            };
+            write!(out,
+                "{comma}\n        {} {}",
+                param_ty.ty(), param_name,
+                comma = if i == 0 { "" } else { "," },
+            )?;
+        }
+        out.write_all(b");\n}\n\n")
+    }
+
+    // `[DllImport]` only imports functions.
+    fn declare_static (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        _: &'_ [&'_ str],
+        name: &'_ str,
+        _: &'_ TypeDesc,
+    ) -> io::Result<()>
+    {
+        write!(definer.out(),
+            "/* `{}`: exported statics cannot be imported through `DllImport` */\n\n",
+            name,
+        )
+    }
+}
+
+/// Emits the definition of a struct or of a union, with the given
+/// `LayoutKind`, and `field_attrs` before each field.
+fn define_aggregate (
+    out: &'_ mut dyn io::Write,
+    me: &'_ TypeDesc,
+    docs: &'_ [&'_ str],
+    fields: &'_ [Field<'_>],
+    layout_kind: &'_ str,
+    field_attrs: &'_ str,
+) -> io::Result<()>
+{
+    backend::c_docs(out, "", docs)?;
+    writeln!(out,
+        "[StructLayout(LayoutKind.{}, Size = {})]\npublic unsafe struct {} {{",
+        layout_kind, me.size, me.ty(),
+    )?;
+    for field in fields {
+        backend::c_docs(out, "    ", field.docs)?;
+        writeln!(out, "    {}public {} {};\n",
+            field_attrs, field.ty.ty(), escape(field.name),
+        )?;
+    }
+    writeln!(out, "}}\n")
+}
+
+fn xml_escape (s: &'_ str)
+  -> Cow<'_, str>
+{
+    if s.contains(&['<', '>', '&'][..]).not() {
+        return Cow::Borrowed(s);
+    }
+    Cow::Owned(
+        s   .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+    )
+}
@@ -0,0 +1,298 @@
+//! The C++ RAII wrappers of the owned types, for
+//! [`.generate_cxx_wrappers()`][`Builder::generate_cxx_wrappers`].
+
+use super::*;
+
+impl<'__> Builder<'__, WhereTo> {
+    /// Generates, in memory, a C++ header (`.hpp`) `#include`-ing the C one
+    /// (`c_header`, such as `"mylib.h"`), with a move-only RAII class per
+    /// owned type ([`repr_c::Box`][`crate::prelude::repr_c::Box`],
+    /// [`repr_c::Vec`][`crate::prelude::repr_c::Vec`], or
+    /// [`char_p::Box`][`crate::prelude::char_p::Box`]) having an exported
+    /// function to free it (see [`Destructor`]), which its destructor calls.
+    ///
+    /// The classes are named after the C types: `Vec_uint8` for
+    /// `Vec_uint8_t`, `Box_Point` for `Point_t *`, and `BoxedString` for
+    /// (the `char *` of) `char_p::Box`. They are put within the
+    /// [C++ namespace][`Builder::with_cxx_namespace`], if any.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main () -> ::std::io::Result<()> { Ok({
+    /// use ::safer_ffi::prelude::*;
+    ///
+    /// #[ffi_export]
+    /// fn mylib_string_free (it: char_p::Box)
+    /// {
+    ///     drop(it)
+    /// }
+    ///
+    /// let hpp =
+    ///     ::safer_ffi::headers::builder()
+    ///         .generate_cxx_wrappers("mylib.h")?
+    /// ;
+    /// ::std::fs::write("include/mylib.hpp", hpp)?;
+    /// # })}
+    /// ```
+    ///
+    /// yields, among others:
+    ///
+    /// ```cpp
+    /// class BoxedString {
+    /// public:
+    ///     typedef char * raw_type;
+    ///
+    ///     explicit BoxedString (raw_type raw) noexcept : raw_(raw) {}
+    ///     BoxedString (BoxedString && other) noexcept : raw_(other.release()) {}
+    ///     /* ... */
+    ///     ~BoxedString () { reset(); }
+    ///
+    ///     raw_type const & get () const noexcept { return raw_; }
+    ///     raw_type release () noexcept { /* ... */ }
+    ///     void reset () noexcept { /* calls `mylib_string_free()` */ }
+    ///     /* ... */
+    /// };
+    /// ```
+    pub
+    fn generate_cxx_wrappers (self, c_header: &'_ str)
+      -> io::Result<String>
+    {
+        if matches!(self.language, Some(it) if it != Language::C) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "C++ wrappers are only supported for C",
+            ));
+        }
+        let guard = match self.guard {
+            | Some(it) => format!("{}_HPP__", it.trim_end_matches('_')),
+            | None => format!("__RUST_{}_HPP__",
+                env::var("CARGO_PKG_NAME")
+                    .unwrap()
+                    .to_ascii_uppercase()
+            ),
+        };
+        let guard_style = self.guard_style.unwrap_or(GuardStyle::Ifndef);
+        let banner = self.banner.unwrap_or(DEFAULT_BANNER);
+        let namespaces: Vec<&'_ str> =
+            self.cxx_namespace
+                .map_or(vec![], |it| it.split("::").collect())
+        ;
+        let report = self.to_writer(io::sink()).generate()?;
+
+        let mut out = String::new();
+        out.push_str(banner);
+        out.push_str("\n\n");
+        out.push_str(&guard_style.open(&guard));
+        out.push_str(&include_directive(c_header));
+        out.push('\n');
+        for namespace in &namespaces {
+            out.push_str(&format!("namespace {} {{\n", namespace));
+        }
+        if namespaces.is_empty().not() {
+            out.push('\n');
+        }
+        let mut classes = HashSet::new();
+        for destructor in &report.destructors {
+            let class = class_name(destructor);
+            if classes.insert(class.clone()) {
+                out.push_str(&wrapper_class(&class, destructor));
+            }
+        }
+        for namespace in namespaces.iter().rev() {
+            out.push_str(&format!("}} /* namespace {} */\n", namespace));
+        }
+        out.push_str(&guard_style.close(&guard));
+        Ok(out)
+    }
+}
+
+/// The name of the C++ class wrapping the type freed by `destructor`.
+fn class_name (destructor: &'_ Destructor)
+  -> String
+{
+    let c_type = destructor.c_type.trim_end_matches(&[' ', '*'][..]);
+    // `strip_suffix` requires Rust 1.45.
+    #[allow(clippy::manual_strip)]
+    let c_type = if c_type.ends_with("_t") {
+        &c_type[.. c_type.len() - 2]
+    } else {
+        c_type
+    };
+    let c_type: String =
+        c_type
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect()
+    ;
+    match destructor.kind {
+        | OwnedKind::Box => format!("Box_{}", c_type),
+        | OwnedKind::PinBox => format!("PinBox_{}", c_type),
+        | OwnedKind::Vec => c_type,
+        | OwnedKind::String => "BoxedString".into(),
+    }
+}
+
+fn wrapper_class (class: &'_ str, destructor: &'_ Destructor)
+  -> String
+{
+    let is_set = match destructor.kind {
+        | OwnedKind::Vec => "raw_.ptr != nullptr",
+        | OwnedKind::Box | OwnedKind::PinBox | OwnedKind::String => {
+            "raw_ != nullptr"
+        },
+    };
+    format!(
+        concat!(
+            "/** \\brief\n",
+            " *  Owns a `{raw}`, freed with `{free}()`.\n",
+            " */\n",
+            "class {class} {{\n",
+            "public:\n",
+            "    typedef {raw} raw_type;\n",
+            "\n",
+            "    explicit {class} (raw_type raw) noexcept : raw_(raw) {{}}\n",
+            "    {class} ({class} && other) noexcept : raw_(other.release()) {{}}\n",
+            "    {class} & operator= ({class} && other) noexcept\n",
+            "    {{\n",
+            "        if (this != &other) {{\n",
+            "            reset();\n",
+            "            raw_ = other.release();\n",
+            "        }}\n",
+            "        return *this;\n",
+            "    }}\n",
+            "    {class} ({class} const &) = delete;\n",
+            "    {class} & operator= ({class} const &) = delete;\n",
+            "    ~{class} () {{ reset(); }}\n",
+            "\n",
+            "    /** \\brief\n",
+            "     *  The owned value (if any), still owned by the wrapper.\n",
+            "     */\n",
+            "    raw_type const & get () const noexcept {{ return raw_; }}\n",
+            "\n",
+            "    /** \\brief\n",
+            "     *  Gives up the ownership of the value, leaving the wrapper empty.\n",
+            "     */\n",
+            "    raw_type release () noexcept\n",
+            "    {{\n",
+            "        raw_type raw = raw_;\n",
+            "        raw_ = raw_type{{}};\n",
+            "        return raw;\n",
+            "    }}\n",
+            "\n",
+            "    /** \\brief\n",
+            "     *  Frees the owned value (if any), leaving the wrapper empty.\n",
+            "     */\n",
+            "    void reset () noexcept\n",
+            "    {{\n",
+            "        if ({is_set}) {{\n",
+            "            {free}(release());\n",
+            "        }}\n",
+            "    }}\n",
+            "\n",
+            "    explicit operator bool () const noexcept {{ return {is_set}; }}\n",
+            "\n",
+            "private:\n",
+            "    raw_type raw_;\n",
+            "}};\n",
+            "\n",
+        ),
+        class = class,
+        raw = destructor.c_type,
+        free = destructor.name,
+        is_set = is_set,
+    )
+}
+
+/// Records the function being declared as a [`Destructor`], when it takes
+/// an owned value as its sole parameter, and returns nothing.
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __report_destructor__ (
+    definer: &'_ mut dyn Definer,
+    fname: &'_ str,
+    params: &'_ [(Option<OwnedKind>, String)],
+    returns: bool,
+)
+{
+    if let (&[(Some(kind), ref c_type)], false) = (params, returns) {
+        definer.set_destructor(&Destructor {
+            name: fname.to_owned(),
+            c_type: c_type.clone(),
+            kind,
+        });
+    }
+}
+
+/// `(&__OwnedProbe__::<T>(PhantomData)).__owned_kind__()` is the [`OwnedKind`]
+/// of `T`, if any, with both traits in scope (autoref-based specialization).
+#[doc(hidden)] /** Not part of the public API **/ pub
+struct __OwnedProbe__<T>(pub PhantomData<T>);
+
+#[doc(hidden)] /** Not part of the public API **/ pub
+mod __owned_probe__ {
+    use super::*;
+    use crate::prelude::{char_p, repr_c};
+
+    pub use super::__OwnedProbe__;
+
+    pub
+    trait Owned {
+        const KIND: OwnedKind;
+    }
+
+    impl<T> Owned
+        for repr_c::Box<T>
+    {
+        const KIND: OwnedKind = OwnedKind::Box;
+    }
+
+    impl<T> Owned
+        for repr_c::PinBox<T>
+    {
+        const KIND: OwnedKind = OwnedKind::PinBox;
+    }
+
+    impl<T> Owned
+        for repr_c::Vec<T>
+    {
+        const KIND: OwnedKind = OwnedKind::Vec;
+    }
+
+    impl Owned
+        for char_p::Box
+    {
+        const KIND: OwnedKind = OwnedKind::String;
+    }
+
+    pub
+    trait IsOwned {
+        fn __owned_kind__ (self: &'_ Self)
+          -> Option<OwnedKind>
+        ;
+    }
+
+    impl<T : Owned> IsOwned
+        for __OwnedProbe__<T>
+    {
+        #[inline]
+        fn __owned_kind__ (self: &'_ Self)
+          -> Option<OwnedKind>
+        {
+            Some(T::KIND)
+        }
+    }
+
+    pub
+    trait IsNotOwned {
+        #[inline]
+        fn __owned_kind__ (self: &'_ Self)
+          -> Option<OwnedKind>
+        {
+            None
+        }
+    }
+
+    impl<T> IsNotOwned
+        for &'_ __OwnedProbe__<T>
+    {}
+}
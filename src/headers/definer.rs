@@ -24,6 +24,76 @@ trait Definer : define_once_seal::__ {
       -> &'_ mut dyn io::Write
     ;
 
+    /// Informs about the [kind][`SymbolKind`] of the item which has been
+    /// successfully [`.insert()`][`Definer::insert`]ed under `name`, when it
+    /// is not a type definition.
+    ///
+    /// For functions and `static`s, this is called once the types they
+    /// depend on have been defined, right before writing their declaration.
+    ///
+    /// It does nothing by default.
+    #[inline]
+    fn set_symbol_kind (self: &'_ mut Self, name: &'_ str, kind: SymbolKind)
+    {
+        let _ = (name, kind);
+    }
+
+    /// Reports a suspicious construct spotted while generating the headers.
+    ///
+    /// It does nothing by default.
+    #[inline]
+    fn warn (self: &'_ mut Self, diagnostic: Diagnostic)
+    {
+        let _ = diagnostic;
+    }
+
+    /// Informs that the function or `static` exported under `name` is
+    /// marked `#[ffi_export(used)]`, _i.e._, that it is meant to be kept by
+    /// the linker even when no other object file references it.
+    ///
+    /// It does nothing by default.
+    #[inline]
+    fn set_used (self: &'_ mut Self, name: &'_ str)
+    {
+        let _ = name;
+    }
+
+    /// Informs that the function being declared frees an owned value (see
+    /// [`Destructor`]).
+    ///
+    /// It does nothing by default.
+    #[inline]
+    fn set_destructor (self: &'_ mut Self, destructor: &'_ Destructor)
+    {
+        let _ = destructor;
+    }
+
+    /// Informs about the signature of the function being declared (see
+    /// [`Signature`]).
+    ///
+    /// It does nothing by default.
+    #[inline]
+    fn set_signature (self: &'_ mut Self, signature: &'_ Signature)
+    {
+        let _ = signature;
+    }
+
+    /// Informs that the type defined under `item` (or, when `field` is
+    /// `Some`, that field of it) has been added in the `since` version of
+    /// the API (_e.g._, `"1.2"`), as annotated with `#[repr_c(since = ...)]`.
+    ///
+    /// It does nothing by default.
+    #[inline]
+    fn set_since (
+        self: &'_ mut Self,
+        item: &'_ str,
+        field: Option<&'_ str>,
+        since: &'_ str,
+    )
+    {
+        let _ = (item, field, since);
+    }
+
     #[cfg(docs)]
     /// Convenience method to perform an [`.insert()`][`Definer::insert`] so
     /// that if it succeeds (thus guaranteeing the call happens for the first
@@ -101,3 +171,53 @@ impl Definer
         &mut *self.out
     }
 }
+
+/// Allows sharing a [`Definer`] across several
+/// [`.generate_with_definer()`][`Builder::generate_with_definer`] passes,
+/// so that the items defined by a pass are not redefined by the next ones.
+impl<D : ?Sized + Definer> Definer
+    for &'_ mut D
+{
+    #[inline]
+    fn insert (self: &'_ mut Self, name: &'_ str)
+      -> bool
+    {
+        (**self).insert(name)
+    }
+
+    #[inline]
+    fn out (self: &'_ mut Self)
+      -> &'_ mut dyn io::Write
+    {
+        (**self).out()
+    }
+
+    #[inline]
+    fn set_symbol_kind (self: &'_ mut Self, name: &'_ str, kind: SymbolKind)
+    {
+        (**self).set_symbol_kind(name, kind)
+    }
+
+    #[inline]
+    fn warn (self: &'_ mut Self, diagnostic: Diagnostic)
+    {
+        (**self).warn(diagnostic)
+    }
+
+    #[inline]
+    fn set_used (self: &'_ mut Self, name: &'_ str)
+    {
+        (**self).set_used(name)
+    }
+
+    #[inline]
+    fn set_since (
+        self: &'_ mut Self,
+        item: &'_ str,
+        field: Option<&'_ str>,
+        since: &'_ str,
+    )
+    {
+        (**self).set_since(item, field, since)
+    }
+}
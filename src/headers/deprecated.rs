@@ -0,0 +1,86 @@
+use super::*;
+
+/// Defines (once) the `SAFER_FFI_DEPRECATED(note)` macro used to annotate
+/// the declarations of the `#[deprecated]` exported items, for the C
+/// compilers to warn about their usages as well.
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __define_deprecated__ (definer: &'_ mut dyn Definer)
+  -> io::Result<()>
+{
+    definer.define_once(
+        "SAFER_FFI_DEPRECATED",
+        &mut |definer| {
+            definer.set_symbol_kind(
+                "SAFER_FFI_DEPRECATED",
+                SymbolKind::Macro,
+            );
+            write!(definer.out(),
+                concat!(
+                    "#ifndef SAFER_FFI_DEPRECATED\n",
+                    "#if defined(__GNUC__) || defined(__clang__)\n",
+                    "#define SAFER_FFI_DEPRECATED(note) ",
+                        "__attribute__((deprecated(note)))\n",
+                    "#elif defined(_MSC_VER)\n",
+                    "#define SAFER_FFI_DEPRECATED(note) ",
+                        "__declspec(deprecated(note))\n",
+                    "#else\n",
+                    "#define SAFER_FFI_DEPRECATED(note)\n",
+                    "#endif\n",
+                    "#endif\n",
+                    "\n",
+                ),
+            )
+        },
+    )
+}
+
+/// The `SAFER_FFI_DEPRECATED(...)` annotation (followed by a space) of the
+/// declaration of an item, if `#[deprecated]` (with the given `note`).
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __deprecated__ (note: Option<&'_ str>)
+  -> String
+{
+    note.map_or_else(String::new, |note| {
+        format!("SAFER_FFI_DEPRECATED({}) ",
+            note.c_literal().unwrap_or_else(|| "\"\"".into()),
+        )
+    })
+}
+
+/// Strips the `SAFER_FFI_DEPRECATED(...)` annotations off the generated
+/// `code` (for the outputs which do not go through the C preprocessor),
+/// returning the note of the first one, if any (as its C literal contents).
+pub(in super)
+fn strip_deprecated (code: &'_ str)
+  -> (String, Option<String>)
+{
+    const ANNOTATION: &str = "SAFER_FFI_DEPRECATED(";
+    let mut ret = String::with_capacity(code.len());
+    let mut note = None;
+    let mut rest = code;
+    while let Some(start) = rest.find(ANNOTATION) {
+        ret.push_str(&rest[.. start]);
+        rest = &rest[start + ANNOTATION.len() ..];
+        // The note is a string literal: skip it (escapes included) before
+        // looking for the closing parenthesis.
+        let mut escaped = false;
+        let mut in_literal = false;
+        let end = rest.char_indices().find(|&(_, c)| {
+            match c {
+                | _ if escaped => escaped = false,
+                | '\\' => escaped = true,
+                | '"' => in_literal = in_literal.not(),
+                | ')' if in_literal.not() => return true,
+                | _ => {},
+            }
+            false
+        });
+        let end = end.map_or(rest.len(), |(i, _)| i);
+        if note.is_none() {
+            note = Some(rest[.. end].trim().trim_matches('"').to_owned());
+        }
+        rest = rest.get(end + 1 ..).unwrap_or("").trim_start_matches(' ');
+    }
+    ret.push_str(rest);
+    (ret, note)
+}
@@ -0,0 +1,160 @@
+use super::*;
+
+/// Asserts that the generated headers do not depend on the order in which
+/// the `#[ffi_export]`ed items are registered, by generating them once in
+/// the default order, and then once per `seed` in a shuffled order, and
+/// checking that the outputs are byte-for-byte equal.
+///
+/// # Panics
+///
+/// If one of the shuffled outputs differs, showing the first differing line.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # macro_rules! ignore { ($($t:tt)*) => () } ignore! {
+/// #[::safer_ffi::cfg_headers]
+/// #[test]
+/// # }
+/// fn headers_are_deterministic ()
+/// {
+///     ::safer_ffi::headers::assert_deterministic(0 .. 8);
+/// }
+/// # headers_are_deterministic();
+/// ```
+pub
+fn assert_deterministic (seeds: impl IntoIterator<Item = u64>)
+{
+    let generate = |seed: Option<u64>| -> String {
+        let mut out = Vec::new();
+        let mut builder = builder().with_guard("__SAFER_FFI_DETERMINISM__");
+        builder.shuffle_seed = seed;
+        builder
+            .to_writer(&mut out)
+            .generate()
+            .expect("Failed to generate the headers");
+        String::from_utf8(out)
+            .expect("The generated headers are not valid UTF-8")
+    };
+    let expected = generate(None);
+    seeds.into_iter().for_each(|seed| {
+        let got = generate(Some(seed));
+        if got == expected { return; }
+        let (line_no, (expected_line, got_line)) =
+            expected.lines().chain(Some("<EOF>"))
+                .zip(got.lines().chain(Some("<EOF>")))
+                .enumerate()
+                .find(|&(_, (expected, got))| expected != got)
+                .unwrap_or((0, ("", "")))
+        ;
+        panic!(
+            concat!(
+                "The generated headers depend on the definition order ",
+                "(shuffle seed: {}).\n",
+                "First difference at line {}:\n",
+                "  - {}\n",
+                "  + {}\n",
+            ),
+            seed, line_no + 1, expected_line, got_line,
+        );
+    });
+}
+
+/// Fisher-Yates shuffle driven by a `xorshift64*` generator seeded from
+/// `seed` (through a `splitmix64` step, so that any seed, even `0`, works).
+pub(in super)
+fn shuffle<T> (elems: &'_ mut [T], seed: u64)
+{
+    let mut state = {
+        let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        (z ^ (z >> 31)) | 1
+    };
+    let mut next = move || {
+        state ^= state >> 12;
+        state ^= state << 25;
+        state ^= state >> 27;
+        state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    };
+    for i in (1 .. elems.len()).rev() {
+        let j = (next() % (i as u64 + 1)) as usize;
+        elems.swap(i, j);
+    }
+}
+
+/// Sorts the exports by the name of the item they declare (see
+/// [`declared_name`]), so that the generated headers do not depend
+/// on the (link-time) registration order.
+///
+/// The types are still defined right before their first use (or hoisted in
+/// that same order), and thus after the types they depend on: the resulting
+/// order is topological with regard to the type dependencies, and
+/// alphabetical otherwise.
+pub(in super)
+fn sort (exports: &'_ mut Vec<registration::Define>)
+{
+    let mut keyed: Vec<(String, registration::Define)> =
+        exports
+            .drain(..)
+            .map(|define| (declared_name(&define).unwrap_or_default(), define))
+            .collect()
+    ;
+    keyed.sort_by(|(a, _), (b, _)| a.cmp(b));
+    exports.extend(keyed.into_iter().map(|(_, define)| define));
+}
+
+/// The (C) name of the item declared by an export (the first function or
+/// `static`, else the first macro, since the types it depends on may define
+/// some), if any, obtained by running it while emitting nothing.
+pub(in super)
+fn declared_name (define: &'_ registration::Define)
+  -> Option<String>
+{
+    /// Records the name of the declared item, while emitting nothing.
+    struct Probe {
+        defined: HashSet<String>,
+        name: Option<String>,
+        macro_name: Option<String>,
+        sink: io::Sink,
+    }
+
+    impl Definer
+        for Probe
+    {
+        fn insert (self: &'_ mut Self, name: &'_ str)
+          -> bool
+        {
+            self.defined.insert(name.to_owned())
+        }
+
+        fn out (self: &'_ mut Self)
+          -> &'_ mut dyn io::Write
+        {
+            &mut self.sink
+        }
+
+        fn set_symbol_kind (self: &'_ mut Self, name: &'_ str, kind: SymbolKind)
+        {
+            let slot = match kind {
+                | SymbolKind::Function
+                | SymbolKind::Static
+                => &mut self.name,
+                | SymbolKind::Macro => &mut self.macro_name,
+                | _ => return,
+            };
+            if slot.is_none() {
+                *slot = Some(name.to_owned());
+            }
+        }
+    }
+
+    let mut probe = Probe {
+        defined: HashSet::new(),
+        name: None,
+        macro_name: None,
+        sink: io::sink(),
+    };
+    let _ = define(&mut probe);
+    probe.name.or(probe.macro_name)
+}
@@ -0,0 +1,218 @@
+use super::*;
+
+/// Suspicious constructs spotted while generating the headers, reported
+/// to the [`.with_on_warning()`][`Builder::with_on_warning`] callback (and
+/// listed in the [`Report`]).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub
+enum Diagnostic {
+    /// Two emitted items whose names only differ by their case or their
+    /// underscores (_e.g._, `fooBar` and `foo_bar`), and which would thus
+    /// collide after a case transform.
+    CaseCollision {
+        first: String,
+        second: String,
+    },
+
+    /// An item, a field, or a parameter named after a C (or C++) keyword.
+    ReservedKeyword {
+        /// The item being defined.
+        item: String,
+        /// The offending name.
+        name: String,
+    },
+
+    /// An `#[ffi_export]`ed item without documentation.
+    Undocumented {
+        item: String,
+    },
+
+    /// A `struct` field added in a version of the API (see
+    /// `#[repr_c(since = ...)]`) but not declared after the fields of the
+    /// previous versions, thus breaking the layout expected by the code
+    /// compiled against those.
+    MisplacedNewField {
+        item: String,
+        field: String,
+        /// The version which introduced `field` (`None` for the original
+        /// fields).
+        since: Option<String>,
+        /// The (more recent) version of a field declared before it.
+        previous: String,
+    },
+}
+
+impl fmt::Display
+    for Diagnostic
+{
+    fn fmt (self: &'_ Self, fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        match *self {
+            | Diagnostic::CaseCollision { ref first, ref second } => write!(fmt,
+                "`{}` and `{}` only differ by their case or underscores",
+                first, second,
+            ),
+            | Diagnostic::ReservedKeyword { ref item, ref name } => {
+                if item == name {
+                    write!(fmt, "`{}` is a C / C++ keyword", name)
+                } else {
+                    write!(fmt,
+                        "`{}`, in `{}`, is a C / C++ keyword", name, item,
+                    )
+                }
+            },
+            | Diagnostic::Undocumented { ref item } => write!(fmt,
+                "`{}` is exported without documentation", item,
+            ),
+            | Diagnostic::MisplacedNewField {
+                ref item,
+                ref field,
+                ref since,
+                ref previous,
+            } => write!(fmt,
+                "`{}.{}`, {}, is declared after a field added in {}: \
+                new fields must be appended at the end of the struct",
+                item,
+                field,
+                since.as_ref().map_or_else(
+                    || "an original field".into(),
+                    |since| format!("added in {}", since),
+                ),
+                previous,
+            ),
+        }
+    }
+}
+
+/// Reports the reserved keywords among `names` (as well as the lack of
+/// documentation, when `documented` is `Some(false)`).
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __lint_item__ (
+    definer: &'_ mut dyn Definer,
+    item: &'_ str,
+    documented: Option<bool>,
+    names: &'_ [&'_ str],
+)
+{
+    if documented == Some(false) {
+        definer.warn(Diagnostic::Undocumented { item: item.into() });
+    }
+    names
+        .iter()
+        .filter(|&&name| keywords::is_c_keyword(name))
+        .for_each(|&name| definer.warn(Diagnostic::ReservedKeyword {
+            item: item.into(),
+            name: name.into(),
+        }))
+    ;
+}
+
+/// Reports the `\since <version>` lines (see `#[repr_c(since = ...)]`)
+/// among the `docs` of the `struct` `item` and of its `fields`, and checks
+/// that the fields of each version are declared after those of the previous
+/// ones.
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __record_since__ (
+    definer: &'_ mut dyn Definer,
+    item: &'_ str,
+    docs: &'_ [&'_ str],
+    fields: &'_ [(&'_ str, &'_ [&'_ str])],
+)
+{
+    if let Some(since) = __since__(docs) {
+        definer.set_since(item, None, since);
+    }
+    let mut latest: Option<&'_ str> = None;
+    for &(field, docs) in fields {
+        let since = __since__(docs);
+        if let Some(since) = since {
+            definer.set_since(item, Some(field), since);
+        }
+        match (since, latest) {
+            | (_, None) => latest = since,
+            | (None, Some(previous)) => definer.warn(
+                Diagnostic::MisplacedNewField {
+                    item: item.into(),
+                    field: field.into(),
+                    since: None,
+                    previous: previous.into(),
+                },
+            ),
+            | (Some(since), Some(previous)) => {
+                if cmp_versions(since, previous) == ::core::cmp::Ordering::Less {
+                    definer.warn(Diagnostic::MisplacedNewField {
+                        item: item.into(),
+                        field: field.into(),
+                        since: Some(since.into()),
+                        previous: previous.into(),
+                    });
+                } else {
+                    latest = Some(since);
+                }
+            },
+        }
+    }
+}
+
+/// The version of the (last) `\since <version>` line of `docs`, if any.
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __since__<'doc> (docs: &'_ [&'doc str])
+  -> Option<&'doc str>
+{
+    docs.iter()
+        .rev()
+        .map(|doc| doc.trim())
+        .find(|doc| doc.starts_with("\\since "))
+        .map(|doc| doc.trim_start_matches("\\since ").trim())
+}
+
+/// Compares `.`-separated versions, numerically when both parts are numbers.
+fn cmp_versions (a: &'_ str, b: &'_ str)
+  -> ::core::cmp::Ordering
+{
+    let mut a = a.split('.');
+    let mut b = b.split('.');
+    loop {
+        let ordering = match (a.next(), b.next()) {
+            | (None, None) => return ::core::cmp::Ordering::Equal,
+            | (None, Some(_)) => return ::core::cmp::Ordering::Less,
+            | (Some(_), None) => return ::core::cmp::Ordering::Greater,
+            | (Some(a), Some(b)) => match (a.parse::<u64>(), b.parse::<u64>()) {
+                | (Ok(a), Ok(b)) => a.cmp(&b),
+                | _ => a.cmp(b),
+            },
+        };
+        if ordering != ::core::cmp::Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+/// Reports the emitted items whose names collide once lowercased and
+/// stripped of underscores.
+pub(in super)
+fn lint_case_collisions (definer: &'_ mut dyn Definer, symbols: &'_ [Symbol])
+{
+    let mut seen = ::std::collections::HashMap::<String, &'_ str>::new();
+    symbols
+        .iter()
+        .filter(|it| it.kind != SymbolKind::Include)
+        .for_each(|it| {
+            let key =
+                it  .name
+                    .chars()
+                    .filter(|&c| c != '_')
+                    .flat_map(char::to_lowercase)
+                    .collect::<String>()
+            ;
+            match seen.get(&key) {
+                | Some(&first) => definer.warn(Diagnostic::CaseCollision {
+                    first: first.into(),
+                    second: it.name.clone(),
+                }),
+                | None => { seen.insert(key, &it.name); },
+            }
+        })
+    ;
+}
@@ -0,0 +1,117 @@
+//! The C standard the generated headers are written against: see
+//! [`Builder::with_c_dialect`].
+
+/// The C standard the generated headers are written against.
+///
+/// Whatever the dialect, the headers only use `/* */` comments, and do not
+/// use `_Static_assert`, so that these never get in the way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub
+enum CDialect {
+    /// ANSI C (C89 / C90): `<stdbool.h>` and `<stdint.h>` are only
+    /// `#include`d when the compiler advertises C99 (or C++), the header
+    /// otherwise defining `bool` and the fixed-width integer types itself,
+    /// and the [SIMD][`crate::simd`] types are aligned with compiler
+    /// extensions.
+    C89,
+
+    /// C99: `<stdbool.h>` and `<stdint.h>` are `#include`d, and the
+    /// [SIMD][`crate::simd`] types are aligned with compiler extensions
+    /// (`__attribute__((aligned(n)))`, or `__declspec(align(n))` with MSVC).
+    C99,
+
+    /// C11 (the default): as with C99, but the [SIMD][`crate::simd`] types
+    /// are aligned with `_Alignas`.
+    C11,
+}
+
+impl CDialect {
+    /// The definitions of `bool`, `true` and `false`.
+    pub(in crate)
+    fn bool_header (self: Self)
+      -> &'static str
+    {
+        match self {
+            | CDialect::C89 => concat!(
+                "\n",
+                "#if defined(__cplusplus) \\\n",
+                "    || (defined(__STDC_VERSION__) && __STDC_VERSION__ >= 199901L)\n",
+                "#include <stdbool.h>\n",
+                "#elif !defined(__bool_true_false_are_defined)\n",
+                "typedef unsigned char bool;\n",
+                "#define true 1\n",
+                "#define false 0\n",
+                "#define __bool_true_false_are_defined 1\n",
+                "#endif\n",
+                "\n",
+            ),
+            | CDialect::C99 | CDialect::C11 => "\n#include <stdbool.h>\n\n",
+        }
+    }
+
+    /// The definitions of `size_t` and of the fixed-width integer types.
+    pub(in crate)
+    fn int_headers (self: Self)
+      -> &'static str
+    {
+        match self {
+            | CDialect::C89 => concat!(
+                "\n",
+                "#include <stddef.h>\n",
+                "#if defined(__cplusplus) \\\n",
+                "    || (defined(__STDC_VERSION__) && __STDC_VERSION__ >= 199901L)\n",
+                "#include <stdint.h>\n",
+                "#else\n",
+                "typedef signed char int8_t;\n",
+                "typedef unsigned char uint8_t;\n",
+                "typedef short int16_t;\n",
+                "typedef unsigned short uint16_t;\n",
+                "typedef int int32_t;\n",
+                "typedef unsigned int uint32_t;\n",
+                "#ifdef _MSC_VER\n",
+                "typedef __int64 int64_t;\n",
+                "typedef unsigned __int64 uint64_t;\n",
+                "#elif defined(__GNUC__)\n",
+                "__extension__ typedef long long int64_t;\n",
+                "__extension__ typedef unsigned long long uint64_t;\n",
+                "#else\n",
+                "typedef long long int64_t;\n",
+                "typedef unsigned long long uint64_t;\n",
+                "#endif\n",
+                "#endif\n",
+                "\n",
+            ),
+            | CDialect::C99 | CDialect::C11 => concat!(
+                "\n",
+                "#include <stddef.h>\n",
+                "#include <stdint.h>\n",
+                "\n",
+            ),
+        }
+    }
+
+    /// The definition of the `SAFER_FFI_ALIGNAS(n)` macro.
+    pub(in crate)
+    fn alignas_macro (self: Self)
+      -> &'static str
+    {
+        match self {
+            | CDialect::C89 | CDialect::C99 => concat!(
+                "#if defined(__cplusplus)\n",
+                "#define SAFER_FFI_ALIGNAS(n) alignas(n)\n",
+                "#elif defined(_MSC_VER)\n",
+                "#define SAFER_FFI_ALIGNAS(n) __declspec(align(n))\n",
+                "#else\n",
+                "#define SAFER_FFI_ALIGNAS(n) __attribute__((aligned(n)))\n",
+                "#endif\n\n",
+            ),
+            | CDialect::C11 => concat!(
+                "#ifdef __cplusplus\n",
+                "#define SAFER_FFI_ALIGNAS(n) alignas(n)\n",
+                "#else\n",
+                "#define SAFER_FFI_ALIGNAS(n) _Alignas(n)\n",
+                "#endif\n\n",
+            ),
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use super::*;
+
+/// Defines (once) the Doxygen `group` of the
+/// `#[ffi_export(doc_group = "<group>")]` items, which are then added to it
+/// through an `\ingroup <group>` line in their doc comment.
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __define_doc_group__ (definer: &'_ mut dyn Definer, group: &'_ str)
+  -> io::Result<()>
+{
+    let name = &format!("__doc_group_{}__", group);
+    definer.define_once(name, &mut |definer| {
+        definer.set_symbol_kind(name, SymbolKind::DocGroup);
+        write!(definer.out(),
+            "/** \\defgroup {0} {0} */\n\n",
+            group,
+        )
+    })
+}
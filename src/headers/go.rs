@@ -0,0 +1,441 @@
+//! The Go (cgo) backend: see [`Language::Go`].
+
+use super::*;
+use ::std::borrow::Cow;
+
+/// The Go name of a field: capitalized, so as to be exported.
+fn field_name (name: &'_ str)
+  -> String
+{
+    let name = name.trim_start_matches("r#");
+    let mut chars = name.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_uppercase().chain(chars).collect()
+    })
+}
+
+/// The Go name of a parameter.
+fn go_param (name: &'_ str)
+  -> Cow<'_, str>
+{
+    let name = name.trim_start_matches("r#");
+    if keywords::is_go_keyword(name) {
+        Cow::Owned(format!("{}_", name))
+    } else {
+        Cow::Borrowed(name)
+    }
+}
+
+/// Emits the definition of `ty`, the Go type of an enum, and of the
+/// constants standing for its variants.
+fn constants (
+    out: &'_ mut dyn io::Write,
+    ty: &'_ str,
+    int: &'_ str,
+    variants: &'_ mut [Variant<'_>],
+) -> io::Result<()>
+{
+    if context::context().variant_order == VariantOrder::Value {
+        // Stable sort: same-valued variants remain in declaration order.
+        variants.sort_by_key(|variant| variant.value);
+    }
+    write!(out, "type {} {}\n\nconst (\n", ty, int)?;
+    for variant in variants {
+        writeln!(out, "\t{}_{} {} = {}", ty, variant.name, ty, variant.value)?;
+    }
+    out.write_all(b")\n\n")
+}
+
+/// The Go spelling of a C type (as given by `c_var("")`), as seen through
+/// cgo.
+fn cgo_ty (c_ty: &'_ str)
+  -> String
+{
+    // Function pointers, when not `typedef`-ed.
+    if c_ty.contains('(') {
+        return "*[0]byte".into();
+    }
+    let depth = c_ty.matches('*').count();
+    let base =
+        c_ty.split_whitespace()
+            .find(|&it| it != "const" && it != "*")
+            .unwrap_or("void")
+    ;
+    match (base, depth) {
+        | ("void", 0) => "".into(),
+        | ("void", _) => format!("{}unsafe.Pointer", "*".repeat(depth - 1)),
+        | _ => format!("{}C.{}", "*".repeat(depth), base),
+    }
+}
+
+/// How a parameter (or the returned value) is converted.
+#[derive(PartialEq)]
+enum Conversion {
+    /// From / to a Go `string`, for C strings.
+    String,
+    /// From / to a Go `[]byte`, for byte slices.
+    Bytes,
+    /// Reinterpreted as is, the Go and C types having the same layout.
+    Layout,
+}
+
+fn conversion (c_ty: &'_ str)
+  -> Conversion
+{
+    if c_ty == "char const *" {
+        Conversion::String
+    } else if
+        *c_ty == *__type_name__(&"slice_ref_uint8")
+        || *c_ty == *__type_name__(&"slice_mut_uint8")
+    {
+        Conversion::Bytes
+    } else {
+        Conversion::Layout
+    }
+}
+
+/// The Go (cgo) [`Backend`].
+pub(in super)
+struct Go;
+
+impl Backend for Go {
+    /// Emits the `package` clause and the cgo preamble: the linking of the
+    /// native library, and the C header itself, for the wrappers to call the
+    /// functions it declares.
+    fn prologue (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        config: &'_ Builder<'_, WhereTo>,
+    ) -> io::Result<()>
+    {
+        let default_package;
+        let package: &'_ str = match config.go_package {
+            | Some(it) => it,
+            | None => {
+                default_package =
+                    env::var("CARGO_PKG_NAME").unwrap().replace('-', "_")
+                ;
+                &default_package
+            },
+        };
+        if !is_c_identifier(package) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Invalid Go package: `{}`", package),
+            ));
+        }
+        // The C header, for the cgo preamble (its diagnostics being those of
+        // the Go generation).
+        let mut header = Vec::new();
+        Builder {
+            language: Some(Language::C),
+            swift_annotations: None,
+            on_warning: Some(|_| ()),
+            .. config.copy()
+        }
+            .to_writer(&mut header)
+            .generate()?
+        ;
+        let header =
+            String::from_utf8(header)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?
+        ;
+        let out = definer.out();
+        write!(out,
+            concat!(
+                "// Code generated by `::safer_ffi`. DO NOT EDIT.\n",
+                "\n",
+                "package {}\n",
+                "\n",
+                "// #cgo LDFLAGS: -l{}\n",
+                "// #include <stdlib.h>\n",
+                "//\n",
+            ),
+            package, backend::dll_name(config),
+        )?;
+        for line in header.lines() {
+            if line.is_empty() {
+                writeln!(out, "//")?;
+            } else {
+                writeln!(out, "// {}", line)?;
+            }
+        }
+        out.write_all(concat!(
+            "import \"C\"\n",
+            "\n",
+            "import \"unsafe\"\n",
+            "\n",
+            "var _ unsafe.Pointer\n",
+            "\n",
+        ).as_bytes())
+    }
+
+    fn primitive_ty (self: &'_ Self, c_short_name: &'_ str)
+      -> &'static str
+    {
+        match c_short_name {
+            | "uint8" => "uint8",
+            | "uint16" => "uint16",
+            | "uint32" => "uint32",
+            | "uint64" => "uint64",
+            | "size" => "uintptr",
+            | "int8" => "int8",
+            | "int16" => "int16",
+            | "int32" => "int32",
+            | "int64" => "int64",
+            | "ssize" => "int",
+            | "float" => "float32",
+            | "double" => "float64",
+            | "bool" => "bool",
+            | "char" => "byte",
+            | "void" => "void",
+            | _ => unreachable!("`{}` is not a primitive type", c_short_name),
+        }
+    }
+
+    fn pointer_ty (self: &'_ Self, pointee: &'_ TypeDesc, _: bool)
+      -> String
+    {
+        match &pointee.ty()[..] {
+            | "void" => "unsafe.Pointer".into(),
+            | pointee => format!("*{}", pointee),
+        }
+    }
+
+    fn fn_ptr_ty (self: &'_ Self, _: &'_ TypeDesc, _: &'_ [TypeDesc], _: bool)
+      -> String
+    {
+        "unsafe.Pointer".into()
+    }
+
+    fn array_ty (self: &'_ Self, _: &'_ str, item: &'_ TypeDesc, len: usize)
+      -> String
+    {
+        format!("[{}]{}", len, item.ty())
+    }
+
+    fn define_array (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        _: &'_ TypeDesc,
+        item: &'_ TypeDesc,
+        _: usize,
+    ) -> io::Result<()>
+    {
+        item.define(definer)
+    }
+
+    fn define_bytes (self: &'_ Self, definer: &'_ mut dyn Definer, me: &'_ TypeDesc)
+      -> io::Result<()>
+    {
+        let ref name = me.ty();
+        definer.define_once(name, &mut |definer| {
+            write!(definer.out(),
+                concat!(
+                    "// `{}` has the same layout as `{}`.\n",
+                    "type {} struct {{\n",
+                    "\t_     [0]uint{}\n",
+                    "\tBytes [{}]byte\n",
+                    "}}\n\n",
+                ),
+                name,
+                me.c_var(),
+                name,
+                8 * me.align.min(8),
+                me.size,
+            )
+        })
+    }
+
+    fn define_struct (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        me: &'_ TypeDesc,
+        docs: &'_ [&'_ str],
+        fields: &'_ [Field<'_>],
+    ) -> io::Result<()>
+    {
+        let out = definer.out();
+        backend::c_docs(out, "", docs)?;
+        writeln!(out, "type {} struct {{", me.ty())?;
+        for field in fields {
+            backend::c_docs(out, "\t", field.docs)?;
+            writeln!(out, "\t{} {}", field_name(field.name), field.ty.ty())?;
+        }
+        writeln!(out, "}}\n")
+    }
+
+    // Go has no unions.
+    fn define_union (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        me: &'_ TypeDesc,
+        _: &'_ [&'_ str],
+        _: &'_ [Field<'_>],
+    ) -> io::Result<()>
+    {
+        self.define_bytes(definer, me)
+    }
+
+    fn define_enum (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        me: &'_ TypeDesc,
+        docs: &'_ [&'_ str],
+        int: &'_ TypeDesc,
+        variants: &'_ mut [Variant<'_>],
+    ) -> io::Result<()>
+    {
+        let out = definer.out();
+        backend::c_docs(out, "", docs)?;
+        constants(out, &me.ty(), &int.ty(), variants)
+    }
+
+    fn define_opaque (self: &'_ Self, definer: &'_ mut dyn Definer, me: &'_ TypeDesc)
+      -> io::Result<()>
+    {
+        write!(definer.out(), "type {} struct{{}}\n\n", me.ty())
+    }
+
+    /// Emits the Go wrapper of an exported function, named after it, in
+    /// `PascalCase`, for it to be exported.
+    fn declare_fn (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        docs: &'_ [&'_ str],
+        name: &'_ str,
+        ret: &'_ TypeDesc,
+        params: &'_ [(&'_ str, TypeDesc)],
+    ) -> io::Result<()>
+    {
+        let (ref ret_c_ty, ref ret_go_ty) = (ret.c_var(), ret.ty());
+        let ret_conversion = conversion(ret_c_ty);
+        let params: Vec<(String, Conversion, String, String)> =
+            params
+                .iter()
+                .enumerate()
+                .map(|(i, &(param_name, ref ty))| {
+                    let c_ty = ty.c_var();
+                    (
+                        // `_` can only name one parameter.
+                        if param_name == "_" {
+                            format!("_{}", i)
+                        } else {
+                            go_param(param_name).into_owned()
+                        },
+                        conversion(&c_ty),
+                        c_ty,
+                        ty.ty(),
+                    )
+                })
+                .collect()
+        ;
+        if params.iter().any(|param| param.1 == Conversion::Bytes) {
+            definer.define_once("cBytesPtr", &mut |definer| {
+                definer.out().write_all(concat!(
+                    "var cEmptyBytes byte\n",
+                    "\n",
+                    "// cBytesPtr borrows the (never `NULL`) pointer of `b`, for the\n",
+                    "// duration of a call.\n",
+                    "func cBytesPtr(b []byte) *C.uint8_t {\n",
+                    "\tif len(b) == 0 {\n",
+                    "\t\treturn (*C.uint8_t)(unsafe.Pointer(&cEmptyBytes))\n",
+                    "\t}\n",
+                    "\treturn (*C.uint8_t)(unsafe.Pointer(&b[0]))\n",
+                    "}\n",
+                    "\n",
+                ).as_bytes())
+            })?;
+        }
+        let out = definer.out();
+        let go_name = Case::PascalCase.apply(name);
+        writeln!(out, "// {} calls `{}`.", go_name, name)?;
+        if docs.is_empty().not() {
+            writeln!(out, "//")?;
+            for line in docs {
+                writeln!(out, "//{}", line)?;
+            }
+        }
+        write!(out, "func {}(", go_name)?;
+        for (i, (arg_name, conv, _, go_ty)) in params.iter().enumerate() {
+            write!(out,
+                "{comma}{} {}",
+                arg_name,
+                match *conv {
+                    | Conversion::String => "string",
+                    | Conversion::Bytes => "[]byte",
+                    | Conversion::Layout => &go_ty[..],
+                },
+                comma = if i == 0 { "" } else { ", " },
+            )?;
+        }
+        let ret_ty = match ret_conversion {
+            | Conversion::String => "string",
+            | Conversion::Bytes => "[]byte",
+            | Conversion::Layout if ret_go_ty == "void" => "",
+            | Conversion::Layout => ret_go_ty,
+        };
+        writeln!(out, "){}{} {{", if ret_ty.is_empty() { "" } else { " " }, ret_ty)?;
+        for (arg_name, conv, _, _) in &params {
+            if *conv == Conversion::String {
+                let c_name = format!("c{}", Case::PascalCase.apply(arg_name));
+                writeln!(out, "\t{} := C.CString({})", c_name, arg_name)?;
+                writeln!(out, "\tdefer C.free(unsafe.Pointer({}))", c_name)?;
+            }
+        }
+        write!(out, "\t{}C.{}(",
+            if ret_ty.is_empty() { "" } else { "cRet := " },
+            name,
+        )?;
+        for (i, (arg_name, conv, c_ty, _)) in params.iter().enumerate() {
+            let c_arg = match *conv {
+                | Conversion::String => {
+                    format!("c{}", Case::PascalCase.apply(arg_name))
+                },
+                | Conversion::Bytes => format!(
+                    "{}{{ptr: cBytesPtr({arg}), len: C.size_t(len({arg}))}}",
+                    cgo_ty(c_ty), arg = arg_name,
+                ),
+                | Conversion::Layout => format!(
+                    "*(*{})(unsafe.Pointer(&{}))",
+                    cgo_ty(c_ty), arg_name,
+                ),
+            };
+            write!(out, "{}\n\t\t{}", if i == 0 { "" } else { "," }, c_arg)?;
+        }
+        if params.is_empty().not() {
+            out.write_all(b",\n\t")?;
+        }
+        out.write_all(b")\n")?;
+        match ret_conversion {
+            | _ if ret_ty.is_empty() => {},
+            | Conversion::String => {
+                writeln!(out, "\treturn C.GoString(cRet)")?;
+            },
+            | Conversion::Bytes => {
+                writeln!(out,
+                    "\treturn C.GoBytes(unsafe.Pointer(cRet.ptr), C.int(cRet.len))",
+                )?;
+            },
+            | Conversion::Layout => {
+                writeln!(out, "\treturn *(*{})(unsafe.Pointer(&cRet))", ret_ty)?;
+            },
+        }
+        out.write_all(b"}\n\n")
+    }
+
+    // Declared by the C header of the cgo preamble.
+    fn declare_static (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        _: &'_ [&'_ str],
+        name: &'_ str,
+        _: &'_ TypeDesc,
+    ) -> io::Result<()>
+    {
+        write!(definer.out(),
+            "// `{}`: exported statics are only reachable as `C.{}`.\n\n",
+            name, name,
+        )
+    }
+}
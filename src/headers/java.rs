@@ -0,0 +1,296 @@
+//! The Java (JNA) backend: see [`Language::Java`].
+
+use super::*;
+use ::std::borrow::Cow;
+
+/// The Java name of a field or parameter.
+fn escape (name: &'_ str)
+  -> Cow<'_, str>
+{
+    let name = name.trim_start_matches("r#");
+    if keywords::is_java_keyword(name) {
+        Cow::Owned(format!("{}_", name))
+    } else {
+        Cow::Borrowed(name)
+    }
+}
+
+/// The Java (JNA) [`Backend`].
+pub(in super)
+struct Java;
+
+impl Backend for Java {
+    /// Emits the `package`, the `import`s, and the opening of the `Ffi`
+    /// interface, which loads the native library.
+    fn prologue (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        config: &'_ Builder<'_, WhereTo>,
+    ) -> io::Result<()>
+    {
+        let out = definer.out();
+        if let Some(package) = config.java_package {
+            if !package.split('.').all(is_c_identifier) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Invalid Java package: `{}`", package),
+                ));
+            }
+            write!(out, "package {};\n\n", package)?;
+        }
+        write!(out,
+            concat!(
+                "import com.sun.jna.*;\n",
+                "\n",
+                "public interface Ffi extends Library {{\n",
+                "    Ffi INSTANCE = Native.load(\"{}\", Ffi.class);\n",
+                "\n",
+            ),
+            backend::dll_name(config).escape_default(),
+        )
+    }
+
+    /// Closes the `Ffi` interface.
+    fn epilogue (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        _: &'_ Builder<'_, WhereTo>,
+    ) -> io::Result<()>
+    {
+        definer.out().write_all(b"}\n")
+    }
+
+    // Java integers being signed, the unsigned ones are mapped to the signed
+    // integers of the same size.
+    fn primitive_ty (self: &'_ Self, c_short_name: &'_ str)
+      -> &'static str
+    {
+        match c_short_name {
+            | "uint8" | "int8" => "byte",
+            | "uint16" | "int16" => "short",
+            | "uint32" | "int32" => "int",
+            | "uint64" | "int64" => "long",
+            | "size" | "ssize" => "long",
+            | "float" => "float",
+            | "double" => "double",
+            | "bool" => "byte",
+            | "char" => "byte",
+            | "void" => "void",
+            | _ => unreachable!("`{}` is not a primitive type", c_short_name),
+        }
+    }
+
+    fn pointer_ty (self: &'_ Self, _: &'_ TypeDesc, _: bool)
+      -> String
+    {
+        "Pointer".into()
+    }
+
+    // The pointee need not be defined, since it is not spelled out.
+    fn define_pointer (self: &'_ Self, _: &'_ mut dyn Definer, _: &'_ TypeDesc)
+      -> io::Result<()>
+    {
+        Ok(())
+    }
+
+    fn fn_ptr_ty (self: &'_ Self, _: &'_ TypeDesc, _: &'_ [TypeDesc], _: bool)
+      -> String
+    {
+        "Pointer".into()
+    }
+
+    fn define_bytes (self: &'_ Self, definer: &'_ mut dyn Definer, me: &'_ TypeDesc)
+      -> io::Result<()>
+    {
+        let ref name = me.ty();
+        definer.define_once(name, &mut |definer| {
+            write!(definer.out(),
+                concat!(
+                    "    /** \\remark Has the same layout as `{}` **/\n",
+                    "    @Structure.FieldOrder({{\"bytes\"}})\n",
+                    "    public static class {} extends Structure",
+                    " implements Structure.ByValue {{\n",
+                    "        public byte[] bytes = new byte[{}];\n",
+                    "    }}\n\n",
+                ),
+                me.c_var(),
+                name,
+                me.size,
+            )
+        })
+    }
+
+    fn define_struct (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        me: &'_ TypeDesc,
+        docs: &'_ [&'_ str],
+        fields: &'_ [Field<'_>],
+    ) -> io::Result<()>
+    {
+        define_aggregate(definer.out(), me, docs, fields, "Structure")
+    }
+
+    fn define_union (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        me: &'_ TypeDesc,
+        docs: &'_ [&'_ str],
+        fields: &'_ [Field<'_>],
+    ) -> io::Result<()>
+    {
+        define_aggregate(definer.out(), me, docs, fields, "Union")
+    }
+
+    // The variants are the constants of an eponymous interface.
+    fn enum_ty (self: &'_ Self, _: &'_ str, int: &'_ TypeDesc)
+      -> String
+    {
+        int.ty()
+    }
+
+    fn define_enum (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        me: &'_ TypeDesc,
+        docs: &'_ [&'_ str],
+        int: &'_ TypeDesc,
+        variants: &'_ mut [Variant<'_>],
+    ) -> io::Result<()>
+    {
+        let out = definer.out();
+        backend::c_docs(out, "    ", docs)?;
+        writeln!(out, "    public interface {} {{",
+            __type_name__(&me.c_short_name()),
+        )?;
+        constants(out, &int.ty(), variants)?;
+        writeln!(out, "    }}\n")
+    }
+
+    // Only ever used behind a `Pointer`.
+    fn define_opaque (self: &'_ Self, _: &'_ mut dyn Definer, _: &'_ TypeDesc)
+      -> io::Result<()>
+    {
+        Ok(())
+    }
+
+    fn declare_fn (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        docs: &'_ [&'_ str],
+        name: &'_ str,
+        ret: &'_ TypeDesc,
+        params: &'_ [(&'_ str, TypeDesc)],
+    ) -> io::Result<()>
+    {
+        let out = definer.out();
+        if docs.is_empty().not() {
+            out.write_all(b"    /**\n")?;
+            for line in docs {
+                writeln!(out, "     *{}", line.replace("*/", "*&#47;"))?;
+            }
+            out.write_all(b"     */\n")?;
+        }
+        // JNA binds the methods to the symbols of the same name.
+        write!(out, "    {} {} (", ret.ty(), name)?;
+        for (i, &(param_name, ref param_ty)) in params.iter().enumerate() {
+            // `_` is not a valid name.
+            let param_name = if param_name == "_" {
+                Cow::Owned(format!("_{}", i))
+            } else {
+                escape(param_name)
+            };
+            write!(out,
+                "{comma}\n        {} {}",
+                param_ty.ty(), param_name,
+                comma = if i == 0 { "" } else { "," },
+            )?;
+        }
+        out.write_all(b");\n\n")
+    }
+
+    // A JNA `Library` only binds functions.
+    fn declare_static (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        _: &'_ [&'_ str],
+        name: &'_ str,
+        _: &'_ TypeDesc,
+    ) -> io::Result<()>
+    {
+        write!(definer.out(),
+            "    /* `{}`: exported statics cannot be bound through JNA */\n\n",
+            name,
+        )
+    }
+}
+
+/// Emits the definition of a struct or of a union, as a subclass of
+/// `superclass` (`Structure` or `Union`).
+fn define_aggregate (
+    out: &'_ mut dyn io::Write,
+    me: &'_ TypeDesc,
+    docs: &'_ [&'_ str],
+    fields: &'_ [Field<'_>],
+    superclass: &'_ str,
+) -> io::Result<()>
+{
+    backend::c_docs(out, "    ", docs)?;
+    // JNA needs the fields, in order.
+    let field_names: Vec<String> =
+        fields
+            .iter()
+            .map(|field| format!("\"{}\"", escape(field.name)))
+            .collect()
+    ;
+    writeln!(out,
+        concat!(
+            "    @Structure.FieldOrder({{{}}})\n",
+            "    public static class {} extends {} ",
+            "implements Structure.ByValue {{",
+        ),
+        field_names.join(", "),
+        me.ty(),
+        superclass,
+    )?;
+    for field in fields {
+        backend::c_docs(out, "        ", field.docs)?;
+        writeln!(out, "        public {} {};\n",
+            field.ty.ty(), escape(field.name),
+        )?;
+    }
+    writeln!(out, "    }}\n")
+}
+
+/// Emits the constants standing for the variants of an enum, of the Java
+/// type `ty` (with the same bit-pattern, Java integers being signed).
+fn constants (
+    out: &'_ mut dyn io::Write,
+    ty: &'_ str,
+    variants: &'_ mut [Variant<'_>],
+) -> io::Result<()>
+{
+    if context::context().variant_order == VariantOrder::Value {
+        // Stable sort: same-valued variants remain in declaration order.
+        variants.sort_by_key(|variant| variant.value);
+    }
+    for variant in variants {
+        let (value, name) = (variant.value as i64, variant.name);
+        let fits = match ty {
+            | "byte" => value as i8 as i64 == value,
+            | "short" => value as i16 as i64 == value,
+            | "int" => value as i32 as i64 == value,
+            | _ => true,
+        };
+        let suffix = if value as i32 as i64 == value { "" } else { "L" };
+        if fits {
+            writeln!(out, "        {} {} = {}{};", ty, name, value, suffix)?;
+        } else {
+            // Out of range (unsigned) values: same bit-pattern.
+            writeln!(out, "        {} {} = ({}) {}{};",
+                ty, name, ty, value, suffix,
+            )?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,142 @@
+/// The keywords of C (up to C11), which cannot be used as identifiers.
+const C_KEYWORDS: &[&str] = &[
+    "auto", "break", "case", "char", "const", "continue", "default", "do",
+    "double", "else", "enum", "extern", "float", "for", "goto", "if",
+    "inline", "int", "long", "register", "restrict", "return", "short",
+    "signed", "sizeof", "static", "struct", "switch", "typedef", "union",
+    "unsigned", "void", "volatile", "while", "_Alignas", "_Alignof",
+    "_Atomic", "_Bool", "_Complex", "_Generic", "_Imaginary", "_Noreturn",
+    "_Static_assert", "_Thread_local",
+];
+
+/// The keywords of C++ which are not C keywords, since the generated headers
+/// are also meant to be `#include`d from C++.
+const CXX_KEYWORDS: &[&str] = &[
+    "alignas", "alignof", "and", "and_eq", "asm", "bitand", "bitor", "bool",
+    "catch", "char16_t", "char32_t", "class", "compl", "concept",
+    "const_cast", "consteval", "constexpr", "constinit", "co_await",
+    "co_return", "co_yield", "decltype", "delete", "dynamic_cast",
+    "explicit", "export", "false", "friend", "mutable", "namespace", "new",
+    "noexcept", "not", "not_eq", "nullptr", "operator", "or", "or_eq",
+    "private", "protected", "public", "reinterpret_cast", "requires",
+    "static_assert", "static_cast", "template", "this", "thread_local",
+    "throw", "true", "try", "typeid", "typename", "using", "virtual",
+    "wchar_t", "xor", "xor_eq",
+];
+
+/// Whether `name` cannot be used as an identifier in a C (or C++) header.
+pub(in crate)
+fn is_c_keyword (name: &'_ str)
+  -> bool
+{
+    C_KEYWORDS.contains(&name) || CXX_KEYWORDS.contains(&name)
+}
+
+/// The (reserved) keywords of C#, which can only be used as identifiers when
+/// prefixed with `@`.
+const CSHARP_KEYWORDS: &[&str] = &[
+    "abstract", "as", "base", "bool", "break", "byte", "case", "catch",
+    "char", "checked", "class", "const", "continue", "decimal", "default",
+    "delegate", "do", "double", "else", "enum", "event", "explicit",
+    "extern", "false", "finally", "fixed", "float", "for", "foreach", "goto",
+    "if", "implicit", "in", "int", "interface", "internal", "is", "lock",
+    "long", "namespace", "new", "null", "object", "operator", "out",
+    "override", "params", "private", "protected", "public", "readonly",
+    "ref", "return", "sbyte", "sealed", "short", "sizeof", "stackalloc",
+    "static", "string", "struct", "switch", "this", "throw", "true", "try",
+    "typeof", "uint", "ulong", "unchecked", "unsafe", "ushort", "using",
+    "virtual", "void", "volatile", "while",
+];
+
+/// Whether `name` cannot be used as is as an identifier in C#.
+pub(in crate)
+fn is_csharp_keyword (name: &'_ str)
+  -> bool
+{
+    CSHARP_KEYWORDS.contains(&name)
+}
+
+/// The (reserved) keywords and literals of Java, which cannot be used as
+/// identifiers.
+const JAVA_KEYWORDS: &[&str] = &[
+    "abstract", "assert", "boolean", "break", "byte", "case", "catch",
+    "char", "class", "const", "continue", "default", "do", "double", "else",
+    "enum", "extends", "false", "final", "finally", "float", "for", "goto",
+    "if", "implements", "import", "instanceof", "int", "interface", "long",
+    "native", "new", "null", "package", "private", "protected", "public",
+    "return", "short", "static", "strictfp", "super", "switch",
+    "synchronized", "this", "throw", "throws", "transient", "true", "try",
+    "void", "volatile", "while",
+];
+
+/// Whether `name` cannot be used as is as an identifier in Java.
+pub(in crate)
+fn is_java_keyword (name: &'_ str)
+  -> bool
+{
+    JAVA_KEYWORDS.contains(&name)
+}
+
+/// The keywords of Go, which cannot be used as identifiers.
+const GO_KEYWORDS: &[&str] = &[
+    "break", "case", "chan", "const", "continue", "default", "defer", "else",
+    "fallthrough", "for", "func", "go", "goto", "if", "import", "interface",
+    "map", "package", "range", "return", "select", "struct", "switch", "type",
+    "var",
+];
+
+/// Whether `name` cannot be used as is as an identifier in Go.
+pub(in crate)
+fn is_go_keyword (name: &'_ str)
+  -> bool
+{
+    GO_KEYWORDS.contains(&name)
+}
+
+/// The keywords of Zig, and the names of its primitive types and values,
+/// which cannot be used as identifiers (unless `@"..."`-quoted).
+const ZIG_KEYWORDS: &[&str] = &[
+    "addrspace", "align", "allowzero", "and", "anyerror", "anyframe",
+    "anyopaque", "anytype", "asm", "async", "await", "bool", "break",
+    "c_char", "c_int", "c_long", "c_longdouble", "c_longlong", "c_short",
+    "c_uint", "c_ulong", "c_ulonglong", "c_ushort", "callconv", "catch",
+    "comptime", "comptime_float", "comptime_int", "const", "continue",
+    "defer", "else", "enum", "errdefer", "error", "export", "extern", "f128",
+    "f16", "f32", "f64", "f80", "false", "fn", "for", "if", "inline",
+    "isize", "linksection", "noalias", "noinline", "noreturn", "nosuspend",
+    "null", "opaque", "or", "orelse", "packed", "pub", "resume", "return",
+    "struct", "suspend", "switch", "test", "threadlocal", "true", "try",
+    "type", "undefined", "union", "unreachable", "usingnamespace", "usize",
+    "var", "void", "volatile", "while",
+];
+
+/// Whether `name` cannot be used as is as an identifier in Zig.
+pub(in crate)
+fn is_zig_keyword (name: &'_ str)
+  -> bool
+{
+    // The arbitrary bit-width integer types, _e.g._, `u8` or `i7`.
+    let is_int_type =
+        name.len() > 1
+        && name.starts_with(&['i', 'u'][..])
+        && name[1 ..].bytes().all(|b| b.is_ascii_digit())
+    ;
+    is_int_type || ZIG_KEYWORDS.contains(&name)
+}
+
+/// The (hard) keywords of Kotlin, which cannot be used as identifiers
+/// (unless `` `...` ``-quoted).
+const KOTLIN_KEYWORDS: &[&str] = &[
+    "as", "break", "class", "continue", "do", "else", "false", "for", "fun",
+    "if", "in", "interface", "is", "null", "object", "package", "return",
+    "super", "this", "throw", "true", "try", "typealias", "typeof", "val",
+    "var", "when", "while",
+];
+
+/// Whether `name` cannot be used as is as an identifier in Kotlin.
+pub(in crate)
+fn is_kotlin_keyword (name: &'_ str)
+  -> bool
+{
+    KOTLIN_KEYWORDS.contains(&name)
+}
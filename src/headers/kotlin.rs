@@ -0,0 +1,232 @@
+//! The Kotlin/Native outputs: the `.def` file of the `cinterop` tool, and
+//! the `expect` / `actual` declarations of the exported functions.
+
+use super::*;
+use ::std::borrow::Cow;
+
+impl<'__> Builder<'__, WhereTo> {
+    /// Generates, in memory, the `.def` file the Kotlin/Native `cinterop`
+    /// tool needs to bind the C header (`c_header`, such as `"mylib.h"`):
+    ///
+    /// ```properties
+    /// headers = mylib.h
+    /// headerFilter = mylib.h
+    /// package = mylib
+    /// linkerOpts = -lmylib
+    /// ```
+    ///
+    /// with the [package][`Builder::with_kotlin_package`] and the
+    /// [library][`Builder::with_dll_name`] defaulting to the name of the
+    /// crate (with `-`s replaced by `_`s).
+    ///
+    /// The `headerFilter` keeps the declarations of the `#include`d headers
+    /// (such as `<stdint.h>`) out of the package.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main () -> ::std::io::Result<()> { Ok({
+    /// let def =
+    ///     ::safer_ffi::headers::builder()
+    ///         .with_kotlin_package("com.example.mylib")
+    ///         .generate_kotlin_def("mylib.h")?
+    /// ;
+    /// ::std::fs::write("src/nativeInterop/cinterop/mylib.def", def)?;
+    /// # })}
+    /// ```
+    pub
+    fn generate_kotlin_def (self, c_header: &'_ str)
+      -> io::Result<String>
+    {
+        let crate_name = env::var("CARGO_PKG_NAME").unwrap().replace('-', "_");
+        let package = kotlin_package(self.kotlin_package, &crate_name)?;
+        let dll_name = self.dll_name.unwrap_or(&crate_name);
+        Ok(format!(
+            concat!(
+                "# File auto-generated by `::safer_ffi`.\n",
+                "# Do not manually edit this file.\n",
+                "\n",
+                "headers = {header}\n",
+                "headerFilter = {header}\n",
+                "package = {}\n",
+                "linkerOpts = -l{}\n",
+            ),
+            package,
+            dll_name,
+            header = c_header,
+        ))
+    }
+
+    /// Generates, in memory, Kotlin `expect` declarations of the exported
+    /// functions, for the common code of a multiplatform project, and the
+    /// `actual` definitions calling the `cinterop` bindings (see
+    /// [`.generate_kotlin_def()`][`Builder::generate_kotlin_def`]), for its
+    /// native code, both in the given `package` (which must differ from the
+    /// `cinterop` one).
+    ///
+    /// Only the functions whose parameters and return value are integers,
+    /// floats, `bool`s (or C strings, as parameters) are expressible in
+    /// common code: the other ones are listed as comments. `size_t` and
+    /// `ssize_t` are mapped to `ULong` and `Long`, which assumes a 64-bit
+    /// target.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main () -> ::std::io::Result<()> { Ok({
+    /// let (expect, actual) =
+    ///     ::safer_ffi::headers::builder()
+    ///         .with_kotlin_package("com.example.mylib.cinterop")
+    ///         .generate_kotlin_expect_actual("com.example.mylib")?
+    /// ;
+    /// ::std::fs::write("src/commonMain/kotlin/MyLib.kt", expect)?;
+    /// ::std::fs::write("src/nativeMain/kotlin/MyLib.kt", actual)?;
+    /// # })}
+    /// ```
+    ///
+    /// yields, for `fn add (x: i32, y: i32) -> i32`:
+    ///
+    /// ```kotlin
+    /// expect fun add(x: Int, y: Int): Int
+    /// ```
+    ///
+    /// and:
+    ///
+    /// ```kotlin
+    /// actual fun add(x: Int, y: Int): Int =
+    ///     com.example.mylib.cinterop.add(x, y)
+    /// ```
+    pub
+    fn generate_kotlin_expect_actual (self, package: &'_ str)
+      -> io::Result<(String, String)>
+    {
+        if matches!(self.language, Some(it) if it != Language::C) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Kotlin/Native bindings are only supported for C",
+            ));
+        }
+        let crate_name = env::var("CARGO_PKG_NAME").unwrap().replace('-', "_");
+        let cinterop_package = kotlin_package(self.kotlin_package, &crate_name)?;
+        let package = kotlin_package(Some(package), "")?;
+        if package == cinterop_package {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("`{}` is already the `cinterop` package", package),
+            ));
+        }
+        let report = self.to_writer(io::sink()).generate()?;
+
+        let prologue = format!(
+            concat!(
+                "// File auto-generated by `::safer_ffi`.\n",
+                "// Do not manually edit this file.\n",
+                "\n",
+                "package {}\n",
+                "\n",
+            ),
+            package,
+        );
+        let mut expect = prologue.clone();
+        let mut actual = prologue;
+        for signature in &report.signatures {
+            let header = match fun_header(signature) {
+                | Ok(it) => it,
+                | Err(c_type) => {
+                    expect.push_str(&format!(
+                        "// `{}`: not expressible in common code (`{}`)\n\n",
+                        signature.name, c_type,
+                    ));
+                    continue;
+                },
+            };
+            expect.push_str(&format!("expect {}\n\n", header));
+            actual.push_str(&format!(
+                "actual {} =\n    {}.{}({})\n\n",
+                header,
+                cinterop_package,
+                escape(&signature.name),
+                signature.params
+                    .iter()
+                    .map(|param| escape(&param.0))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ));
+        }
+        for file in &mut [&mut expect, &mut actual] {
+            let len = file.trim_end().len();
+            file.truncate(len);
+            file.push('\n');
+        }
+        Ok((expect, actual))
+    }
+}
+
+/// The (validated) package, defaulting to `default`.
+fn kotlin_package<'s> (package: Option<&'s str>, default: &'s str)
+  -> io::Result<&'s str>
+{
+    let package = package.unwrap_or(default);
+    if package.split('.').all(is_c_identifier).not() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Invalid Kotlin package: `{}`", package),
+        ));
+    }
+    Ok(package)
+}
+
+/// The Kotlin name of a function or parameter.
+fn escape (name: &'_ str)
+  -> Cow<'_, str>
+{
+    let name = name.trim_start_matches("r#");
+    if keywords::is_kotlin_keyword(name) {
+        Cow::Owned(format!("`{}`", name))
+    } else {
+        Cow::Borrowed(name)
+    }
+}
+
+/// The Kotlin type of a parameter (or of the returned value), as seen by
+/// common code, given its C type.
+fn kotlin_ty (c_type: &'_ str, is_ret: bool)
+  -> Option<&'static str>
+{
+    Some(match c_type {
+        | "int8_t" => "Byte",
+        | "uint8_t" => "UByte",
+        | "int16_t" => "Short",
+        | "uint16_t" => "UShort",
+        | "int32_t" => "Int",
+        | "uint32_t" => "UInt",
+        | "int64_t" | "ssize_t" => "Long",
+        | "uint64_t" | "size_t" => "ULong",
+        | "float" | "_Float32" => "Float",
+        | "double" | "_Float64" => "Double",
+        | "bool" => "Boolean",
+        // `cinterop` converts the `String` arguments, but not the returned
+        // pointers (whose ownership is unknown).
+        | "char const *" if is_ret.not() => "String",
+        | "void" if is_ret => "Unit",
+        | _ => return None,
+    })
+}
+
+/// `fun name(params): Ret`, or the first C type without a Kotlin
+/// counterpart.
+fn fun_header (signature: &'_ Signature)
+  -> Result<String, &'_ str>
+{
+    let mut params = Vec::with_capacity(signature.params.len());
+    for (name, c_type) in &signature.params {
+        let ty = kotlin_ty(c_type, false).ok_or(&**c_type)?;
+        params.push(format!("{}: {}", escape(name), ty));
+    }
+    let ret = kotlin_ty(&signature.ret, true).ok_or(&*signature.ret)?;
+    Ok(format!("fun {}({}){}",
+        escape(&signature.name),
+        params.join(", "),
+        if ret == "Unit" { String::new() } else { format!(": {}", ret) },
+    ))
+}
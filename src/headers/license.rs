@@ -0,0 +1,63 @@
+use super::*;
+
+/// The lines of the license header, regardless of the comment syntax of the
+/// output language.
+///
+/// A single-line license without `:` is deemed to be an SPDX license
+/// expression (_e.g._, `MIT OR Apache-2.0`), and gets the
+/// `SPDX-License-Identifier: ` prefix scanners look for.
+pub(in crate)
+fn lines (license: &'_ str)
+  -> Vec<String>
+{
+    let license = license.trim();
+    if license.lines().nth(1).is_none() && license.contains(':').not() {
+        vec![format!("SPDX-License-Identifier: {}", license)]
+    } else {
+        license
+            .lines()
+            .map(|line| line.trim_end().to_owned())
+            .collect()
+    }
+}
+
+/// `/* ... */`-style comment, with SPDX identifiers on a single line as
+/// expected by the SPDX specification for C files.
+pub(in super)
+fn c_comment (lines: &'_ [String])
+  -> String
+{
+    match lines {
+        | [line] => format!("/* {} */", line),
+        | _ => {
+            let mut ret = String::from("/*\n");
+            lines.iter().for_each(|line| {
+                if line.is_empty() {
+                    ret.push_str(" *\n");
+                } else {
+                    ret.push_str(" * ");
+                    ret.push_str(line);
+                    ret.push('\n');
+                }
+            });
+            ret.push_str(" */");
+            ret
+        },
+    }
+}
+
+/// `// ...`-style comment, for the languages without block comments.
+pub(in super)
+fn line_comment (lines: &'_ [String])
+  -> String
+{
+    lines
+        .iter()
+        .map(|line| if line.is_empty() {
+            "//".to_owned()
+        } else {
+            format!("// {}", line)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
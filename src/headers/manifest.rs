@@ -0,0 +1,200 @@
+use super::*;
+
+impl<'__> Builder<'__, WhereTo> {
+    /// Generates the headers in memory, and returns a JSON description of
+    /// the exported API, for dynamic-language hosts to discover and bind it:
+    ///
+    /// ```json
+    /// {
+    ///   "header": "/*! \\file */\n...",
+    ///   "symbols": [
+    ///     { "name": "rust_concat", "kind": "function" },
+    ///     ...
+    ///   ]
+    /// }
+    /// ```
+    ///
+    ///   - `"header"` is the contents of the generated header;
+    ///
+    ///   - `"symbols"` are the emitted items, in order of emission, with a
+    ///     `"kind"` among `"function"`, `"static"`, `"type"`, `"macro"` and
+    ///     `"include"`, and, for the types annotated with
+    ///     `#[repr_c(since = ...)]` (or with fields annotated so), a
+    ///     `"since"` version and / or a `"fields_since"` object mapping those
+    ///     fields to their version (_e.g._, `{ "timeout_ms": "1.3" }`).
+    ///
+    /// See [`ffi_export_describe_api!`] to export it from the library itself.
+    ///
+    /// [`ffi_export_describe_api!`]: `crate::ffi_export_describe_api`
+    pub
+    fn generate_manifest (self)
+      -> io::Result<String>
+    {
+        let mut header = Vec::new();
+        let report = self.to_writer(&mut header).generate()?;
+        let header =
+            String::from_utf8(header)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?
+        ;
+        let mut json = String::from("{\n");
+        json.push_str(&format!("  \"header\": {},\n", json_string(&header)));
+        json.push_str("  \"symbols\": [");
+        for (i, symbol) in report.symbols.iter().enumerate() {
+            let mut since = String::new();
+            if let Some(ref version) = symbol.since {
+                since.push_str(&format!(", \"since\": {}", json_string(version)));
+            }
+            if symbol.fields_since.is_empty().not() {
+                since.push_str(", \"fields_since\": {");
+                for (j, (field, version)) in
+                    symbol.fields_since.iter().enumerate()
+                {
+                    since.push_str(&format!(
+                        "{} {}: {}",
+                        if j == 0 { "" } else { "," },
+                        json_string(field),
+                        json_string(version),
+                    ));
+                }
+                since.push_str(" }");
+            }
+            json.push_str(&format!(
+                "{}\n    {{ \"name\": {}, \"kind\": \"{}\"{} }}",
+                if i == 0 { "" } else { "," },
+                json_string(&symbol.name),
+                match symbol.kind {
+                    | SymbolKind::Function => "function",
+                    | SymbolKind::Static => "static",
+                    | SymbolKind::Type => "type",
+                    | SymbolKind::Macro => "macro",
+                    | SymbolKind::Include => "include",
+                    | SymbolKind::DocGroup => "doc_group",
+                },
+                since,
+            ));
+        }
+        json.push_str(if report.symbols.is_empty() { "]\n}\n" } else { "\n  ]\n}\n" });
+        Ok(json)
+    }
+}
+
+/// `s` as a JSON string literal.
+fn json_string (s: &'_ str)
+  -> String
+{
+    let mut ret = String::with_capacity(s.len() + 2);
+    ret.push('"');
+    for c in s.chars() {
+        match c {
+            | '"' => ret.push_str("\\\""),
+            | '\\' => ret.push_str("\\\\"),
+            | '\n' => ret.push_str("\\n"),
+            | '\r' => ret.push_str("\\r"),
+            | '\t' => ret.push_str("\\t"),
+            | _ if (c as u32) < 0x20 => ret.push_str(&format!("\\u{:04x}", c as u32)),
+            | _ => ret.push(c),
+        }
+    }
+    ret.push('"');
+    ret
+}
+
+/// Lazily computes, and then caches (for the lifetime of the program), the
+/// manifest returned by an [`ffi_export_describe_api!`]-exported function.
+///
+/// [`ffi_export_describe_api!`]: `crate::ffi_export_describe_api`
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __describe_api__ (
+    cache: &'static ::core::sync::atomic::AtomicPtr<::std::os::raw::c_char>,
+    manifest: impl FnOnce() -> io::Result<String>,
+) -> Option<crate::char_p::char_p_ref<'static>>
+{
+    use ::core::sync::atomic::Ordering;
+    use ::std::ffi::{CStr, CString};
+
+    let mut ptr = cache.load(Ordering::Acquire);
+    if ptr.is_null() {
+        // JSON strings have their control characters (thus nul) escaped.
+        let new = CString::new(manifest().ok()?).ok()?.into_raw();
+        ptr = match cache.compare_exchange(
+            ::core::ptr::null_mut(), new, Ordering::AcqRel, Ordering::Acquire,
+        )
+        {
+            | Ok(_) => new,
+            | Err(winner) => {
+                drop(unsafe {
+                    // Safety: `new` comes from `into_raw` and has not been
+                    // shared.
+                    CString::from_raw(new)
+                });
+                winner
+            },
+        };
+    }
+    Some(unsafe {
+        // Safety: the cached pointer is a leaked (thus `'static`) `CString`.
+        CStr::from_ptr(ptr)
+    }.into())
+}
+
+/// Exports a `char const * <name> (void)` function returning the JSON
+/// description of the exported API (see [`Builder::generate_manifest`]), so
+/// that dynamic-language hosts can discover and bind the API at runtime,
+/// without shipping the header separately.
+///
+/// ```rust,ignore
+/// ::safer_ffi::ffi_export_describe_api!(mylib_describe_api);
+///
+/// // or, with a custom configuration of the generated header:
+/// ::safer_ffi::ffi_export_describe_api!(mylib_describe_api =>
+///     ::safer_ffi::headers::builder()
+///         .with_guard("__MYLIB_H__")
+/// );
+/// ```
+///
+/// The manifest is only compiled into the library when the `"headers"`
+/// feature of `::safer_ffi` is enabled (otherwise, this macro expands to
+/// nothing). It is generated on the first call, and then cached until the
+/// library is unloaded. The function returns `NULL` if the generation fails.
+///
+/// [`Builder::generate_manifest`]: `crate::headers::Builder::generate_manifest`
+#[macro_export]
+macro_rules! ffi_export_describe_api {
+    (
+        $name:ident $(,)?
+    ) => (
+        $crate::ffi_export_describe_api!($name =>
+            $crate::headers::builder().with_guard(
+                &$crate::std::format!("__RUST_{}__",
+                    $crate::core::env!("CARGO_PKG_NAME").to_ascii_uppercase(),
+                ),
+            )
+        );
+    );
+
+    (
+        $name:ident => $builder:expr $(,)?
+    ) => (
+        $crate::__cfg_headers__! {
+            #[$crate::ffi_export]
+            /// Returns the JSON description of the exported API, or `NULL`
+            /// on error.
+            ///
+            /// The returned string is valid until the library is unloaded,
+            /// and must not be freed.
+            pub
+            fn $name ()
+              -> $crate::core::option::Option<$crate::prelude::char_p::Ref<'static>>
+            {
+                static CACHE: $crate::core::sync::atomic::AtomicPtr<
+                    $crate::std::os::raw::c_char,
+                > = $crate::core::sync::atomic::AtomicPtr::new(
+                    0 as *mut _,
+                );
+                $crate::headers::__describe_api__(&CACHE, || {
+                    $builder.generate_manifest()
+                })
+            }
+        }
+    );
+}
@@ -0,0 +1,260 @@
+//! The Markdown API reference: see [`Builder::generate_markdown`].
+
+use super::*;
+
+impl<'__> Builder<'__, WhereTo> {
+    /// Generates a Markdown (CommonMark, and thus mdBook-compatible) API
+    /// reference of the exported items, as an alternative to running Doxygen
+    /// on the C headers.
+    ///
+    /// The items are listed by kind (constants, types, globals, and
+    /// functions), in the order of the headers, each one with its doc comment
+    /// (and its deprecation note, if any) and its C declaration (for the
+    /// types, their full definition).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main () -> ::std::io::Result<()> { Ok({
+    /// let reference = ::safer_ffi::headers::builder().generate_markdown()?;
+    /// ::std::fs::write("book/src/api.md", reference)?;
+    /// # })}
+    /// ```
+    ///
+    /// yields, for a `point_new` function:
+    ///
+    /// ````text
+    /// ## Functions
+    ///
+    /// ### `point_new`
+    ///
+    /// Creates a point.
+    ///
+    /// ```c
+    /// Point_t point_new (
+    ///     double x,
+    ///     double y);
+    /// ```
+    /// ````
+    pub
+    fn generate_markdown (self)
+      -> io::Result<String>
+    {
+        let mut header = Vec::new();
+        Builder {
+            emit_guard: Some(false),
+            export_macro: None,
+            language: Some(Language::C),
+            swift_annotations: None,
+            contract_macros: None,
+            .. self
+        }
+            .to_writer(&mut header)
+            .generate()?
+        ;
+        let header =
+            String::from_utf8(header)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?
+        ;
+        let header = cffi::strip_preprocessor(&header, &|_name, _value| true);
+        Ok(render(&items(&header)))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ItemKind {
+    Constant,
+    Type,
+    Global,
+    Function,
+}
+
+struct Item {
+    kind: ItemKind,
+    name: String,
+    docs: Vec<String>,
+    /// The note of the `#[deprecated]` item.
+    deprecated: Option<String>,
+    code: String,
+}
+
+/// The documented items of the (preprocessed) header.
+fn items (header: &'_ str)
+  -> Vec<Item>
+{
+    let mut items = vec![];
+    let mut docs = vec![];
+    let mut in_comment = false;
+    let mut in_doc = false;
+    let mut code = String::new();
+    let mut depth = 0_isize;
+    for line in header.lines() {
+        let trimmed = line.trim();
+        if code.is_empty() {
+            if in_comment.not() && trimmed.starts_with("/*") {
+                in_comment = true;
+                in_doc = trimmed.starts_with("/**");
+            }
+            if in_comment {
+                if in_doc {
+                    docs.extend(doc_line(trimmed));
+                }
+                in_comment = trimmed.ends_with("*/").not();
+                continue;
+            }
+            // The doc comments of the `\defgroup`s, _e.g._, are not followed
+            // by an item.
+            if trimmed.is_empty() {
+                docs.clear();
+                continue;
+            }
+        }
+        code.push_str(line);
+        code.push('\n');
+        depth += line.matches('{').count() as isize;
+        depth -= line.matches('}').count() as isize;
+        let is_define = code.starts_with("#define ");
+        if is_define || (depth == 0 && trimmed.ends_with(';')) {
+            let (stripped, deprecated) = deprecated::strip_deprecated(&code);
+            code = stripped;
+            let kind = if is_define {
+                ItemKind::Constant
+            } else if code.starts_with("typedef ") {
+                ItemKind::Type
+            } else if code.starts_with("extern ") {
+                ItemKind::Global
+            } else {
+                ItemKind::Function
+            };
+            items.extend(item_name(kind, &code).map(|name| Item {
+                kind,
+                name,
+                docs: mem::take(&mut docs),
+                deprecated,
+                code: mem::take(&mut code),
+            }));
+            docs.clear();
+            code.clear();
+            depth = 0;
+        }
+    }
+    items
+}
+
+/// The Markdown line of a line of a doc comment, if any.
+fn doc_line (line: &'_ str)
+  -> Option<String>
+{
+    let mut line = line.trim();
+    if line.starts_with("/**") {
+        line = &line[3 ..];
+    } else if line.starts_with('*') && line.starts_with("*/").not() {
+        line = &line[1 ..];
+    }
+    if line.ends_with("*/") {
+        line = line[.. line.len() - 2].trim_end_matches('*');
+    }
+    let line = line.trim_end();
+    // The lines are emitted as ` *  <line>`, with `<line>` usually starting
+    // with a space.
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    let line = &line[indent.min(2) ..];
+    let trimmed = line.trim_start();
+    Some(match trimmed.split(' ').next().unwrap_or("") {
+        | "\\ingroup" | "\\defgroup" | "\\file" => return None,
+        | "\\brief" => trimmed["\\brief".len() ..].trim_start().into(),
+        | "\\remark" => trimmed["\\remark".len() ..].trim_start().into(),
+        | "\\code{.c}" => "```c".into(),
+        | "\\endcode" => "```".into(),
+        | _ => line.into(),
+    })
+}
+
+/// The name of the item declared by `code`.
+fn item_name (kind: ItemKind, code: &'_ str)
+  -> Option<String>
+{
+    let decl = match kind {
+        | ItemKind::Constant => {
+            return code["#define ".len() ..]
+                .split_whitespace()
+                .next()
+                .map(Into::into)
+            ;
+        },
+        | ItemKind::Type => {
+            // The declaration ends with the first top-level `;`.
+            let mut depth = 0;
+            let end =
+                code.char_indices()
+                    .find(|&(_, c)| {
+                        match c {
+                            | '{' => depth += 1,
+                            | '}' => depth -= 1,
+                            | _ => {},
+                        }
+                        c == ';' && depth == 0
+                    })
+                    .map_or(code.len(), |(i, _)| i)
+            ;
+            let decl = &code[.. end];
+            // Function pointer: `typedef Ret (*Name) (Args);`
+            if let Some(start) = decl.find("(*") {
+                return identifiers(&decl[start + 2 ..]).next();
+            }
+            decl
+        },
+        | ItemKind::Global => code,
+        | ItemKind::Function => code.split('(').next()?,
+    };
+    identifiers(decl).last()
+}
+
+fn identifiers (s: &'_ str)
+  -> impl Iterator<Item = String> + '_
+{
+    s   .split(|c: char| (c.is_alphanumeric() || c == '_').not())
+        .filter(|it| it.is_empty().not())
+        .map(Into::into)
+}
+
+fn render (items: &'_ [Item])
+  -> String
+{
+    let mut ret = String::from("# API reference\n");
+    let sections = &[
+        (ItemKind::Constant, "Constants"),
+        (ItemKind::Type, "Types"),
+        (ItemKind::Global, "Globals"),
+        (ItemKind::Function, "Functions"),
+    ];
+    for &(kind, title) in sections {
+        let mut items = items.iter().filter(|it| it.kind == kind).peekable();
+        if items.peek().is_none() {
+            continue;
+        }
+        ret.push_str(&format!("\n## {}\n", title));
+        for item in items {
+            ret.push_str(&format!("\n### `{}`\n\n", item.name));
+            let docs = &item.docs[..];
+            let start = docs.iter().position(|it| it.is_empty().not());
+            let end = docs.iter().rposition(|it| it.is_empty().not());
+            if let (Some(start), Some(end)) = (start, end) {
+                for line in &docs[start ..= end] {
+                    ret.push_str(line);
+                    ret.push('\n');
+                }
+                ret.push('\n');
+            }
+            match item.deprecated.as_ref().map(|it| &it[..]) {
+                | None => {},
+                | Some("") => ret.push_str("**Deprecated.**\n\n"),
+                | Some(note) => {
+                    ret.push_str(&format!("**Deprecated:** {}\n\n", note));
+                },
+            }
+            ret.push_str(&format!("```c\n{}```\n", item.code));
+        }
+    }
+    ret
+}
@@ -0,0 +1,116 @@
+use super::*;
+use crate::sync::FfiMutex;
+
+/// The signature of the functions emitting an export.
+pub(in super)
+type Define = fn(&'_ mut dyn Definer) -> io::Result<()>;
+
+/// The exports registered by the functions generated by
+/// [`register_exports_in!`][`crate::register_exports_in`].
+static REGISTERED: FfiMutex<Vec<Define>> = FfiMutex::new(Vec::new());
+
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __register_export__ (define: Define)
+{
+    if let Ok(mut registered) = REGISTERED.lock() {
+        if registered.iter().all(|&it| it as usize != define as usize) {
+            registered.push(define);
+        }
+    }
+}
+
+/// The exports submitted to the `inventory` (through global constructors),
+/// in definition order, followed by the explicitly registered ones which
+/// are not already among them.
+pub(in super)
+fn exports ()
+  -> Vec<Define>
+{
+    let mut exports =
+        crate::inventory::iter
+            .into_iter()
+            .map(|&crate::FfiExport(define)| define)
+            // Iterate in reverse fashion to more closely match
+            // the Rust definition order.
+            .collect::<Vec<_>>().into_iter().rev()
+            .collect::<Vec<_>>()
+    ;
+    if let Ok(registered) = REGISTERED.lock() {
+        for &define in registered.iter() {
+            if exports.iter().all(|&it| it as usize != define as usize) {
+                exports.push(define);
+            }
+        }
+    }
+    exports
+}
+
+/// What the headers would contain in the current build, _i.e._, with its
+/// `#[cfg]`s and enabled features, without writing them anywhere.
+///
+/// ```rust,no_run
+/// use ::safer_ffi::headers::{self, SymbolKind};
+///
+/// let report = headers::registered_exports().unwrap();
+/// assert!(report.names(SymbolKind::Function).all(|name| !name.starts_with("debug_")));
+/// ```
+pub
+fn registered_exports ()
+  -> io::Result<Report>
+{
+    builder()
+        .with_guard("")
+        .with_emit_guard(false)
+        .to_writer(io::sink())
+        .generate()
+}
+
+/// Defines a function registering the given `#[ffi_export]`ed functions
+/// for the headers generation, without relying on global constructors.
+///
+/// By default, each `#[ffi_export]` registers itself before `main` runs,
+/// through a global constructor. Some environments (_e.g._, `musl` static
+/// builds, or some embedded linkers) strip those, leading to empty headers:
+/// there, the header-generator binary can call the registration function
+/// before generating the headers.
+///
+/// ```rust,ignore
+/// ::safer_ffi::register_exports_in!(register_exports: [
+///     rust_concat,
+///     ffi::rust_free,
+/// ]);
+///
+/// // in the header-generator binary:
+/// my_crate::register_exports();
+/// ::safer_ffi::headers::builder()
+///     .to_file("my_header.h")?
+///     .generate()?;
+/// ```
+///
+/// The exported functions are listed by path, and the types they use are
+/// defined as usual. Registering a function which has also been registered
+/// through a global constructor is harmless.
+///
+/// The function is only defined when the `"headers"` feature of
+/// `::safer_ffi` is enabled.
+#[macro_export]
+macro_rules! register_exports_in {(
+    $fn_name:ident : [
+        $($($export:ident)::+),* $(,)?
+    ] $(;)?
+) => (
+    $crate::__cfg_headers__! {
+        /// Registers the exports of this crate for the headers generation.
+        pub
+        fn $fn_name ()
+        {
+            $(
+                $crate::headers::__register_export__(
+                    $crate::__ffi_export_path__!(
+                        [] $($export)::+ => ::__define_self__
+                    ),
+                );
+            )*
+        }
+    }
+)}
@@ -0,0 +1,390 @@
+use super::*;
+
+/// The kind of a [`Symbol`] emitted in the generated headers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub
+enum SymbolKind {
+    /// An `#[ffi_export]`ed function.
+    Function,
+    /// An `#[ffi_export]`ed `static`.
+    Static,
+    /// A type definition (`struct`, `enum`, `typedef`, _etc._).
+    Type,
+    /// A preprocessor macro (_e.g._, a `MY_STRUCT_DEFAULT` initializer).
+    Macro,
+    /// An `#include` of standard headers.
+    Include,
+    /// A Doxygen group (see `#[ffi_export(doc_group = ...)]`).
+    DocGroup,
+}
+
+/// An item emitted in the generated headers.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub
+struct Symbol {
+    pub
+    name: String,
+
+    pub
+    kind: SymbolKind,
+
+    /// The version of the API which introduced the item, when annotated
+    /// with `#[repr_c(since = ...)]`.
+    pub
+    since: Option<String>,
+
+    /// The `(field, version)` pairs of the fields of a `struct` annotated
+    /// with `#[repr_c(since = ...)]`, in declaration order.
+    pub
+    fields_since: Vec<(String, String)>,
+}
+
+/// The kind of owned value freed by a [`Destructor`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub
+enum OwnedKind {
+    /// A [`repr_c::Box`][`crate::prelude::repr_c::Box`] (`T *`).
+    Box,
+    /// A [`repr_c::PinBox`][`crate::prelude::repr_c::PinBox`] (`T *`).
+    PinBox,
+    /// A [`repr_c::Vec`][`crate::prelude::repr_c::Vec`] (`Vec_T_t`).
+    Vec,
+    /// A [`char_p::Box`][`crate::prelude::char_p::Box`] (`char *`).
+    String,
+}
+
+/// An `#[ffi_export]`ed function freeing an owned value, _i.e._, taking a
+/// [`repr_c::Box`][`crate::prelude::repr_c::Box`],
+/// [`repr_c::PinBox`][`crate::prelude::repr_c::PinBox`],
+/// [`repr_c::Vec`][`crate::prelude::repr_c::Vec`] or
+/// [`char_p::Box`][`crate::prelude::char_p::Box`] as its sole parameter, and
+/// returning nothing.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub
+struct Destructor {
+    /// The name of the function.
+    pub
+    name: String,
+
+    /// The C type of its parameter (_e.g._, `Vec_uint8_t`).
+    pub
+    c_type: String,
+
+    pub
+    kind: OwnedKind,
+}
+
+/// The C signature of an `#[ffi_export]`ed function.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub
+struct Signature {
+    /// The name of the function.
+    pub
+    name: String,
+
+    /// The names and C types (_e.g._, `int32_t`) of its parameters.
+    pub
+    params: Vec<(String, String)>,
+
+    /// The C type of its return value (`void` if none).
+    pub
+    ret: String,
+}
+
+/// What [`.generate()`][`Builder::generate`] has emitted.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # fn main () -> ::std::io::Result<()> { Ok({
+/// use ::safer_ffi::headers::{self, SymbolKind};
+///
+/// let report =
+///     headers::builder()
+///         .to_file("my_header.h")?
+///         .generate()?
+/// ;
+/// println!("Exported {}", report);
+/// assert!(report.names(SymbolKind::Function).any(|name| name == "my_init"));
+/// # })}
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub
+struct Report {
+    /// Every emitted item, in order of emission.
+    pub
+    symbols: Vec<Symbol>,
+
+    /// The (deduplicated) names of the definitions which have been skipped
+    /// because an item with the same name had already been emitted (_e.g._,
+    /// a type used by several functions, or an item already emitted by a
+    /// previous pass sharing the same [`Definer`]).
+    pub
+    skipped: Vec<String>,
+
+    /// The suspicious constructs spotted during the generation.
+    pub
+    warnings: Vec<Diagnostic>,
+
+    /// The names of the `#[ffi_export(used)]` items: see
+    /// [`.linker_flags()`][`Report::linker_flags`].
+    pub
+    used: Vec<String>,
+
+    /// The functions freeing owned values: see
+    /// [`.generate_cxx_wrappers()`][`Builder::generate_cxx_wrappers`].
+    pub
+    destructors: Vec<Destructor>,
+
+    /// The signatures of the exported functions: see
+    /// [`.generate_kotlin_expect_actual()`][`Builder::generate_kotlin_expect_actual`].
+    pub
+    signatures: Vec<Signature>,
+}
+
+impl Report {
+    /// The number of emitted items of the given `kind`.
+    pub
+    fn count (self: &'_ Self, kind: SymbolKind)
+      -> usize
+    {
+        self.names(kind).count()
+    }
+
+    /// The names of the emitted items of the given `kind`.
+    pub
+    fn names (self: &'_ Self, kind: SymbolKind)
+      -> impl '_ + Iterator<Item = &'_ str>
+    {
+        self.symbols
+            .iter()
+            .filter(move |it| it.kind == kind)
+            .map(|it| &*it.name)
+    }
+
+    /// The flags to pass to the C compiler (driving the linker) when linking
+    /// against the `staticlib`, so that the `#[ffi_export(used)]` items are
+    /// kept, even when no other object file references them (_e.g._, for
+    /// them to be looked up with `dlsym()`), despite `--gc-sections` / LTO.
+    ///
+    /// That is, `-Wl,-u,<name>` for each of them (with the `_` prefix of
+    /// C symbols on Apple platforms, when generating from one of them).
+    pub
+    fn linker_flags (self: &'_ Self)
+      -> Vec<String>
+    {
+        let prefix = if cfg!(target_vendor = "apple") { "_" } else { "" };
+        self.used
+            .iter()
+            .map(|name| format!("-Wl,-u,{}{}", prefix, name))
+            .collect()
+    }
+}
+
+impl fmt::Display
+    for Report
+{
+    fn fmt (self: &'_ Self, fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        write!(fmt,
+            "{} function(s), {} static(s), {} type(s), {} macro(s)",
+            self.count(SymbolKind::Function),
+            self.count(SymbolKind::Static),
+            self.count(SymbolKind::Type),
+            self.count(SymbolKind::Macro),
+        )
+    }
+}
+
+/// [`Definer`] adapter recording what the wrapped one defines.
+pub(in super)
+struct ReportingDefiner<D> {
+    pub(in super)
+    inner: D,
+
+    pub(in super)
+    report: Report,
+
+    pub(in super)
+    on_warning: Option<fn(Diagnostic)>,
+
+    skipped: HashSet<String>,
+
+    /// When hoisting the type definitions, the buffered type definitions,
+    /// and the buffered declarations.
+    hoisted: Option<Hoisted>,
+}
+
+struct Hoisted {
+    types: Vec<u8>,
+    declarations: Vec<u8>,
+    in_declaration: bool,
+}
+
+impl<D : Definer> ReportingDefiner<D> {
+    pub(in super)
+    fn new (inner: D)
+      -> Self
+    {
+        ReportingDefiner {
+            inner,
+            report: Report::default(),
+            on_warning: None,
+            skipped: HashSet::new(),
+            hoisted: None,
+        }
+    }
+
+    /// Until [`.end_hoisting()`][`ReportingDefiner::end_hoisting`], buffers
+    /// the type definitions and the declarations separately.
+    pub(in super)
+    fn start_hoisting (self: &'_ mut Self)
+    {
+        self.hoisted = Some(Hoisted {
+            types: Vec::new(),
+            declarations: Vec::new(),
+            in_declaration: false,
+        });
+    }
+
+    /// Writes the buffered type definitions, and then the buffered
+    /// declarations.
+    pub(in super)
+    fn end_hoisting (self: &'_ mut Self)
+      -> io::Result<()>
+    {
+        if let Some(hoisted) = self.hoisted.take() {
+            let out = self.inner.out();
+            out.write_all(&hoisted.types)?;
+            out.write_all(&hoisted.declarations)?;
+        }
+        Ok(())
+    }
+}
+
+impl<D : Definer> Definer
+    for ReportingDefiner<D>
+{
+    fn insert (self: &'_ mut Self, name: &'_ str)
+      -> bool
+    {
+        let inserted = self.inner.insert(name);
+        if inserted {
+            if let Some(ref mut hoisted) = self.hoisted {
+                hoisted.in_declaration = false;
+            }
+            self.report.symbols.push(Symbol {
+                name: name.to_owned(),
+                kind: SymbolKind::Type,
+                since: None,
+                fields_since: vec![],
+            });
+        } else if self.skipped.insert(name.to_owned()) {
+            self.report.skipped.push(name.to_owned());
+        }
+        inserted
+    }
+
+    fn out (self: &'_ mut Self)
+      -> &'_ mut dyn io::Write
+    {
+        match self.hoisted {
+            | Some(Hoisted { in_declaration: false, ref mut types, .. }) => {
+                types
+            },
+            | Some(Hoisted { ref mut declarations, .. }) => declarations,
+            | None => self.inner.out(),
+        }
+    }
+
+    fn set_symbol_kind (self: &'_ mut Self, name: &'_ str, kind: SymbolKind)
+    {
+        if let Some(ref mut hoisted) = self.hoisted {
+            hoisted.in_declaration = match kind {
+                | SymbolKind::Function
+                | SymbolKind::Static
+                | SymbolKind::Macro
+                => true,
+                | SymbolKind::Type
+                | SymbolKind::Include
+                | SymbolKind::DocGroup
+                => false,
+            };
+        }
+        if let Some(symbol) =
+            self.report.symbols.iter_mut().rev().find(|it| it.name == name)
+        {
+            symbol.kind = kind;
+        }
+        self.inner.set_symbol_kind(name, kind);
+    }
+
+    fn warn (self: &'_ mut Self, diagnostic: Diagnostic)
+    {
+        if let Some(on_warning) = self.on_warning {
+            on_warning(diagnostic.clone());
+        }
+        self.report.warnings.push(diagnostic.clone());
+        self.inner.warn(diagnostic);
+    }
+
+    fn set_used (self: &'_ mut Self, name: &'_ str)
+    {
+        self.report.used.push(name.to_owned());
+        self.inner.set_used(name);
+    }
+
+    fn set_destructor (self: &'_ mut Self, destructor: &'_ Destructor)
+    {
+        self.report.destructors.push(destructor.clone());
+        self.inner.set_destructor(destructor);
+    }
+
+    fn set_signature (self: &'_ mut Self, signature: &'_ Signature)
+    {
+        self.report.signatures.push(signature.clone());
+        self.inner.set_signature(signature);
+    }
+
+    fn set_since (
+        self: &'_ mut Self,
+        item: &'_ str,
+        field: Option<&'_ str>,
+        since: &'_ str,
+    )
+    {
+        if let Some(symbol) =
+            self.report.symbols.iter_mut().rev().find(|it| it.name == item)
+        {
+            match field {
+                | Some(field) => symbol.fields_since.push((
+                    field.to_owned(),
+                    since.to_owned(),
+                )),
+                | None => symbol.since = Some(since.to_owned()),
+            }
+        }
+        self.inner.set_since(item, field, since);
+    }
+}
+
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __report_signature__ (
+    definer: &'_ mut dyn Definer,
+    fname: &'_ str,
+    params: &'_ [(&'_ str, String)],
+    ret: String,
+)
+{
+    definer.set_signature(&Signature {
+        name: fname.to_owned(),
+        params:
+            params
+                .iter()
+                .map(|&(name, ref c_type)| (name.to_owned(), c_type.clone()))
+                .collect()
+        ,
+        ret,
+    });
+}
@@ -0,0 +1,270 @@
+//! Splitting the generated headers into one file per group of exports.
+
+use super::*;
+use ::std::{
+    cell::RefCell,
+    collections::{BTreeSet, HashMap},
+};
+
+/// How [`.generate_split()`][`Builder::generate_split`] groups the exports
+/// into header files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub
+enum Grouping {
+    /// One header per Rust module: the exports of `mylib::audio::mixer` make
+    /// up the `audio_mixer` group, and the ones of the root module, the
+    /// `root` group.
+    ByModule,
+
+    /// One header per `#[ffi_export(group = "<name>")]` group, the exports
+    /// without such a `group` making up the `default` group.
+    ByAttribute,
+}
+
+enum Selection {
+    /// Collects the names of the groups, while emitting nothing.
+    Discover(Vec<String>),
+
+    /// Only emits the exports of the given group.
+    Group(String),
+}
+
+::std::thread_local! {
+    // `const { … }` `thread_local!` initializers require Rust 1.59.
+    #[allow(clippy::missing_const_for_thread_local)]
+    static SELECTION: RefCell<Option<(Grouping, Selection)>> =
+        RefCell::new(None)
+    ;
+}
+
+/// Runs `f` with `selection` as the current [`Selection`].
+fn with_selection<R> (
+    selection: (Grouping, Selection),
+    f: impl FnOnce() -> R,
+) -> (R, Selection)
+{
+    struct Restore;
+    impl Drop for Restore {
+        fn drop (self: &'_ mut Restore)
+        {
+            SELECTION.with(|it| *it.borrow_mut() = None);
+        }
+    }
+    SELECTION.with(|it| *it.borrow_mut() = Some(selection));
+    let restore = Restore;
+    let ret = f();
+    let (_, selection) =
+        SELECTION
+            .with(|it| it.borrow_mut().take())
+            .expect("Missing selection")
+    ;
+    drop(restore);
+    (ret, selection)
+}
+
+/// Whether the export defined in `module_path` (and in the given `group`, if
+/// any) belongs to the group currently being generated (always, when not
+/// splitting the headers).
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __in_group__ (module_path: &'_ str, group: Option<&'_ str>)
+  -> bool
+{
+    SELECTION.with(|it| match *it.borrow_mut() {
+        | None => true,
+        | Some((grouping, ref mut selection)) => {
+            let name = match grouping {
+                | Grouping::ByModule => {
+                    match module_path.find("::") {
+                        | Some(i) => module_path[i + 2 ..].replace("::", "_"),
+                        | None => "root".into(),
+                    }
+                },
+                | Grouping::ByAttribute => group.unwrap_or("default").into(),
+            };
+            match *selection {
+                | Selection::Discover(ref mut groups) => {
+                    if groups.contains(&name).not() {
+                        groups.push(name);
+                    }
+                    false
+                },
+                | Selection::Group(ref current) => *current == name,
+            }
+        },
+    })
+}
+
+/// [`Definer`] shared by the generation passes of the groups, so that each
+/// type is only defined in the header of the first group using it, and
+/// recording which (previous) headers the current one depends on.
+#[derive(Default)]
+struct GroupDefiner {
+    /// The index of the group having defined each name.
+    owners: HashMap<String, usize>,
+    current: usize,
+    dependencies: BTreeSet<usize>,
+    out: Vec<u8>,
+}
+
+impl Definer
+    for GroupDefiner
+{
+    fn insert (self: &'_ mut Self, name: &'_ str)
+      -> bool
+    {
+        match self.owners.get(name) {
+            | Some(&owner) => {
+                if owner != self.current {
+                    self.dependencies.insert(owner);
+                }
+                false
+            },
+            | None => {
+                self.owners.insert(name.to_owned(), self.current);
+                true
+            },
+        }
+    }
+
+    fn out (self: &'_ mut Self)
+      -> &'_ mut dyn io::Write
+    {
+        &mut self.out
+    }
+}
+
+impl Builder<'_, WhereTo> {
+    /// Alternative to [`.to_file()`][`Builder::to_file`]`.generate()` which
+    /// emits one header per group of exports (see [`Grouping`]), named
+    /// after the `umbrella` header and the group (_e.g._, `mylib_audio.h`),
+    /// and then the `umbrella` header itself, `#include`-ing all of them.
+    ///
+    /// Each type is defined in the header of the first group (in
+    /// alphabetical order) using it, which the headers of the other groups
+    /// using it then `#include`.
+    ///
+    /// This is only supported for [`Language::C`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main () -> ::std::io::Result<()> { Ok({
+    /// use ::safer_ffi::headers::{self, Grouping};
+    ///
+    /// // `include/mylib.h`, `include/mylib_audio.h`, _etc._
+    /// headers::builder()
+    ///     .generate_split("include/mylib.h", Grouping::ByAttribute)?;
+    /// # })}
+    /// ```
+    pub
+    fn generate_split (
+        self,
+        umbrella: impl AsRef<Path>,
+        grouping: Grouping,
+    ) -> io::Result<Report>
+    {
+        if matches!(self.language, Some(it) if it != Language::C) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Split headers are only supported for C",
+            ));
+        }
+        let umbrella = umbrella.as_ref();
+        let stem =
+            umbrella
+                .file_stem()
+                .and_then(|it| it.to_str())
+                .ok_or_else(|| io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "Invalid umbrella header path",
+                ))?
+        ;
+        let dir = umbrella.parent().unwrap_or_else(|| Path::new(""));
+        let guard = match self.guard {
+            | Some(it) => it.to_owned(),
+            | None => format!("__RUST_{}__",
+                env::var("CARGO_PKG_NAME")
+                    .unwrap()
+                    .to_ascii_uppercase()
+            ),
+        };
+        let emit_guard = self.emit_guard.unwrap_or(true);
+        let guard_style = self.guard_style.unwrap_or(GuardStyle::Ifndef);
+
+        let (discovered, selection) = with_selection(
+            (grouping, Selection::Discover(vec![])),
+            || Builder { target: WhereTo, .. self }.to_writer(io::sink()).generate(),
+        );
+        discovered?;
+        let mut groups = match selection {
+            | Selection::Discover(groups) => groups,
+            | Selection::Group(_) => unreachable!(),
+        };
+        groups.sort();
+
+        let file_name = |group: &'_ str| format!("{}_{}.h", stem, group);
+        let mut definer = GroupDefiner::default();
+        let mut report = Report::default();
+        for (i, group) in groups.iter().enumerate() {
+            definer.current = i;
+            definer.dependencies.clear();
+            let group_guard = format!("{}_{}__",
+                guard.trim_end_matches('_'),
+                group.to_ascii_uppercase(),
+            );
+            let (pass, _) = with_selection(
+                (grouping, Selection::Group(group.clone())),
+                || Builder {
+                    target: WhereTo,
+                    guard: Some(&*group_guard),
+                    .. self
+                }.generate_with_definer(&mut definer),
+            );
+            let pass = pass?;
+            report.symbols.extend(pass.symbols);
+            report.skipped.extend(pass.skipped);
+            report.warnings.extend(pass.warnings);
+            report.used.extend(pass.used);
+            report.destructors.extend(pass.destructors);
+            report.signatures.extend(pass.signatures);
+            let mut header =
+                String::from_utf8(mem::take(&mut definer.out))
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?
+            ;
+            if definer.dependencies.is_empty().not() {
+                let mut includes = String::new();
+                for &dependency in &definer.dependencies {
+                    includes.push_str(&format!("#include \"{}\"\n",
+                        file_name(&groups[dependency]),
+                    ));
+                }
+                includes.push('\n');
+                let at = header.find("#ifdef __cplusplus\n").unwrap_or(0);
+                header.insert_str(at, &includes);
+            }
+            atomic_file::write_atomically(
+                &dir.join(file_name(group)),
+                header.as_bytes(),
+            )?;
+        }
+
+        let mut out = String::new();
+        if let Some(license) = self.license_header {
+            out.push_str(&license::c_comment(&license::lines(license)));
+            out.push('\n');
+        }
+        out.push_str(self.banner.unwrap_or(DEFAULT_BANNER));
+        out.push_str("\n\n");
+        if emit_guard {
+            out.push_str(&guard_style.open(&guard));
+        }
+        for group in &groups {
+            out.push_str(&format!("#include \"{}\"\n", file_name(group)));
+        }
+        if emit_guard {
+            out.push_str(&guard_style.close(&guard));
+        }
+        atomic_file::write_atomically(umbrella, out.as_bytes())?;
+        Ok(report)
+    }
+}
@@ -0,0 +1,177 @@
+//! Importing the library into Swift: the `module.modulemap` and the
+//! annotated header of [`.generate_swift_module()`][
+//! `Builder::generate_swift_module`].
+
+use super::*;
+
+impl Builder<'_, WhereTo> {
+    /// Generates, within `dir`, a `module.modulemap` declaring the
+    /// `module_name` (Clang) module, and its umbrella header,
+    /// `<module_name>.h`, [annotated for Swift][
+    /// `Builder::with_swift_annotations`], so that the library can be
+    /// `import`ed from Swift (_e.g._, as the system library target of a
+    /// Swift package).
+    ///
+    /// The module links against the library named after the crate (with
+    /// `-`s replaced by `_`s).
+    ///
+    /// This is only supported for [`Language::C`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main () -> ::std::io::Result<()> { Ok({
+    /// // `Sources/CMyLib/module.modulemap` and `Sources/CMyLib/CMyLib.h`.
+    /// ::safer_ffi::headers::builder()
+    ///     .generate_swift_module("Sources/CMyLib", "CMyLib")?;
+    /// # })}
+    /// ```
+    ///
+    /// yields the following `module.modulemap`:
+    ///
+    /// ```text
+    /// module CMyLib {
+    ///     header "CMyLib.h"
+    ///     link "mylib"
+    ///     export *
+    /// }
+    /// ```
+    pub
+    fn generate_swift_module (
+        self,
+        dir: impl AsRef<Path>,
+        module_name: &'_ str,
+    ) -> io::Result<Report>
+    {
+        if matches!(self.language, Some(it) if it != Language::C) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Swift modules are only supported for C",
+            ));
+        }
+        if is_c_identifier(module_name).not() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Invalid module name: `{}`", module_name),
+            ));
+        }
+        let dir = dir.as_ref();
+        let header = format!("{}.h", module_name);
+        let report =
+            Builder { swift_annotations: Some(true), .. self }
+                .to_file(dir.join(&header))?
+                .generate()?
+        ;
+        let modulemap = format!(
+            concat!(
+                "module {} {{\n",
+                "    header \"{}\"\n",
+                "    link \"{}\"\n",
+                "    export *\n",
+                "}}\n",
+            ),
+            module_name,
+            header,
+            env::var("CARGO_PKG_NAME").unwrap().replace('-', "_"),
+        );
+        atomic_file::write_atomically(
+            &dir.join("module.modulemap"),
+            modulemap.as_bytes(),
+        )?;
+        Ok(report)
+    }
+}
+
+/// Emitted right after the opening of the `extern "C"` block, when
+/// [annotating for Swift][`Builder::with_swift_annotations`].
+pub(in super)
+const PROLOGUE: &str = concat!(
+    "#if defined(__has_attribute)\n",
+    "#  if __has_attribute(swift_name)\n",
+    "#    define SAFER_FFI_SWIFT_NAME(name) __attribute__((swift_name(name)))\n",
+    "#  endif\n",
+    "#endif\n",
+    "#ifndef SAFER_FFI_SWIFT_NAME\n",
+    "#  define SAFER_FFI_SWIFT_NAME(name)\n",
+    "#endif\n",
+    "\n",
+    "#ifdef __clang__\n",
+    "#pragma clang diagnostic push\n",
+    "/* Only the pointers of the function signatures are annotated. */\n",
+    "#pragma clang diagnostic ignored \"-Wnullability-completeness\"\n",
+    "#else\n",
+    "#  define _Nullable\n",
+    "#  define _Nonnull\n",
+    "#endif\n",
+    "\n",
+);
+
+/// Emitted right before the closing of the `extern "C"` block, when
+/// [annotating for Swift][`Builder::with_swift_annotations`].
+pub(in super)
+const EPILOGUE: &str = concat!(
+    "\n",
+    "#ifdef __clang__\n",
+    "#pragma clang diagnostic pop\n",
+    "#endif\n",
+);
+
+/// The nullability qualifier (if any, followed by a space) of a parameter
+/// or return value of type `T`, when [annotating for Swift][
+/// `Builder::with_swift_annotations`]: `_Nullable` for the pointers which
+/// can be `NULL` (such as an `Option<&T>`), and `_Nonnull` for the others.
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __nullability__<T : ReprC> ()
+  -> &'static str
+{
+    if context::context().swift_annotations.not() {
+        return "";
+    }
+    match pointer_nullability::<T>() {
+        | Some(true) => "_Nullable ",
+        | Some(false) => "_Nonnull ",
+        | None => "",
+    }
+}
+
+/// Whether `T` can be `NULL`, if it is a (C) pointer.
+pub(in super)
+fn pointer_nullability<T : ReprC> ()
+  -> Option<bool>
+{
+    if T::CLayout::c_var("").to_string().trim_end().ends_with('*').not() {
+        return None;
+    }
+    let null: T::CLayout = unsafe {
+        // Safety: a (C) pointer type, for which `NULL` is a valid
+        // bit-pattern.
+        mem::zeroed()
+    };
+    Some(T::is_valid(&null))
+}
+
+/// The `SAFER_FFI_SWIFT_NAME(...)` annotation (preceded by a space) of the
+/// declaration of the function, when [annotating for Swift][
+/// `Builder::with_swift_annotations`]: `mylib_point_new(x, y)` is imported
+/// as `mylibPointNew(x:y:)`.
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __swift_name__ (fname: &'_ str, arg_names: &'_ [&'_ str])
+  -> String
+{
+    if context::context().swift_annotations.not() {
+        return String::new();
+    }
+    let labels: String =
+        arg_names
+            .iter()
+            .map(|&arg_name| match arg_name {
+                | "_" => "_:".into(),
+                | _ => format!("{}:", Case::CamelCase.apply(arg_name)),
+            })
+            .collect()
+    ;
+    format!(" SAFER_FFI_SWIFT_NAME(\"{}({})\")",
+        Case::CamelCase.apply(fname),
+        labels,
+    )
+}
@@ -0,0 +1,52 @@
+use super::*;
+
+/// Defines (once) the `SAFER_FFI_SYSTEM_ABI` macro used to declare the
+/// `extern "system"` functions (and function pointers), such as the
+/// `#[ffi_export(system_abi)]` variants of the exported functions: Rust's
+/// `extern "system"` is `__stdcall` on (32-bit) Windows, and the C calling
+/// convention everywhere else.
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __define_system_abi__ (definer: &'_ mut dyn Definer)
+  -> io::Result<()>
+{
+    definer.define_once(
+        "SAFER_FFI_SYSTEM_ABI",
+        &mut |definer| {
+            definer.set_symbol_kind(
+                "SAFER_FFI_SYSTEM_ABI",
+                SymbolKind::Macro,
+            );
+            write!(definer.out(),
+                concat!(
+                    "#ifndef SAFER_FFI_SYSTEM_ABI\n",
+                    "#if defined(_WIN32)\n",
+                    "#define SAFER_FFI_SYSTEM_ABI __stdcall\n",
+                    "#else\n",
+                    "#define SAFER_FFI_SYSTEM_ABI\n",
+                    "#endif\n",
+                    "#endif\n",
+                    "\n",
+                ),
+            )
+        },
+    )
+}
+
+/// The annotation of the declarations of the functions exported with the
+/// given `abi` (the one of their `extern "<abi>"`), defining (once) the
+/// macro it involves, if any.
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __abi_annotation__ (
+    definer: &'_ mut dyn Definer,
+    abi: &'_ str,
+) -> io::Result<&'static str>
+{
+    Ok(match abi {
+        | "system" => {
+            __define_system_abi__(definer)?;
+            "SAFER_FFI_SYSTEM_ABI "
+        },
+        // `"C-unwind"` has the same calling convention as `"C"`.
+        | _ => "",
+    })
+}
@@ -0,0 +1,172 @@
+//! Localized doc comments: see [`Builder::with_doc_translations`].
+
+use super::*;
+use ::core::cell::RefCell;
+use ::std::{borrow::Cow, collections::HashMap};
+
+/// Doc comments replacing the ones of the Rust items in the generated
+/// headers, keyed by the (Rust) name of the item, such as `point_new` for a
+/// function, `Point` for a type, or `Point.x` for one of its fields.
+///
+/// They are typically loaded from a translations file (see
+/// [`DocTranslations::parse`]), one per language.
+#[derive(Debug, Clone, Default)]
+pub
+struct DocTranslations {
+    docs: HashMap<String, Vec<String>>,
+}
+
+impl DocTranslations {
+    pub
+    fn new ()
+      -> DocTranslations
+    {
+        DocTranslations::default()
+    }
+
+    /// Sets the (possibly multi-line) doc comment of the `symbol` item.
+    pub
+    fn insert (
+        self: &'_ mut Self,
+        symbol: impl Into<String>,
+        doc: &'_ str,
+    ) -> &'_ mut Self
+    {
+        self.docs.insert(symbol.into(), doc.lines().map(Into::into).collect());
+        self
+    }
+
+    /// Parses a translations file, where each doc comment follows the
+    /// `[symbol]` line of its item (surrounding blank lines being ignored):
+    ///
+    /// ```text
+    /// [Point]
+    /// 二次元の点。
+    ///
+    /// [point_new]
+    /// 点を作成します。
+    ///
+    /// `x` と `y` は座標です。
+    /// ```
+    pub
+    fn parse (s: &'_ str)
+      -> io::Result<DocTranslations>
+    {
+        let mut ret = DocTranslations::new();
+        let mut current: Option<(&'_ str, Vec<String>)> = None;
+        for (line, i) in s.lines().zip(1 ..) {
+            let trimmed = line.trim_end();
+            if trimmed.starts_with('[') && trimmed.ends_with(']') {
+                let symbol = &trimmed[1 .. trimmed.len() - 1];
+                if symbol.split('.').all(is_c_identifier) {
+                    ret.extend(current.take());
+                    current = Some((symbol, vec![]));
+                    continue;
+                }
+            }
+            match current {
+                | Some((_, ref mut lines)) => lines.push(trimmed.into()),
+                | None if trimmed.is_empty() => {},
+                | None => return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Line {}: expected a `[symbol]` line", i),
+                )),
+            }
+        }
+        ret.extend(current);
+        Ok(ret)
+    }
+
+    /// [Parses][`DocTranslations::parse`] the given translations file.
+    pub
+    fn from_file (path: impl AsRef<Path>)
+      -> io::Result<DocTranslations>
+    {
+        DocTranslations::parse(&fs::read_to_string(path)?)
+    }
+
+    fn extend (
+        self: &'_ mut Self,
+        entry: Option<(&'_ str, Vec<String>)>,
+    )
+    {
+        if let Some((symbol, mut lines)) = entry {
+            while matches!(lines.last(), Some(it) if it.is_empty()) {
+                lines.pop();
+            }
+            let start = lines.iter().position(|it| it.is_empty().not());
+            lines.drain(.. start.unwrap_or(lines.len()));
+            self.docs.insert(symbol.into(), lines);
+        }
+    }
+}
+
+::std::thread_local! {
+    // `const { … }` `thread_local!` initializers require Rust 1.59.
+    #[allow(clippy::missing_const_for_thread_local)]
+    static TRANSLATIONS: RefCell<Option<DocTranslations>> = RefCell::new(None);
+}
+
+/// Runs `f` with `translations` as the ones of the ongoing generation.
+pub(in super)
+fn with_translations<R> (
+    translations: Option<DocTranslations>,
+    f: impl FnOnce() -> R,
+) -> R
+{
+    struct Restore(Option<DocTranslations>);
+    impl Drop for Restore {
+        fn drop (self: &'_ mut Restore)
+        {
+            let prev = self.0.take();
+            TRANSLATIONS.with(|it| *it.borrow_mut() = prev);
+        }
+    }
+    let prev = TRANSLATIONS.with(|it| mem::replace(&mut *it.borrow_mut(), translations));
+    let _restore = Restore(prev);
+    f()
+}
+
+/// The doc lines of the `symbol` item: its translation, if any, or else
+/// `docs`, its Rust ones.
+///
+/// Like the Rust ones, the translated lines are prefixed with a space.
+pub(in super)
+fn translated<'doc> (symbol: &'_ str, docs: &'_ [&'doc str])
+  -> Vec<Cow<'doc, str>>
+{
+    TRANSLATIONS.with(|it| {
+        it.borrow()
+            .as_ref()
+            .filter(|_| context::context().language == Language::C)
+            .and_then(|translations| translations.docs.get(symbol))
+            .map(|lines| {
+                lines.iter().map(|line| match &line[..] {
+                    | "" => Cow::Borrowed(""),
+                    | _ => Cow::Owned(format!(" {}", line)),
+                }).collect()
+            })
+            .unwrap_or_else(|| docs.iter().map(|&it| Cow::Borrowed(it)).collect())
+    })
+}
+
+/// Emits the translated doc comment of the `symbol` item, if any, and
+/// returns whether it did so (for the Rust one to be emitted otherwise).
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __output_translated_docs__ (
+    out: &'_ mut dyn io::Write,
+    pad: &'_ str,
+    symbol: &'_ str,
+) -> io::Result<bool>
+{
+    let lines = code_blocks::__doc_lines__(symbol, &[]);
+    if lines.is_empty() {
+        return Ok(false);
+    }
+    writeln!(out, "{}/** \\brief", pad)?;
+    for line in lines {
+        writeln!(out, "{} * {}", pad, line)?;
+    }
+    writeln!(out, "{} */", pad)?;
+    Ok(true)
+}
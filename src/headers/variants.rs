@@ -0,0 +1,29 @@
+use super::*;
+
+/// Emits the constants of an enum, as `    <docs><name>[ = <value>],` lines,
+/// in the configured [`VariantOrder`].
+///
+/// Each variant is given as its value, its (already formatted) doc comment,
+/// its name, and its explicit discriminant, if any.
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __output_variants__ (
+    out: &'_ mut dyn io::Write,
+    variants: &'_ mut [(i128, &'_ str, &'_ str, Option<&'_ dyn fmt::Display>)],
+) -> io::Result<()>
+{
+    let by_value = context::context().variant_order == VariantOrder::Value;
+    if by_value {
+        // Stable sort: same-valued variants remain in declaration order.
+        variants.sort_by_key(|&(value, _, _, _)| value);
+    }
+    for &mut (value, docs, name, discriminant) in variants {
+        write!(out, "{}    {}", docs, name)?;
+        if by_value {
+            write!(out, " = {}", value)?;
+        } else if let Some(discriminant) = discriminant {
+            write!(out, " = {}", discriminant)?;
+        }
+        writeln!(out, ",")?;
+    }
+    Ok(())
+}
@@ -0,0 +1,45 @@
+//! The version macros of the headers: see [`Builder::with_version_macros`].
+
+use super::*;
+
+/// Emits the `<prefix>_VERSION_{MAJOR,MINOR,PATCH}` and `<prefix>_VERSION`
+/// macros, from the version of the crate generating the headers.
+pub(in super)
+fn define_version_macros (out: &'_ mut dyn io::Write, prefix: &'_ str)
+  -> io::Result<()>
+{
+    if is_c_identifier(prefix).not() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Invalid version macros prefix: `{}`", prefix),
+        ));
+    }
+    let version = env::var("CARGO_PKG_VERSION").map_err(|_| io::Error::new(
+        io::ErrorKind::NotFound,
+        "Error, `CARGO_PKG_VERSION` is not set: the headers need to be \
+        generated by a binary (or a test) run by Cargo for the version macros",
+    ))?;
+    // `1.2.3-beta.1+build` => `1.2.3`
+    let numbers = version.split(&['-', '+'][..]).next().unwrap();
+    let mut numbers = numbers.split('.').map(|it| it.parse::<u64>().ok());
+    let (major, minor, patch) =
+        match (numbers.next(), numbers.next(), numbers.next(), numbers.next()) {
+            | (Some(Some(major)), Some(Some(minor)), Some(Some(patch)), None) => {
+                (major, minor, patch)
+            },
+            | _ => return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Invalid crate version: `{}`", version),
+            )),
+        }
+    ;
+    write!(out,
+        concat!(
+            "#define {0}_VERSION_MAJOR {1}\n",
+            "#define {0}_VERSION_MINOR {2}\n",
+            "#define {0}_VERSION_PATCH {3}\n",
+            "#define {0}_VERSION \"{4}\"\n\n",
+        ),
+        prefix, major, minor, patch, version,
+    )
+}
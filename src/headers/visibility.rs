@@ -0,0 +1,107 @@
+//! Restricting the symbols of a shared library to the exported API.
+
+use super::*;
+
+/// Emits the definition of the [export macro][`Builder::with_export_macro`],
+/// `dllexport`ing the declarations when `dllexport_define` is defined.
+pub(in super)
+fn define_export_macro (
+    out: &'_ mut dyn io::Write,
+    export_macro: &'_ str,
+    dllexport_define: &'_ str,
+) -> io::Result<()>
+{
+    write!(out,
+        concat!(
+            "#ifndef {0}\n",
+            "#  if defined(_WIN32) || defined(__CYGWIN__)\n",
+            "#    ifdef {1}\n",
+            "#      define {0} __declspec(dllexport)\n",
+            "#    else\n",
+            "#      define {0} __declspec(dllimport)\n",
+            "#    endif\n",
+            "#  elif defined(__GNUC__) || defined(__clang__)\n",
+            "#    define {0} __attribute__((visibility(\"default\")))\n",
+            "#  else\n",
+            "#    define {0}\n",
+            "#  endif\n",
+            "#endif\n\n",
+        ),
+        export_macro,
+        dllexport_define,
+    )
+}
+
+impl Report {
+    /// A (GNU `ld`, `gold` and `lld`) version script keeping only the
+    /// exported functions and `static`s visible:
+    ///
+    /// ```text
+    /// {
+    ///   global:
+    ///     mylib_init;
+    ///     mylib_free;
+    ///   local:
+    ///     *;
+    /// };
+    /// ```
+    ///
+    /// This is meant for a shared library embedding the `staticlib` of the
+    /// crate, which would otherwise also export every public symbol of the
+    /// latter (including the ones of the Rust standard library):
+    ///
+    /// ```shell
+    /// cc -shared -o libmylib.so \
+    ///     -Wl,--whole-archive libmylib.a -Wl,--no-whole-archive \
+    ///     -Wl,--version-script=mylib.map
+    /// ```
+    ///
+    /// A `cdylib` does not need it: `rustc` already restricts its dynamic
+    /// symbols to the `#[no_mangle]` ones (and passing a second version
+    /// script to the linker is not supported).
+    pub
+    fn version_script (self: &'_ Self)
+      -> String
+    {
+        let mut ret = String::from("{\n");
+        let mut exports =
+            self.names(SymbolKind::Function)
+                .chain(self.names(SymbolKind::Static))
+                .peekable()
+        ;
+        if exports.peek().is_some() {
+            ret.push_str("  global:\n");
+            exports.for_each(|name| {
+                ret.push_str(&format!("    {};\n", name));
+            });
+        }
+        ret.push_str("  local:\n    *;\n};\n");
+        ret
+    }
+
+    /// Writes the [`.version_script()`][`Report::version_script`] to the
+    /// given file (atomically, see [`AtomicFile`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main () -> ::std::io::Result<()> { Ok({
+    /// ::safer_ffi::headers::builder()
+    ///     .with_export_macro("MYLIB_API")
+    ///     .to_file("mylib.h")?
+    ///     .generate()?
+    ///     .write_version_script("mylib.map")?;
+    /// # })}
+    /// ```
+    pub
+    fn write_version_script (
+        self: &'_ Self,
+        filename: impl AsRef<Path>,
+    ) -> io::Result<()>
+    {
+        atomic_file::write_atomically(
+            filename.as_ref(),
+            self.version_script().as_bytes(),
+        )
+    }
+}
@@ -0,0 +1,281 @@
+//! The Zig backend: see [`Language::Zig`].
+
+use super::*;
+use ::std::borrow::Cow;
+
+/// The Zig name of a field or parameter.
+fn escape (name: &'_ str)
+  -> Cow<'_, str>
+{
+    let name = name.trim_start_matches("r#");
+    if keywords::is_zig_keyword(name) {
+        Cow::Owned(format!("@\"{}\"", name))
+    } else {
+        Cow::Borrowed(name)
+    }
+}
+
+/// Emits the `docs` (lines) as `///` doc comments.
+fn docs (
+    out: &'_ mut dyn io::Write,
+    pad: &'_ str,
+    docs: &'_ [&'_ str],
+) -> io::Result<()>
+{
+    for line in docs.iter().flat_map(|doc| doc.split('\n')) {
+        writeln!(out, "{}///{}", pad, line.trim_end())?;
+    }
+    Ok(())
+}
+
+/// The Zig [`Backend`].
+pub(in super)
+struct Zig;
+
+impl Backend for Zig {
+    /// Zig does not have `/* ... */` comments.
+    fn banner (self: &'_ Self)
+      -> &'static str
+    {
+        concat!(
+            "// File auto-generated by `::safer_ffi`.\n",
+            "//\n",
+            "// Do not manually edit this file.",
+        )
+    }
+
+    fn comment (self: &'_ Self, lines: &'_ [String])
+      -> String
+    {
+        license::line_comment(lines)
+    }
+
+    fn primitive_ty (self: &'_ Self, c_short_name: &'_ str)
+      -> &'static str
+    {
+        match c_short_name {
+            | "uint8" => "u8",
+            | "uint16" => "u16",
+            | "uint32" => "u32",
+            | "uint64" => "u64",
+            | "size" => "usize",
+            | "int8" => "i8",
+            | "int16" => "i16",
+            | "int32" => "i32",
+            | "int64" => "i64",
+            | "ssize" => "isize",
+            | "float" => "f32",
+            | "double" => "f64",
+            | "bool" => "bool",
+            | "char" => "c_char",
+            | "void" => "void",
+            | _ => unreachable!("`{}` is not a primitive type", c_short_name),
+        }
+    }
+
+    fn pointer_ty (self: &'_ Self, pointee: &'_ TypeDesc, mutable: bool)
+      -> String
+    {
+        let qualifier = if mutable { "" } else { "const " };
+        match &pointee.ty()[..] {
+            | "void" => format!("?*{}anyopaque", qualifier),
+            | pointee => format!("?*{}{}", qualifier, pointee),
+        }
+    }
+
+    // Zig has no counterpart of the `"system"` calling convention.
+    fn fn_ptr_ty (
+        self: &'_ Self,
+        ret: &'_ TypeDesc,
+        params: &'_ [TypeDesc],
+        system_abi: bool,
+    ) -> String
+    {
+        if system_abi {
+            return "?*const anyopaque".into();
+        }
+        let params: Vec<String> = params.iter().map(TypeDesc::ty).collect();
+        format!("?*const fn ({}) callconv(.C) {}", params.join(", "), ret.ty())
+    }
+
+    fn define_fn_ptr (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        ret: &'_ TypeDesc,
+        params: &'_ [TypeDesc],
+        system_abi: bool,
+    ) -> io::Result<()>
+    {
+        if system_abi {
+            return Ok(());
+        }
+        ret.define(definer)?;
+        for param in params {
+            param.define(definer)?;
+        }
+        Ok(())
+    }
+
+    fn define_array (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        me: &'_ TypeDesc,
+        item: &'_ TypeDesc,
+        len: usize,
+    ) -> io::Result<()>
+    {
+        item.define(definer)?;
+        let ref name = me.ty();
+        definer.define_once(name, &mut |definer| {
+            write!(definer.out(),
+                concat!(
+                    "pub const {} = extern struct {{\n",
+                    "    idx: [{}]{},\n",
+                    "}};\n\n",
+                ),
+                name,
+                len,
+                item.ty(),
+            )
+        })
+    }
+
+    fn define_bytes (self: &'_ Self, definer: &'_ mut dyn Definer, me: &'_ TypeDesc)
+      -> io::Result<()>
+    {
+        let ref name = me.ty();
+        definer.define_once(name, &mut |definer| {
+            write!(definer.out(),
+                concat!(
+                    "/// Has the same layout as `{}`.\n",
+                    "pub const {} = extern struct {{\n",
+                    "    bytes: [{}]u8 align({}),\n",
+                    "}};\n\n",
+                ),
+                me.c_var(),
+                name,
+                me.size,
+                me.align,
+            )
+        })
+    }
+
+    fn define_struct (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        me: &'_ TypeDesc,
+        docs: &'_ [&'_ str],
+        fields: &'_ [Field<'_>],
+    ) -> io::Result<()>
+    {
+        define_aggregate(definer.out(), me, docs, fields, "struct")
+    }
+
+    fn define_union (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        me: &'_ TypeDesc,
+        docs: &'_ [&'_ str],
+        fields: &'_ [Field<'_>],
+    ) -> io::Result<()>
+    {
+        define_aggregate(definer.out(), me, docs, fields, "union")
+    }
+
+    fn define_enum (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        me: &'_ TypeDesc,
+        docs: &'_ [&'_ str],
+        int: &'_ TypeDesc,
+        variants: &'_ mut [Variant<'_>],
+    ) -> io::Result<()>
+    {
+        let out = definer.out();
+        self::docs(out, "", docs)?;
+        if context::context().variant_order == VariantOrder::Value {
+            // Stable sort: same-valued variants remain in declaration order.
+            variants.sort_by_key(|variant| variant.value);
+        }
+        writeln!(out, "pub const {} = enum({}) {{", me.ty(), int.ty())?;
+        for variant in variants {
+            self::docs(out, "    ", variant.docs)?;
+            writeln!(out, "    {} = {},", escape(variant.name), variant.value)?;
+        }
+        out.write_all(b"};\n\n")
+    }
+
+    fn define_opaque (self: &'_ Self, definer: &'_ mut dyn Definer, me: &'_ TypeDesc)
+      -> io::Result<()>
+    {
+        write!(definer.out(), "pub const {} = opaque {{}};\n\n", me.ty())
+    }
+
+    fn declare_fn (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        docs: &'_ [&'_ str],
+        name: &'_ str,
+        ret: &'_ TypeDesc,
+        params: &'_ [(&'_ str, TypeDesc)],
+    ) -> io::Result<()>
+    {
+        let out = definer.out();
+        self::docs(out, "", docs)?;
+        write!(out, "pub extern fn {}(", escape(name))?;
+        for (i, &(param_name, ref param_ty)) in params.iter().enumerate() {
+            write!(out,
+                "{comma}\n    {}: {}",
+                escape(param_name), param_ty.ty(),
+                comma = if i == 0 { "" } else { "," },
+            )?;
+        }
+        if params.is_empty().not() {
+            out.write_all(b",\n")?;
+        }
+        writeln!(out, ") {};\n", ret.ty())
+    }
+
+    fn declare_static (
+        self: &'_ Self,
+        definer: &'_ mut dyn Definer,
+        docs: &'_ [&'_ str],
+        name: &'_ str,
+        ty: &'_ TypeDesc,
+    ) -> io::Result<()>
+    {
+        ty.define(definer)?;
+        definer.set_symbol_kind(name, SymbolKind::Static);
+        let out = definer.out();
+        self::docs(out, "", docs)?;
+        write!(out, "pub extern const {}: {};\n\n", escape(name), ty.ty())
+    }
+
+    // Zig only has line comments.
+    fn declare_const (self: &'_ Self, definer: &'_ mut dyn Definer, name: &'_ str)
+      -> io::Result<()>
+    {
+        write!(definer.out(),
+            "// `{}`: exported constants are only emitted in the C headers\n\n",
+            name,
+        )
+    }
+}
+
+/// Emits the definition of an `extern struct` or of an `extern union`.
+fn define_aggregate (
+    out: &'_ mut dyn io::Write,
+    me: &'_ TypeDesc,
+    docs: &'_ [&'_ str],
+    fields: &'_ [Field<'_>],
+    kind: &'_ str,
+) -> io::Result<()>
+{
+    self::docs(out, "", docs)?;
+    writeln!(out, "pub const {} = extern {} {{", me.ty(), kind)?;
+    for field in fields {
+        self::docs(out, "    ", field.docs)?;
+        writeln!(out, "    {}: {},", escape(field.name), field.ty.ty())?;
+    }
+    writeln!(out, "}};\n")
+}
@@ -0,0 +1,272 @@
+//! Hot-reload support for `cdylib`-based plugins.
+//!
+//! A host calling the exported functions of a plugin directly cannot swap
+//! the plugin for a new version of it without re-resolving every symbol.
+//! Instead, the plugin exports its functions through an indirection table:
+//! a `#[repr(C)]` struct of function pointers (typically, a
+//! `#[derive_ReprC(callback_table)]` one), living in an
+//! [`FfiLazy`][`crate::sync::FfiLazy`] `static`.
+//!
+//! The [`ffi_export_hot_reload!`][`crate::ffi_export_hot_reload`] macro then
+//! exports:
+//!
+//!   - a function returning a pointer to that table;
+//!
+//!   - a `migrate_state(old_table)` hook, called on the freshly loaded
+//!     version of the plugin with the table of the previous one, so that it
+//!     can take over its state (by calling into the old table).
+//!
+//! On the host side, a [`HotSwap`] holds the table currently in use, and
+//! [`HotSwap::reload`] swaps it for the one of a new version of the plugin,
+//! once its `migrate_state` hook has succeeded. Since a table only lives as
+//! long as its library stays loaded, which only the host knows about, it is
+//! handed around as a raw [`NonNull`][`::core::ptr::NonNull`] pointer.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ::core::sync::atomic::{AtomicU64, Ordering};
+//! use ::safer_ffi::{prelude::*, hot_reload::HotSwap, sync::FfiLazy};
+//!
+//! #[derive_ReprC(callback_table)]
+//! #[repr(C)]
+//! pub
+//! struct PluginApi {
+//!     pub get_count: repr_c::FnPtr<(), u64>,
+//!     pub set_count: repr_c::FnPtr<(u64,)>,
+//! }
+//!
+//! // Plugin side:
+//! static COUNT: AtomicU64 = AtomicU64::new(0);
+//!
+//! extern "C" fn get_count () -> u64 { COUNT.load(Ordering::Relaxed) }
+//! extern "C" fn set_count (n: u64) { COUNT.store(n, Ordering::Relaxed) }
+//!
+//! static API: FfiLazy<PluginApi> = FfiLazy::new(|| PluginApi {
+//!     get_count: repr_c::FnPtr::from(get_count as extern "C" fn() -> _),
+//!     set_count: repr_c::FnPtr::from(set_count as extern "C" fn(_)),
+//! });
+//!
+//! fn migrate (old: &'_ PluginApi) -> bool
+//! {
+//!     old.get_count().map(|n| set_count(n)).is_ok()
+//! }
+//!
+//! ::safer_ffi::ffi_export_hot_reload! {
+//!     API: PluginApi => {
+//!         table: myplugin_api,
+//!         migrate_state: myplugin_migrate_state = migrate,
+//!     }
+//! }
+//!
+//! # fn main () {
+//! // Host side (with the symbols of the newly loaded plugin):
+//! static PLUGIN: HotSwap<PluginApi> = HotSwap::new();
+//!
+//! let new_table = myplugin_api().unwrap();
+//! unsafe {
+//!     // Safety: the plugin is never unloaded.
+//!     PLUGIN.reload(new_table.into(), |old| myplugin_migrate_state(old)).unwrap();
+//!     assert_eq!(PLUGIN.get().unwrap().as_ref().get_count(), Ok(0));
+//! }
+//! # }
+//! ```
+//!
+//! exports:
+//!
+//! ```C
+//! PluginApi_t const * myplugin_api (void);
+//!
+//! bool myplugin_migrate_state (
+//!     PluginApi_t const * old_table);
+//! ```
+
+use_prelude!();
+use ::core::{
+    ptr,
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+/// The indirection table of the version of a plugin currently in use.
+///
+/// Reloads are expected to be serialized by the host, since a concurrent
+/// one makes [`.reload()`][`HotSwap::reload`] fail.
+pub
+struct HotSwap<Table : 'static> {
+    current: AtomicPtr<Table>,
+}
+
+/// Error returned by [`HotSwap::reload`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub
+enum ReloadError {
+    /// The `migrate_state` hook of the new version of the plugin failed: the
+    /// previous table is still in use.
+    MigrationFailed,
+    /// The table has been swapped by another reload in the meantime.
+    ConcurrentReload,
+}
+
+impl fmt::Display
+    for ReloadError
+{
+    fn fmt (self: &'_ Self, fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        fmt.write_str(match *self {
+            | ReloadError::MigrationFailed => "failed to migrate the state",
+            | ReloadError::ConcurrentReload => "concurrent reload",
+        })
+    }
+}
+
+impl ::std::error::Error
+    for ReloadError
+{}
+
+impl<Table> HotSwap<Table> {
+    /// A `HotSwap` without any table yet.
+    pub
+    const
+    fn new ()
+      -> Self
+    {
+        HotSwap {
+            current: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+}
+
+impl<Table : Sync> HotSwap<Table> {
+    /// The table currently in use, if any.
+    ///
+    /// It may only be dereferenced while the library it belongs to is loaded
+    /// (see [`.reload()`][`HotSwap::reload`]).
+    pub
+    fn get (self: &'_ Self)
+      -> Option<ptr::NonNull<Table>>
+    {
+        ptr::NonNull::new(self.current.load(Ordering::Acquire))
+    }
+
+    /// Runs the `migrate_state` hook of the new version of the plugin with
+    /// the current table (`None` on the first load), and, on success, swaps
+    /// it for `new_table`.
+    ///
+    /// Returns the previous table: the library it belongs to may only be
+    /// unloaded once no thread uses it anymore.
+    ///
+    /// # Safety
+    ///
+    /// `new_table` must point to a table that stays valid, _i.e._, whose
+    /// library stays loaded, until it has been swapped out by a subsequent
+    /// reload _and_ no thread uses it anymore (including through a pointer
+    /// returned by [`.get()`][`HotSwap::get`]).
+    pub
+    unsafe
+    fn reload (
+        self: &'_ Self,
+        new_table: ptr::NonNull<Table>,
+        migrate_state: impl FnOnce(Option<&'_ Table>) -> bool,
+    ) -> Result<Option<ptr::NonNull<Table>>, ReloadError>
+    {
+        let old_table = self.get();
+        // Safety: the current table is still valid, as per the contract of
+        // the reload that stored it.
+        if migrate_state(old_table.map(|it| &*it.as_ptr())).not() {
+            return Err(ReloadError::MigrationFailed);
+        }
+        let old_ptr = old_table.map_or(ptr::null_mut(), ptr::NonNull::as_ptr);
+        let new_ptr = new_table.as_ptr();
+        match self.current.compare_exchange(
+            old_ptr, new_ptr, Ordering::AcqRel, Ordering::Acquire,
+        )
+        {
+            | Ok(_) => Ok(old_table),
+            | Err(_) => Err(ReloadError::ConcurrentReload),
+        }
+    }
+}
+
+impl<Table> Default
+    for HotSwap<Table>
+{
+    #[inline]
+    fn default ()
+      -> Self
+    {
+        Self::new()
+    }
+}
+
+impl<Table> fmt::Debug
+    for HotSwap<Table>
+{
+    fn fmt (self: &'_ Self, fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        fmt .debug_struct("HotSwap")
+            .field("current", &self.current)
+            .finish()
+    }
+}
+
+/// Exports the indirection table of a plugin, living in an
+/// [`FfiLazy`][`crate::sync::FfiLazy`] `static`, and its `migrate_state`
+/// hook (see [the `hot_reload` module][`crate::hot_reload`]).
+///
+/// ```rust,ignore
+/// ::safer_ffi::ffi_export_hot_reload! {
+///     API: PluginApi => {
+///         table: myplugin_api,
+///         migrate_state: myplugin_migrate_state = migrate,
+///     }
+/// }
+/// ```
+///
+/// where `migrate` is a `fn(&PluginApi) -> bool` taking over the state of
+/// the previous version of the plugin, exports:
+///
+/// ```C
+/// PluginApi_t const * myplugin_api (void);
+///
+/// bool myplugin_migrate_state (
+///     PluginApi_t const * old_table);
+/// ```
+///
+///   - `table` returns `NULL` if the initialization of the table fails;
+///
+///   - `migrate_state` returns whether the migration succeeded (it always
+///     does when `old_table` is `NULL`, _i.e._, on the first load).
+#[macro_export]
+macro_rules! ffi_export_hot_reload {(
+    $TABLE:path : $Table:ty => {
+        table: $table:ident,
+        migrate_state: $migrate_state:ident = $migrate:expr $(,)?
+    } $(;)?
+) => (
+    #[$crate::ffi_export]
+    /// Returns the indirection table of the plugin, or `NULL` on error.
+    pub
+    fn $table ()
+      -> $crate::core::option::Option<&'static $Table>
+    {
+        $TABLE.get().ok()
+    }
+
+    #[$crate::ffi_export]
+    /// Takes over the state of the previous version of the plugin, through
+    /// its `old_table`, returning whether it succeeded.
+    pub
+    fn $migrate_state (old_table: $crate::core::option::Option<&'_ $Table>)
+      -> bool
+    {
+        match old_table {
+            | $crate::core::option::Option::Some(old_table) => {
+                let migrate: fn(&'_ $Table) -> bool = $migrate;
+                migrate(old_table)
+            },
+            | $crate::core::option::Option::None => true,
+        }
+    }
+)}
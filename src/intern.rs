@@ -0,0 +1,258 @@
+//! Process-wide string interning, shared with C.
+//!
+//! Interning a string yields a 4-byte [`InternedId`], which is cheap to copy,
+//! compare and hash, so that hot paths can pass these ids across the FFI
+//! boundary rather than strings. The interned strings are never freed, so
+//! that [resolving][`InternedId::resolve_c`] an id yields a `char const *`
+//! valid for the remainder of the program, without any copy.
+//!
+//! The C side interns and resolves strings through the functions exported by
+//! [`ffi_export_interning!`][`crate::ffi_export_interning`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use ::safer_ffi::{prelude::*, intern::Symbol};
+//!
+//! ::safer_ffi::ffi_export_interning! {
+//!     intern: mylib_intern,
+//!     resolve: mylib_resolve,
+//! }
+//!
+//! #[ffi_export]
+//! /// Whether the event is a click.
+//! fn mylib_is_click (event: Symbol) -> bool
+//! {
+//!     Symbol::get("click") == Some(event)
+//! }
+//!
+//! # fn main () {
+//! let click = Symbol::intern("click").unwrap();
+//! assert_eq!(click.resolve(), Some("click"));
+//! assert!(mylib_is_click(click));
+//! # }
+//! ```
+//!
+//! exports:
+//!
+//! ```C
+//! typedef uint32_t InternedId_t;
+//!
+//! InternedId_t mylib_intern (
+//!     char const * s);
+//!
+//! char const * mylib_resolve (
+//!     InternedId_t id);
+//!
+//! bool mylib_is_click (
+//!     InternedId_t event);
+//! ```
+
+use_prelude!();
+use ::std::collections::HashMap;
+use crate::sync::{AccessError, FfiMutex};
+
+/// The id of an interned string (`0` being the id of none).
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub
+struct InternedId(u32);
+
+crate::__newtype_ReprC__! {
+    /// The id of an interned string (`0` being the id of none).
+    InternedId(u32)
+}
+
+/// The Rust-side name of an [`InternedId`].
+pub
+type Symbol = InternedId;
+
+/// The error returned by [`InternedId::intern`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub
+enum InternError {
+    /// The string contains a nul byte, and thus cannot be resolved to a C
+    /// string.
+    InteriorNul,
+
+    /// All the (`u32`) ids have been used.
+    Full,
+
+    /// The table could not be accessed.
+    Access(AccessError),
+}
+
+impl fmt::Display
+    for InternError
+{
+    fn fmt (self: &'_ Self, fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        match *self {
+            | InternError::InteriorNul => fmt.write_str(
+                "Cannot intern a string with an interior nul byte",
+            ),
+            | InternError::Full => fmt.write_str(
+                "Too many interned strings",
+            ),
+            | InternError::Access(ref err) => fmt::Display::fmt(err, fmt),
+        }
+    }
+}
+
+impl ::std::error::Error
+    for InternError
+{}
+
+struct Table {
+    /// The interned strings, nul-terminated, the one of id `n` being at
+    /// index `n - 1`.
+    strings: rust::Vec<&'static str>,
+
+    /// The id of each interned string (without its nul terminator).
+    ids: Option<HashMap<&'static str, u32>>,
+}
+
+static TABLE: FfiMutex<Table> = FfiMutex::new(Table {
+    strings: rust::Vec::new(),
+    ids: None,
+});
+
+impl InternedId {
+    /// The id of none.
+    pub
+    const NONE: InternedId = InternedId(0);
+
+    /// Interns `s` (if need be), and returns its id.
+    pub
+    fn intern (s: &'_ str)
+      -> Result<InternedId, InternError>
+    {
+        if s.contains('\0') {
+            return Err(InternError::InteriorNul);
+        }
+        let mut table = TABLE.lock().map_err(InternError::Access)?;
+        let table = &mut *table;
+        let ids = table.ids.get_or_insert_with(HashMap::new);
+        if let Some(&id) = ids.get(s) {
+            return Ok(InternedId(id));
+        }
+        let id = table.strings.len() as u64 + 1;
+        if id > u64::from(u32::MAX) {
+            return Err(InternError::Full);
+        }
+        let interned: &'static str =
+            rust::Box::leak(format!("{}\0", s).into_boxed_str())
+        ;
+        table.strings.push(interned);
+        ids.insert(&interned[.. s.len()], id as u32);
+        Ok(InternedId(id as u32))
+    }
+
+    /// The id of `s`, if it has been interned.
+    pub
+    fn get (s: &'_ str)
+      -> Option<InternedId>
+    {
+        TABLE.lock().ok()?
+            .ids.as_ref()?
+            .get(s)
+            .map(|&id| InternedId(id))
+    }
+
+    /// The interned string, if any.
+    pub
+    fn resolve (self: Self)
+      -> Option<&'static str>
+    {
+        self.resolve_with_nul().map(|s| &s[.. s.len() - 1])
+    }
+
+    /// The interned string, as a C string, if any.
+    pub
+    fn resolve_c (self: Self)
+      -> Option<char_p::Ref<'static>>
+    {
+        self.resolve_with_nul()
+            .and_then(|s| char_p::Ref::from_str_with_nul(s).ok())
+    }
+
+    /// The raw value of the id.
+    #[inline]
+    pub
+    fn as_u32 (self: Self)
+      -> u32
+    {
+        self.0
+    }
+
+    fn resolve_with_nul (self: Self)
+      -> Option<&'static str>
+    {
+        let index = (self.0 as usize).checked_sub(1)?;
+        TABLE.lock().ok()?.strings.get(index).copied()
+    }
+}
+
+impl Default
+    for InternedId
+{
+    #[inline]
+    fn default ()
+      -> InternedId
+    {
+        InternedId::NONE
+    }
+}
+
+/// Exports the functions interning and resolving strings (see
+/// [`intern`][`crate::intern`]).
+///
+/// ```rust,ignore
+/// ::safer_ffi::ffi_export_interning! {
+///     intern: mylib_intern,
+///     resolve: mylib_resolve,
+/// }
+/// ```
+///
+/// exports:
+///
+/// ```C
+/// typedef uint32_t InternedId_t;
+///
+/// InternedId_t mylib_intern (
+///     char const * s);
+///
+/// char const * mylib_resolve (
+///     InternedId_t id);
+/// ```
+///
+///   - `intern` returns the id of the string, or `0` on error;
+///
+///   - `resolve` returns the interned string (valid until the end of the
+///     program), or `NULL` for an unknown id.
+#[macro_export]
+macro_rules! ffi_export_interning {(
+    intern: $intern:ident,
+    resolve: $resolve:ident $(,)?
+) => (
+    #[$crate::ffi_export]
+    /// Interns the string (if need be), and returns its id, or `0` on error.
+    pub
+    fn $intern (s: $crate::char_p::char_p_ref<'_>)
+      -> $crate::intern::InternedId
+    {
+        $crate::intern::InternedId::intern(s.to_str())
+            .unwrap_or($crate::intern::InternedId::NONE)
+    }
+
+    #[$crate::ffi_export]
+    /// Returns the interned string of the given id (valid until the end of
+    /// the program), or `NULL` if there is none.
+    pub
+    fn $resolve (id: $crate::intern::InternedId)
+      -> $crate::core::option::Option<$crate::char_p::char_p_ref<'static>>
+    {
+        id.resolve_c()
+    }
+)}
@@ -11,6 +11,8 @@ mod macros;
 
 #[doc(inline)]
 pub use crate::{from_CType_impl_ReprC, ReprC, CType};
+#[doc(hidden)] /* Not part of the public API */ pub
+use crate::__field_offsets__;
 
 cfg_proc_macros! {
     pub use ::proc_macro::{
@@ -26,6 +28,15 @@ type_level_enum! {
     }
 }
 
+/// `offset` rounded up to a multiple of `align` (a power of two).
+#[doc(hidden)] /** Not part of the public API **/ pub
+const
+fn __round_up__ (offset: usize, align: usize)
+  -> usize
+{
+    (offset + align - 1) & !(align - 1)
+}
+
 pub
 fn __assert_concrete__<T>() where
     T : ReprC,
@@ -81,6 +92,13 @@ unsafe trait CType
     Copy +
 {
     type OPAQUE_KIND : OpaqueKind::__;
+
+    /// The size of the type, in bytes (usable in `const` contexts).
+    const SIZE: usize = ::core::mem::size_of::<Self>();
+
+    /// The alignment of the type, in bytes (usable in `const` contexts).
+    const ALIGN: usize = ::core::mem::align_of::<Self>();
+
     __cfg_headers__! {
         /// A short-name description of the type, mainly used to fill
         /// "placeholders" such as when monomorphising generics structs or
@@ -360,6 +378,33 @@ unsafe trait CType
                 _phantom: Default::default(),
             }
         }
+
+        /// The type the type is mapped to, in the bindings of the languages
+        /// other than C (see [`Language`][`crate::headers::Language`]).
+        ///
+        /// It defaults to `<short_name>_t`, the name of the type defined by
+        /// the default implementation of [`CType::binding_define_self`]`()`.
+        #[inline]
+        fn binding_ty ()
+          -> rust::String
+        {
+            crate::headers::__type_name__(&Self::c_short_name())
+        }
+
+        /// The counterpart of [`CType::c_define_self`]`()` for the languages
+        /// other than C, for [`CType::binding_ty`]`()` to make sense.
+        ///
+        /// It defaults to defining `<short_name>_t` as a type with the same
+        /// size and alignment as `Self` (but no visible fields), so that it
+        /// can at least be passed around by value.
+        fn binding_define_self (definer: &'_ mut dyn Definer)
+          -> io::Result<()>
+        {
+            crate::headers::__backend__().define_bytes(
+                definer,
+                &crate::headers::TypeDesc::of::<Self>(),
+            )
+        }
     }
 }
 
@@ -668,6 +713,25 @@ trait ReprC : Sized {
     fn is_valid (it: &'_ Self::CLayout)
       -> bool
     ;
+
+    /// Turns an instance of the `CType` layout deemed invalid by
+    /// [`ReprC::is_valid`] into a valid one, when there is a sensible way to
+    /// do so, returning whether it did.
+    ///
+    /// It is only used under the [`BoundaryPolicy::LogAndClamp`] policy, and
+    /// does nothing by default (there is no sensible way to "clamp" a
+    /// dangling pointer, for instance); [`Bounded`] integers are clamped to
+    /// their range.
+    ///
+    /// [`BoundaryPolicy::LogAndClamp`]: `crate::boundary::BoundaryPolicy::LogAndClamp`
+    /// [`Bounded`]: `crate::bounded::Bounded`
+    #[inline]
+    fn clamp (it: &'_ mut Self::CLayout)
+      -> bool
+    {
+        let _ = it;
+        false
+    }
 }
 
 #[doc(hidden)] /** For clarity;
@@ -739,6 +803,50 @@ fn from_raw<T : ReprC> (c_layout: T::CLayout)
     }
 }
 
+/// Error of [`try_from_raw`], when the `CLayout` value is not a valid
+/// bit-pattern for the `ReprC` type (_e.g._, an out-of-range discriminant).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub
+struct InvalidBitPattern {
+    /// The name of the `ReprC` type.
+    pub type_name: &'static str,
+}
+
+impl fmt::Display
+    for InvalidBitPattern
+{
+    fn fmt (self: &'_ Self, fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        write!(fmt,
+            "not a valid bit-pattern for the type `{}`", self.type_name,
+        )
+    }
+}
+
+cfg_std! {
+    impl ::std::error::Error
+        for InvalidBitPattern
+    {}
+}
+
+/// Same as [`from_raw`], but with an [`InvalidBitPattern`] error (naming the
+/// type) rather than `None`, _e.g._, to `?` it into a [`crate::Error`].
+///
+/// # Safety
+///
+/// Same as [`from_raw`].
+#[inline]
+pub
+unsafe
+fn try_from_raw<T : ReprC> (c_layout: T::CLayout)
+  -> Result<T, InvalidBitPattern>
+{
+    from_raw::<T>(c_layout).ok_or(InvalidBitPattern {
+        type_name: ::core::any::type_name::<T>(),
+    })
+}
+
 #[cfg_attr(all(feature = "proc_macros", not(docs)),
     require_unsafe_in_body,
 )]
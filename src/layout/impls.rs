@@ -12,10 +12,10 @@ const _: () = { macro_rules! impl_CTypes {
         impl_CTypes! { @zsts }
         impl_CTypes! { @floats
             unsafe
-            f32 => "float",
+            f32 => "float" / "_Float32",
 
             unsafe
-            f64 => "double",
+            f64 => "double" / "_Float64",
         }
         impl_CTypes! { @integers
 
@@ -118,11 +118,11 @@ const _: () = { macro_rules! impl_CTypes {
                     &mut |definer| {
                         Item::c_define_self(definer)?;
                         write!(definer.out(),
-                            "typedef struct {{ {}; }} {}_t;\n\n",
+                            "typedef struct {{ {}; }} {};\n\n",
                             Item::c_var(concat!(
                                 "idx[", stringify!($N), "]",
                             )),
-                            short_name,
+                            crate::headers::__type_name__(short_name),
                         )
                     }
                 )
@@ -135,12 +135,33 @@ const _: () = { macro_rules! impl_CTypes {
             {
                 // _e.g._, item_N_array_t
                 write!(fmt,
-                    "{}_t{sep}{}",
-                    Self::c_short_name(),
+                    "{}{sep}{}",
+                    crate::headers::__type_name__(&Self::c_short_name()),
                     var_name,
                     sep = if var_name.is_empty() { "" } else { " " },
                 )
             }
+
+            fn binding_ty ()
+              -> rust::String
+            {
+                crate::headers::__backend__().array_ty(
+                    &crate::headers::__type_name__(&Self::c_short_name()),
+                    &crate::headers::TypeDesc::of::<Item>(),
+                    $N,
+                )
+            }
+
+            fn binding_define_self (definer: &'_ mut dyn Definer)
+              -> io::Result<()>
+            {
+                crate::headers::__backend__().define_array(
+                    definer,
+                    &crate::headers::TypeDesc::of::<Self>(),
+                    &crate::headers::TypeDesc::of::<Item>(),
+                    $N,
+                )
+            }
         } type OPAQUE_KIND = OpaqueKind::Concrete; }
 
         // ReprC
@@ -207,6 +228,15 @@ const _: () = { macro_rules! impl_CTypes {
                 Ret::c_define_self(definer)?; $(
                 $An::c_define_self(definer)?; $(
                 $Ai::c_define_self(definer)?; )*)?
+                if let Some(ref name) =
+                    crate::headers::__fn_ptr_typedef__(&Self::c_short_name())
+                {
+                    definer.define_once(name, &mut |definer| {
+                        writeln!(definer.out(),
+                            "typedef {};\n", Self::c_var(name),
+                        )
+                    })?;
+                }
                 Ok(())
             }
 
@@ -215,6 +245,17 @@ const _: () = { macro_rules! impl_CTypes {
                 var_name: &'_ str,
             ) -> fmt::Result
             {
+                // The `typedef` itself being the inline declaration of its
+                // name.
+                match crate::headers::__fn_ptr_typedef__(&Self::c_short_name()) {
+                    | Some(ref name) if name != var_name => return write!(fmt,
+                        "{}{sep}{}",
+                        name,
+                        var_name,
+                        sep = if var_name.is_empty() { "" } else { " " },
+                    ),
+                    | _ => {},
+                }
                 write!(fmt, "{} ", Ret::c_var(""))?;
                 write!(fmt, "(*{})(", var_name)?;
                 let _empty = true; $(
@@ -226,6 +267,33 @@ const _: () = { macro_rules! impl_CTypes {
                 }
                 fmt.write_str(")")
             }
+
+            fn binding_ty ()
+              -> rust::String
+            {
+                crate::headers::__backend__().fn_ptr_ty(
+                    &crate::headers::TypeDesc::of::<Ret>(),
+                    &[$(
+                        crate::headers::TypeDesc::of::<$An>(), $(
+                        crate::headers::TypeDesc::of::<$Ai>(), )*)?
+                    ],
+                    false,
+                )
+            }
+
+            fn binding_define_self (definer: &'_ mut dyn Definer)
+              -> io::Result<()>
+            {
+                crate::headers::__backend__().define_fn_ptr(
+                    definer,
+                    &crate::headers::TypeDesc::of::<Ret>(),
+                    &[$(
+                        crate::headers::TypeDesc::of::<$An>(), $(
+                        crate::headers::TypeDesc::of::<$Ai>(), )*)?
+                    ],
+                    false,
+                )
+            }
         } type OPAQUE_KIND = OpaqueKind::Concrete; }
 
         /// Simplified for lighter documentation, but the actual impls include
@@ -276,6 +344,17 @@ const _: () = { macro_rules! impl_CTypes {
             }
         }
 
+        #[cfg(feature = "c-unwind")]
+        impl_CTypes! {
+            @unwind_fns
+            ($($An, $($Ai ,)*)?)
+        }
+
+        impl_CTypes! {
+            @system_fns
+            ($($An, $($Ai ,)*)?)
+        }
+
         /* == ReprC for Option-less == */
 
         /// Simplified for lighter documentation, but the actual impls include
@@ -365,6 +444,311 @@ const _: () = { macro_rules! impl_CTypes {
         }
     );
 
+    (@unwind_fns
+        (
+            $(
+                $An:ident $(,
+                $Ai:ident)* $(,)?
+            )?
+        )
+    ) => (
+        /// Simplified for lighter documentation, but the actual impls include
+        /// **up to 9 function parameters**.
+        unsafe // Safety: same layout (and C declaration) as the `"C"` ABI
+               // function pointer; the `CLayout` is never called through.
+        impl<
+            Ret : ReprC, $(
+            $An : ReprC, $(
+            $Ai : ReprC,
+        )*)?> ReprC
+            for Option<unsafe extern "C-unwind" fn ($($An, $($Ai ,)*)?) -> Ret>
+        {
+            type CLayout = Option<
+                unsafe extern "C"
+                fn ($($An::CLayout, $($Ai::CLayout ,)*)?) -> Ret::CLayout
+            >;
+
+            #[inline]
+            fn is_valid (_: &'_ Self::CLayout)
+              -> bool
+            {
+                true
+            }
+        }
+
+        /// Simplified for lighter documentation, but the actual impls include
+        /// **up to 9 function parameters**.
+        unsafe // Safety: same layout (and C declaration) as the `"C"` ABI
+               // function pointer; the `CLayout` is never called through.
+        impl<
+            Ret : ReprC, $(
+            $An : ReprC, $(
+            $Ai : ReprC,
+        )*)?> ReprC
+            for Option</*unsafe*/ extern "C-unwind" fn ($($An, $($Ai ,)*)?) -> Ret>
+        {
+            type CLayout = Option<
+                unsafe extern "C"
+                fn ($($An::CLayout, $($Ai::CLayout ,)*)?) -> Ret::CLayout
+            >;
+
+            #[inline]
+            fn is_valid (_: &'_ Self::CLayout)
+              -> bool
+            {
+                true
+            }
+        }
+
+        /// Simplified for lighter documentation, but the actual impls include
+        /// **up to 9 function parameters**.
+        unsafe // Safety: same layout (and C declaration) as the `"C"` ABI
+               // function pointer; the `CLayout` is never called through.
+        impl<
+            Ret : ReprC, $(
+            $An : ReprC, $(
+            $Ai : ReprC,
+        )*)?> ReprC
+            for unsafe extern "C-unwind" fn ($($An, $($Ai ,)*)?) -> Ret
+        {
+            type CLayout = Option<
+                unsafe extern "C"
+                fn ($($An::CLayout, $($Ai::CLayout ,)*)?) -> Ret::CLayout
+            >;
+
+            #[inline]
+            fn is_valid (c_layout: &'_ Self::CLayout)
+              -> bool
+            {
+                c_layout.is_some()
+            }
+        }
+
+        /// Simplified for lighter documentation, but the actual impls include
+        /// **up to 9 function parameters**.
+        unsafe // Safety: same layout (and C declaration) as the `"C"` ABI
+               // function pointer; the `CLayout` is never called through.
+        impl<
+            Ret : ReprC, $(
+            $An : ReprC, $(
+            $Ai : ReprC,
+        )*)?> ReprC
+            for /*unsafe*/ extern "C-unwind" fn ($($An, $($Ai ,)*)?) -> Ret
+        {
+            type CLayout = Option<
+                unsafe extern "C"
+                fn ($($An::CLayout, $($Ai::CLayout ,)*)?) -> Ret::CLayout
+            >;
+
+            #[inline]
+            fn is_valid (c_layout: &'_ Self::CLayout)
+              -> bool
+            {
+                c_layout.is_some()
+            }
+        }
+    );
+
+    (@system_fns
+        (
+            $(
+                $An:ident $(,
+                $Ai:ident)* $(,)?
+            )?
+        )
+    ) => (
+        /// Simplified for lighter documentation, but the actual impls include
+        /// **up to 9 function parameters**.
+        unsafe // Safety: same as for the `"C"` ABI function pointers.
+        impl<
+            Ret : CType, $(
+            $An : CType, $(
+            $Ai : CType,
+        )*)?> CType
+            for Option<unsafe extern "system" fn ($($An, $($Ai ,)*)?) -> Ret>
+        { __cfg_headers__! {
+            fn c_short_name_fmt (fmt: &'_ mut fmt::Formatter<'_>)
+              -> fmt::Result
+            {
+                // ret_t_arg1_t_arg2_t_system_fptr
+                Ret::c_short_name_fmt(fmt)?; $(
+                write!(fmt, "_{}", $An::c_short_name())?; $(
+                write!(fmt, "_{}", $Ai::c_short_name())?; )*)?
+                fmt.write_str("_system_fptr")
+            }
+
+            fn c_define_self (definer: &'_ mut dyn Definer)
+              -> io::Result<()>
+            {
+                Ret::c_define_self(definer)?; $(
+                $An::c_define_self(definer)?; $(
+                $Ai::c_define_self(definer)?; )*)?
+                crate::headers::__define_system_abi__(definer)?;
+                if let Some(ref name) =
+                    crate::headers::__fn_ptr_typedef__(&Self::c_short_name())
+                {
+                    definer.define_once(name, &mut |definer| {
+                        writeln!(definer.out(),
+                            "typedef {};\n", Self::c_var(name),
+                        )
+                    })?;
+                }
+                Ok(())
+            }
+
+            fn c_var_fmt (
+                fmt: &'_ mut fmt::Formatter<'_>,
+                var_name: &'_ str,
+            ) -> fmt::Result
+            {
+                // The `typedef` itself being the inline declaration of its
+                // name.
+                match crate::headers::__fn_ptr_typedef__(&Self::c_short_name()) {
+                    | Some(ref name) if name != var_name => return write!(fmt,
+                        "{}{sep}{}",
+                        name,
+                        var_name,
+                        sep = if var_name.is_empty() { "" } else { " " },
+                    ),
+                    | _ => {},
+                }
+                write!(fmt, "{} ", Ret::c_var(""))?;
+                write!(fmt, "(SAFER_FFI_SYSTEM_ABI *{})(", var_name)?;
+                let _empty = true; $(
+                let _empty = false;
+                write!(fmt, "{}", $An::c_var(""))?; $(
+                write!(fmt, ", {}", $Ai::c_var(""))?; )*)?
+                if _empty {
+                    fmt.write_str("void")?;
+                }
+                fmt.write_str(")")
+            }
+
+            fn binding_ty ()
+              -> rust::String
+            {
+                crate::headers::__backend__().fn_ptr_ty(
+                    &crate::headers::TypeDesc::of::<Ret>(),
+                    &[$(
+                        crate::headers::TypeDesc::of::<$An>(), $(
+                        crate::headers::TypeDesc::of::<$Ai>(), )*)?
+                    ],
+                    true,
+                )
+            }
+
+            fn binding_define_self (definer: &'_ mut dyn Definer)
+              -> io::Result<()>
+            {
+                crate::headers::__backend__().define_fn_ptr(
+                    definer,
+                    &crate::headers::TypeDesc::of::<Ret>(),
+                    &[$(
+                        crate::headers::TypeDesc::of::<$An>(), $(
+                        crate::headers::TypeDesc::of::<$Ai>(), )*)?
+                    ],
+                    true,
+                )
+            }
+        } type OPAQUE_KIND = OpaqueKind::Concrete; }
+
+        /// Simplified for lighter documentation, but the actual impls include
+        /// **up to 9 function parameters**.
+        unsafe // Safety: byte-wise the layout is the same, but the safety
+               // invariants will still have to be checked at each site.
+        impl<
+            Ret : ReprC, $(
+            $An : ReprC, $(
+            $Ai : ReprC,
+        )*)?> ReprC
+            for Option<unsafe extern "system" fn ($($An, $($Ai ,)*)?) -> Ret>
+        {
+            type CLayout = Option<
+                unsafe extern "system"
+                fn ($($An::CLayout, $($Ai::CLayout ,)*)?) -> Ret::CLayout
+            >;
+
+            #[inline]
+            fn is_valid (_: &'_ Self::CLayout)
+              -> bool
+            {
+                true
+            }
+        }
+
+        /// Simplified for lighter documentation, but the actual impls include
+        /// **up to 9 function parameters**.
+        unsafe // Safety: byte-wise the layout is the same, but the safety
+               // invariants will still have to be checked at each site.
+        impl<
+            Ret : ReprC, $(
+            $An : ReprC, $(
+            $Ai : ReprC,
+        )*)?> ReprC
+            for Option</*unsafe*/ extern "system" fn ($($An, $($Ai ,)*)?) -> Ret>
+        {
+            type CLayout = Option<
+                unsafe extern "system"
+                fn ($($An::CLayout, $($Ai::CLayout ,)*)?) -> Ret::CLayout
+            >;
+
+            #[inline]
+            fn is_valid (_: &'_ Self::CLayout)
+              -> bool
+            {
+                true
+            }
+        }
+
+        /// Simplified for lighter documentation, but the actual impls include
+        /// **up to 9 function parameters**.
+        unsafe // Safety: byte-wise the layout is the same, but the safety
+               // invariants will still have to be checked at each site.
+        impl<
+            Ret : ReprC, $(
+            $An : ReprC, $(
+            $Ai : ReprC,
+        )*)?> ReprC
+            for unsafe extern "system" fn ($($An, $($Ai ,)*)?) -> Ret
+        {
+            type CLayout = Option<
+                unsafe extern "system"
+                fn ($($An::CLayout, $($Ai::CLayout ,)*)?) -> Ret::CLayout
+            >;
+
+            #[inline]
+            fn is_valid (c_layout: &'_ Self::CLayout)
+              -> bool
+            {
+                c_layout.is_some()
+            }
+        }
+
+        /// Simplified for lighter documentation, but the actual impls include
+        /// **up to 9 function parameters**.
+        unsafe // Safety: byte-wise the layout is the same, but the safety
+               // invariants will still have to be checked at each site.
+        impl<
+            Ret : ReprC, $(
+            $An : ReprC, $(
+            $Ai : ReprC,
+        )*)?> ReprC
+            for /*unsafe*/ extern "system" fn ($($An, $($Ai ,)*)?) -> Ret
+        {
+            type CLayout = Option<
+                unsafe extern "system"
+                fn ($($An::CLayout, $($Ai::CLayout ,)*)?) -> Ret::CLayout
+            >;
+
+            #[inline]
+            fn is_valid (c_layout: &'_ Self::CLayout)
+              -> bool
+            {
+                c_layout.is_some()
+            }
+        }
+    );
+
     (@integers
         $(
             $unsafe:tt
@@ -386,14 +770,18 @@ const _: () = { macro_rules! impl_CTypes {
             {
                 definer.define_once(
                     "__int_headers__",
-                    &mut |definer| write!(definer.out(),
-                        concat!(
-                            "\n",
-                            "#include <stddef.h>\n",
-                            "#include <stdint.h>\n",
-                            "\n",
-                        ),
-                    ),
+                    &mut |definer| {
+                        definer.set_symbol_kind(
+                            "__int_headers__",
+                            crate::headers::SymbolKind::Include,
+                        );
+                        definer.out().write_all(
+                            crate::headers::context::context()
+                                .c_dialect
+                                .int_headers()
+                                .as_bytes()
+                        )
+                    },
                 )
             }
 
@@ -408,6 +796,18 @@ const _: () = { macro_rules! impl_CTypes {
                     sep = if var_name.is_empty() { "" } else { " " },
                 )
             }
+
+            fn binding_ty ()
+              -> rust::String
+            {
+                crate::headers::__backend__().primitive_ty($CInt).into()
+            }
+
+            fn binding_define_self (_: &'_ mut dyn Definer)
+              -> io::Result<()>
+            {
+                Ok(())
+            }
         } type OPAQUE_KIND = OpaqueKind::Concrete; }
         from_CType_impl_ReprC! { $RustInt }
     )*);
@@ -415,7 +815,7 @@ const _: () = { macro_rules! impl_CTypes {
     (@floats
         $(
             $unsafe:tt
-            $fN:ident => $Cty:literal,
+            $fN:ident => $Cty:literal / $Cty_iec:literal,
         )*
     ) => ($(
         $unsafe // Safety: guaranteed by the caller of the macro
@@ -433,12 +833,29 @@ const _: () = { macro_rules! impl_CTypes {
                 var_name: &'_ str,
             ) -> fmt::Result
             {
+                use crate::headers::{context, FloatTypes};
                 write!(fmt,
-                    concat!($Cty, "{sep}{}"),
+                    "{}{sep}{}",
+                    match context::context().float_types {
+                        | FloatTypes::Standard => $Cty,
+                        | FloatTypes::Iec60559 => $Cty_iec,
+                    },
                     var_name,
                     sep = if var_name.is_empty() { "" } else { " " },
                 )
             }
+
+            fn binding_ty ()
+              -> rust::String
+            {
+                crate::headers::__backend__().primitive_ty($Cty).into()
+            }
+
+            fn binding_define_self (_: &'_ mut dyn Definer)
+              -> io::Result<()>
+            {
+                Ok(())
+            }
         } type OPAQUE_KIND = OpaqueKind::Concrete; }
         from_CType_impl_ReprC! { $fN }
     )*);
@@ -474,6 +891,24 @@ const _: () = { macro_rules! impl_CTypes {
                     sep = if var_name.is_empty() { "" } else { " " },
                 )
             }
+
+            fn binding_ty ()
+              -> rust::String
+            {
+                crate::headers::__backend__().pointer_ty(
+                    &crate::headers::TypeDesc::of::<T>(),
+                    false,
+                )
+            }
+
+            fn binding_define_self (definer: &'_ mut dyn Definer)
+              -> io::Result<()>
+            {
+                crate::headers::__backend__().define_pointer(
+                    definer,
+                    &crate::headers::TypeDesc::of::<T>(),
+                )
+            }
         } type OPAQUE_KIND = OpaqueKind::Concrete; }
         unsafe
         impl<T : ReprC> ReprC
@@ -517,6 +952,24 @@ const _: () = { macro_rules! impl_CTypes {
                     sep = if var_name.is_empty() { "" } else { " " },
                 )
             }
+
+            fn binding_ty ()
+              -> rust::String
+            {
+                crate::headers::__backend__().pointer_ty(
+                    &crate::headers::TypeDesc::of::<T>(),
+                    true,
+                )
+            }
+
+            fn binding_define_self (definer: &'_ mut dyn Definer)
+              -> io::Result<()>
+            {
+                crate::headers::__backend__().define_pointer(
+                    definer,
+                    &crate::headers::TypeDesc::of::<T>(),
+                )
+            }
         } type OPAQUE_KIND = OpaqueKind::Concrete; }
         unsafe
         impl<T : ReprC> ReprC
@@ -632,8 +1085,15 @@ unsafe
                 definer.define_once(
                     "bool",
                     &mut |definer| {
+                        definer.set_symbol_kind(
+                            "bool",
+                            crate::headers::SymbolKind::Include,
+                        );
                         definer.out().write_all(
-                            b"\n#include <stdbool.h>\n\n"
+                            crate::headers::context::context()
+                                .c_dialect
+                                .bool_header()
+                                .as_bytes()
                         )
                     },
                 )
@@ -650,6 +1110,18 @@ unsafe
                     sep = if var_name.is_empty() { "" } else { " " },
                 )
             }
+
+            fn binding_ty ()
+              -> rust::String
+            {
+                crate::headers::__backend__().primitive_ty("bool").into()
+            }
+
+            fn binding_define_self (_: &'_ mut dyn Definer)
+              -> io::Result<()>
+            {
+                Ok(())
+            }
         }
 
         type OPAQUE_KIND = OpaqueKind::Concrete;
@@ -58,6 +58,65 @@ macro_rules! __with_doc__ {
     )
 
 }
+/// Re-exports the `CLayout` type of a `ReprC!` type (at `$path`) under the
+/// given `@layout[<visibility> <Name>]`, if any, or else under the default
+/// one, _i.e._, `<Name>_Layout`, with the visibility of the type.
+#[doc(hidden)] #[macro_export]
+macro_rules! __layout_alias__ {
+    (
+        []
+        [$pub:vis $Default:ident]
+        $($path:tt)*
+    ) => (
+        #[allow(nonstandard_style)]
+        $pub use $($path)* as $Default;
+    );
+
+    (
+        [$layout_vis:vis $Layout:ident]
+        $default:tt
+        $($path:tt)*
+    ) => (
+        #[allow(nonstandard_style)]
+        $layout_vis use $($path)* as $Layout;
+    );
+}
+
+/// Defines the `OFFSET_<field_name>` associated constants of a `#[repr(C)]`
+/// struct, given the end offset of the previous fields.
+#[doc(hidden)] #[macro_export]
+macro_rules! __field_offsets__ {
+    (
+        $prev_end:expr ;
+        $field_name:ident : $field_ty:ty
+        $(, $rest_name:ident : $rest_ty:ty)* $(,)?
+    ) => (
+        $crate::paste::item! {
+            #[doc = $crate::core::concat!(
+                "Offset of the `", $crate::core::stringify!($field_name),
+                "` field, in bytes.",
+            )]
+            pub
+            const [< OFFSET_ $field_name >]: usize = $crate::layout::__round_up__(
+                $prev_end,
+                $crate::core::mem::align_of::<$field_ty>(),
+            );
+        }
+
+        $crate::layout::__field_offsets__! {
+            $crate::layout::__round_up__(
+                $prev_end,
+                $crate::core::mem::align_of::<$field_ty>(),
+            ) + $crate::core::mem::size_of::<$field_ty>() ;
+            $($rest_name : $rest_ty),*
+        }
+    );
+
+    (
+        $prev_end:expr ;
+    ) => ();
+}
+
 /// Safely implement [`CType`][`trait@crate::layout::CType`]
 /// for a `#[repr(C)]` struct **when all its fields are `CType`**.
 ///
@@ -82,6 +141,9 @@ macro_rules! CType {
     )?
     {
         $(
+            $(
+                @doc_meta( $($field_doc_meta:tt)* )
+            )?
             $(#[$($field_meta:tt)*])*
             $field_pub:vis
             $field_name:ident : $field_ty:ty
@@ -118,7 +180,10 @@ macro_rules! CType {
         fn c_short_name_fmt (fmt: &'_ mut $crate::core::fmt::Formatter<'_>)
           -> $crate::core::fmt::Result
         {
-            fmt.write_str($crate::core::stringify!($StructName))?;
+            $crate::headers::__c_short_name__(fmt,
+                $crate::core::stringify!($StructName),
+                $crate::core::module_path!(),
+            )?;
             $($(
                 $(
                     $crate::core::write!(fmt, "_{}",
@@ -150,21 +215,76 @@ macro_rules! CType {
                     $(
                         <$field_ty as $crate::layout::CType>::c_define_self(definer)?;
                     )*
+                    $crate::headers::__lint_item__(definer, me, None, &[$(
+                        $crate::core::stringify!($field_name),
+                    )*]);
+                    $crate::headers::__record_since__(definer, me,
+                        $crate::__doc_strs__!([]
+                            $($($doc_meta)*)?
+                            $(#[$($meta)*])*
+                        ),
+                        &[$(
+                            (
+                                $crate::core::stringify!($field_name),
+                                $crate::__doc_strs__!([]
+                                    $($($field_doc_meta)*)?
+                                    $(#[$($field_meta)*])*
+                                ),
+                            ),
+                        )*],
+                    );
+                    let deprecated = $crate::__deprecated_note__!(
+                        $($($doc_meta)*)?
+                        $(#[$($meta)*])*
+                    );
+                    if deprecated.is_some() {
+                        $crate::headers::__define_deprecated__(definer)?;
+                    }
                     let out = definer.out();
-                    $(
-                        $crate::__output_docs__!(out, "", $($doc_meta)*);
+                    if !$crate::headers::__output_translated_docs__(
+                        out, "", $crate::core::stringify!($StructName),
                     )?
-                    $crate::__output_docs__!(out, "", $(#[$($meta)*])*);
-                    $crate::core::writeln!(out, "typedef struct {{\n")?;
+                    {
+                        $(
+                            $crate::__output_docs__!(out, "", $($doc_meta)*);
+                        )?
+                        $crate::__output_docs__!(out, "", $(#[$($meta)*])*);
+                    }
+                    $crate::core::writeln!(out, "typedef {}struct {{\n",
+                        $crate::headers::__deprecated__(deprecated),
+                    )?;
                     $(
                         if $crate::core::mem::size_of::<$field_ty>() > 0 {
                             // $crate::core::writeln!(out, "")?;
-                            $crate::__output_docs__!(out, "    ",
-                                $(#[$($field_meta)*])*
-                            );
+                            if !$crate::headers::__output_translated_docs__(
+                                out, "    ", $crate::core::concat!(
+                                    $crate::core::stringify!($StructName), ".",
+                                    $crate::core::stringify!($field_name),
+                                ),
+                            )?
+                            {
+                                $crate::__output_docs__!(out, "    ",
+                                    $(#[$($field_meta)*])*
+                                );
+                            }
+                            $(
+                                if let $crate::core::option::Option::Some(since) =
+                                    $crate::headers::__since__(
+                                        $crate::__doc_strs__!([]
+                                            $($field_doc_meta)*
+                                        ),
+                                    )
+                                {
+                                    $crate::core::writeln!(out,
+                                        "    /** \\since {} */", since,
+                                    )?;
+                                }
+                            )?
                             $crate::core::writeln!(out, "    {};\n",
                                 <$field_ty as $crate::layout::CType>::c_var(
-                                    $crate::core::stringify!($field_name),
+                                    &*$crate::headers::__c_escape__(
+                                        $crate::core::stringify!($field_name),
+                                    ),
                                 ),
                             )?;
                         } else {
@@ -178,7 +298,9 @@ macro_rules! CType {
                             );
                         }
                     )+
-                    $crate::core::writeln!(out, "}} {}_t;\n", me)
+                    $crate::core::writeln!(out, "}} {};\n",
+                        $crate::headers::__type_name__(me),
+                    )
                 },
             )
         }
@@ -189,14 +311,54 @@ macro_rules! CType {
         ) -> $crate::core::fmt::Result
         {
             $crate::core::write!(fmt,
-                "{}_t{sep}{}",
-                <Self as $crate::layout::CType>::c_short_name(),
+                "{}{sep}{}",
+                $crate::headers::__type_name__(
+                    &<Self as $crate::layout::CType>::c_short_name(),
+                ),
                 var_name,
                 sep = if var_name.is_empty() { "" } else { " " },
             )
         }
+
+        fn binding_define_self (definer: &'_ mut dyn $crate::headers::Definer)
+          -> $crate::std::io::Result<()>
+        {
+            $crate::headers::__define_struct__(
+                definer,
+                $crate::headers::TypeDesc::of::<Self>(),
+                $crate::__doc_strs__!([]
+                    $($($doc_meta)*)?
+                    $(#[$($meta)*])*
+                ),
+                &[$(
+                    $crate::headers::Field {
+                        name: $crate::core::stringify!($field_name),
+                        docs: $crate::__doc_strs__!([] $(#[$($field_meta)*])*),
+                        ty: $crate::headers::TypeDesc::of::<$field_ty>(),
+                    },
+                )*],
+            )
+        }
     } type OPAQUE_KIND = $crate::layout::OpaqueKind::Concrete; }
 
+    #[allow(nonstandard_style, trivial_bounds)]
+    impl $(<$($lt ,)* $($($generics),+)?>)? $StructName$(<$($lt ,)* $($($generics),+)?>)?
+    where
+        $(
+            $field_ty : $crate::layout::CType,
+        )*
+        $(
+            $($(
+                $generics : $crate::layout::ReprC,
+            )+)?
+            $($($bounds)*)?
+        )?
+    {
+        $crate::layout::__field_offsets__! {
+            0 ; $($field_name : $field_ty),*
+        }
+    }
+
     $crate::layout::from_CType_impl_ReprC! {
         $(@for [$($lt ,)* $($($generics),+)?])?
             $StructName
@@ -259,7 +421,10 @@ macro_rules! CType {
         fn c_short_name_fmt (fmt: &'_ mut $crate::core::fmt::Formatter<'_>)
           -> $crate::core::fmt::Result
         {
-            fmt.write_str($crate::core::stringify!($UnionName))?;
+            $crate::headers::__c_short_name__(fmt,
+                $crate::core::stringify!($UnionName),
+                $crate::core::module_path!(),
+            )?;
             $($(
                 $(
                     $crate::core::write!(fmt, "_{}",
@@ -291,21 +456,48 @@ macro_rules! CType {
                     $(
                         <$field_ty as $crate::layout::CType>::c_define_self(definer)?;
                     )*
+                    $crate::headers::__lint_item__(definer, me, None, &[$(
+                        $crate::core::stringify!($field_name),
+                    )*]);
+                    let deprecated = $crate::__deprecated_note__!(
+                        $($($doc_meta)*)?
+                        $(#[$($meta)*])*
+                    );
+                    if deprecated.is_some() {
+                        $crate::headers::__define_deprecated__(definer)?;
+                    }
                     let out = definer.out();
-                    $(
-                        $crate::__output_docs__!(out, "", $($doc_meta)*);
+                    if !$crate::headers::__output_translated_docs__(
+                        out, "", $crate::core::stringify!($UnionName),
                     )?
-                    $crate::__output_docs__!(out, "", $(#[$($meta)*])*);
-                    $crate::core::writeln!(out, "typedef union {{\n")?;
+                    {
+                        $(
+                            $crate::__output_docs__!(out, "", $($doc_meta)*);
+                        )?
+                        $crate::__output_docs__!(out, "", $(#[$($meta)*])*);
+                    }
+                    $crate::core::writeln!(out, "typedef {}union {{\n",
+                        $crate::headers::__deprecated__(deprecated),
+                    )?;
                     $(
                         if $crate::core::mem::size_of::<$field_ty>() > 0 {
                             // $crate::core::writeln!(out, "")?;
-                            $crate::__output_docs__!(out, "    ",
-                                $(#[$($field_meta)*])*
-                            );
+                            if !$crate::headers::__output_translated_docs__(
+                                out, "    ", $crate::core::concat!(
+                                    $crate::core::stringify!($UnionName), ".",
+                                    $crate::core::stringify!($field_name),
+                                ),
+                            )?
+                            {
+                                $crate::__output_docs__!(out, "    ",
+                                    $(#[$($field_meta)*])*
+                                );
+                            }
                             $crate::core::writeln!(out, "    {};\n",
                                 <$field_ty as $crate::layout::CType>::c_var(
-                                    $crate::core::stringify!($field_name),
+                                    &*$crate::headers::__c_escape__(
+                                        $crate::core::stringify!($field_name),
+                                    ),
                                 ),
                             )?;
                         } else {
@@ -319,7 +511,9 @@ macro_rules! CType {
                             );
                         }
                     )+
-                    $crate::core::writeln!(out, "}} {}_t;\n", me)
+                    $crate::core::writeln!(out, "}} {};\n",
+                        $crate::headers::__type_name__(me),
+                    )
                 },
             )
         }
@@ -330,12 +524,34 @@ macro_rules! CType {
         ) -> $crate::core::fmt::Result
         {
             $crate::core::write!(fmt,
-                "{}_t{sep}{}",
-                <Self as $crate::layout::CType>::c_short_name(),
+                "{}{sep}{}",
+                $crate::headers::__type_name__(
+                    &<Self as $crate::layout::CType>::c_short_name(),
+                ),
                 var_name,
                 sep = if var_name.is_empty() { "" } else { " " },
             )
         }
+
+        fn binding_define_self (definer: &'_ mut dyn $crate::headers::Definer)
+          -> $crate::std::io::Result<()>
+        {
+            $crate::headers::__define_union__(
+                definer,
+                $crate::headers::TypeDesc::of::<Self>(),
+                $crate::__doc_strs__!([]
+                    $($($doc_meta)*)?
+                    $(#[$($meta)*])*
+                ),
+                &[$(
+                    $crate::headers::Field {
+                        name: $crate::core::stringify!($field_name),
+                        docs: $crate::__doc_strs__!([] $(#[$($field_meta)*])*),
+                        ty: $crate::headers::TypeDesc::of::<$field_ty>(),
+                    },
+                )*],
+            )
+        }
     } type OPAQUE_KIND = $crate::layout::OpaqueKind::Concrete; }
 
     $crate::layout::from_CType_impl_ReprC! {
@@ -416,6 +632,7 @@ macro_rules! CType {
 macro_rules! ReprC {
     // struct
     (
+        $(@layout[$layout_vis:vis $Layout:ident])?
         $( @[doc = $doc:expr] )?
         $(#[doc = $prev_doc:tt])* // support doc comments _before_ `#[repr(C)]`
         #[repr(C)]
@@ -438,9 +655,9 @@ macro_rules! ReprC {
     ) => (
         $crate::__with_doc__! {
             #[doc = $crate::core::concat!(
-                "  - [`",
+                "  - `<",
                 $crate::core::stringify!($StructName),
-                "_Layout`]"
+                " as ::safer_ffi::layout::ReprC>::CLayout`",
             )]
             $(#[doc = $prev_doc])*
             #[repr(C)]
@@ -464,15 +681,14 @@ macro_rules! ReprC {
         }
 
         $crate::paste::item! {
-            #[allow(nonstandard_style)]
-            $pub use
+            $crate::__layout_alias__! {
+                [$($layout_vis $Layout)?]
+                [$pub [< $StructName _Layout >]]
                 [< __ $StructName _safer_ffi_mod >]::$StructName
-                as
-                [< $StructName _Layout >]
-            ;
+            }
         }
 
-        #[allow(trivial_bounds)]
+        #[allow(deprecated, trivial_bounds)]
         unsafe // Safety: struct is `#[repr(C)]` and contains `ReprC` fields
         impl $(<$($lt ,)* $($($generics),+)?>)? $crate::layout::ReprC
             for $StructName $(<$($lt ,)* $($($generics),+)?>)?
@@ -493,7 +709,7 @@ macro_rules! ReprC {
         {
 
             type CLayout = $crate::paste::__item__! {
-                [<$StructName _Layout>]
+                [< __ $StructName _safer_ffi_mod >]::$StructName
                     $(<$($lt ,)* $($($generics),+)?>)?
             };
 
@@ -545,6 +761,7 @@ macro_rules! ReprC {
                     } {
                         $(
                             // $(#[$($field_meta)*])*
+                            @doc_meta( $(#[$($field_meta)*])* )
                             pub
                             $field_name :
                                 <$field_ty as $crate::layout::ReprC>::CLayout
@@ -554,6 +771,7 @@ macro_rules! ReprC {
                 }
             }
         }
+        #[allow(deprecated)]
         const _: () = {
             $crate::paste::item! {
                 use [< __ $StructName _safer_ffi_mod >]::*;
@@ -594,10 +812,29 @@ macro_rules! ReprC {
                 }
             }
         };
+
+        #[allow(deprecated, nonstandard_style, trivial_bounds)]
+        impl $(<$($lt ,)* $($($generics),+)?>)? $StructName $(<$($lt ,)* $($($generics),+)?>)?
+        where
+            $(
+                $field_ty : $crate::layout::ReprC,
+            )*
+            $(
+                $($(
+                    $generics : $crate::layout::ReprC,
+                )+)?
+                $($($bounds)*)?
+            )?
+        {
+            $crate::layout::__field_offsets__! {
+                0 ; $($field_name : $field_ty),*
+            }
+        }
     );
 
     // union
     (
+        $(@layout[$layout_vis:vis $Layout:ident])?
         $( @[doc = $doc:expr] )?
         $(#[doc = $prev_doc:tt])* // support doc comments _before_ `#[repr(C)]`
         #[repr(C)]
@@ -620,9 +857,9 @@ macro_rules! ReprC {
     ) => (
         $crate::__with_doc__! {
             #[doc = $crate::core::concat!(
-                "  - [`",
+                "  - `<",
                 $crate::core::stringify!($UnionName),
-                "_Layout`]"
+                " as ::safer_ffi::layout::ReprC>::CLayout`",
             )]
             $(#[doc = $prev_doc])*
             #[repr(C)]
@@ -646,15 +883,14 @@ macro_rules! ReprC {
         }
 
         $crate::paste::item! {
-            #[allow(nonstandard_style)]
-            $pub use
+            $crate::__layout_alias__! {
+                [$($layout_vis $Layout)?]
+                [$pub [< $UnionName _Layout >]]
                 [< __ $UnionName _safer_ffi_mod >]::$UnionName
-                as
-                [< $UnionName _Layout >]
-            ;
+            }
         }
 
-        #[allow(trivial_bounds)]
+        #[allow(deprecated, trivial_bounds)]
         unsafe // Safety: union is `#[repr(C)]` and contains `ReprC` fields
         impl $(<$($lt ,)* $($($generics),+)?>)? $crate::layout::ReprC
             for $UnionName $(<$($lt ,)* $($($generics),+)?>)?
@@ -675,7 +911,7 @@ macro_rules! ReprC {
         {
 
             type CLayout = $crate::paste::__item__! {
-                [<$UnionName _Layout>]
+                [< __ $UnionName _safer_ffi_mod >]::$UnionName
                     $(<$($lt ,)* $($($generics),+)?>)?
             };
 
@@ -726,6 +962,7 @@ macro_rules! ReprC {
                 }
             }
         }
+        #[allow(deprecated)]
         const _: () = {
             $crate::paste::item! {
                 use [< __ $UnionName _safer_ffi_mod >]::*;
@@ -856,6 +1093,7 @@ macro_rules! ReprC {
 
     // field-less `enum`
     (
+        $(@layout[$layout_vis:vis $Layout:ident])?
         $(#[doc = $prev_doc:tt])*
         #[repr($Int:ident)]
         $(#[$($meta:tt)*])*
@@ -888,15 +1126,24 @@ macro_rules! ReprC {
         }
 
         $crate::paste::item! {
-            #[repr(transparent)]
-            #[derive(Clone, Copy, PartialEq, Eq)]
-            pub
-            struct [< $EnumName _Layout >] /* = */ (
-                $crate::$Int,
-            );
+            #[allow(nonstandard_style)]
+            mod [< __ $EnumName _safer_ffi_mod >] {
+                #[repr(transparent)]
+                #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+                pub
+                struct [< $EnumName _Layout >] /* = */ (
+                    pub(in super) $crate::$Int,
+                );
+            }
+
+            $crate::__layout_alias__! {
+                [$($layout_vis $Layout)?]
+                [$pub [< $EnumName _Layout >]]
+                [< __ $EnumName _safer_ffi_mod >]::[< $EnumName _Layout >]
+            }
 
             impl $crate::core::convert::From<$crate::$Int>
-                for [< $EnumName _Layout >]
+                for [< __ $EnumName _safer_ffi_mod >]::[< $EnumName _Layout >]
             {
                 #[inline]
                 fn from (it: $crate::$Int)
@@ -906,14 +1153,18 @@ macro_rules! ReprC {
                 }
             }
 
+            #[allow(deprecated)]
             unsafe
             impl $crate::layout::CType
-                for [< $EnumName _Layout >]
+                for [< __ $EnumName _safer_ffi_mod >]::[< $EnumName _Layout >]
             { $crate::__cfg_headers__! {
                 fn c_short_name_fmt (fmt: &'_ mut $crate::core::fmt::Formatter<'_>)
                   -> $crate::core::fmt::Result
                 {
-                    fmt.write_str($crate::core::stringify!($EnumName).trim())
+                    $crate::headers::__c_short_name__(fmt,
+                        $crate::core::stringify!($EnumName).trim(),
+                        $crate::core::module_path!(),
+                    )
                 }
 
                 fn c_define_self (definer: &'_ mut dyn $crate::headers::Definer)
@@ -923,78 +1174,128 @@ macro_rules! ReprC {
                         <Self as $crate::layout::CType>
                             ::c_short_name().to_string()
                     ;
+                    let ref ty = $crate::headers::__type_name__(me);
                     definer.define_once(
                         me,
                         &mut |definer| {
                             <$crate::$Int as $crate::layout::CType>::c_define_self(
                                 definer,
                             )?;
-                            let out = definer.out();
-                            $crate::__output_docs__!(out, "",
+                            let deprecated = $crate::__deprecated_note__!(
                                 $(#[doc = $prev_doc])*
                                 $(#[$($meta)*])*
                             );
-                            $crate::core::writeln!(out,
+                            if deprecated.is_some() {
+                                $crate::headers::__define_deprecated__(definer)?;
+                            }
+                            let out = definer.out();
+                            if !$crate::headers::__output_translated_docs__(
+                                out, "", $crate::core::stringify!($EnumName),
+                            )?
+                            {
+                                $crate::__output_docs__!(out, "",
+                                    $(#[doc = $prev_doc])*
+                                    $(#[$($meta)*])*
+                                );
+                            }
+                            $crate::core::write!(out,
                                 $crate::core::concat!(
                                     "/** \\remark Has the same ABI as `{int}` **/\n",
                                     "#ifdef DOXYGEN\n",
-                                    "typedef enum {me}\n",
+                                    "typedef {deprecated}enum {me}\n",
                                     "#else\n",
-                                    "typedef {int__me}_t; enum\n",
+                                    "typedef {deprecated}{int__ty}; enum\n",
                                     "#endif\n",
                                     "{{\n",
-                                    $(
-                                        $crate::layout::ReprC! { @first
-                                            $((concat!(
-                                                "    /** \\brief\n",
-                                                $(
-                                                    "     * ", $variant_doc, "\n",
-                                                )*
-                                                "     */\n",
-                                            )))?
-                                            (
-                                                "    /** . */\n"
-                                            )
-                                        },
-                                        "    {}",
-                                        $( $crate::layout::ReprC! {
-                                            @first(
-                                                " = {}"
-                                            ) $discriminant
-                                        },)?
-                                        ",\n",
-                                    )*
+                                ),
+                                me = me,
+                                deprecated = $crate::headers::__deprecated__(deprecated),
+                                int = <$crate::$Int as $crate::layout::CType>::c_var(""),
+                                int__ty = <$crate::$Int as $crate::layout::CType>::c_var(
+                                    ty,
+                                ),
+                            )?;
+                            $crate::headers::__output_variants__(out, &mut [$(
+                                (
+                                    $EnumName::$Variant as $crate::$Int as i128,
+                                    $crate::layout::ReprC! { @first
+                                        $((concat!(
+                                            "    /** \\brief\n",
+                                            $(
+                                                "     * ", $variant_doc, "\n",
+                                            )*
+                                            "     */\n",
+                                        )))?
+                                        (
+                                            "    /** . */\n"
+                                        )
+                                    },
+                                    &$crate::std::string::ToString::to_string(
+                                        &$crate::__utils__::screaming_case(
+                                            me,
+                                            $crate::core::stringify!($Variant).trim(),
+                                        ),
+                                    ),
+                                    None $(.or(Some(&$discriminant as &dyn $crate::core::fmt::Display)))?,
+                                ),
+                            )*])?;
+                            $crate::core::writeln!(out,
+                                $crate::core::concat!(
                                     "}}\n",
                                     "#ifdef DOXYGEN\n",
-                                    "{me}_t\n",
+                                    "{ty}\n",
                                     "#endif\n",
                                     ";\n",
                                 ),
-                                $(
-                                    $crate::__utils__::screaming_case(
-                                        me,
-                                        $crate::core::stringify!($Variant).trim(),
-                                    ),
-                                    $($discriminant,)?
-                                )*
-                                me = me,
-                                int = <$crate::$Int as $crate::layout::CType>::c_var(""),
-                                int__me = <$crate::$Int as $crate::layout::CType>::c_var(
-                                    me,
-                                ),
+                                ty = ty,
                             )
                         },
                     )
                 }
 
+                fn binding_ty ()
+                  -> $crate::std::string::String
+                {
+                    $crate::headers::__backend__().enum_ty(
+                        &$crate::headers::__type_name__(
+                            &<Self as $crate::layout::CType>::c_short_name(),
+                        ),
+                        &$crate::headers::TypeDesc::of::<$crate::$Int>(),
+                    )
+                }
+
+                fn binding_define_self (definer: &'_ mut dyn $crate::headers::Definer)
+                  -> $crate::std::io::Result<()>
+                {
+                    $crate::headers::__define_enum__(
+                        definer,
+                        $crate::headers::TypeDesc::of::<Self>(),
+                        $crate::__doc_strs__!([]
+                            $(#[doc = $prev_doc])*
+                            $(#[$($meta)*])*
+                        ),
+                        $crate::headers::TypeDesc::of::<$crate::$Int>(),
+                        &mut [$(
+                            $crate::headers::Variant {
+                                value: $EnumName::$Variant as $crate::$Int as i128,
+                                name: $crate::core::stringify!($Variant).trim(),
+                                docs: &[$($($variant_doc),+)?],
+                                discriminant: None $(.or(Some(&$discriminant as &dyn $crate::core::fmt::Display)))?,
+                            },
+                        )*],
+                    )
+                }
+
                 fn c_var_fmt (
                     fmt: &'_ mut $crate::core::fmt::Formatter<'_>,
                     var_name: &'_ str,
                 ) -> $crate::core::fmt::Result
                 {
                     $crate::core::write!(fmt,
-                        "{}_t{sep}{}",
-                        <Self as $crate::layout::CType>::c_short_name(),
+                        "{}{sep}{}",
+                        $crate::headers::__type_name__(
+                            &<Self as $crate::layout::CType>::c_short_name(),
+                        ),
                         var_name,
                         sep = if var_name.is_empty() { "" } else { " " },
                     )
@@ -1002,14 +1303,46 @@ macro_rules! ReprC {
             } type OPAQUE_KIND = $crate::layout::OpaqueKind::Concrete; }
 
             $crate::layout::from_CType_impl_ReprC! {
-                [< $EnumName _Layout >]
+                [< __ $EnumName _safer_ffi_mod >]::[< $EnumName _Layout >]
             }
 
+            $crate::__cfg_headers__! {
+                #[allow(deprecated)]
+                impl $crate::headers::CLiteral
+                    for $EnumName
+                {
+                    fn c_literal (self: &'_ Self)
+                      -> $crate::core::option::Option<$crate::std::string::String>
+                    {
+                        let me =
+                            <
+                                <$EnumName as $crate::layout::ReprC>::CLayout
+                                as
+                                $crate::layout::CType
+                            >::c_short_name().to_string()
+                        ;
+                        let variant = match *self {
+                            $(
+                                | $EnumName::$Variant => {
+                                    $crate::core::stringify!($Variant).trim()
+                                },
+                            )*
+                        };
+                        $crate::core::option::Option::Some(
+                            $crate::std::string::ToString::to_string(
+                                &$crate::__utils__::screaming_case(&me, variant)
+                            )
+                        )
+                    }
+                }
+            }
+
+            #[allow(deprecated)]
             unsafe
             impl $crate::layout::ReprC
                 for $EnumName
             {
-                type CLayout = [< $EnumName _Layout >];
+                type CLayout = [< __ $EnumName _safer_ffi_mod >]::[< $EnumName _Layout >];
 
                 #[inline]
                 fn is_valid (&discriminant: &'_ Self::CLayout)
@@ -1026,6 +1359,7 @@ macro_rules! ReprC {
                 }
             }
 
+            #[allow(deprecated)]
             unsafe
             impl $crate::layout::__HasNiche__
                 for $EnumName
@@ -1034,8 +1368,11 @@ macro_rules! ReprC {
                 fn is_niche (it: &'_ <Self as $crate::layout::ReprC>::CLayout)
                   -> bool
                 {
-                    *it == unsafe { $crate::core::mem::transmute(
-                        $crate::core::option::Option::None::<Self>
+                    *it == unsafe { $crate::core::mem::transmute::<
+                        $crate::core::option::Option<Self>,
+                        <Self as $crate::layout::ReprC>::CLayout,
+                    >(
+                        $crate::core::option::Option::None
                     ) }
                 }
             }
@@ -1090,6 +1427,7 @@ macro_rules! ReprC {
         )?
         { $($opaque)* }
 
+        #[allow(unused_parens)]
         const _: () = {
             pub
             struct __safer_ffi_Opaque__ $(
@@ -1132,6 +1470,22 @@ macro_rules! ReprC {
             )?
             {
                 fn clone (self: &'_ Self) -> Self
+                {
+                    *self
+                }
+            }
+
+            impl $(<$($lt ,)* $($($generics),+)?>)?
+                $crate::core::fmt::Debug
+            for
+                __safer_ffi_Opaque__ $(<$($lt ,)* $($($generics),+)?>)?
+            $(
+                where
+                    $($($bounds)*)?
+            )?
+            {
+                fn fmt (self: &'_ Self, _: &'_ mut $crate::core::fmt::Formatter<'_>)
+                  -> $crate::core::fmt::Result
                 {
                     match self._void {}
                 }
@@ -1158,16 +1512,18 @@ macro_rules! ReprC {
                             let it = $c_name;
                             let _c_name = it.as_ref();
                         )?)?
-                        fmt.write_str(_c_name)
+                        $crate::headers::__c_short_name__(fmt,
+                            _c_name,
+                            $crate::core::module_path!(),
+                        )
                     }
                     fn c_define_self (definer: &'_ mut (dyn $crate::headers::Definer))
                         -> $crate::std::io::Result<()>
                     {
-                        let _c_name = $crate::core::stringify!($StructName);
-                        $($(
-                            let it = $c_name;
-                            let _c_name = it.as_ref();
-                        )?)?
+                        let ref _c_name =
+                            <Self as $crate::layout::CType>
+                                ::c_short_name().to_string()
+                        ;
                         definer.define_once(_c_name, &mut |definer| {
                             assert!(_c_name.chars().all(|c| $crate::core::matches!(c,
                                 'a' ..= 'z' |
@@ -1175,8 +1531,9 @@ macro_rules! ReprC {
                                 '0' ..= '9' | '_'
                             )));
                             $crate::core::write!(definer.out(),
-                                "typedef struct {0} {0}_t;\n\n",
+                                "typedef struct {} {};\n\n",
                                 _c_name,
+                                $crate::headers::__type_name__(&_c_name),
                             )
                         })
                     }
@@ -1185,18 +1542,25 @@ macro_rules! ReprC {
                         var_name: &'_ $crate::str,
                     ) -> $crate::core::fmt::Result
                     {
-                        let _c_name = $crate::core::stringify!($StructName);
-                        $($(
-                            let it = $c_name;
-                            let _c_name: &'_ $crate::str = it.as_ref();
-                        )?)?
+                        let ref _c_name =
+                            <Self as $crate::layout::CType>
+                                ::c_short_name().to_string()
+                        ;
                         $crate::core::write!(fmt,
-                            "{}_t{sep}{}",
-                            _c_name,
+                            "{}{sep}{}",
+                            $crate::headers::__type_name__(&_c_name),
                             var_name,
                             sep = if var_name.is_empty() { "" } else { " " },
                         )
                     }
+                    fn binding_define_self (definer: &'_ mut (dyn $crate::headers::Definer))
+                        -> $crate::std::io::Result<()>
+                    {
+                        $crate::headers::__define_opaque__(
+                            definer,
+                            $crate::headers::TypeDesc::of::<Self>(),
+                        )
+                    }
                 }
             }
             $crate::layout::from_CType_impl_ReprC! {
@@ -1343,6 +1707,183 @@ macro_rules! __output_docs__ {
     });
 }
 
+/// The `#[doc = ...]` strings among the given attributes, as a `&[&str]`.
+#[cfg(feature = "headers")]
+#[doc(hidden)] #[macro_export]
+macro_rules! __doc_strs__ {
+    (
+        [$($doc:expr,)*]
+    ) => (
+        &[$($doc),*] as &[&'static str]
+    );
+
+    (
+        [$($doc:expr,)*]
+            #[doc = $new_doc:expr]
+            $(#[$($meta:tt)*])*
+    ) => (
+        $crate::__doc_strs__! {
+            [$($doc,)* $new_doc,] $(#[$($meta)*])*
+        }
+    );
+
+    (
+        [$($doc:expr,)*]
+            #[$not_doc_meta:meta]
+            $(#[$($meta:tt)*])*
+    ) => (
+        $crate::__doc_strs__! {
+            [$($doc,)*] $(#[$($meta)*])*
+        }
+    );
+}
+
+/// The note of the `#[deprecated]` attribute among the given ones (`""` if
+/// none), as an `Option<&str>`.
+#[cfg(feature = "headers")]
+#[doc(hidden)] #[macro_export]
+macro_rules! __deprecated_note__ {
+    (
+    ) => (
+        $crate::core::option::Option::None::<&'static str>
+    );
+
+    (
+        #[deprecated]
+        $(#[$($meta:tt)*])*
+    ) => (
+        $crate::core::option::Option::Some("")
+    );
+
+    (
+        #[deprecated = $note:literal]
+        $(#[$($meta:tt)*])*
+    ) => (
+        $crate::core::option::Option::Some($note)
+    );
+
+    (
+        #[deprecated( $($args:tt)* )]
+        $(#[$($meta:tt)*])*
+    ) => (
+        $crate::__deprecated_note__! { @args $($args)* }
+    );
+
+    (
+        #[$($not_deprecated_meta:tt)*]
+        $(#[$($meta:tt)*])*
+    ) => (
+        $crate::__deprecated_note__! { $(#[$($meta)*])* }
+    );
+
+    (@args
+    ) => (
+        $crate::core::option::Option::Some("")
+    );
+
+    (@args
+        note = $note:literal $($rest:tt)*
+    ) => (
+        $crate::core::option::Option::Some($note)
+    );
+
+    (@args
+        $skipped:tt $($rest:tt)*
+    ) => (
+        $crate::__deprecated_note__! { @args $($rest)* }
+    );
+}
+
+/// `#[derive_ReprC(newtype)]`: implement `CType` and `ReprC` for a
+/// `#[repr(transparent)]` wrapper around a `CType`, with its own C `typedef`.
+#[doc(hidden)] #[macro_export]
+macro_rules! __newtype_ReprC__ {(
+    $(#[doc = $doc:expr])*
+    $Name:ident ( $Inner:ty )
+) => (
+    unsafe // Safety: `#[repr(transparent)]` wrapper around a `CType`.
+    impl $crate::layout::CType
+        for $Name
+    { $crate::__cfg_headers__! {
+        fn c_short_name_fmt (fmt: &'_ mut $crate::core::fmt::Formatter<'_>)
+          -> $crate::core::fmt::Result
+        {
+            $crate::headers::__c_short_name__(fmt,
+                $crate::core::stringify!($Name),
+                $crate::core::module_path!(),
+            )
+        }
+
+        fn c_define_self (definer: &'_ mut dyn $crate::headers::Definer)
+          -> $crate::std::io::Result<()>
+        {
+            let ref me =
+                <Self as $crate::layout::CType>
+                    ::c_short_name().to_string()
+            ;
+            definer.define_once(
+                me,
+                &mut |definer| {
+                    <$Inner as $crate::layout::CType>::c_define_self(definer)?;
+                    let out = definer.out();
+                    if !$crate::headers::__output_translated_docs__(
+                        out, "", $crate::core::stringify!($Name),
+                    )?
+                    {
+                        $crate::__output_docs__!(out, "", $(#[doc = $doc])*);
+                    }
+                    $crate::core::writeln!(out, "typedef {};\n",
+                        <$Inner as $crate::layout::CType>::c_var(
+                            &$crate::headers::__type_name__(me),
+                        ),
+                    )
+                },
+            )
+        }
+
+        fn binding_ty ()
+          -> $crate::std::string::String
+        {
+            <$Inner as $crate::layout::CType>::binding_ty()
+        }
+
+        fn binding_define_self (definer: &'_ mut dyn $crate::headers::Definer)
+          -> $crate::std::io::Result<()>
+        {
+            <$Inner as $crate::layout::CType>::binding_define_self(definer)
+        }
+
+        fn c_var_fmt (
+            fmt: &'_ mut $crate::core::fmt::Formatter<'_>,
+            var_name: &'_ str,
+        ) -> $crate::core::fmt::Result
+        {
+            $crate::core::write!(fmt,
+                "{}{sep}{}",
+                $crate::headers::__type_name__(
+                    &<Self as $crate::layout::CType>::c_short_name(),
+                ),
+                var_name,
+                sep = if var_name.is_empty() { "" } else { " " },
+            )
+        }
+    } type OPAQUE_KIND = $crate::layout::OpaqueKind::Concrete; }
+
+    unsafe // Safety: `#[repr(transparent)]` wrapper, with the same validity.
+    impl $crate::layout::ReprC
+        for $Name
+    {
+        type CLayout = Self;
+
+        #[inline]
+        fn is_valid (it: &'_ Self)
+          -> bool
+        {
+            <$Inner as $crate::layout::ReprC>::is_valid(&it.0)
+        }
+    }
+)}
+
 #[cfg(test)]
 crate::layout::ReprC! {
     // #[derive_ReprC]
@@ -1461,4 +2002,37 @@ mod test {
             {}
         }
     }
+
+    cfg_proc_macros! { mod c_builder {
+        doc_test! { with_optional_fields:
+            fn main () {}
+
+            use ::safer_ffi::prelude::*;
+
+            #[derive_ReprC(c_builder, optional_fields)]
+            #[repr(C)]
+            #[derive(Default)]
+            pub
+            struct Config {
+                #[optional]
+                pub threads: u32,
+            }
+        }
+
+        doc_test! { without_optional_fields:
+            #![compile_fail]
+            fn main () {}
+
+            use ::safer_ffi::prelude::*;
+
+            #[derive_ReprC(c_builder)]
+            #[repr(C)]
+            #[derive(Default)]
+            pub
+            struct Config {
+                #[optional]
+                pub threads: u32,
+            }
+        }
+    }}
 }
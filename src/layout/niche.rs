@@ -73,7 +73,7 @@ unsafe_impls! {
     // char_p::Raw => |it| it.is_null(),
 
     bool => |&it| {
-        it == unsafe { mem::transmute(None::<bool>) }
+        it == unsafe { mem::transmute::<Option<bool>, <bool as ReprC>::CLayout>(None) }
     },
 }
 
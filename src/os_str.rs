@@ -0,0 +1,353 @@
+//! Platform strings (`OsStr` / `OsString`), for environment variables, command
+//! line arguments, _etc._, where a (UTF-8) `char_p` would be lossy.
+//!
+//! The representation is platform-specific: [`os_char`] is
+//!
+//!   - a byte (`uint8_t`) on Unix-like platforms, where the string may be
+//!     any sequence of (non-null) bytes;
+//!
+//!   - a 16-bit code unit (`uint16_t`) on Windows, where the string is
+//!     (potentially ill-formed) UTF-16, _i.e._, what the `*W` APIs expect.
+
+use_prelude!();
+use ::core::slice;
+use ::std::ffi::{OsStr, OsString};
+
+/// Code unit of a platform string: `u8` on Unix-like platforms, `u16` on
+/// Windows.
+#[cfg(not(windows))]
+#[allow(nonstandard_style)]
+pub
+type os_char = u8;
+
+/// Code unit of a platform string: `u8` on Unix-like platforms, `u16` on
+/// Windows.
+#[cfg(windows)]
+#[allow(nonstandard_style)]
+pub
+type os_char = u16;
+
+type PhantomCovariantLifetime<'lt> =
+    PhantomData<&'lt ()>
+;
+
+ReprC! {
+    #[repr(C)]
+    /// `&'lt OsStr`, but with a guaranteed `#[repr(C)]` layout.
+    ///
+    /// `ptr` points to `len` code units (no null terminator): bytes on
+    /// Unix-like platforms, and (potentially ill-formed) UTF-16 code units
+    /// on Windows.
+    pub
+    struct os_str_ref['lt,] {
+        /// Pointer to the first code unit (if any).
+        pub(in crate)
+        ptr: ptr::NonNullRef<os_char>,
+
+        /// Code unit count.
+        pub(in crate)
+        len: usize,
+
+        pub(in crate)
+        _lt: PhantomCovariantLifetime<'lt>,
+    }
+}
+
+impl<'lt> From<&'lt [os_char]>
+    for os_str_ref<'lt>
+{
+    #[inline]
+    fn from (code_units: &'lt [os_char])
+      -> os_str_ref<'lt>
+    {
+        os_str_ref {
+            len: code_units.len(),
+            ptr: unsafe {
+                ptr::NonNull::new_unchecked(code_units.as_ptr() as _)
+            }.into(),
+            _lt: PhantomCovariantLifetime::default(),
+        }
+    }
+}
+
+impl<'lt> os_str_ref<'lt> {
+    /// The code units of the string.
+    #[inline]
+    pub
+    fn as_slice (self: os_str_ref<'lt>)
+      -> &'lt [os_char]
+    {
+        unsafe {
+            slice::from_raw_parts(self.ptr.as_ptr(), self.len)
+        }
+    }
+
+    #[inline]
+    pub
+    fn to_os_string (self: os_str_ref<'lt>)
+      -> OsString
+    {
+        #[cfg(unix)] {
+            self.as_os_str().to_owned()
+        }
+        #[cfg(windows)] {
+            ::std::os::windows::ffi::OsStringExt::from_wide(self.as_slice())
+        }
+        #[cfg(not(any(unix, windows)))] {
+            rust::String::from_utf8_lossy(self.as_slice()).into_owned().into()
+        }
+    }
+}
+
+#[cfg(unix)]
+impl<'lt> From<&'lt OsStr>
+    for os_str_ref<'lt>
+{
+    #[inline]
+    fn from (s: &'lt OsStr)
+      -> os_str_ref<'lt>
+    {
+        ::std::os::unix::ffi::OsStrExt::as_bytes(s).into()
+    }
+}
+
+#[cfg(unix)]
+impl<'lt> os_str_ref<'lt> {
+    /// Zero-cost conversion to an `&OsStr` (only available on Unix-like
+    /// platforms: on Windows, see [`os_str_ref::to_os_string`]).
+    #[inline]
+    pub
+    fn as_os_str (self: os_str_ref<'lt>)
+      -> &'lt OsStr
+    {
+        ::std::os::unix::ffi::OsStrExt::from_bytes(self.as_slice())
+    }
+}
+
+impl<'lt> Copy
+    for os_str_ref<'lt>
+{}
+
+impl<'lt> Clone
+    for os_str_ref<'lt>
+{
+    #[inline]
+    fn clone (self: &'_ os_str_ref<'lt>)
+      -> os_str_ref<'lt>
+    {
+        *self
+    }
+}
+
+unsafe // Safety: equivalent to that of `&'lt [os_char]`.
+    impl Send
+        for os_str_ref<'_>
+    {}
+
+unsafe // Safety: equivalent to that of `&'lt [os_char]`.
+    impl Sync
+        for os_str_ref<'_>
+    {}
+
+impl fmt::Debug
+    for os_str_ref<'_>
+{
+    fn fmt (self: &'_ Self, fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        fmt::Debug::fmt(&self.to_os_string(), fmt)
+    }
+}
+
+ReprC! {
+    #[repr(C)]
+    /// [`OsString`], but with a guaranteed `#[repr(C)]` layout.
+    ///
+    /// `ptr` points to `len` (heap-allocated) code units (no null
+    /// terminator): bytes on Unix-like platforms, and (potentially
+    /// ill-formed) UTF-16 code units on Windows.
+    pub
+    struct os_str_boxed {
+        /// Pointer to the first code unit (if any).
+        pub(in crate)
+        ptr: ptr::NonNullOwned<os_char>,
+
+        /// Code unit count.
+        pub(in crate)
+        len: usize,
+    }
+}
+
+impl From<rust::Box<[os_char]>>
+    for os_str_boxed
+{
+    #[inline]
+    fn from (code_units: rust::Box<[os_char]>)
+      -> os_str_boxed
+    {
+        os_str_boxed {
+            len: code_units.len(),
+            ptr: unsafe {
+                ptr::NonNull::new_unchecked(
+                    rust::Box::leak(code_units).as_mut_ptr()
+                )
+            }.into(),
+        }
+    }
+}
+
+impl From<OsString>
+    for os_str_boxed
+{
+    /// Does not copy the string on Unix-like platforms.
+    fn from (s: OsString)
+      -> os_str_boxed
+    {
+        #[cfg(unix)] {
+            ::std::os::unix::ffi::OsStringExt::into_vec(s)
+                .into_boxed_slice()
+                .into()
+        }
+        #[cfg(windows)] {
+            ::std::os::windows::ffi::OsStrExt::encode_wide(&*s)
+                .collect::<rust::Box<[_]>>()
+                .into()
+        }
+        #[cfg(not(any(unix, windows)))] {
+            s   .to_string_lossy()
+                .into_owned()
+                .into_bytes()
+                .into_boxed_slice()
+                .into()
+        }
+    }
+}
+
+impl<'lt> From<&'lt OsStr>
+    for os_str_boxed
+{
+    #[inline]
+    fn from (s: &'lt OsStr)
+      -> os_str_boxed
+    {
+        s.to_owned().into()
+    }
+}
+
+impl os_str_boxed {
+    #[inline]
+    pub
+    fn as_ref (self: &'_ os_str_boxed)
+      -> os_str_ref<'_>
+    {
+        self.as_slice().into()
+    }
+
+    /// The code units of the string.
+    #[inline]
+    pub
+    fn as_slice (self: &'_ os_str_boxed)
+      -> &'_ [os_char]
+    {
+        unsafe {
+            slice::from_raw_parts(self.ptr.as_ptr(), self.len)
+        }
+    }
+
+    /// Does not copy the string on Unix-like platforms.
+    pub
+    fn into_os_string (self: os_str_boxed)
+      -> OsString
+    {
+        let code_units: rust::Box<[os_char]> = self.into();
+        #[cfg(unix)] {
+            ::std::os::unix::ffi::OsStringExt::from_vec(code_units.into())
+        }
+        #[cfg(windows)] {
+            ::std::os::windows::ffi::OsStringExt::from_wide(&code_units)
+        }
+        #[cfg(not(any(unix, windows)))] {
+            rust::String::from_utf8_lossy(&code_units).into_owned().into()
+        }
+    }
+}
+
+impl From<os_str_boxed>
+    for rust::Box<[os_char]>
+{
+    #[inline]
+    fn from (it: os_str_boxed)
+      -> rust::Box<[os_char]>
+    {
+        let mut this = mem::ManuallyDrop::new(it);
+        unsafe {
+            rust::Box::from_raw(
+                slice::from_raw_parts_mut(
+                    this.ptr.as_mut_ptr(),
+                    this.len,
+                )
+            )
+        }
+    }
+}
+
+impl From<os_str_boxed>
+    for OsString
+{
+    #[inline]
+    fn from (it: os_str_boxed)
+      -> OsString
+    {
+        it.into_os_string()
+    }
+}
+
+impl Drop
+    for os_str_boxed
+{
+    #[inline]
+    fn drop (self: &'_ mut os_str_boxed)
+    {
+        unsafe {
+            drop::<rust::Box<[os_char]>>(
+                rust::Box::from_raw(
+                    slice::from_raw_parts_mut(
+                        self.ptr.as_mut_ptr(),
+                        self.len,
+                    )
+                )
+            );
+        }
+    }
+}
+
+impl Clone
+    for os_str_boxed
+{
+    #[inline]
+    fn clone (self: &'_ os_str_boxed)
+      -> os_str_boxed
+    {
+        rust::Box::<[os_char]>::from(self.as_slice()).into()
+    }
+}
+
+unsafe // Safety: equivalent to that of `Box<[os_char]>`.
+    impl Send
+        for os_str_boxed
+    {}
+
+unsafe // Safety: equivalent to that of `Box<[os_char]>`.
+    impl Sync
+        for os_str_boxed
+    {}
+
+impl fmt::Debug
+    for os_str_boxed
+{
+    fn fmt (self: &'_ Self, fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        fmt::Debug::fmt(&self.as_ref(), fmt)
+    }
+}
@@ -0,0 +1,277 @@
+//! Fixed-capacity pools of objects, handed out to C without allocating.
+//!
+//! Real-time C callers (audio callbacks, control loops, _etc._) cannot afford
+//! to go through the allocator on their hot paths. An [`ObjectPool`]
+//! allocates all of its objects upfront, and then merely hands them out
+//! ([`acquire`][`ObjectPool::acquire`]) and takes them back
+//! ([`release`][`ObjectPool::release`]), while keeping track of which ones
+//! are in use: releasing a pointer which does not belong to the pool, or
+//! which has already been released, is thus detected (and refused) rather
+//! than corrupting it.
+//!
+//! The `new / acquire / release / free` functions are generated, for a given
+//! type of objects, with the [`ffi_export_pool!`] macro.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ::safer_ffi::{prelude::*, pool::ObjectPool};
+//!
+//! #[derive_ReprC]
+//! #[repr(C)]
+//! #[derive(Default)]
+//! pub
+//! struct Particle {
+//!     x: f32,
+//!     y: f32,
+//! }
+//!
+//! ::safer_ffi::ffi_export_pool! {
+//!     Particle => {
+//!         new: particle_pool_new,
+//!         acquire: particle_pool_acquire,
+//!         release: particle_pool_release,
+//!         free: particle_pool_free,
+//!     }
+//! }
+//!
+//! # fn main () {
+//! let mut pool = ObjectPool::<Particle>::new(2);
+//! let a = pool.acquire().unwrap();
+//! let _b = pool.acquire().unwrap();
+//! assert!(pool.acquire().is_none());
+//! assert!(pool.release(a.as_ptr()));
+//! assert!(!pool.release(a.as_ptr()));
+//! assert_eq!(pool.available(), 1);
+//! # }
+//! ```
+//!
+//! which, C-wise, is used as:
+//!
+//! ```C
+//! ObjectPool_Particle_t * pool = particle_pool_new(64);
+//! Particle_t * particle = particle_pool_acquire(pool);
+//! if (particle) {
+//!     particle->x = 1.0;
+//!     /* ... */
+//!     particle_pool_release(pool, particle);
+//! }
+//! particle_pool_free(pool);
+//! ```
+
+use_prelude!();
+use ::core::cell::UnsafeCell;
+
+ReprC! {
+    #[ReprC::opaque(
+        format!("ObjectPool_{}", <T::CLayout as CType>::c_short_name())
+    )]
+    /// Pool of (up to `capacity`) preallocated `T`s, handed out through
+    /// [`ObjectPool::acquire`].
+    ///
+    /// It is an opaque type from the point of view of C.
+    pub
+    struct ObjectPool[T]
+    where {
+        T : ReprC,
+    }
+    {
+        slots: rust::Box<[UnsafeCell<T>]>,
+        acquired: rust::Box<[bool]>,
+        /// The indices of the slots which are not acquired, with enough
+        /// capacity for all of them, so that `release` never reallocates.
+        free: rust::Vec<usize>,
+    }
+}
+
+impl<T : ReprC + Default> ObjectPool<T> {
+    /// Allocates the `capacity` objects of the pool, all at once.
+    ///
+    /// # Panics
+    ///
+    /// If `T` is zero-sized, since its objects could not be told apart.
+    pub
+    fn new (capacity: usize)
+      -> ObjectPool<T>
+    {
+        assert_ne!(mem::size_of::<T>(), 0,
+            "Cannot pool zero-sized objects",
+        );
+        ObjectPool {
+            slots: (0 .. capacity).map(|_| UnsafeCell::new(T::default())).collect(),
+            acquired: (0 .. capacity).map(|_| false).collect(),
+            free: (0 .. capacity).rev().collect(),
+        }
+    }
+
+    /// Hands out a (freshly defaulted) object of the pool, or `None` when all
+    /// of them are in use.
+    ///
+    /// The object remains valid until it is [released][`ObjectPool::release`]
+    /// or the pool is dropped.
+    pub
+    fn acquire (self: &'_ mut ObjectPool<T>)
+      -> Option<ptr::NonNull<T>>
+    {
+        let index = self.free.pop()?;
+        self.acquired[index] = true;
+        let object = self.slots[index].get();
+        unsafe {
+            // Safety: the slot was not acquired, so nobody else points to it.
+            *object = T::default();
+        }
+        ptr::NonNull::new(object)
+    }
+}
+
+impl<T : ReprC> ObjectPool<T> {
+    /// Takes back an object [acquired][`ObjectPool::acquire`] from this pool.
+    ///
+    /// Returns `false`, doing nothing, if `object` does not point to an
+    /// acquired object of this pool (_e.g._, it has already been released).
+    pub
+    fn release (self: &'_ mut ObjectPool<T>, object: *const T)
+      -> bool
+    {
+        let index = match self.index_of(object) {
+            | Some(index) => index,
+            | None => return false,
+        };
+        if self.acquired[index].not() {
+            return false;
+        }
+        self.acquired[index] = false;
+        self.free.push(index);
+        true
+    }
+
+    /// The total number of objects of the pool.
+    #[inline]
+    pub
+    fn capacity (self: &'_ Self)
+      -> usize
+    {
+        self.slots.len()
+    }
+
+    /// The number of objects which can currently be acquired.
+    #[inline]
+    pub
+    fn available (self: &'_ Self)
+      -> usize
+    {
+        self.free.len()
+    }
+
+    fn index_of (self: &'_ Self, object: *const T)
+      -> Option<usize>
+    {
+        let size = mem::size_of::<UnsafeCell<T>>();
+        let offset = (object as usize).checked_sub(self.slots.as_ptr() as usize)?;
+        if offset % size != 0 {
+            return None;
+        }
+        Some(offset / size).filter(|&index| index < self.slots.len())
+    }
+}
+
+impl<T : ReprC> fmt::Debug
+    for ObjectPool<T>
+{
+    fn fmt (self: &'_ Self, fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        fmt .debug_struct("ObjectPool")
+            .field("capacity", &self.capacity())
+            .field("available", &self.available())
+            .finish()
+    }
+}
+
+/// Exports the `new`, `acquire`, `release` and `free` functions of an
+/// [`ObjectPool`][`crate::pool::ObjectPool`]`<T>`.
+///
+/// ```rust,ignore
+/// ::safer_ffi::ffi_export_pool! {
+///     T => {
+///         new: my_pool_new,
+///         acquire: my_pool_acquire,
+///         release: my_pool_release,
+///         free: my_pool_free,
+///     }
+/// }
+/// ```
+///
+/// exports:
+///
+/// ```C
+/// ObjectPool_T_t * my_pool_new (size_t capacity);
+///
+/// T_t * my_pool_acquire (ObjectPool_T_t * pool);
+///
+/// bool my_pool_release (ObjectPool_T_t * pool, T_t * object);
+///
+/// void my_pool_free (ObjectPool_T_t * pool);
+/// ```
+///
+///   - `acquire` returns (a pointer to) a freshly defaulted object, or `NULL`
+///     when all of them are in use;
+///
+///   - `release` returns `false` when `object` is not an acquired object of
+///     the pool (_e.g._, when released twice);
+///
+///   - `free` destroys the pool (`NULL` is allowed), invalidating all of its
+///     objects.
+#[macro_export]
+macro_rules! ffi_export_pool {(
+    $T:ty => {
+        new: $new:ident,
+        acquire: $acquire:ident,
+        release: $release:ident,
+        free: $free:ident $(,)?
+    } $(;)?
+) => (
+    #[$crate::ffi_export]
+    /// Allocates a pool of `capacity` objects.
+    pub
+    fn $new (capacity: usize)
+      -> $crate::boxed::Box<$crate::pool::ObjectPool<$T>>
+    {
+        $crate::boxed::Box::new($crate::pool::ObjectPool::new(capacity))
+    }
+
+    #[$crate::ffi_export]
+    /// Hands out a freshly initialized object of the pool, or `NULL` when
+    /// all of them are in use.
+    pub
+    fn $acquire (pool: &'_ mut $crate::pool::ObjectPool<$T>)
+      -> *mut $T
+    {
+        pool.acquire()
+            .map_or_else($crate::core::ptr::null_mut, |it| it.as_ptr())
+    }
+
+    #[$crate::ffi_export]
+    /// Gives an acquired object back to the pool.
+    ///
+    /// Returns `false`, doing nothing, if `object` is not an acquired object
+    /// of the pool.
+    pub
+    fn $release (
+        pool: &'_ mut $crate::pool::ObjectPool<$T>,
+        object: *mut $T,
+    ) -> bool
+    {
+        pool.release(object)
+    }
+
+    #[$crate::ffi_export]
+    /// Destroys the pool, invalidating all of its objects.
+    pub
+    fn $free (
+        pool: Option<$crate::boxed::Box<$crate::pool::ObjectPool<$T>>>,
+    )
+    {
+        $crate::core::mem::drop(pool)
+    }
+)}
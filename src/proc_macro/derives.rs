@@ -2,12 +2,45 @@ fn feed_to_macro_rules (input: TokenStream, name: Ident)
   -> TokenStream
 {
     let DeriveInput {
-        attrs,
+        mut attrs,
         vis,
         ident,
         generics,
-        data,
+        mut data,
     } = parse_macro_input!(input);
+    let mut layout = LayoutOptions::default();
+    let repr_c_attrs = repr_c_attrs(&mut attrs, Some(&mut layout)).and_then(|()| match data {
+        | Data::Struct(DataStruct { ref mut fields, .. }) => {
+            fields.iter_mut().try_for_each(|f| repr_c_attrs(&mut f.attrs, None))
+        },
+        | Data::Union(DataUnion { ref mut fields, .. }) => {
+            fields.named.iter_mut().try_for_each(|f| repr_c_attrs(&mut f.attrs, None))
+        },
+        | Data::Enum(_) => Ok(()),
+    });
+    if let Err(err) = repr_c_attrs {
+        return err.to_compile_error().into();
+    }
+    // The re-export of the `CLayout`, if customized.
+    let layout = if layout.name.is_some() || layout.vis.is_some() {
+        let is_transparent = attrs.iter().any(|attr| {
+            attr.path.is_ident("repr")
+            && attr.tokens.to_string().contains("transparent")
+        });
+        if name != "ReprC" || is_transparent {
+            return Error::new(Span2::call_site(),
+                "The `layout` options do not apply to this type, \
+                which has no `CLayout` companion type",
+            ).to_compile_error().into();
+        }
+        let name =
+            layout.name.unwrap_or_else(|| format_ident!("{}_Layout", ident))
+        ;
+        let vis = layout.vis.unwrap_or_else(|| vis.clone());
+        Some(quote!( @layout[#vis #name] ))
+    } else {
+        None
+    };
     let ret = TokenStream::from(match data {
         | Data::Enum(DataEnum {
             enum_token: ref enum_,
@@ -15,6 +48,7 @@ fn feed_to_macro_rules (input: TokenStream, name: Ident)
             ..
         }) => quote! {
             ::safer_ffi::layout::ReprC! {
+                #layout
                 #(#attrs)*
                 #vis
                 #enum_ #ident {
@@ -30,6 +64,7 @@ fn feed_to_macro_rules (input: TokenStream, name: Ident)
             let (params, bounds) = generics.my_split();
             quote! {
                 ::safer_ffi::layout::#name! {
+                    #layout
                     #(#attrs)*
                     #vis
                     #struct_ #ident
@@ -49,6 +84,7 @@ fn feed_to_macro_rules (input: TokenStream, name: Ident)
             let (params, bounds) = generics.my_split();
             quote! {
                 ::safer_ffi::layout::#name! {
+                    #layout
                     #(#attrs)*
                     #vis
                     #union_ #ident
@@ -67,6 +103,72 @@ fn feed_to_macro_rules (input: TokenStream, name: Ident)
     ret
 }
 
+/// The `#[repr_c(layout = "<Name>", layout_vis = "<visibility>")]` options
+/// of a type: the name and the visibility of the re-export of its `CLayout`.
+#[derive(Default)]
+struct LayoutOptions {
+    name: Option<Ident>,
+    vis: Option<Visibility>,
+}
+
+/// Handles the `#[repr_c(...)]` attributes of a type (or, when `layout` is
+/// `None`, of a field):
+///
+///   - replaces `since = "<version>"` with a trailing `\since <version>` doc
+///     line, for the headers to pick it up;
+///
+///   - parses the `layout = "<Name>"` and `layout_vis = "<visibility>"`
+///     options of the type into `layout`.
+fn repr_c_attrs (
+    attrs: &'_ mut Vec<Attribute>,
+    mut layout: Option<&'_ mut LayoutOptions>,
+) -> Result<()>
+{
+    let expected = if layout.is_some() {
+        "Expected `#[repr_c(since = \"<version>\")]`, \
+        `#[repr_c(layout = \"<Name>\")]` or \
+        `#[repr_c(layout_vis = \"<visibility>\")]`"
+    } else {
+        "Expected `#[repr_c(since = \"<version>\")]`"
+    };
+    let mut since = None;
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("repr_c")) {
+        let nested = match attr.parse_meta()? {
+            | Meta::List(MetaList { nested, .. }) if !nested.is_empty() => {
+                nested
+            },
+            | _ => return Err(Error::new_spanned(attr, expected)),
+        };
+        for meta in nested {
+            let (key, value) = match meta {
+                | NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    ref path,
+                    lit: Lit::Str(ref value),
+                    ..
+                })) if path.get_ident().is_some() => {
+                    (path.get_ident().unwrap().to_string(), value.clone())
+                },
+                | _ => return Err(Error::new_spanned(meta, expected)),
+            };
+            match (&key[..], layout.as_mut()) {
+                | ("since", _) => since = Some(value.value()),
+                | ("layout", Some(layout)) => layout.name = Some(value.parse()?),
+                | ("layout_vis", Some(layout)) => layout.vis = Some(value.parse()?),
+                | _ => return Err(Error::new_spanned(meta, expected)),
+            }
+        }
+    }
+    attrs.retain(|attr| !attr.path.is_ident("repr_c"));
+    if let Some(since) = since {
+        if attrs.iter().any(|attr| attr.path.is_ident("doc")) {
+            attrs.push(parse_quote!( #[doc = ""] ));
+        }
+        let doc = format!(" \\since {}", since);
+        attrs.push(parse_quote!( #[doc = #doc] ));
+    }
+    Ok(())
+}
+
 /// Safely implement [`ReprC`]
 /// for a `#[repr(C)]` struct **when all its fields are [`ReprC`]**.
 ///
@@ -96,6 +198,13 @@ fn feed_to_macro_rules (input: TokenStream, name: Ident)
 ///     } Instant_t;
 ///     ```
 ///
+///   - with `const` byte offsets of its fields (on top of the `SIZE` and
+///     `ALIGN` constants of every `CType`):
+///
+///     ```rust,ignore
+///     const NANOS_OFFSET: usize = Instant::OFFSET_nanos; // 8
+///     ```
+///
 /// ### Field-less `enum`
 ///
 /// ```rust
@@ -124,6 +233,15 @@ fn feed_to_macro_rules (input: TokenStream, name: Ident)
 ///     }
 ///     ```
 ///
+/// With `#[derive_ReprC(export_is_valid)]`, a
+/// `<snake_case_name>_is_valid(value)` function is also exported, for C code
+/// to check a value it got from elsewhere (_e.g._, a config file) before
+/// using it as a `Status_t`:
+///
+/// ```C
+/// bool status_is_valid (int64_t value);
+/// ```
+///
 /// ### Generic `struct`
 ///
 /// In that case, it is required that the struct's generic types carry a
@@ -159,17 +277,795 @@ fn feed_to_macro_rules (input: TokenStream, name: Ident)
 ///         double y;
 ///     } Point_double_t;
 ///     ```
+///
+/// ### Callback table
+///
+/// With `#[derive_ReprC(callback_table)]`, a struct of `repr_c::FnPtr` fields
+/// (the "ops table" pattern of C APIs) also gets:
+///
+///   - for each field, a method of the same name calling the callback, which
+///     returns `Err(NullFnPtr)` rather than calling a `NULL` pointer;
+///
+///   - a `.validate()` method, checking that all the callbacks not marked
+///     `#[optional]` are non-`NULL`.
+///
+/// ```rust
+/// use ::safer_ffi::prelude::*;
+///
+/// #[derive_ReprC(callback_table)]
+/// #[repr(C)]
+/// pub
+/// struct StreamOps {
+///     pub read: repr_c::FnPtr<(*mut u8, usize), isize>,
+///     pub write: repr_c::FnPtr<(*const u8, usize), isize>,
+///     #[optional]
+///     pub flush: repr_c::FnPtr<()>,
+/// }
+/// ```
+///
+/// ### Default initializer
+///
+/// Zero-initializing a struct on the C side may lead to invalid values, such
+/// as `enum` discriminants that do not start at `0`. With
+/// `#[derive_ReprC(default_init)]`, a struct implementing `Default` gets:
+///
+///   - an exported `<snake_case_name>_init(out)` function, writing the
+///     default value to `out` (an `unsafe fn` on the Rust side, since `out`
+///     must be `NULL` or valid for a write);
+///
+///   - a `<SCREAMING_CASE_NAME>_DEFAULT` initializer macro in the headers,
+///     made of the C literals of the default field values. A field whose
+///     value has no C literal (see `::safer_ffi::headers::CLiteral`) needs a
+///     `#[c_default = "<C expression>"]` attribute.
+///
+/// ```rust
+/// use ::safer_ffi::prelude::*;
+///
+/// #[derive_ReprC]
+/// #[repr(u8)]
+/// pub
+/// enum Mode {
+///     Fast = 42,
+///     Accurate,
+/// }
+///
+/// #[derive_ReprC(default_init)]
+/// #[repr(C)]
+/// pub
+/// struct Settings {
+///     pub mode: Mode,
+///     pub ratio: f32,
+///     #[c_default = "NULL"]
+///     pub name: Option<char_p::Ref<'static>>,
+/// }
+///
+/// impl Default for Settings {
+///     fn default () -> Self
+///     {
+///         Settings { mode: Mode::Accurate, ratio: 0.5, name: None }
+///     }
+/// }
+/// ```
+///
+///   - corresponding to the following C definitions:
+///
+///     ```C
+///     typedef struct {
+///         Mode_t mode;
+///         float ratio;
+///         char const * name;
+///     } Settings_t;
+///
+///     void settings_init (Settings_t * out);
+///
+///     #define SETTINGS_DEFAULT { /* .mode = */ MODE_ACCURATE, /* .ratio = */ 0.5f, /* .name = */ NULL }
+///     ```
+///
+/// ### Builder
+///
+/// Big config structs, with many fields that C callers usually leave to
+/// their default value, are tedious (and error-prone) to construct by value.
+/// With `#[derive_ReprC(c_builder)]`, a struct implementing `Default` gets an
+/// opaque `<Name>Builder` type, and the exported functions:
+///
+///   - `<snake_case_name>_builder_new()`, returning a builder initialized
+///     with the default value of the struct;
+///
+///   - `<snake_case_name>_builder_set_<field>(builder, value)`, for each
+///     field (marking the `#[optional]` ones as provided, which requires
+///     combining it with `optional_fields`);
+///
+///   - `<snake_case_name>_builder_build(builder)`, consuming (and freeing)
+///     the builder to return the built struct;
+///
+///   - `<snake_case_name>_builder_free(builder)`, to free a builder that
+///     will not be built.
+///
+/// ```rust
+/// use ::safer_ffi::prelude::*;
+///
+/// #[derive_ReprC(c_builder)]
+/// #[repr(C)]
+/// pub
+/// struct Config {
+///     pub verbose: bool,
+///     pub threads: u32,
+/// }
+///
+/// impl Default for Config {
+///     fn default () -> Self
+///     {
+///         Config { verbose: false, threads: 1 }
+///     }
+/// }
+/// ```
+///
+///   - corresponding to the following C definitions:
+///
+///     ```C
+///     typedef struct {
+///         bool verbose;
+///         uint32_t threads;
+///     } Config_t;
+///
+///     typedef struct ConfigBuilder ConfigBuilder_t;
+///
+///     ConfigBuilder_t * config_builder_new (void);
+///
+///     void config_builder_set_verbose (
+///         ConfigBuilder_t * builder,
+///         bool value);
+///
+///     void config_builder_set_threads (
+///         ConfigBuilder_t * builder,
+///         uint32_t value);
+///
+///     Config_t config_builder_build (
+///         ConfigBuilder_t * builder);
+///
+///     void config_builder_free (
+///         ConfigBuilder_t * builder);
+///     ```
+///
+///     to be used as:
+///
+///     ```C
+///     ConfigBuilder_t * builder = config_builder_new();
+///     config_builder_set_threads(builder, 4);
+///     Config_t config = config_builder_build(builder);
+///     ```
+///
+/// ### Extensible (`struct_size`-first) struct
+///
+/// With `#[derive_ReprC(struct_size)]`, a leading `pub struct_size: usize`
+/// field is added to the struct, so that new fields can be appended to it
+/// in later versions without breaking the ABI. The struct needs to implement
+/// `Default`, and gets:
+///
+///   - an exported `<snake_case_name>_init(out, struct_size)` function, for
+///     C to initialize its version of the struct with the default values (an
+///     `unsafe fn` on the Rust side, since `out` must be `NULL` or valid for
+///     writes of `struct_size` bytes);
+///
+///   - an `unsafe fn from_versioned_ptr(ptr: *const Self) -> Option<Self>`
+///     method, for Rust to read a struct provided by C, whatever its version:
+///     the fields unknown to the caller get their default value, and the ones
+///     unknown to Rust are ignored.
+///
+/// ```rust
+/// use ::safer_ffi::prelude::*;
+///
+/// #[derive_ReprC(struct_size)]
+/// #[repr(C)]
+/// pub
+/// struct DecoderConfig {
+///     pub verbose: bool,
+///     pub threads: u32,
+/// }
+///
+/// impl Default for DecoderConfig {
+///     fn default () -> Self
+///     {
+///         DecoderConfig {
+///             struct_size: Self::STRUCT_SIZE,
+///             verbose: false,
+///             threads: 1,
+///         }
+///     }
+/// }
+///
+/// #[ffi_export]
+/// fn decoder_new (config: *const DecoderConfig) -> bool
+/// {
+///     let config = match unsafe { DecoderConfig::from_versioned_ptr(config) } {
+///         | Some(it) => it,
+///         | None => return false,
+///     };
+///     // ...
+///     # drop(config);
+///     true
+/// }
+/// ```
+///
+///   - corresponding to the following C definitions:
+///
+///     ```C
+///     typedef struct {
+///         size_t struct_size;
+///         bool verbose;
+///         uint32_t threads;
+///     } DecoderConfig_t;
+///
+///     void decoder_config_init (DecoderConfig_t * out, size_t struct_size);
+///     ```
+///
+///     to be used as:
+///
+///     ```C
+///     DecoderConfig_t config;
+///     decoder_config_init(&config, sizeof(config));
+///     config.threads = 4;
+///     decoder_new(&config);
+///     ```
+///
+/// ### Optional fields (presence bitmask)
+///
+/// Telling a field "not provided" apart from one set to zero usually
+/// requires wrapping it in a tagged struct. With
+/// `#[derive_ReprC(optional_fields)]`, a leading `pub present: u64` bitmask
+/// field is added to the struct instead, with one bit per field marked
+/// `#[optional]` (in order, up to 64 of them), and:
+///
+///   - for each `#[optional]` field, a `.has_<field>()` method, and a
+///     `.set_<field>(value)` method also marking it as provided;
+///
+///   - for each `#[optional]` field, a `<SCREAMING_CASE_NAME>_<FIELD>_PRESENT`
+///     bit and a `<SCREAMING_CASE_NAME>_HAS_<FIELD>(it)` macro in the
+///     headers.
+///
+/// ```rust
+/// use ::safer_ffi::prelude::*;
+///
+/// #[derive_ReprC(optional_fields)]
+/// #[repr(C)]
+/// pub
+/// struct Limits {
+///     #[optional]
+///     pub max_threads: u32,
+///     #[optional]
+///     pub timeout_ms: u64,
+/// }
+///
+/// #[ffi_export]
+/// fn apply_limits (limits: &Limits) -> u32
+/// {
+///     if limits.has_max_threads() { limits.max_threads } else { 1 }
+/// }
+/// ```
+///
+///   - corresponding to the following C definitions:
+///
+///     ```C
+///     typedef struct {
+///         uint64_t present;
+///         uint32_t max_threads;
+///         uint64_t timeout_ms;
+///     } Limits_t;
+///
+///     #define LIMITS_MAX_THREADS_PRESENT (UINT64_C(1) << 0)
+///
+///     #define LIMITS_HAS_MAX_THREADS(it) ((((it).present) & LIMITS_MAX_THREADS_PRESENT) != 0)
+///
+///     #define LIMITS_TIMEOUT_MS_PRESENT (UINT64_C(1) << 1)
+///
+///     #define LIMITS_HAS_TIMEOUT_MS(it) ((((it).present) & LIMITS_TIMEOUT_MS_PRESENT) != 0)
+///     ```
+///
+///     to be used as:
+///
+///     ```C
+///     Limits_t limits = {0};
+///     limits.timeout_ms = 500;
+///     limits.present |= LIMITS_TIMEOUT_MS_PRESENT;
+///     apply_limits(&limits);
+///     ```
+///
+/// ### Versioned fields
+///
+/// A type, and the fields of a struct, can be annotated with the version of
+/// the API which introduced them, with `#[repr_c(since = "<version>")]`
+/// (with any of the above options). The version is then:
+///
+///   - emitted as a Doxygen `\since` command in the docs of the C definition;
+///
+///   - listed in the [`Report`] and in the JSON manifest of the headers;
+///
+///   - checked against the previous fields: since the code compiled against
+///     an older version of the struct only knows about its first fields, the
+///     new fields must be appended at the end of the struct, which is
+///     otherwise reported as a [`Diagnostic::MisplacedNewField`] warning.
+///
+/// ```rust
+/// use ::safer_ffi::prelude::*;
+///
+/// /// Server configuration.
+/// #[derive_ReprC(struct_size)]
+/// #[repr(C)]
+/// #[repr_c(since = "1.0")]
+/// #[derive(Default)]
+/// pub
+/// struct ServerConfig {
+///     pub port: u16,
+///     #[repr_c(since = "1.2")]
+///     pub timeout_ms: u64,
+/// }
+/// ```
+///
+///   - corresponding to the following C definition:
+///
+///     ```C
+///     /** \brief
+///      *  Server configuration.
+///      *
+///      *  \since 1.0
+///      */
+///     typedef struct {
+///         size_t struct_size;
+///
+///         uint16_t port;
+///
+///         /** \since 1.2 */
+///         uint64_t timeout_ms;
+///     } ServerConfig_t;
+///     ```
+///
+/// [`Report`]: /safer_ffi/headers/struct.Report.html
+/// [`Diagnostic::MisplacedNewField`]: /safer_ffi/headers/enum.Diagnostic.html#variant.MisplacedNewField
+///
+/// ### The `CLayout` companion type
+///
+/// The C layout of a `#[repr(C)]` struct (or union) or of a field-less enum,
+/// [`<Name as ReprC>::CLayout`], is a distinct type, which is re-exported,
+/// next to it and with its visibility, as `<Name>_Layout`. Since it is
+/// seldom needed, and otherwise better named through the
+/// `<Name as ReprC>::CLayout` path, the name and the visibility of that
+/// re-export can be changed with the `#[repr_c(layout = "<Name>")]` and
+/// `#[repr_c(layout_vis = "<visibility>")]` attributes, _e.g._, to keep it
+/// out of the public API (and its docs):
+///
+/// ```rust
+/// use ::safer_ffi::{layout::ReprC, prelude::*};
+///
+/// #[derive_ReprC]
+/// #[repr(C)]
+/// #[repr_c(layout_vis = "pub(crate)")]
+/// pub
+/// struct Point {
+///     pub x: f64,
+///     pub y: f64,
+/// }
+///
+/// // Both name the same type.
+/// let _: Option<Point_Layout> = None::<<Point as ReprC>::CLayout>;
+/// ```
+///
+/// [`<Name as ReprC>::CLayout`]: /safer_ffi/layout/trait.ReprC.html#associatedtype.CLayout
+///
+/// ### Measurement newtype
+///
+/// With `#[derive_ReprC(newtype)]`, a single-field tuple struct wrapping a
+/// `CType` (typically an integer or a float), such as a duration in
+/// milliseconds, a size in bytes, or an identifier, is made
+/// `#[repr(transparent)]`, and gets:
+///
+///   - its own C `typedef`, so that C code also sees a distinct type;
+///
+///   - `From` conversions from and into the wrapped type;
+///
+///   - the `+`, `-` (and `+=`, `-=`) operators between two values, and the
+///     `*`, `/` (and `*=`, `/=`) operators with a scalar of the wrapped type.
+///
+/// ```rust
+/// use ::safer_ffi::prelude::*;
+///
+/// /// Duration, in milliseconds.
+/// #[derive_ReprC(newtype)]
+/// #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+/// pub
+/// struct Millis(pub u64);
+///
+/// #[ffi_export]
+/// fn timeout_after (delay: Millis, retries: u64) -> Millis
+/// {
+///     delay * (retries + 1) + Millis::from(10)
+/// }
+/// ```
+///
+///   - corresponding to the following C definitions:
+///
+///     ```C
+///     /** \brief
+///      *  Duration, in milliseconds.
+///      */
+///     typedef uint64_t Millis_t;
+///
+///     Millis_t timeout_after (
+///         Millis_t delay,
+///         uint64_t retries);
+///     ```
+///
+/// ### Combining options
+///
+/// Two pairs of options can be combined:
+///
+///   - `#[derive_ReprC(struct_size, default_init)]`: the `_init` function is
+///     the `struct_size` one, and the `_DEFAULT` initializer sets the
+///     `struct_size` field to the `sizeof` the struct;
+///
+///   - `#[derive_ReprC(c_builder, optional_fields)]`: the builder setters of
+///     the `#[optional]` fields also mark them as provided.
 #[cfg(feature = "proc_macros")]
 #[proc_macro_attribute] pub
 fn derive_ReprC (attrs: TokenStream, input: TokenStream)
   -> TokenStream
 {
-    if let Some(tt) = TokenStream2::from(attrs).into_iter().next() {
-        return Error::new_spanned(tt,
-            "Unexpected parameter",
-        ).to_compile_error().into();
+    let options = match Punctuated::<Ident, Token![,]>::parse_terminated.parse(attrs) {
+        | Ok(it) => it,
+        | Err(err) => return err.to_compile_error().into(),
+    };
+    let mut names = vec![];
+    for option in &options {
+        match &option.to_string()[..] {
+            | "c_builder"
+            | "callback_table"
+            | "default_init"
+            | "export_is_valid"
+            | "newtype"
+            | "optional_fields"
+            | "struct_size"
+            => {},
+            | _ => return Error::new_spanned(option,
+                "Unexpected parameter",
+            ).to_compile_error().into(),
+        }
+        if names.contains(&option.to_string()) {
+            return Error::new_spanned(option,
+                "Duplicate parameter",
+            ).to_compile_error().into();
+        }
+        names.push(option.to_string());
+    }
+    names.sort();
+    let names: Vec<&str> = names.iter().map(|it| &it[..]).collect();
+    match names[..] {
+        | [] => feed_to_macro_rules(input, parse_quote!(ReprC)),
+        | ["c_builder"] => with_repr_c(input, |input| c_builder(input, false)),
+        | ["callback_table"] => callback_table(input),
+        | ["default_init"] => with_repr_c(input, |input| {
+            default_init(input, false)
+        }),
+        | ["export_is_valid"] => export_is_valid(input),
+        | ["newtype"] => newtype(input),
+        | ["optional_fields"] => with_repr_c(input, optional_fields),
+        | ["struct_size"] => with_repr_c(input, struct_size),
+        | ["default_init", "struct_size"] => with_repr_c(input, |input| {
+            let struct_size = struct_size(input)?;
+            let default_init = default_init(input, true)?;
+            Ok(quote!( #struct_size #default_init ))
+        }),
+        | ["c_builder", "optional_fields"] => with_repr_c(input, |input| {
+            // Before `optional_fields` strips the `#[optional]` markers.
+            let c_builder = c_builder(input, true)?;
+            let optional_fields = optional_fields(input)?;
+            Ok(quote!( #c_builder #optional_fields ))
+        }),
+        | _ => Error::new_spanned(&options,
+            "Only `default_init, struct_size` and `c_builder, optional_fields` \
+            can be combined",
+        ).to_compile_error().into(),
+    }
+}
+
+/// Runs the given option-specific pass, which may alter the struct (_e.g._,
+/// to add fields) and yields the items to emit alongside it, before feeding
+/// the (altered) struct to `ReprC!`.
+#[cfg(feature = "proc_macros")]
+fn with_repr_c (
+    input: TokenStream,
+    pass: impl FnOnce(&mut DeriveInput) -> Result<TokenStream2>,
+) -> TokenStream
+{
+    let mut input: DeriveInput = parse_macro_input!(input);
+    let items = match pass(&mut input) {
+        | Ok(it) => it,
+        | Err(err) => return err.to_compile_error().into(),
+    };
+    let repr_c_impl = TokenStream2::from(feed_to_macro_rules(
+        input.to_token_stream().into(),
+        parse_quote!(ReprC),
+    ));
+    let ret = quote! {
+        #repr_c_impl
+
+        #items
+    };
+    #[cfg(feature = "verbose-expansions")]
+    println!("{}", ret.to_string());
+    ret.into()
+}
+
+/// `#[derive_ReprC(c_builder)]`: an FFI-safe builder for (big) config
+/// structs, starting from their `Default` value.
+///
+/// On top of the `ReprC` impl, it generates an opaque `<Name>Builder` type,
+/// and the exported functions:
+///
+///   - `<snake_case_name>_builder_new()`;
+///
+///   - `<snake_case_name>_builder_set_<field>(builder, value)`, for each
+///     field;
+///
+///   - `<snake_case_name>_builder_build(builder)`, consuming the builder;
+///
+///   - `<snake_case_name>_builder_free(builder)`.
+///
+/// `optional_fields` tells whether it is combined with `optional_fields`,
+/// which `#[optional]` fields require.
+#[cfg(feature = "proc_macros")]
+fn c_builder (input: &'_ DeriveInput, optional_fields: bool)
+  -> Result<TokenStream2>
+{
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(&input.generics,
+            "`c_builder` does not support generic structs",
+        ));
+    }
+    let fields = match input.data {
+        | Data::Struct(DataStruct {
+            fields: Fields::Named(ref it),
+            ..
+        }) => &it.named,
+        | _ => return Err(Error::new(Span2::call_site(),
+            "`c_builder` expects a struct with named fields",
+        )),
+    };
+    if !optional_fields {
+        let mut attrs = fields.iter().flat_map(|field| &field.attrs);
+        if let Some(attr) = attrs.find(|attr| attr.path.is_ident("optional")) {
+            return Err(Error::new_spanned(attr,
+                "`#[optional]` fields require \
+                `#[derive_ReprC(c_builder, optional_fields)]`",
+            ));
+        }
+    }
+    let StructName = &input.ident;
+    let vis = &input.vis;
+    let BuilderName = format_ident!("{}Builder", StructName);
+    let prefix = format!("{}_builder", to_snake_case(&StructName.to_string()));
+    let new_name = format_ident!("{}_new", prefix);
+    let build_name = format_ident!("{}_build", prefix);
+    let free_name = format_ident!("{}_free", prefix);
+    let builder_doc = format!(
+        " Builder of a `{}`, starting from its default value.",
+        StructName,
+    );
+    let new_doc = format!(
+        " Returns a new builder of a `{}`, initialized with its default \
+        value.",
+        StructName,
+    );
+    let build_doc = format!(
+        " Consumes (and frees) the `builder`, returning the built `{}`.",
+        StructName,
+    );
+    let setters = fields.iter().map(|field| {
+        let field_name = field.ident.as_ref().unwrap();
+        let field_ty = &field.ty;
+        let set_name = format_ident!("{}_set_{}", prefix, field_name);
+        let set_doc = format!(" Sets the `{}` of the built `{}`.", field_name, StructName);
+        // With `optional_fields`, also mark the field as provided.
+        let set = if field.attrs.iter().any(|attr| attr.path.is_ident("optional")) {
+            let set_field = format_ident!("set_{}", field_name);
+            quote!( builder.value.#set_field(value); )
+        } else {
+            quote!( builder.value.#field_name = value; )
+        };
+        quote! {
+            #[::safer_ffi::ffi_export]
+            #[doc = #set_doc]
+            #vis
+            fn #set_name (builder: &'_ mut #BuilderName, value: #field_ty)
+            {
+                #set
+            }
+        }
+    });
+    Ok(quote! {
+        ::safer_ffi::layout::ReprC! {
+            #[ReprC::opaque]
+            #[doc = #builder_doc]
+            #vis
+            struct #BuilderName {
+                value: #StructName,
+            }
+        }
+
+        #[::safer_ffi::ffi_export]
+        #[doc = #new_doc]
+        #vis
+        fn #new_name ()
+          -> ::safer_ffi::boxed::Box<#BuilderName>
+        {
+            ::safer_ffi::boxed::Box::new(#BuilderName {
+                value: ::safer_ffi::core::default::Default::default(),
+            })
+        }
+
+        #(#setters)*
+
+        #[::safer_ffi::ffi_export]
+        #[doc = #build_doc]
+        #vis
+        fn #build_name (builder: ::safer_ffi::boxed::Box<#BuilderName>)
+          -> #StructName
+        {
+            builder.into().value
+        }
+
+        #[::safer_ffi::ffi_export]
+        /// Frees a builder that is not to be built.
+        #vis
+        fn #free_name (
+            builder: ::safer_ffi::core::option::Option<
+                ::safer_ffi::boxed::Box<#BuilderName>,
+            >,
+        )
+        {
+            ::safer_ffi::core::mem::drop(builder)
+        }
+    })
+}
+
+/// `#[derive_ReprC(callback_table)]`: a `#[repr(C)]` struct of
+/// `repr_c::FnPtr<Args, Ret>` fields, _i.e._, a C "ops table".
+///
+/// On top of the `ReprC` impl, it generates:
+///
+///   - for each field, a method of the same name calling the function
+///     pointer with the given arguments, or returning a `NullFnPtr` error;
+///
+///   - a `validate()` method, checking that all the fields not marked
+///     `#[optional]` are non-`NULL`.
+#[cfg(feature = "proc_macros")]
+fn callback_table (input: TokenStream)
+  -> TokenStream
+{
+    let mut input: DeriveInput = parse_macro_input!(input);
+    let fields = match input.data {
+        | Data::Struct(DataStruct {
+            fields: Fields::Named(ref mut it),
+            ..
+        }) => &mut it.named,
+        | _ => return Error::new(Span2::call_site(),
+            "`callback_table` expects a struct with named fields",
+        ).to_compile_error().into(),
+    };
+    let mut methods = vec![];
+    let mut required = vec![];
+    for field in fields.iter_mut() {
+        let is_optional = field.attrs.iter().any(|attr| {
+            attr.path.is_ident("optional")
+        });
+        field.attrs.retain(|attr| !attr.path.is_ident("optional"));
+        let field_name = field.ident.as_ref().unwrap();
+        let (args, ret) = match fn_ptr_signature(&field.ty) {
+            | Some(it) => it,
+            | None => return Error::new_spanned(&field.ty,
+                "`callback_table` fields must be `repr_c::FnPtr<Args, Ret>`s",
+            ).to_compile_error().into(),
+        };
+        let arg_names: Vec<Ident> =
+            (0 .. args.len())
+                .map(|i| format_ident!("arg_{}", i))
+                .collect()
+        ;
+        let vis = &field.vis;
+        let doc = format!(
+            " Calls the `{}` callback, unless it is `NULL`.",
+            field_name,
+        );
+        methods.push(quote! {
+            #[doc = #doc]
+            #[inline]
+            #vis
+            fn #field_name (self: &'_ Self, #(#arg_names : #args),*)
+              -> ::safer_ffi::core::result::Result<
+                    #ret,
+                    ::safer_ffi::fn_ptr::NullFnPtr,
+                >
+            {
+                self.#field_name.call((#(#arg_names ,)*))
+            }
+        });
+        if !is_optional {
+            let name = field_name.to_string();
+            required.push(quote! {
+                if self.#field_name.is_null() {
+                    return ::safer_ffi::core::result::Result::Err(
+                        ::safer_ffi::fn_ptr::MissingCallback(#name)
+                    );
+                }
+            });
+        }
     }
-    feed_to_macro_rules(input, parse_quote!(ReprC))
+    let StructName = &input.ident;
+    let vis = &input.vis;
+    let (intro_generics, fwd_generics, where_clause) =
+        input.generics.split_for_impl()
+    ;
+    let repr_c_impl = TokenStream2::from(feed_to_macro_rules(
+        input.to_token_stream().into(),
+        parse_quote!(ReprC),
+    ));
+    let ret = quote! {
+        #repr_c_impl
+
+        impl #intro_generics #StructName #fwd_generics
+        #where_clause
+        {
+            #(#methods)*
+
+            /// Checks that none of the required (_i.e._, non-`#[optional]`)
+            /// callbacks are `NULL`.
+            #vis
+            fn validate (self: &'_ Self)
+              -> ::safer_ffi::core::result::Result<
+                    (),
+                    ::safer_ffi::fn_ptr::MissingCallback,
+                >
+            {
+                #(#required)*
+                ::safer_ffi::core::result::Result::Ok(())
+            }
+        }
+    };
+    #[cfg(feature = "verbose-expansions")]
+    println!("{}", ret.to_string());
+    ret.into()
+}
+
+/// `[repr_c::]FnPtr<(A1, ..., An), Ret>` => `([A1, ..., An], Ret)`.
+#[cfg(feature = "proc_macros")]
+fn fn_ptr_signature (ty: &'_ Type)
+  -> Option<(Vec<Type>, Type)>
+{
+    let last = match *ty {
+        | Type::Path(TypePath { qself: None, ref path }) => {
+            path.segments.iter().last()?
+        },
+        | _ => return None,
+    };
+    if last.ident != "FnPtr" { return None; }
+    let mut generics = match last.arguments {
+        | PathArguments::AngleBracketed(ref it) => it.args.iter(),
+        | _ => return None,
+    };
+    let args = match generics.next()? {
+        | GenericArgument::Type(Type::Tuple(ref tuple)) => {
+            tuple.elems.iter().cloned().collect()
+        },
+        | GenericArgument::Type(Type::Paren(ref paren)) => {
+            vec![(*paren.elem).clone()]
+        },
+        | _ => return None,
+    };
+    let ret = match generics.next() {
+        | None => parse_quote!( () ),
+        | Some(GenericArgument::Type(ref ret)) => ret.clone(),
+        | Some(_) => return None,
+    };
+    if generics.next().is_some() { return None; }
+    Some((args, ret))
 }
 
 #[proc_macro_attribute] pub
@@ -181,3 +1077,642 @@ fn derive_CType (attrs: TokenStream, input: TokenStream)
     }
     feed_to_macro_rules(input, parse_quote!(CType))
 }
+
+/// `#[derive_ReprC(struct_size)]`: the "`struct_size`-first" extensible
+/// struct convention, letting a struct grow new (trailing) fields without
+/// breaking the ABI.
+///
+/// It prepends a `pub struct_size: usize` field to the struct, and generates:
+///
+///   - a `STRUCT_SIZE` associated constant (the current size of the struct);
+///
+///   - an exported `<snake_case_name>_init(out, struct_size)` function,
+///     initializing the fields of the caller's version of the struct with
+///     their `Default` values;
+///
+///   - an `unsafe fn from_versioned_ptr(ptr)` method, reading the fields
+///     provided by the caller's version of the struct, with the missing ones
+///     falling back to their `Default` value.
+#[cfg(feature = "proc_macros")]
+fn struct_size (input: &'_ mut DeriveInput)
+  -> Result<TokenStream2>
+{
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(&input.generics,
+            "`struct_size` does not support generic structs",
+        ));
+    }
+    let fields = match input.data {
+        | Data::Struct(DataStruct {
+            fields: Fields::Named(ref mut it),
+            ..
+        }) => &mut it.named,
+        | _ => return Err(Error::new(Span2::call_site(),
+            "`struct_size` expects a struct with named fields",
+        )),
+    };
+    fields.insert(0, Field::parse_named.parse2(quote! {
+        /// `sizeof` the struct, as known by the code that filled it.
+        pub
+        struct_size: usize
+    }).unwrap());
+    let field_names: Vec<Ident> =
+        fields.iter().map(|f| f.ident.clone().unwrap()).collect()
+    ;
+    let field_tys: Vec<Type> = fields.iter().map(|f| f.ty.clone()).collect();
+    let field_count = field_names.len();
+    let StructName = &input.ident;
+    let vis = &input.vis;
+    let init_name = format_ident!("{}_init", to_snake_case(&StructName.to_string()));
+    let init_doc = format!(
+        " Initializes the fields of the `struct_size`-long `{}` pointed to by \
+        `out` with their default values.",
+        StructName,
+    );
+    Ok(quote! {
+        impl #StructName {
+            /// The current `sizeof` the struct.
+            #vis
+            const STRUCT_SIZE: usize =
+                ::safer_ffi::core::mem::size_of::<Self>()
+            ;
+
+            /// Byte offsets of the end of each field.
+            #[doc(hidden)]
+            fn __field_ends__ (it: &'_ Self)
+              -> [usize; #field_count]
+            {
+                let base = it as *const Self as usize;
+                [#(
+                    &it.#field_names as *const _ as usize - base
+                    + ::safer_ffi::core::mem::size_of::<#field_tys>()
+                ),*]
+            }
+
+            /// Reads the fields provided by the caller's version of the
+            /// struct (as per its `struct_size`), the missing ones (if any)
+            /// falling back to their `Default` value.
+            ///
+            /// Returns `None` if `ptr` is `NULL`, or if a field is invalid.
+            ///
+            /// # Safety
+            ///
+            /// `ptr` must point to `(*ptr).struct_size` readable bytes.
+            #vis
+            unsafe
+            fn from_versioned_ptr (ptr: *const Self)
+              -> ::safer_ffi::core::option::Option<Self>
+            {
+                if ptr.is_null() {
+                    return ::safer_ffi::core::option::Option::None;
+                }
+                let struct_size = (ptr as *const usize).read_unaligned();
+                let default: Self = ::safer_ffi::core::default::Default::default();
+                let ends = Self::__field_ends__(&default);
+                let mut ends = ends.iter().copied();
+                ::safer_ffi::core::option::Option::Some(Self {
+                    #(
+                        #field_names: {
+                            let end = ends.next().unwrap();
+                            let size = ::safer_ffi::core::mem::size_of::<#field_tys>();
+                            if end <= struct_size {
+                                ::safer_ffi::layout::from_raw::<#field_tys>(
+                                    ((ptr as *const u8).add(end - size)
+                                        as *const <#field_tys as ::safer_ffi::layout::ReprC>::CLayout
+                                    ).read_unaligned()
+                                )?
+                            } else {
+                                default.#field_names
+                            }
+                        },
+                    )*
+                })
+                .map(|mut it: Self| {
+                    it.struct_size = Self::STRUCT_SIZE;
+                    it
+                })
+            }
+        }
+
+        #[::safer_ffi::ffi_export]
+        #[doc = #init_doc]
+        #[doc = ""]
+        #[doc = " # Safety"]
+        #[doc = ""]
+        #[doc = " `out` must be `NULL` or valid for writes of `struct_size` bytes."]
+        #vis
+        unsafe
+        fn #init_name (out: *mut #StructName, struct_size: usize)
+        {
+            if out.is_null() {
+                return;
+            }
+            let default = <#StructName as ::safer_ffi::core::default::Default>::default();
+            let ends = #StructName::__field_ends__(&default);
+            let mut ends = ends.iter().copied();
+            // Safety: `out` is valid for writes of `struct_size` bytes, as per
+            // the contract.
+            let out = out as *mut u8;
+            if struct_size > #StructName::STRUCT_SIZE {
+                out .add(#StructName::STRUCT_SIZE)
+                    .write_bytes(0, struct_size - #StructName::STRUCT_SIZE);
+            }
+            #(
+                let end = ends.next().unwrap();
+                let size = ::safer_ffi::core::mem::size_of::<#field_tys>();
+                if end <= struct_size {
+                    (out.add(end - size)
+                        as *mut <#field_tys as ::safer_ffi::layout::ReprC>::CLayout
+                    ).write_unaligned(
+                        ::safer_ffi::layout::into_raw(default.#field_names)
+                    );
+                }
+            )*
+            if ::safer_ffi::core::mem::size_of::<usize>() <= struct_size {
+                (out as *mut usize).write_unaligned(struct_size);
+            }
+        }
+    })
+}
+
+/// `#[derive_ReprC(default_init)]`: let C initialize the struct with its
+/// (Rust) `Default` value, rather than zero-initializing it (which may lead
+/// to invalid values, such as `enum` discriminants).
+///
+/// On top of the `ReprC` impl, it generates:
+///
+///   - an exported `<snake_case_name>_init(out)` function;
+///
+///   - a `#define <SCREAMING_CASE_NAME>_DEFAULT { ... }` initializer macro in
+///     the headers, with the C literals of the default field values, or the
+///     C expression given by a `#[c_default = "..."]` field attribute.
+///
+/// When `versioned`, _i.e._, combined with (and run after) `struct_size`, the
+/// `_init` function is the `struct_size` one, and the leading `struct_size`
+/// field defaults to the `sizeof` the struct.
+#[cfg(feature = "proc_macros")]
+fn default_init (input: &'_ mut DeriveInput, versioned: bool)
+  -> Result<TokenStream2>
+{
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(&input.generics,
+            "`default_init` does not support generic structs",
+        ));
+    }
+    let fields = match input.data {
+        | Data::Struct(DataStruct {
+            fields: Fields::Named(ref mut it),
+            ..
+        }) => &mut it.named,
+        | _ => return Err(Error::new(Span2::call_site(),
+            "`default_init` expects a struct with named fields",
+        )),
+    };
+    let mut field_literals = vec![];
+    for (i, field) in fields.iter_mut().enumerate() {
+        if versioned && i == 0 {
+            // The `struct_size` field, prepended by `struct_size`.
+            field_literals.push(quote!(
+                ::safer_ffi::std::format!("/* .struct_size = */ sizeof({})",
+                    <
+                        <Self as ::safer_ffi::layout::ReprC>::CLayout
+                        as
+                        ::safer_ffi::layout::CType
+                    >::c_var(""),
+                )
+            ));
+            continue;
+        }
+        let mut c_default = None;
+        let mut errors = vec![];
+        field.attrs.retain(|attr| {
+            if !attr.path.is_ident("c_default") {
+                return true;
+            }
+            match attr.parse_meta() {
+                | Ok(Meta::NameValue(MetaNameValue {
+                    lit: Lit::Str(ref lit),
+                    ..
+                })) => c_default = Some(lit.clone()),
+                | _ => errors.push(Error::new_spanned(attr,
+                    "expected `#[c_default = \"<C expression>\"]`",
+                )),
+            }
+            false
+        });
+        if let Some(err) = errors.pop() {
+            return Err(err);
+        }
+        let field_name = field.ident.as_ref().unwrap();
+        let label = format!("/* .{} = */ ", field_name);
+        field_literals.push(match c_default {
+            | Some(c_expr) => quote!(
+                ::safer_ffi::std::format!("{}{}", #label, #c_expr)
+            ),
+            | None => quote!(
+                ::safer_ffi::std::format!("{}{}",
+                    #label,
+                    ::safer_ffi::headers::CLiteral::c_literal(
+                        &self.#field_name,
+                    )?,
+                )
+            ),
+        });
+    }
+    let StructName = &input.ident;
+    let vis = &input.vis;
+    let init_name = format_ident!("{}_init", to_snake_case(&StructName.to_string()));
+    let init_doc = format!(
+        " Initializes the `{}` pointed to by `out` with its default value.",
+        StructName,
+    );
+    let init_safety_doc = format!(
+        " `out` must be `NULL` or valid for (and properly aligned for) a write \
+        of a `{}`.",
+        StructName,
+    );
+    // With `struct_size`, its (versioned) `_init` function is the one to use.
+    let init_fn = if versioned {
+        quote!()
+    } else {
+        quote! {
+            #[::safer_ffi::ffi_export]
+            #[doc = #init_doc]
+            #[doc = ""]
+            #[doc = " # Safety"]
+            #[doc = ""]
+            #[doc = #init_safety_doc]
+            #vis
+            unsafe
+            fn #init_name (out: *mut #StructName)
+            {
+                if out.is_null() {
+                    return;
+                }
+                // Safety: non-null out-pointer, valid as per the contract.
+                out.write(::safer_ffi::core::default::Default::default());
+            }
+        }
+    };
+    Ok(quote! {
+        ::safer_ffi::__cfg_headers__! {
+            impl ::safer_ffi::headers::CLiteral
+                for #StructName
+            {
+                fn c_literal (self: &'_ Self)
+                  -> ::safer_ffi::core::option::Option<
+                        ::safer_ffi::std::string::String,
+                    >
+                {
+                    let fields: &[::safer_ffi::std::string::String] = &[
+                        #(#field_literals),*
+                    ];
+                    ::safer_ffi::core::option::Option::Some(
+                        ::safer_ffi::std::format!("{{ {} }}", fields.join(", "))
+                    )
+                }
+            }
+        }
+
+        #init_fn
+
+        ::safer_ffi::__ffi_export_c_default__! {
+            #StructName
+        }
+    })
+}
+
+/// `#[derive_ReprC(export_is_valid)]`: on top of the `ReprC` impl of a
+/// field-less `enum`, export a `<snake_case_name>_is_valid(value)` function,
+/// for C code to check a value (_e.g._, read from a config file) before
+/// using it as an instance of the `enum`.
+#[cfg(feature = "proc_macros")]
+fn export_is_valid (input: TokenStream)
+  -> TokenStream
+{
+    let input: DeriveInput = parse_macro_input!(input);
+    if let Data::Enum(_) = input.data {} else {
+        return Error::new(Span2::call_site(),
+            "`export_is_valid` expects a field-less `enum`",
+        ).to_compile_error().into();
+    }
+    let Int: Option<Ident> = input.attrs.iter().find_map(|attr| {
+        if !attr.path.is_ident("repr") {
+            return None;
+        }
+        attr.parse_args().ok()
+    });
+    let Int = match Int {
+        | Some(it) => it,
+        | None => return Error::new(Span2::call_site(),
+            "missing `#[repr(<integer type>)]`",
+        ).to_compile_error().into(),
+    };
+    let EnumName = &input.ident;
+    let vis = &input.vis;
+    let fname = format_ident!("{}_is_valid", to_snake_case(&EnumName.to_string()));
+    let doc = format!(" Whether `value` is a valid `{}`.", EnumName);
+    let repr_c_impl = TokenStream2::from(feed_to_macro_rules(
+        input.to_token_stream().into(),
+        parse_quote!(ReprC),
+    ));
+    let ret = quote! {
+        #repr_c_impl
+
+        #[::safer_ffi::ffi_export]
+        #[doc = #doc]
+        #vis
+        fn #fname (value: i64)
+          -> bool
+        {
+            match <::safer_ffi::#Int as ::safer_ffi::core::convert::TryFrom<i64>>
+                    ::try_from(value)
+            {
+                | ::safer_ffi::core::result::Result::Ok(it) => {
+                    <#EnumName as ::safer_ffi::layout::ReprC>::is_valid(
+                        &::safer_ffi::core::convert::Into::into(it)
+                    )
+                },
+                | ::safer_ffi::core::result::Result::Err(_) => false,
+            }
+        }
+    };
+    #[cfg(feature = "verbose-expansions")]
+    println!("{}", ret.to_string());
+    ret.into()
+}
+
+/// `FooBar` => `foo_bar`
+#[cfg(feature = "proc_macros")]
+fn to_snake_case (name: &'_ str)
+  -> String
+{
+    let mut ret = String::with_capacity(name.len() + 4);
+    name.chars().enumerate().for_each(|(i, c)| {
+        if c.is_uppercase() {
+            if i > 0 {
+                ret.push('_');
+            }
+            ret.extend(c.to_lowercase());
+        } else {
+            ret.push(c);
+        }
+    });
+    ret
+}
+
+/// `#[derive_ReprC(optional_fields)]`: a presence bitmask for the fields
+/// marked `#[optional]`, so that "not provided" can be told apart from zero.
+///
+/// It prepends a `pub present: u64` field to the struct, and generates, for
+/// each `#[optional]` field:
+///
+///   - `.has_<field>()` and `.set_<field>(value)` methods;
+///
+///   - `#define <SCREAMING_CASE_NAME>_<FIELD>_PRESENT` and
+///     `#define <SCREAMING_CASE_NAME>_HAS_<FIELD>(it)` macros in the headers.
+#[cfg(feature = "proc_macros")]
+fn optional_fields (input: &'_ mut DeriveInput)
+  -> Result<TokenStream2>
+{
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(&input.generics,
+            "`optional_fields` does not support generic structs",
+        ));
+    }
+    let fields = match input.data {
+        | Data::Struct(DataStruct {
+            fields: Fields::Named(ref mut it),
+            ..
+        }) => &mut it.named,
+        | _ => return Err(Error::new(Span2::call_site(),
+            "`optional_fields` expects a struct with named fields",
+        )),
+    };
+    let mut optional = vec![];
+    for field in fields.iter_mut() {
+        let is_optional = field.attrs.iter().any(|attr| {
+            attr.path.is_ident("optional")
+        });
+        field.attrs.retain(|attr| !attr.path.is_ident("optional"));
+        if is_optional {
+            optional.push((field.ident.clone().unwrap(), field.ty.clone()));
+        }
+    }
+    if optional.is_empty() {
+        return Err(Error::new(Span2::call_site(),
+            "`optional_fields` expects some fields to be marked `#[optional]`",
+        ));
+    }
+    if optional.len() > 64 {
+        return Err(Error::new_spanned(&optional[64].0,
+            "`optional_fields` supports up to 64 `#[optional]` fields",
+        ));
+    }
+    fields.insert(0, Field::parse_named.parse2(quote! {
+        /// Bitmask of the provided `#[optional]` fields.
+        pub
+        present: u64
+    }).unwrap());
+    let StructName = &input.ident;
+    let vis = &input.vis;
+    let methods = optional.iter().enumerate().map(|(bit, (field_name, field_ty))| {
+        let bit = bit as u32;
+        let has_name = format_ident!("has_{}", field_name);
+        let set_name = format_ident!("set_{}", field_name);
+        let has_doc = format!(" Whether the `{}` field is provided.", field_name);
+        let set_doc = format!(
+            " Sets the `{}` field, marking it as provided.",
+            field_name,
+        );
+        quote! {
+            #[doc = #has_doc]
+            #[inline]
+            #vis
+            fn #has_name (self: &'_ Self)
+              -> bool
+            {
+                self.present & (1 << #bit) != 0
+            }
+
+            #[doc = #set_doc]
+            #[inline]
+            #vis
+            fn #set_name (self: &'_ mut Self, value: #field_ty)
+            {
+                self.#field_name = value;
+                self.present |= 1 << #bit;
+            }
+        }
+    });
+    let field_names = optional.iter().map(|(field_name, _)| field_name);
+    Ok(quote! {
+        impl #StructName {
+            #(#methods)*
+        }
+
+        ::safer_ffi::__ffi_export_presence__! {
+            #StructName [#(#field_names),*]
+        }
+    })
+}
+
+/// `#[derive_ReprC(newtype)]`: a `#[repr(transparent)]` wrapper around a
+/// `CType`, for measurement types (durations, sizes, identifiers, _etc._).
+///
+/// On top of the `ReprC` impl (with a distinct C `typedef`), it generates:
+///
+///   - `From` conversions from and into the wrapped type;
+///
+///   - the `Add`, `Sub` (and `-Assign`) impls between two values, and the
+///     `Mul`, `Div` (and `-Assign`) impls with a scalar of the wrapped type.
+#[cfg(feature = "proc_macros")]
+fn newtype (input: TokenStream)
+  -> TokenStream
+{
+    let mut input: DeriveInput = parse_macro_input!(input);
+    if !input.generics.params.is_empty() {
+        return Error::new_spanned(&input.generics,
+            "`newtype` does not support generic structs",
+        ).to_compile_error().into();
+    }
+    let Inner = match input.data {
+        | Data::Struct(DataStruct {
+            fields: Fields::Unnamed(ref it),
+            ..
+        }) if it.unnamed.len() == 1 => it.unnamed[0].ty.clone(),
+        | _ => return Error::new(Span2::call_site(),
+            "`newtype` expects a tuple struct with a single field",
+        ).to_compile_error().into(),
+    };
+    if input.attrs.iter().all(|attr| !attr.path.is_ident("repr")) {
+        input.attrs.push(parse_quote!( #[repr(transparent)] ));
+    }
+    let docs = input.attrs.iter().filter(|attr| attr.path.is_ident("doc"));
+    let Name = &input.ident;
+    let ret = quote! {
+        #input
+
+        ::safer_ffi::__newtype_ReprC__! {
+            #(#docs)*
+            #Name (#Inner)
+        }
+
+        impl ::safer_ffi::core::convert::From<#Inner>
+            for #Name
+        {
+            #[inline]
+            fn from (it: #Inner)
+              -> #Name
+            {
+                #Name(it)
+            }
+        }
+
+        impl ::safer_ffi::core::convert::From<#Name>
+            for #Inner
+        {
+            #[inline]
+            fn from (it: #Name)
+              -> #Inner
+            {
+                it.0
+            }
+        }
+
+        impl ::safer_ffi::core::ops::Add
+            for #Name
+        {
+            type Output = #Name;
+
+            #[inline]
+            fn add (self: #Name, rhs: #Name)
+              -> #Name
+            {
+                #Name(self.0 + rhs.0)
+            }
+        }
+
+        impl ::safer_ffi::core::ops::Sub
+            for #Name
+        {
+            type Output = #Name;
+
+            #[inline]
+            fn sub (self: #Name, rhs: #Name)
+              -> #Name
+            {
+                #Name(self.0 - rhs.0)
+            }
+        }
+
+        impl ::safer_ffi::core::ops::Mul<#Inner>
+            for #Name
+        {
+            type Output = #Name;
+
+            #[inline]
+            fn mul (self: #Name, rhs: #Inner)
+              -> #Name
+            {
+                #Name(self.0 * rhs)
+            }
+        }
+
+        impl ::safer_ffi::core::ops::Div<#Inner>
+            for #Name
+        {
+            type Output = #Name;
+
+            #[inline]
+            fn div (self: #Name, rhs: #Inner)
+              -> #Name
+            {
+                #Name(self.0 / rhs)
+            }
+        }
+
+        impl ::safer_ffi::core::ops::AddAssign
+            for #Name
+        {
+            #[inline]
+            fn add_assign (self: &'_ mut #Name, rhs: #Name)
+            {
+                self.0 += rhs.0;
+            }
+        }
+
+        impl ::safer_ffi::core::ops::SubAssign
+            for #Name
+        {
+            #[inline]
+            fn sub_assign (self: &'_ mut #Name, rhs: #Name)
+            {
+                self.0 -= rhs.0;
+            }
+        }
+
+        impl ::safer_ffi::core::ops::MulAssign<#Inner>
+            for #Name
+        {
+            #[inline]
+            fn mul_assign (self: &'_ mut #Name, rhs: #Inner)
+            {
+                self.0 *= rhs;
+            }
+        }
+
+        impl ::safer_ffi::core::ops::DivAssign<#Inner>
+            for #Name
+        {
+            #[inline]
+            fn div_assign (self: &'_ mut #Name, rhs: #Inner)
+            {
+                self.0 /= rhs;
+            }
+        }
+    };
+    #[cfg(feature = "verbose-expansions")]
+    println!("{}", ret.to_string());
+    ret.into()
+}
@@ -48,18 +48,791 @@
 /// `struct` definitions with the [`#[derive_ReprC]`](
 /// /safer_ffi/layout/attr.derive_ReprC.html)
 /// (on top of the obviously required `#[repr(C)]`).
+///
+/// # `static`s
+///
+/// Some host applications (_e.g._, audio plugin hosts) look up a named
+/// structure (typically, a table of callbacks) rather than an entry function.
+/// `#[ffi_export]` can thus also be applied to a (non-`mut`) `static` of a
+/// [`ReprC`] type, which gets exported under its own name, and declared
+/// `extern const` in the headers:
+///
+/// ```rust
+/// use ::safer_ffi::prelude::*;
+///
+/// #[derive_ReprC]
+/// #[repr(C)]
+/// pub
+/// struct PluginInfo {
+///     version: u32,
+///     max_channels: u8,
+/// }
+///
+/// #[ffi_export]
+/// /// Looked up by the host.
+/// pub
+/// static PLUGIN_INFO: PluginInfo = PluginInfo {
+///     version: 1,
+///     max_channels: 2,
+/// };
+/// ```
+///
+///   - which generates:
+///
+///     ```C
+///     typedef struct {
+///         uint32_t version;
+///         uint8_t max_channels;
+///     } PluginInfo_t;
+///
+///     /** \brief
+///      *  Looked up by the host.
+///      */
+///     extern PluginInfo_t const PLUGIN_INFO;
+///     ```
+///
+/// Global version or configuration strings can be exported as
+/// `char_p::Ref<'static>`s:
+///
+/// ```rust
+/// use ::safer_ffi::prelude::*;
+///
+/// #[ffi_export]
+/// pub
+/// static MYLIB_VERSION: char_p::Ref<'static> = c!("1.2.3");
+/// ```
+///
+///   - which generates:
+///
+///     ```C
+///     extern char const * const MYLIB_VERSION;
+///     ```
+///
+/// # `const`s
+///
+/// `#[ffi_export]` can also be applied to a `const` whose value can be
+/// written as a C literal (see `::safer_ffi::headers::CLiteral`): integers,
+/// floats, and strings (`&str` or `char_p::Ref<'static>`), which gets
+/// `#define`d in the headers, so that both sides share a single definition.
+/// Nothing is exported from the library.
+///
+/// ```rust
+/// use ::safer_ffi::prelude::*;
+///
+/// #[ffi_export]
+/// /// The maximum number of channels.
+/// pub
+/// const MAX_CHANNELS: u8 = 8;
+///
+/// #[ffi_export]
+/// pub
+/// const VERSION: &str = "1.0.0";
+/// ```
+///
+///   - which generates:
+///
+///     ```C
+///     /** \brief
+///      *  The maximum number of channels.
+///      */
+///     #define MAX_CHANNELS 8u
+///
+///     #define VERSION "1.0.0"
+///     ```
+///
+/// # Returning references
+///
+/// An exported function can return a reference, typically borrowed from one
+/// of its parameters (lifetime elision applying as usual), which is then
+/// declared as a pointer with the matching const-ness:
+///
+///   - `&T` and `Option<&T>` become a `T const *`;
+///
+///   - `&mut T` and `Option<&mut T>` become a `T *`;
+///
+///   - `&[T]`, `&mut [T]` and `&str` (which are not [`ReprC`], being fat
+///     pointers) are returned as their `#[repr(C)]` counterparts:
+///     `c_slice::Ref<'_, T>` (`slice_ref_T_t`), `c_slice::Mut<'_, T>`
+///     (`slice_mut_T_t`) and `str::Ref<'_>` (`slice_ref_uint8_t`),
+///     respectively.
+///
+/// The `Option`-less ones are never `NULL`.
+///
+/// ```rust
+/// use ::safer_ffi::prelude::*;
+///
+/// #[ffi_export]
+/// /// Returns the largest element of `ints`, if any.
+/// fn max (ints: c_slice::Ref<'_, i32>) -> Option<&i32>
+/// {
+///     ints.as_slice().iter().max()
+/// }
+///
+/// #[ffi_export]
+/// /// Returns all the elements of `ints` but the first one.
+/// fn tail (ints: c_slice::Ref<'_, i32>) -> &[i32]
+/// {
+///     ints.as_slice().get(1 ..).unwrap_or(&[])
+/// }
+/// ```
+///
+///   - which generates:
+///
+///     ```C
+///     int32_t const * max (
+///         slice_ref_int32_t ints);
+///
+///     slice_ref_int32_t tail (
+///         slice_ref_int32_t ints);
+///     ```
+///
+/// The C caller has to uphold the aliasing rules of the Rust references for
+/// as long as it uses the returned pointer (or slice):
+///
+///   - it points into the memory borrowed by the function, and is thus only
+///     valid for as long as that memory is: _e.g._, the above `tail` result
+///     dangles once the array backing `ints` is freed;
+///
+///   - the memory behind a `T const *` must not be mutated while the pointer
+///     is in use (a shared borrow);
+///
+///   - a `T *` (an exclusive borrow) must be the only way the memory it
+///     points to is accessed while it is in use, including through the
+///     parameter it has been borrowed from.
+///
+/// # C examples
+///
+/// Within the doc comment of an exported item, the ```` ```c ```` fenced
+/// code blocks become Doxygen `\code ... \endcode` blocks in the headers,
+/// so that the C usage examples can be written next to the Rust source:
+///
+/// ```rust
+/// use ::safer_ffi::prelude::*;
+///
+/// #[ffi_export]
+/// /// Returns the sum of `x` and `y`.
+/// ///
+/// /// ```c
+/// /// int32_t three = add(1, 2);
+/// /// ```
+/// fn add (x: i32, y: i32) -> i32
+/// {
+///     x.wrapping_add(y)
+/// }
+/// ```
+///
+///   - which generates:
+///
+///     ```C
+///     /** \brief
+///      *  Returns the sum of `x` and `y`.
+///      *
+///      *  \code{.c}
+///      *  int32_t three = add(1, 2);
+///      *  \endcode
+///      */
+///     int32_t add (
+///         int32_t x,
+///         int32_t y);
+///     ```
+///
+/// # `#[deprecated]`
+///
+/// The `#[deprecated]` attribute of an exported function or `static` (as
+/// well as the one of a `#[derive_ReprC]` type) is also propagated to its
+/// declaration in the headers, through the `SAFER_FFI_DEPRECATED(note)`
+/// macro, so that the C callers get a warning as well (with GCC, Clang and
+/// MSVC; it expands to nothing otherwise):
+///
+/// ```rust
+/// use ::safer_ffi::prelude::*;
+///
+/// #[ffi_export]
+/// #[deprecated(note = "use `add` instead")]
+/// fn add_i32 (x: i32, y: i32) -> i32
+/// {
+///     x.wrapping_add(y)
+/// }
+/// ```
+///
+///   - which generates:
+///
+///     ```C
+///     SAFER_FFI_DEPRECATED("use `add` instead") int32_t add_i32 (
+///         int32_t x,
+///         int32_t y);
+///     ```
+///
+/// # `#[cfg]`
+///
+/// The `#[cfg]` attributes of the exported item also apply to its
+/// registration for the headers generation, so that the headers generated
+/// with a given set of features only contain the items exported with it
+/// (see `::safer_ffi::headers::registered_exports()` to list them).
+///
+/// # Parameters
+///
+///   - `#[ffi_export(progress)]`: appends a trailing
+///     `progress: ::safer_ffi::progress::ProgressCallback` parameter to the
+///     function, for it to report its progress with (and to be cancelled).
+///     The convention is also documented on the generated C declaration.
+///
+///     ```rust
+///     use ::safer_ffi::prelude::*;
+///
+///     #[ffi_export(progress)]
+///     /// Counts up to `n`.
+///     fn count_up_to (n: u32) -> u32
+///     {
+///         for i in 0 .. n {
+///             let percent = (100 * u64::from(i) / u64::from(n)) as u8;
+///             if progress.report(percent, c!("counting")).not() {
+///                 return i;
+///             }
+///         }
+///         n
+///     }
+///     ```
+///
+///   - `#[ffi_export(timeout_arg)]`: appends a trailing `timeout_ms: u32`
+///     parameter to the function, and gives its body the corresponding
+///     `deadline` (`0` meaning no timeout), a
+///     [`Deadline`](/safer_ffi/timeout/struct.Deadline.html) to poll: once it
+///     has [`expired()`](/safer_ffi/timeout/struct.Deadline.html#method.expired),
+///     the body is expected to return early, signaling the timeout (_e.g._,
+///     with [`TimedOut::timed_out()`](/safer_ffi/timeout/trait.TimedOut.html),
+///     such as `TimeoutStatus::TimedOut`, or `None`). The cancellation is
+///     thus cooperative: the body runs on the caller's thread, and may borrow
+///     from the parameters.
+///
+///     ```rust
+///     use ::safer_ffi::{prelude::*, timeout::TimeoutStatus};
+///
+///     #[ffi_export(timeout_arg)]
+///     fn wait_for_ms (ms: u64) -> TimeoutStatus
+///     {
+///         let start = ::std::time::Instant::now();
+///         while start.elapsed().as_millis() < ms.into() {
+///             if deadline.expired() {
+///                 return TimeoutStatus::TimedOut;
+///             }
+///             ::std::thread::yield_now();
+///         }
+///         TimeoutStatus::Ok
+///     }
+///     ```
+///
+///   - `#[ffi_export(debug_only)]`: only compiles (and exports) the function
+///     with `debug_assertions` (or, with `debug_only = "<feature>"`, with the
+///     given feature of the crate), for test hooks which are not meant to
+///     ship in release builds. Its declaration is nevertheless always
+///     emitted in the headers, within an `#ifndef NDEBUG` block:
+///
+///     ```rust
+///     use ::safer_ffi::prelude::*;
+///
+///     #[ffi_export(debug_only)]
+///     /// Resets the internal caches.
+///     fn test_reset_caches ()
+///     {}
+///     ```
+///
+///     ```C
+///     #ifndef NDEBUG
+///     /** \brief
+///      *  Resets the internal caches.
+///      */
+///     void test_reset_caches (void);
+///     #endif /* NDEBUG */
+///     ```
+///
+///   - `#[ffi_export(transparent_wrapper)]`: for functions whose parameter and
+///     return types are their own C layout (such as integers, floats, raw
+///     pointers, or `#[repr(C)]` structs thereof), guarantees that the
+///     exported wrapper does not convert (nor check) the values, _i.e._, that
+///     it adds no copies nor branches, by failing to compile otherwise. The
+///     wrapper is also marked `#[inline]`, so that it can be inlined within
+///     LTO-ed `staticlib` consumers (an explicit `#[inline(...)]` attribute
+///     on the function is forwarded to the wrapper as well).
+///
+///     ```rust
+///     use ::safer_ffi::prelude::*;
+///
+///     #[ffi_export(transparent_wrapper)]
+///     fn mix (x: u32, y: u32) -> u32
+///     {
+///         x.rotate_left(5) ^ y
+///     }
+///     ```
+///
+///   - `#[ffi_export(used)]` (on functions and `static`s): marks the export
+///     as `#[used]`, so that it is kept when linking the crate as a
+///     `staticlib`, even with `--gc-sections` or (cross-language) LTO,
+///     where nothing on the C side references it yet (_e.g._, for symbols
+///     only looked up at runtime). The headers generation
+///     [`Report`](/safer_ffi/headers/struct.Report.html) then lists these
+///     symbols, and its `.linker_flags()` yields the matching `-Wl,-u,<symbol>`
+///     flags, for the C build to keep them as well.
+///
+///     ```rust
+///     use ::safer_ffi::prelude::*;
+///
+///     #[ffi_export(used)]
+///     /// Looked up with `dlsym()`.
+///     fn plugin_entry_point () -> i32
+///     {
+///         42
+///     }
+///     ```
+///
+///   - `#[ffi_export(system_abi)]`: also exports an `extern "system"` variant
+///     of the function (_i.e._, `__stdcall` on 32-bit Windows, and the C
+///     calling convention everywhere else), for consumers (such as COM-style
+///     clients) expecting that calling convention. It is exported under the
+///     name of the function suffixed with `_system` (or, with
+///     `system_abi = "<suffix>"`, with the given suffix), and declared right
+///     after the C one in the headers:
+///
+///     ```rust
+///     use ::safer_ffi::prelude::*;
+///
+///     #[ffi_export(system_abi = "_stdcall")]
+///     /// Returns the version of the library.
+///     fn lib_version () -> u32
+///     {
+///         42
+///     }
+///     ```
+///
+///     ```C
+///     /** \brief
+///      *  Returns the version of the library.
+///      */
+///     uint32_t lib_version (void);
+///
+///     /** \brief
+///      *  `lib_version`, with the `system` calling convention.
+///      */
+///     uint32_t SAFER_FFI_SYSTEM_ABI lib_version_stdcall (void);
+///     ```
+///
+///   - `#[ffi_export(group = "<name>")]` (on functions, `static`s and
+///     `const`s): puts the export in the given group, for
+///     [`.generate_split()`](/safer_ffi/headers/struct.Builder.html#method.generate_split)
+///     to emit one header per group (with
+///     [`Grouping::ByAttribute`](/safer_ffi/headers/enum.Grouping.html)).
+///
+///     ```rust
+///     use ::safer_ffi::prelude::*;
+///
+///     #[ffi_export(group = "audio")]
+///     /// Sets the volume, in percents.
+///     fn set_volume (percent: u8)
+///     {
+///         drop(percent);
+///     }
+///     ```
+///
+///   - `#[ffi_export(doc_group = "<name>")]` (on functions, `static`s and `const`s):
+///     adds the export to the given Doxygen group, defined (with
+///     `\defgroup`) in the headers before its first item, so that the
+///     generated documentation is organized in sections (_modules_), rather
+///     than one flat list:
+///
+///     ```rust
+///     use ::safer_ffi::prelude::*;
+///
+///     #[ffi_export(doc_group = "strings")]
+///     /// Returns the length of the string.
+///     fn string_len (s: char_p::Ref<'_>) -> usize
+///     {
+///         s.to_bytes().len()
+///     }
+///     ```
+///
+///     ```C
+///     /** \defgroup strings strings */
+///
+///     /** \brief
+///      *  Returns the length of the string.
+///      *
+///      *  \ingroup strings
+///      */
+///     size_t string_len (
+///         char const * s);
+///     ```
+///
+///   - `#[ffi_export(abi = "C-unwind")]` (Rust 1.71 or later): exports the
+///     function as `extern "C-unwind"` rather than `extern "C"`, for
+///     codebases which deliberately propagate unwinding across the FFI
+///     boundary: a panic then unwinds into the (C++) caller, rather than
+///     aborting the process, and an exception thrown by a C++ callback may
+///     unwind through the function. The C declaration in the headers is
+///     unchanged, the calling convention being the same, but the caller has
+///     to be compiled with unwinding support (_e.g._, `-fexceptions`).
+///
+///     With the `c-unwind` feature of `::safer_ffi`, the
+///     `extern "C-unwind" fn` pointer types (and their `Option`s) are
+///     [`ReprC`](/safer_ffi/layout/trait.ReprC.html) too, for such callbacks.
+///
+///     ```rust,ignore
+///     use ::safer_ffi::prelude::*;
+///
+///     #[ffi_export(abi = "C-unwind")]
+///     fn for_each_item (cb: extern "C-unwind" fn(i32))
+///     {
+///         (0 .. 3).for_each(|i| cb(i));
+///     }
+///     ```
+///
+///   - `#[ffi_export(abi = "system")]`: exports the function as
+///     `extern "system"` (only), _i.e._, with the `__stdcall` calling
+///     convention on 32-bit Windows (that of `WINAPI` functions), and the C
+///     one everywhere else. Its C declaration is annotated with the
+///     `SAFER_FFI_SYSTEM_ABI` macro, defined accordingly, so that callers
+///     compiled against the headers use the matching calling convention.
+///
+///     The `extern "system" fn` pointer types (and their `Option`s) are
+///     [`ReprC`](/safer_ffi/layout/trait.ReprC.html) too, and their C type
+///     is annotated likewise, for callbacks with that calling convention.
+///
+///     ```rust
+///     use ::safer_ffi::prelude::*;
+///
+///     #[ffi_export(abi = "system")]
+///     fn register_window_proc (
+///         window_proc: extern "system" fn(u32, usize) -> isize,
+///     ) -> isize
+///     {
+///         window_proc(0, 0)
+///     }
+///     ```
+///
+///     ```C
+///     ssize_t SAFER_FFI_SYSTEM_ABI register_window_proc (
+///         ssize_t (SAFER_FFI_SYSTEM_ABI *window_proc)(uint32_t, size_t));
+///     ```
+///
+///   - `#[ffi_export(batched)]`: also exports a `_batch` variant of the
+///     function, taking a slice per parameter (and an `outputs` slice for the
+///     results), which calls the function on each element, so that hot
+///     functions only cross the FFI boundary once per batch. It returns the
+///     number of calls, _i.e._, the length of the shortest slice. The
+///     function needs at least one parameter, and the types of the parameters
+///     and of the result have to be `Copy`.
+///
+///     ```rust
+///     use ::safer_ffi::prelude::*;
+///
+///     #[ffi_export(batched)]
+///     /// Applies the gain to the sample.
+///     fn apply_gain (sample: f32, gain: f32) -> f32
+///     {
+///         sample * gain
+///     }
+///     ```
+///
+///     ```C
+///     float apply_gain (
+///         float sample,
+///         float gain);
+///
+///     /** \brief
+///      *  `apply_gain`, batched: calls it with the elements of the given slices
+///      *  (writing the results to `outputs`), and returns the number of calls,
+///      *  _i.e._, the length of the shortest slice.
+///      */
+///     size_t apply_gain_batch (
+///         slice_ref_float_t sample,
+///         slice_ref_float_t gain,
+///         slice_mut_float_t outputs);
+///     ```
+///
+///   - `#[ffi_export(realtime_safe)]`: fails to compile unless all the
+///     parameter and return types are
+///     [`RealtimeSafe`](/safer_ffi/realtime/trait.RealtimeSafe.html), _i.e._,
+///     can be passed around without allocating, freeing, or locking anything
+///     (such as integers, pointers, or borrowed slices, but not a
+///     `repr_c::Vec`), and mentions it in the documentation of the function.
+///
+///     ```rust
+///     use ::safer_ffi::prelude::*;
+///
+///     #[ffi_export(realtime_safe)]
+///     /// Fills the buffer with silence.
+///     fn audio_callback (buffer: c_slice::Mut<'_, f32>)
+///     {
+///         buffer.as_slice().iter_mut().for_each(|it| *it = 0.0);
+///     }
+///     ```
+///
+///     ```C
+///     /** \brief
+///      *  Fills the buffer with silence.
+///      *
+///      *  Real-time safe: passing its parameters and its return value neither
+///      *  allocates, frees, nor locks anything.
+///      */
+///     void audio_callback (
+///         slice_mut_float_t buffer);
+///     ```
 #[proc_macro_attribute] pub
 fn ffi_export (attrs: TokenStream, input: TokenStream)
   -> TokenStream
 {
     use ::proc_macro::{*, TokenTree as TT};
-    if let Some(unexpected_tt) = attrs.into_iter().next() {
-        return compile_error("Unexpected parameter", unexpected_tt.span());
+    let options = match Options::parse(attrs) {
+        | Ok(it) => it,
+        | Err(err) => return err,
+    };
+    // The `#[cfg]`s apply to all the generated items, including the
+    // registration for the headers generation.
+    let (mut cfgs, input) = split_attrs(input, "cfg");
+    let (deprecated, input) = split_attrs(input, "deprecated");
+    let deprecated = match deprecated_prefix(deprecated) {
+        | Ok(it) => it,
+        | Err(err) => return err,
+    };
+    if is_const(&input) {
+        if options.progress
+        || options.timeout_arg
+        || options.debug_only.is_some()
+        || options.transparent_wrapper
+        || options.used
+        || options.system_abi.is_some()
+        || options.abi.is_some()
+        || options.batched
+        || options.realtime_safe
+        {
+            return compile_error(
+                "only `group` and `doc_group` apply to `const`s",
+                Span::call_site(),
+            );
+        }
+        #[cfg(feature = "proc_macros")] {
+            let input = input.clone();
+            let _: ItemConst = parse_macro_input!(input);
+        }
+        let input = with_prefixes(input, vec![
+            ("group", options.group),
+            ("doc_group", options.doc_group),
+            ("deprecated", deprecated),
+        ]);
+        cfgs.extend(forward_to("__ffi_export_const__", input));
+        return cfgs;
+    }
+    if is_static(&input) {
+        if options.progress
+        || options.timeout_arg
+        || options.debug_only.is_some()
+        || options.transparent_wrapper
+        || options.system_abi.is_some()
+        || options.abi.is_some()
+        || options.batched
+        || options.realtime_safe
+        {
+            return compile_error(
+                "`progress`, `timeout_arg`, `debug_only`, `transparent_wrapper`, \
+                `system_abi`, `abi`, `batched` and `realtime_safe` only apply to \
+                functions",
+                Span::call_site(),
+            );
+        }
+        #[cfg(feature = "proc_macros")] {
+            let input = input.clone();
+            let _: ItemStatic = parse_macro_input!(input);
+        }
+        let input = with_prefixes(input, vec![
+            ("used", used(&options)),
+            ("group", options.group),
+            ("doc_group", options.doc_group),
+            ("deprecated", deprecated),
+        ]);
+        cfgs.extend(forward_to("__ffi_export_static__", input));
+        return cfgs;
     }
     #[cfg(feature = "proc_macros")] {
         let input = input.clone();
         let _: ItemFn = parse_macro_input!(input);
     }
+    if options.transparent_wrapper && options.timeout_arg {
+        return compile_error(
+            "`transparent_wrapper` is incompatible with `timeout_arg`",
+            Span::call_site(),
+        );
+    }
+    if options.abi.is_some() && options.system_abi.is_some() {
+        return compile_error(
+            "`abi` is incompatible with `system_abi`",
+            Span::call_site(),
+        );
+    }
+    // The number of calls is that of the elements of the (shortest)
+    // parameter slice: without parameters, it would be unbounded.
+    if options.batched && !has_params(&input) {
+        return compile_error(
+            "`batched` requires the function to have parameters",
+            Span::call_site(),
+        );
+    }
+    // The injected bindings are (for lack of `Span::mixed_site()`) visible
+    // to the body, and must thus not shadow parameters.
+    let injected: &[(bool, &str, &str)] = &[
+        (options.progress, "progress", "progress"),
+        (options.timeout_arg, "timeout_arg", "timeout_ms"),
+        (options.timeout_arg, "timeout_arg", "deadline"),
+    ];
+    for param in param_names(&input) {
+        let param_name = param.to_string();
+        let collision = injected.iter().find(|&&(enabled, _, name)| {
+            enabled && param_name == name
+        });
+        if let Some(&(_, option, name)) = collision {
+            return compile_error(
+                &format!("`{}` already injects a `{}` binding", option, name),
+                param.span(),
+            );
+        }
+    }
+    // `#[inline]` is forwarded to the exported wrapper.
+    let (inline, mut input) = split_attrs(input, "inline");
+    let inline = match inline.into_iter().last() {
+        | Some(TT::Group(attr)) => Some(attr.stream()),
+        | _ if options.transparent_wrapper => Some("inline".parse().unwrap()),
+        | _ => None,
+    };
+    if options.progress {
+        input = with_extra_docs(input, &[
+            "",
+            "`progress` follows the progress-reporting convention: it is called",
+            "with the completion percentage (`0` to `100`) and a short description",
+            "of the current stage, and returning `false` requests a cancellation",
+            "of the operation.",
+        ]);
+        input = with_extra_param(input, "progress", &[
+            "safer_ffi", "progress", "ProgressCallback",
+        ]);
+    }
+    if options.timeout_arg {
+        input = with_extra_docs(input, &[
+            "",
+            "`timeout_ms` bounds the duration of the call (`0` meaning no timeout):",
+            "once it elapses, the function returns early, signaling the timeout.",
+        ]);
+        input = with_extra_param(input, "timeout_ms", &["safer_ffi", "u32"]);
+        input = with_wrapped_body(input, |body| {
+            let mut ret: TokenStream =
+                "#[allow(unused_variables)] \
+                let deadline = ::safer_ffi::timeout::Deadline::after_ms(timeout_ms);"
+                    .parse()
+                    .unwrap()
+            ;
+            ret.extend(body);
+            ret
+        });
+    }
+    if options.realtime_safe {
+        input = with_extra_docs(input, &[
+            "",
+            "Real-time safe: passing its parameters and its return value neither",
+            "allocates, frees, nor locks anything.",
+        ]);
+    }
+    input = with_slice_return(input);
+    let used = used(&options);
+    let system_abi = options.system_abi.as_ref().and_then(|suffix| {
+        let fname = fn_name(&input)?;
+        Some(TT::Ident(Ident::new(
+            &format!("{}{}", fname, suffix),
+            fname.span(),
+        )).into())
+    });
+    let batched = if options.batched {
+        fn_name(&input).map(|fname| TT::Ident(Ident::new(
+            &format!("{}_batch", fname),
+            fname.span(),
+        )).into())
+    } else {
+        None
+    };
+    let abi = options.abi.unwrap_or("\"C\"");
+    input = with_prefixes(input, vec![
+        ("abi", Some(abi.parse().unwrap())),
+        ("debug_only", options.debug_only),
+        ("inline", inline),
+        ("used", used),
+        ("system_abi", system_abi),
+        ("group", options.group),
+        ("doc_group", options.doc_group),
+        ("batched", batched),
+        ("deprecated", deprecated),
+    ]);
+    let export_macro =
+        if options.transparent_wrapper {
+            "__ffi_export_transparent__"
+        } else {
+            "__ffi_export__"
+        }
+    ;
+    if options.realtime_safe {
+        let next: TokenStream = TT::Ident(Ident::new(export_macro, Span::call_site())).into();
+        let mut forwarded: TokenStream =
+            TT::Group(Group::new(Delimiter::Bracket, next)).into()
+        ;
+        forwarded.extend(input);
+        cfgs.extend(forward_to("__ffi_export_realtime_safe__", forwarded));
+    } else {
+        cfgs.extend(forward_to(export_macro, input));
+    }
+    cfgs
+}
+
+/// `@name[<arg>] ... <input>`, for each given (`Some`) prefix.
+fn with_prefixes (
+    input: TokenStream,
+    prefixes: Vec<(&'_ str, Option<TokenStream>)>,
+) -> TokenStream
+{
+    use ::proc_macro::{*, TokenTree as TT};
+    let span = Span::call_site();
+    prefixes
+        .into_iter()
+        .filter_map(|(name, arg)| Some(vec![
+            TT::Punct(Punct::new('@', Spacing::Alone)),
+            TT::Ident(Ident::new(name, span)),
+            TT::Group(Group::new(Delimiter::Bracket, arg?)),
+        ]))
+        .flatten()
+        .chain(input)
+        .collect()
+}
+
+/// The argument of the `@used[...]` prefix, if any.
+fn used (options: &'_ Options)
+  -> Option<TokenStream>
+{
+    if options.used {
+        Some("used".parse().unwrap())
+    } else {
+        None
+    }
+}
+
+/// Whether `lit` is a string literal usable as a Doxygen group name.
+fn is_doc_group_name (lit: &'_ str)
+  -> bool
+{
+    let name = lit.trim_matches('"');
+    lit.starts_with('"')
+    && !name.is_empty()
+    && name.chars().all(|c| c == '_' || c.is_ascii_alphanumeric())
+}
+
+/// `::safer_ffi::<macro_name>! { <input> }`
+fn forward_to (macro_name: &'_ str, input: TokenStream)
+  -> TokenStream
+{
+    use ::proc_macro::{*, TokenTree as TT};
     let span = Span::call_site();
     <TokenStream as ::std::iter::FromIterator<_>>::from_iter(vec![
         TT::Punct(Punct::new(':', Spacing::Joint)),
@@ -70,7 +843,7 @@ fn ffi_export (attrs: TokenStream, input: TokenStream)
         TT::Punct(Punct::new(':', Spacing::Joint)),
         TT::Punct(Punct::new(':', Spacing::Alone)),
 
-        TT::Ident(Ident::new("__ffi_export__", span)),
+        TT::Ident(Ident::new(macro_name, span)),
 
         TT::Punct(Punct::new('!', Spacing::Alone)),
 
@@ -80,3 +853,649 @@ fn ffi_export (attrs: TokenStream, input: TokenStream)
         )),
     ])
 }
+
+/// The (comma-separated) parameters of `#[ffi_export(...)]`.
+#[derive(Default)]
+struct Options {
+    /// `progress`: append a `progress: ProgressCallback` parameter.
+    progress: bool,
+
+    /// `timeout_arg`: append a `timeout_ms: u32` parameter, and give the body
+    /// the corresponding `deadline`.
+    timeout_arg: bool,
+
+    /// `debug_only [= "<feature>"]`: the `cfg` predicate under which the
+    /// function is compiled (`debug_assertions`, or `feature = "<feature>"`).
+    debug_only: Option<TokenStream>,
+
+    /// `transparent_wrapper`: check that the exported wrapper is a no-op,
+    /// and make it `#[inline]`.
+    transparent_wrapper: bool,
+
+    /// `used`: mark the export as `#[used]`, and list it in the `Report`.
+    used: bool,
+
+    /// `system_abi [= "<suffix>"]`: the suffix of the name of the
+    /// `extern "system"` variant of the function (`_system` by default).
+    system_abi: Option<String>,
+
+    /// `group = "<name>"`: the group of the export, when splitting the
+    /// headers.
+    group: Option<TokenStream>,
+
+    /// `doc_group = "<name>"`: the Doxygen group of the export.
+    doc_group: Option<TokenStream>,
+
+    /// `abi = "C-unwind"`: let panics (and foreign exceptions) unwind across
+    /// the function, rather than aborting; `abi = "system"`: export it with
+    /// the `system` calling convention.
+    abi: Option<&'static str>,
+
+    /// `batched`: also export the `_batch` variant of the function.
+    batched: bool,
+
+    /// `realtime_safe`: check that the types of the signature are
+    /// `RealtimeSafe`.
+    realtime_safe: bool,
+}
+
+impl Options {
+    fn parse (attrs: TokenStream)
+      -> ::core::result::Result<Self, TokenStream>
+    {
+        use ::proc_macro::{*, TokenTree as TT};
+        let mut ret = Self::default();
+        let mut attrs = attrs.into_iter();
+        while let Some(tt) = attrs.next() {
+            match tt {
+                | TT::Ident(ref ident) if ident.to_string() == "progress" => {
+                    ret.progress = true;
+                },
+                | TT::Ident(ref ident) if ident.to_string() == "timeout_arg" => {
+                    ret.timeout_arg = true;
+                },
+                | TT::Ident(ref ident) if ident.to_string() == "transparent_wrapper" => {
+                    ret.transparent_wrapper = true;
+                },
+                | TT::Ident(ref ident) if ident.to_string() == "used" => {
+                    ret.used = true;
+                },
+                | TT::Ident(ref ident) if ident.to_string() == "batched" => {
+                    ret.batched = true;
+                },
+                | TT::Ident(ref ident) if ident.to_string() == "realtime_safe" => {
+                    ret.realtime_safe = true;
+                },
+                | TT::Ident(ref ident) if ident.to_string() == "group" => {
+                    ret.group = Some(match (attrs.next(), attrs.next()) {
+                        | (Some(TT::Punct(ref p)), Some(TT::Literal(lit)))
+                            if p.as_char() == '='
+                            && lit.to_string().starts_with('"')
+                        => TT::Literal(lit).into(),
+                        | _ => return Err(compile_error(
+                            "Expected `group = \"<name>\"`",
+                            ident.span(),
+                        )),
+                    });
+                },
+                | TT::Ident(ref ident) if ident.to_string() == "doc_group" => {
+                    ret.doc_group = Some(match (attrs.next(), attrs.next()) {
+                        | (Some(TT::Punct(ref p)), Some(TT::Literal(lit)))
+                            if p.as_char() == '='
+                            && is_doc_group_name(&lit.to_string())
+                        => TT::Literal(lit).into(),
+                        | _ => return Err(compile_error(
+                            "Expected `doc_group = \"<name>\"`, \
+                            with a name made of alphanumerics and `_`s",
+                            ident.span(),
+                        )),
+                    });
+                },
+                | TT::Ident(ref ident) if ident.to_string() == "abi" => {
+                    ret.abi = match (attrs.next(), attrs.next()) {
+                        | (Some(TT::Punct(ref p)), Some(TT::Literal(ref lit)))
+                            if p.as_char() == '='
+                        => match &*lit.to_string() {
+                            | "\"C\"" => None,
+                            | "\"C-unwind\"" => Some("\"C-unwind\""),
+                            | "\"system\"" => Some("\"system\""),
+                            | _ => return Err(compile_error(
+                                "Only the `\"C\"`, `\"C-unwind\"` and `\"system\"` \
+                                ABIs are supported",
+                                lit.span(),
+                            )),
+                        },
+                        | _ => return Err(compile_error(
+                            "Expected `abi = \"<ABI>\"`",
+                            ident.span(),
+                        )),
+                    };
+                },
+                | TT::Ident(ref ident) if ident.to_string() == "system_abi" => {
+                    let mut lookahead = attrs.clone();
+                    ret.system_abi = Some(match lookahead.next() {
+                        | Some(TT::Punct(ref p)) if p.as_char() == '=' => {
+                            let suffix = match lookahead.next() {
+                                | Some(TT::Literal(lit)) => lit.to_string(),
+                                | _ => String::new(),
+                            };
+                            let suffix = suffix.trim_matches('"');
+                            if suffix.is_empty()
+                            || suffix.chars().any(|c| {
+                                c != '_' && !c.is_ascii_alphanumeric()
+                            })
+                            {
+                                return Err(compile_error(
+                                    "Expected `system_abi = \"<suffix>\"`",
+                                    ident.span(),
+                                ));
+                            }
+                            attrs = lookahead;
+                            suffix.to_owned()
+                        },
+                        | _ => "_system".to_owned(),
+                    });
+                },
+                | TT::Ident(ref ident) if ident.to_string() == "debug_only" => {
+                    let mut lookahead = attrs.clone();
+                    ret.debug_only = Some(match lookahead.next() {
+                        | Some(TT::Punct(ref p)) if p.as_char() == '=' => {
+                            let feature = match lookahead.next() {
+                                | Some(TT::Literal(lit)) => lit,
+                                | _ => return Err(compile_error(
+                                    "Expected `debug_only = \"<feature>\"`",
+                                    ident.span(),
+                                )),
+                            };
+                            attrs = lookahead;
+                            vec![
+                                TT::Ident(Ident::new("feature", ident.span())),
+                                TT::Punct(Punct::new('=', Spacing::Alone)),
+                                TT::Literal(feature),
+                            ].into_iter().collect()
+                        },
+                        | _ => TT::Ident(Ident::new(
+                            "debug_assertions", ident.span(),
+                        )).into(),
+                    });
+                },
+                | _ => return Err(compile_error(
+                    "Unexpected parameter",
+                    tt.span(),
+                )),
+            }
+            match attrs.next() {
+                | None => break,
+                | Some(TT::Punct(ref p)) if p.as_char() == ',' => {},
+                | Some(unexpected_tt) => return Err(compile_error(
+                    "Expected `,`",
+                    unexpected_tt.span(),
+                )),
+            }
+        }
+        Ok(ret)
+    }
+}
+
+/// Splits the leading `#[<name>...]` attributes off the item.
+fn split_attrs (input: TokenStream, name: &'_ str)
+  -> (TokenStream, TokenStream)
+{
+    use ::proc_macro::TokenTree as TT;
+    let mut input = input.into_iter().peekable();
+    let mut matches = vec![];
+    let mut rest = vec![];
+    loop {
+        match input.peek() {
+            | Some(TT::Punct(p)) if p.as_char() == '#' => {},
+            | _ => break,
+        }
+        let pound = input.next();
+        let attr = input.next();
+        let is_match = match attr {
+            | Some(TT::Group(ref group)) => match group.stream().into_iter().next() {
+                | Some(TT::Ident(ref ident)) => ident.to_string() == name,
+                | _ => false,
+            },
+            | _ => false,
+        };
+        let target = if is_match { &mut matches } else { &mut rest };
+        target.extend(pound.into_iter().chain(attr));
+    }
+    rest.extend(input);
+    (matches.into_iter().collect(), rest.into_iter().collect())
+}
+
+/// The argument of the `@deprecated[...]` prefix, if the item is
+/// `#[deprecated]`: the note (`""` if none), and the attribute itself.
+fn deprecated_prefix (attrs: TokenStream)
+  -> ::core::result::Result<Option<TokenStream>, TokenStream>
+{
+    use ::proc_macro::{*, TokenTree as TT};
+    let attr = match attrs.into_iter().last() {
+        | Some(TT::Group(attr)) => attr,
+        | _ => return Ok(None),
+    };
+    let mut tts = attr.stream().into_iter().skip(1);
+    let note = match (tts.next(), tts.next()) {
+        | (None, _) => None,
+        | (Some(TT::Punct(ref p)), Some(TT::Literal(note))) if p.as_char() == '=' => {
+            Some(note)
+        },
+        | (Some(TT::Group(ref args)), None) => {
+            let args: Vec<_> = args.stream().into_iter().collect();
+            args.windows(3).find_map(|window| match window {
+                | &[TT::Ident(ref name), TT::Punct(ref p), TT::Literal(ref note)]
+                    if name.to_string() == "note" && p.as_char() == '='
+                => {
+                    Some(note.clone())
+                },
+                | _ => None,
+            })
+        },
+        | _ => return Err(compile_error(
+            "expected `#[deprecated]`, `#[deprecated = \"note\"]` \
+            or `#[deprecated(note = \"note\")]`",
+            attr.span(),
+        )),
+    };
+    let mut ret: TokenStream =
+        TT::Literal(note.unwrap_or_else(|| Literal::string(""))).into()
+    ;
+    ret.extend(Some(TT::Punct(Punct::new(',', Spacing::Alone))));
+    ret.extend(attr.stream());
+    Ok(Some(ret))
+}
+
+/// Whether the annotated item is a `static` (rather than a function).
+fn is_static (input: &'_ TokenStream)
+  -> bool
+{
+    use ::proc_macro::TokenTree as TT;
+    input.clone().into_iter().find_map(|tt| match tt {
+        | TT::Ident(ref ident) if ident.to_string() == "static" => Some(true),
+        | TT::Ident(ref ident) if ident.to_string() == "fn" => Some(false),
+        | _ => None,
+    }) == Some(true)
+}
+
+/// Whether the annotated item is a `const` (rather than a `const fn`).
+fn is_const (input: &'_ TokenStream)
+  -> bool
+{
+    use ::proc_macro::TokenTree as TT;
+    let mut input = input.clone().into_iter();
+    while let Some(tt) = input.next() {
+        match tt {
+            | TT::Ident(ref ident) if ident.to_string() == "const" => {
+                return match input.next() {
+                    | Some(TT::Ident(ref ident)) => match &ident.to_string()[..] {
+                        | "fn" | "unsafe" | "extern" | "async" => false,
+                        | _ => true,
+                    },
+                    | _ => false,
+                };
+            },
+            | TT::Ident(ref ident)
+                if ident.to_string() == "fn" || ident.to_string() == "static"
+            => {
+                return false;
+            },
+            | _ => {},
+        }
+    }
+    false
+}
+
+/// The name of the function.
+fn fn_name (input: &'_ TokenStream)
+  -> Option<::proc_macro::Ident>
+{
+    use ::proc_macro::TokenTree as TT;
+    let mut input = input.clone().into_iter();
+    input.find(|tt| match *tt {
+        | TT::Ident(ref ident) => ident.to_string() == "fn",
+        | _ => false,
+    })?;
+    match input.next() {
+        | Some(TT::Ident(ident)) => Some(ident),
+        | _ => None,
+    }
+}
+
+/// Whether the function has (at least one) parameter.
+fn has_params (input: &'_ TokenStream)
+  -> bool
+{
+    use ::proc_macro::{*, TokenTree as TT};
+    let mut input = input.clone().into_iter();
+    input.find(|tt| match *tt {
+        | TT::Ident(ref ident) => ident.to_string() == "fn",
+        | _ => false,
+    });
+    input
+        .find_map(|tt| match tt {
+            | TT::Group(group) if group.delimiter() == Delimiter::Parenthesis => {
+                Some(!group.stream().is_empty())
+            },
+            | _ => None,
+        })
+        .unwrap_or(false)
+}
+
+/// The names of the (`<name> : <ty>` or `mut <name> : <ty>`) parameters of
+/// the function.
+fn param_names (input: &'_ TokenStream)
+  -> Vec<::proc_macro::Ident>
+{
+    use ::proc_macro::{*, TokenTree as TT};
+    let mut input = input.clone().into_iter();
+    input.find(|tt| match *tt {
+        | TT::Ident(ref ident) => ident.to_string() == "fn",
+        | _ => false,
+    });
+    let params = input.find_map(|tt| match tt {
+        | TT::Group(group) if group.delimiter() == Delimiter::Parenthesis => {
+            Some(group.stream().into_iter().collect::<Vec<_>>())
+        },
+        | _ => None,
+    });
+    let params = params.unwrap_or_default();
+    params
+        .windows(2)
+        .filter_map(|window| match (&window[0], &window[1]) {
+            | (&TT::Ident(ref name), &TT::Punct(ref colon))
+                if colon.as_char() == ':' && colon.spacing() == Spacing::Alone
+            => Some(name.clone()),
+            | _ => None,
+        })
+        .collect()
+}
+
+/// Appends `#[doc = ...]` lines to the (leading) attributes of the function.
+fn with_extra_docs (input: TokenStream, lines: &'_ [&'_ str])
+  -> TokenStream
+{
+    use ::proc_macro::{*, TokenTree as TT};
+    let span = Span::call_site();
+    let mut input = input.into_iter().peekable();
+    let mut ret = vec![];
+    // Skip the leading attributes.
+    loop {
+        match input.peek() {
+            | Some(TT::Punct(p)) if p.as_char() == '#' => {},
+            | _ => break,
+        }
+        ret.extend(input.next());
+        ret.extend(input.next()); // [ ... ]
+    }
+    lines.iter().for_each(|&line| {
+        ret.push(TT::Punct(Punct::new('#', Spacing::Alone)));
+        ret.push(TT::Group(Group::new(
+            Delimiter::Bracket,
+            vec![
+                TT::Ident(Ident::new("doc", span)),
+                TT::Punct(Punct::new('=', Spacing::Alone)),
+                TT::Literal(Literal::string(&format!(" {}", line))),
+            ].into_iter().collect(),
+        )));
+    });
+    ret.extend(input);
+    ret.into_iter().collect()
+}
+
+/// Appends a `name: ::path::to::Type` parameter to the function.
+fn with_extra_param (
+    input: TokenStream,
+    name: &'_ str,
+    ty_path: &'_ [&'_ str],
+) -> TokenStream
+{
+    use ::proc_macro::{*, TokenTree as TT};
+    let span = Span::call_site();
+    // Only the parameters of the function itself (the first `fn` of the
+    // item), not those of the `fn` pointers of its signature.
+    let mut seen_fn = false;
+    let mut done = false;
+    input.into_iter().map(|tt| match tt {
+        | TT::Ident(ref ident) if !done && ident.to_string() == "fn" => {
+            seen_fn = true;
+            tt
+        },
+        | TT::Group(ref group)
+            if seen_fn && !done && group.delimiter() == Delimiter::Parenthesis
+        => {
+            done = true;
+            let mut params: Vec<TT> = group.stream().into_iter().collect();
+            match params.last() {
+                | None => {},
+                | Some(TT::Punct(p)) if p.as_char() == ',' => {},
+                | Some(_) => {
+                    params.push(TT::Punct(Punct::new(',', Spacing::Alone)));
+                },
+            }
+            params.push(TT::Ident(Ident::new(name, span)));
+            params.push(TT::Punct(Punct::new(':', Spacing::Alone)));
+            ty_path.iter().for_each(|&segment| {
+                params.push(TT::Punct(Punct::new(':', Spacing::Joint)));
+                params.push(TT::Punct(Punct::new(':', Spacing::Alone)));
+                params.push(TT::Ident(Ident::new(segment, span)));
+            });
+            let mut group = Group::new(
+                Delimiter::Parenthesis,
+                params.into_iter().collect(),
+            );
+            group.set_span(tt.span());
+            TT::Group(group)
+        },
+        | _ => tt,
+    }).collect()
+}
+
+/// Replaces the `{ <body> }` of the function with `{ <wrap({ <body> })> }`.
+fn with_wrapped_body (
+    input: TokenStream,
+    wrap: impl FnOnce(TokenStream) -> TokenStream,
+) -> TokenStream
+{
+    use ::proc_macro::{*, TokenTree as TT};
+    let mut input: Vec<TT> = input.into_iter().collect();
+    if let Some(TT::Group(body)) = input.pop() {
+        let span = body.span();
+        let mut wrapped = Group::new(
+            Delimiter::Brace,
+            wrap(TT::Group(body).into()),
+        );
+        wrapped.set_span(span);
+        input.push(TT::Group(wrapped));
+    }
+    input.into_iter().collect()
+}
+
+/// `-> &[T]`, `-> &mut [T]` and `-> &str`, which are (fat pointers, and thus)
+/// not `ReprC`: the function returns their `#[repr(C)]` counterpart (with
+/// the same lifetime) instead, converted from the value returned by the
+/// original function, which is kept as a nested function.
+fn with_slice_return (input: TokenStream)
+  -> TokenStream
+{
+    use ::proc_macro::{*, TokenTree as TT};
+    let span = Span::call_site();
+    let is_punct = |tt: Option<&TT>, c: char| match tt {
+        | Some(&TT::Punct(ref p)) => p.as_char() == c,
+        | _ => false,
+    };
+    let is_ident = |tt: Option<&TT>, s: &str| match tt {
+        | Some(&TT::Ident(ref ident)) => ident.to_string() == s,
+        | _ => false,
+    };
+    let tts: Vec<TT> = input.clone().into_iter().collect();
+    let fn_idx = match tts.iter().position(|tt| is_ident(Some(tt), "fn")) {
+        | Some(it) => it,
+        | None => return input,
+    };
+    let params_idx = match tts[fn_idx ..].iter().position(|tt| match *tt {
+        | TT::Group(ref group) => group.delimiter() == Delimiter::Parenthesis,
+        | _ => false,
+    })
+    {
+        | Some(it) => fn_idx + it,
+        | None => return input,
+    };
+    // `-> & ['lt] [mut] ([T] | str) { <body> }`
+    let ret_idx = params_idx + 3;
+    if !(
+        is_punct(tts.get(params_idx + 1), '-')
+        && is_punct(tts.get(params_idx + 2), '>')
+        && is_punct(tts.get(ret_idx), '&')
+    )
+    {
+        return input;
+    }
+    let mut i = ret_idx + 1;
+    let lifetime: TokenStream = if is_punct(tts.get(i), '\'') {
+        i += 2;
+        tts[i - 2 .. i].iter().cloned().collect()
+    } else {
+        "'_".parse().unwrap()
+    };
+    let mutable = is_ident(tts.get(i), "mut");
+    if mutable {
+        i += 1;
+    }
+    let mut repr_c_ty: TokenStream = match tts.get(i) {
+        | Some(&TT::Group(ref group))
+            if group.delimiter() == Delimiter::Bracket
+        => {
+            let mut ty: TokenStream = if mutable {
+                "::safer_ffi::slice::slice_mut<"
+            } else {
+                "::safer_ffi::slice::slice_ref<"
+            }.parse().unwrap();
+            ty.extend(lifetime);
+            ty.extend(Some(TT::Punct(Punct::new(',', Spacing::Alone))));
+            ty.extend(group.stream());
+            ty
+        },
+        | Some(&TT::Ident(ref ident))
+            if ident.to_string() == "str" && !mutable
+        => {
+            let mut ty: TokenStream =
+                "::safer_ffi::string::str_ref<".parse().unwrap()
+            ;
+            ty.extend(lifetime);
+            ty
+        },
+        | _ => return input,
+    };
+    repr_c_ty.extend(Some(TT::Punct(Punct::new('>', Spacing::Alone))));
+    let body_idx = i + 1;
+    match tts.get(body_idx) {
+        | Some(&TT::Group(ref group))
+            if group.delimiter() == Delimiter::Brace
+            && body_idx + 1 == tts.len()
+        => {},
+        | _ => return input,
+    }
+    let is_unsafe = tts[.. fn_idx].iter().any(|tt| is_ident(Some(tt), "unsafe"));
+    let (params, args) = match tts[params_idx] {
+        | TT::Group(ref group) => match slice_return_params(group.stream()) {
+            | Some(it) => it,
+            | None => return input,
+        },
+        | _ => unreachable!(),
+    };
+    let mut body = TokenStream::new();
+    if is_unsafe {
+        body.extend(Some(TT::Ident(Ident::new("unsafe", span))));
+    }
+    body.extend(vec![
+        TT::Ident(Ident::new("fn", span)),
+        TT::Ident(Ident::new("__ffi_export_body__", span)),
+    ]);
+    body.extend(tts[fn_idx + 2 .. body_idx].iter().cloned());
+    body.extend(Some(tts[body_idx].clone()));
+    let mut call: TokenStream =
+        TT::Ident(Ident::new("__ffi_export_body__", span)).into()
+    ;
+    call.extend(Some(TT::Group(Group::new(Delimiter::Parenthesis, args))));
+    if is_unsafe {
+        call = vec![
+            TT::Ident(Ident::new("unsafe", span)),
+            TT::Group(Group::new(Delimiter::Brace, call)),
+        ].into_iter().collect();
+    }
+    body.extend(Some(TT::Group(Group::new(Delimiter::Parenthesis, call))));
+    body.extend(".into()".parse::<TokenStream>().unwrap());
+    let mut ret: TokenStream = tts[.. params_idx].iter().cloned().collect();
+    ret.extend(Some(TT::Group(Group::new(Delimiter::Parenthesis, params))));
+    ret.extend(tts[params_idx + 1 .. ret_idx].iter().cloned());
+    ret.extend(repr_c_ty);
+    ret.extend(Some(TT::Group(Group::new(Delimiter::Brace, body))));
+    ret
+}
+
+/// The parameters of the function returning a slice, and the arguments
+/// forwarded to the original (nested) one: each parameter is bound to an
+/// ident, that of its pattern when it is one (`x`, `mut x`), or a fresh
+/// `arg_<i>` otherwise (`_`, `(x, y)`, _etc._).
+#[cfg(feature = "proc_macros")]
+fn slice_return_params (params: TokenStream)
+  -> Option<(TokenStream, TokenStream)>
+{
+    let params =
+        Punctuated::<FnArg, Token![,]>::parse_terminated
+            .parse(params)
+            .ok()?
+    ;
+    let mut idents = vec![];
+    let mut tys = vec![];
+    for (i, param) in params.iter().enumerate() {
+        let (pat, ty) = match *param {
+            | FnArg::Typed(PatType { ref pat, ref ty, .. }) => (pat, ty),
+            | FnArg::Receiver(_) => return None,
+        };
+        idents.push(match **pat {
+            | Pat::Ident(PatIdent { ref ident, .. }) => ident.clone(),
+            | _ => format_ident!("arg_{}", i),
+        });
+        tys.push(ty);
+    }
+    Some((
+        quote!( #(#idents : #tys),* ).into(),
+        quote!( #(#idents),* ).into(),
+    ))
+}
+
+/// Without `syn`, only the `<name> : <ty>` parameters are supported.
+#[cfg(not(feature = "proc_macros"))]
+fn slice_return_params (params: TokenStream)
+  -> Option<(TokenStream, TokenStream)>
+{
+    use ::proc_macro::{*, TokenTree as TT};
+    let is_punct = |tt: Option<&TT>, c: char| match tt {
+        | Some(&TT::Punct(ref p)) => p.as_char() == c,
+        | _ => false,
+    };
+    let tts: Vec<TT> = params.clone().into_iter().collect();
+    let mut args = TokenStream::new();
+    (0 .. tts.len()).for_each(|j| {
+        let is_name =
+            (j == 0 || is_punct(tts.get(j - 1), ','))
+            && match tts.get(j + 1) {
+                | Some(&TT::Punct(ref p)) => {
+                    p.as_char() == ':' && p.spacing() == Spacing::Alone
+                },
+                | _ => false,
+            }
+        ;
+        match tts[j] {
+            | TT::Ident(ref name) if is_name => args.extend(vec![
+                TT::Ident(name.clone()),
+                TT::Punct(Punct::new(',', Spacing::Alone)),
+            ]),
+            | _ => {},
+        }
+    });
+    Some((params, args))
+}
@@ -9,8 +9,10 @@ use ::{
     proc_macro2::{
         Span as Span2,
         TokenStream as TokenStream2,
+        TokenTree as TokenTree2,
     },
     quote::{
+        format_ident,
         quote,
         quote_spanned,
         ToTokens,
@@ -0,0 +1,186 @@
+//! Statistics on the calls of the `#[ffi_export]`ed functions.
+//!
+//! With the `profile-ffi` feature, the exported functions count their calls
+//! (from C), and the time spent in them, so that embedders can spot the
+//! chatty call patterns without attaching an external profiler.
+//!
+//! These statistics can be exported to C with [`ffi_export_stats!`][
+//! `crate::ffi_export_stats`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use ::safer_ffi::{prelude::*, profile};
+//!
+//! #[ffi_export]
+//! fn mylib_process (x: i32) -> i32
+//! {
+//!     x + 1
+//! }
+//!
+//! ::safer_ffi::ffi_export_stats!(mylib_ffi_stats);
+//!
+//! # fn main () {
+//! for stats in profile::stats() {
+//!     println!("{}: {} call(s), {} ns", stats.name, stats.calls, stats.total_ns);
+//! }
+//! # }
+//! ```
+
+use_prelude!();
+use ::core::sync::atomic::{self, AtomicU64};
+use ::std::time::Duration;
+use rust::Vec;
+
+ReprC! {
+    #[repr(C)]
+    /// The call statistics of an `#[ffi_export]`ed function.
+    #[derive(Debug, Clone, Copy)]
+    pub
+    struct FfiCallStats {
+        /// The name of the function.
+        pub
+        name: char_p::Ref<'static>,
+
+        /// The number of calls so far.
+        pub
+        calls: u64,
+
+        /// The cumulated duration of these calls, in nanoseconds.
+        pub
+        total_ns: u64,
+    }
+}
+
+/// The counters of an exported function, updated by its wrapper.
+#[allow(missing_debug_implementations)]
+#[doc(hidden)] /** Not part of the public API **/ pub
+struct FnStats {
+    /// The nul-terminated name of the function.
+    name: &'static str,
+    calls: AtomicU64,
+    total_ns: AtomicU64,
+}
+
+impl FnStats {
+    pub
+    const
+    fn new (name: &'static str)
+      -> FnStats
+    {
+        FnStats {
+            name,
+            calls: AtomicU64::new(0),
+            total_ns: AtomicU64::new(0),
+        }
+    }
+
+    pub
+    fn record (self: &'_ Self, elapsed: Duration)
+    {
+        let elapsed_ns = elapsed.as_secs()
+            .saturating_mul(1_000_000_000)
+            .saturating_add(elapsed.subsec_nanos().into())
+        ;
+        self.calls.fetch_add(1, atomic::Ordering::Relaxed);
+        self.total_ns.fetch_add(elapsed_ns, atomic::Ordering::Relaxed);
+    }
+
+    fn snapshot (self: &'_ Self)
+      -> FfiCallStats
+    {
+        FfiCallStats {
+            name: char_p::Ref::from_str_with_nul(self.name)
+                .expect("Missing nul terminator")
+            ,
+            calls: self.calls.load(atomic::Ordering::Relaxed),
+            total_ns: self.total_ns.load(atomic::Ordering::Relaxed),
+        }
+    }
+}
+
+/// Registers the [`FnStats`] of an exported function.
+#[allow(missing_copy_implementations, missing_debug_implementations)]
+#[doc(hidden)] /** Not part of the public API **/ pub
+struct Registered(pub &'static FnStats);
+
+::inventory::collect!(Registered);
+
+/// The call statistics of all the exported functions, sorted by name.
+pub
+fn stats ()
+  -> Vec<FfiCallStats>
+{
+    let mut ret: Vec<_> =
+        ::inventory::iter::<Registered>
+            .into_iter()
+            .map(|&Registered(stats)| stats.snapshot())
+            .collect()
+    ;
+    ret.sort_by(|a, b| a.name.to_str().cmp(b.name.to_str()));
+    ret
+}
+
+/// Writes (up to `out.len()` of) the [`stats()`] to `out`, and returns the
+/// number of exported functions.
+pub
+fn write_stats (out: &'_ mut [FfiCallStats])
+  -> usize
+{
+    let stats = stats();
+    out.iter_mut()
+        .zip(&stats)
+        .for_each(|(out, &stats)| *out = stats);
+    stats.len()
+}
+
+/// Resets the call statistics of all the exported functions.
+pub
+fn reset ()
+{
+    ::inventory::iter::<Registered>
+        .into_iter()
+        .for_each(|&Registered(stats)| {
+            stats.calls.store(0, atomic::Ordering::Relaxed);
+            stats.total_ns.store(0, atomic::Ordering::Relaxed);
+        });
+}
+
+/// Exports a function, named as given, writing the call statistics of the
+/// exported functions (see [`write_stats`][`crate::profile::write_stats`])
+/// to a caller-provided buffer.
+///
+/// ```rust,ignore
+/// ::safer_ffi::ffi_export_stats!(mylib_ffi_stats);
+/// ```
+///
+/// exports:
+///
+/// ```C
+/// typedef struct {
+///     char const * name;
+///     uint64_t calls;
+///     uint64_t total_ns;
+/// } FfiCallStats_t;
+///
+/// size_t mylib_ffi_stats (
+///     slice_mut_FfiCallStats_t out);
+/// ```
+///
+/// which returns the number of exported functions, for the caller to call
+/// it a first time with an empty slice, and then with a big enough one.
+#[macro_export]
+macro_rules! ffi_export_stats {(
+    $fname:ident $(;)?
+) => (
+    #[$crate::ffi_export]
+    /// Writes (up to `out.len`) call statistics of the exported functions
+    /// to `out`, and returns the number of exported functions.
+    pub
+    fn $fname (
+        out: $crate::slice::slice_mut<'_, $crate::profile::FfiCallStats>,
+    ) -> usize
+    {
+        $crate::profile::write_stats(out.as_slice())
+    }
+)}
@@ -0,0 +1,74 @@
+//! Standard progress-reporting callback for long-running exported functions.
+//!
+//! See [`ProgressCallback`] and the `progress` option of
+//! [`#[ffi_export]`][`crate::ffi_export`].
+
+use_prelude!();
+
+ReprC! {
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    /// Progress-reporting callback of a long-running operation.
+    ///
+    /// The `report` function is called with the `ctx` pointer, the completion
+    /// percentage of the operation (from `0` to `100`), and a short,
+    /// human-readable description of the current stage (only valid for the
+    /// duration of the call).
+    ///
+    /// It returns whether the operation should go on: returning `false`
+    /// requests a cancellation of the operation.
+    ///
+    /// A `NULL` `report` function disables progress reporting.
+    pub
+    struct ProgressCallback {
+        /// Opaque context, forwarded as is to `report`.
+        pub
+        ctx: *mut c_void,
+
+        /// Returns `false` to cancel the operation.
+        pub
+        report: Option<
+            unsafe extern "C"
+            fn (
+                ctx: *mut c_void,
+                percent: u8,
+                stage: char_p::Raw,
+            ) -> bool
+        >,
+    }
+}
+
+impl ProgressCallback {
+    /// Reports `percent` (clamped to `100`) completion of the `stage` of the
+    /// operation.
+    ///
+    /// Returns whether the operation should go on.
+    pub
+    fn report (
+        self: &'_ Self,
+        percent: u8,
+        stage: char_p::Ref<'_>,
+    ) -> bool
+    {
+        match self.report {
+            | Some(report) => unsafe {
+                // Safety: upheld by the C caller that provided the callback.
+                report(self.ctx, percent.min(100), stage.into())
+            },
+            | None => true,
+        }
+    }
+}
+
+impl fmt::Debug
+    for ProgressCallback
+{
+    fn fmt (self: &'_ Self, fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        fmt .debug_struct("ProgressCallback")
+            .field("ctx", &self.ctx)
+            .field("report", &self.report)
+            .finish()
+    }
+}
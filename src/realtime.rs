@@ -0,0 +1,191 @@
+//! The types which can cross the FFI boundary without allocating nor
+//! locking, for the `#[ffi_export(realtime_safe)]` functions.
+//!
+//! Real-time callbacks (_e.g._, audio ones) must not allocate, free, or lock
+//! anything, lest they miss their deadline. Passing a
+//! [`repr_c::Vec`][`crate::vec::Vec`] to such a function, for instance,
+//! means freeing it when done with it, and returning one, allocating it.
+//!
+//! `#[ffi_export(realtime_safe)]` thus requires all the parameter and return
+//! types of the function to be [`RealtimeSafe`], and mentions it in its
+//! documentation (and thus in the generated headers):
+//!
+//! ```rust
+//! use ::safer_ffi::{prelude::*, realtime::RealtimeSafe};
+//!
+//! #[derive_ReprC]
+//! #[repr(C)]
+//! #[derive(Clone, Copy)]
+//! pub
+//! struct Gain {
+//!     left: f32,
+//!     right: f32,
+//! }
+//!
+//! impl RealtimeSafe for Gain {}
+//!
+//! #[ffi_export(realtime_safe)]
+//! /// Applies the gain to the (interleaved) stereo samples.
+//! fn apply_gain (samples: c_slice::Mut<'_, f32>, gain: Gain)
+//! {
+//!     for frame in samples.as_slice().chunks_mut(2) {
+//!         frame[0] *= gain.left;
+//!         frame[1] *= gain.right;
+//!     }
+//! }
+//! ```
+//!
+//! whereas the following fails to compile:
+//!
+//! ```rust,compile_fail
+//! use ::safer_ffi::prelude::*;
+//!
+//! #[ffi_export(realtime_safe)]
+//! fn samples () -> repr_c::Vec<f32>
+//! {
+//!     vec![0.0; 64].into()
+//! }
+//! ```
+//!
+//! Note that this only audits the signature of the function: its body is
+//! still responsible for not allocating nor locking anything.
+
+use_prelude!();
+use crate::{
+    char_p::char_p_ref,
+    slice::{slice_mut, slice_ref},
+    string::str_ref,
+};
+
+/// Marker trait of the types whose values can be passed to (and returned
+/// from) a real-time function without allocating, freeing, nor locking
+/// anything.
+///
+/// It is implemented for the primitive types, the pointers and references
+/// (whatever the pointee, since borrowing never frees it), the borrowed
+/// slices and strings, and the function pointers, but not for the owned
+/// [`repr_c::Box`][`crate::boxed::Box`], [`repr_c::Vec`][`crate::vec::Vec`]
+/// or [`char_p::Box`][`crate::char_p::char_p_boxed`], for instance.
+///
+/// It can be implemented for custom types, such as `#[repr(C)]` structs
+/// of `RealtimeSafe` fields, or handles which are `Copy` and do not lock.
+pub
+trait RealtimeSafe {}
+
+macro_rules! impl_RealtimeSafe {(
+    $($T:ty),* $(,)?
+) => (
+    $(
+        impl RealtimeSafe
+            for $T
+        {}
+    )*
+)}
+
+impl_RealtimeSafe! {
+    (),
+    bool,
+    u8, u16, u32, u64, usize,
+    i8, i16, i32, i64, isize,
+    f32, f64,
+    char_p_ref<'_>,
+    str_ref<'_>,
+}
+
+impl<T : ?Sized> RealtimeSafe
+    for *const T
+{}
+
+impl<T : ?Sized> RealtimeSafe
+    for *mut T
+{}
+
+impl<T : ?Sized> RealtimeSafe
+    for &'_ T
+{}
+
+impl<T : ?Sized> RealtimeSafe
+    for &'_ mut T
+{}
+
+impl<T> RealtimeSafe
+    for ::core::ptr::NonNull<T>
+{}
+
+impl<T : RealtimeSafe> RealtimeSafe
+    for Option<T>
+{}
+
+impl<T> RealtimeSafe
+    for slice_ref<'_, T>
+{}
+
+impl<T> RealtimeSafe
+    for slice_mut<'_, T>
+{}
+
+macro_rules! impl_RealtimeSafe_for_fn_ptrs {(
+    $( $An:ident $(, $Ai:ident)* )?
+) => (
+    impl<Ret $(, $An $(, $Ai)*)?> RealtimeSafe
+        for extern "C" fn ($($An $(, $Ai)*)?) -> Ret
+    {}
+
+    impl<Ret $(, $An $(, $Ai)*)?> RealtimeSafe
+        for unsafe extern "C" fn ($($An $(, $Ai)*)?) -> Ret
+    {}
+
+    $(
+        impl_RealtimeSafe_for_fn_ptrs! { $($Ai),* }
+    )?
+)}
+
+impl_RealtimeSafe_for_fn_ptrs! { A9, A8, A7, A6, A5, A4, A3, A2, A1 }
+
+/// Checks that the types of the signature of an `#[ffi_export(realtime_safe)]`
+/// function are [`RealtimeSafe`], and then forwards it to the given
+/// `__ffi_export__`-like macro.
+#[doc(hidden)] #[macro_export]
+macro_rules! __ffi_export_realtime_safe__ {(
+    [$next:ident]
+    $(@$prefix:ident[$($prefix_arg:tt)*])*
+    $($(#[doc = $doc:expr])+)?
+    $pub:vis
+    $(unsafe $(@$hack:ident@)?)?
+    $(extern $("C")?)?
+    fn $fname:ident $(<$($lt:lifetime $(: $sup_lt:lifetime)?),* $(,)?>)? (
+        $(
+            $arg_name:ident : $arg_ty:ty
+        ),* $(,)?
+    ) $(-> $Ret:ty)?
+        $body:block
+) => (
+    #[allow(dead_code, nonstandard_style)]
+    const _: () = {
+        fn realtime_safe<T : ?Sized + $crate::realtime::RealtimeSafe> ()
+        {}
+
+        fn $fname $(<$($lt $(: $sup_lt)?),*>)? ()
+        {
+            $(
+                realtime_safe::<$arg_ty>();
+            )*
+            $(
+                realtime_safe::<$Ret>();
+            )?
+        }
+    };
+
+    $crate::$next! {
+        $(@$prefix[$($prefix_arg)*])*
+        $($(#[doc = $doc])+)?
+        $pub
+        $(unsafe $(@$hack@)?)?
+        fn $fname $(<$($lt $(: $sup_lt)?),*>)? (
+            $(
+                $arg_name : $arg_ty,
+            )*
+        ) $(-> $Ret)?
+            $body
+    }
+)}
@@ -0,0 +1,155 @@
+//! Fixed-size SIMD-friendly vectors, such as the `float[4]` with a 16-byte
+//! alignment used by game-engine math types.
+//!
+//! Their C definition depends on
+//! [`headers::SimdTypes`][`crate::headers::SimdTypes`] (with the `headers`
+//! feature):
+//!
+//!   - by default, a portable (over-)aligned array:
+//!
+//!     ```C
+//!     typedef struct {
+//!         SAFER_FFI_ALIGNAS(16) float lanes[4];
+//!     } f32x4_t;
+//!     ```
+//!
+//!   - or, the x86 intrinsic type with the same layout, when there is one:
+//!
+//!     ```C
+//!     #include <xmmintrin.h>
+//!
+//!     typedef __m128 f32x4_t;
+//!     ```
+//!
+//!     Only the _layout_ is the same: these types may be used behind
+//!     pointers or as fields, but they must not be passed by value, since
+//!     the calling conventions pass SIMD vectors and `struct`s differently.
+
+use_prelude!();
+
+macro_rules! simd_types {(
+    $(
+        $(#[$meta:meta])*
+        $name:ident : [$T:ident; $N:literal], align($align:literal)
+        $(=> $intrinsic:literal in $include:literal)?
+    ),* $(,)?
+) => (
+    $(
+        $(#[$meta])*
+        #[repr(C, align($align))]
+        #[derive(Debug, Clone, Copy, PartialEq, Default)]
+        #[allow(nonstandard_style)]
+        pub
+        struct $name(pub [$T; $N]);
+
+        impl From<[$T; $N]>
+            for $name
+        {
+            #[inline]
+            fn from (lanes: [$T; $N])
+              -> $name
+            {
+                $name(lanes)
+            }
+        }
+
+        impl From<$name>
+            for [$T; $N]
+        {
+            #[inline]
+            fn from (it: $name)
+              -> [$T; $N]
+            {
+                it.0
+            }
+        }
+
+        unsafe // Safety: `#[repr(C)]` struct with a `CType` array field.
+        impl CType
+            for $name
+        { __cfg_headers__! {
+            fn c_short_name_fmt (fmt: &'_ mut fmt::Formatter<'_>)
+              -> fmt::Result
+            {
+                fmt.write_str(stringify!($name))
+            }
+
+            fn c_define_self (definer: &'_ mut dyn crate::headers::Definer)
+              -> io::Result<()>
+            {
+                use crate::headers::{context, SimdTypes};
+                let me = stringify!($name);
+                if let SimdTypes::Intrinsics = context::context().simd_types {
+                    $(
+                        return definer.define_once(me, &mut |definer| {
+                            write!(definer.out(),
+                                "#include <{}>\n\ntypedef {} {};\n\n",
+                                $include, $intrinsic,
+                                crate::headers::__type_name__(&me),
+                            )
+                        });
+                    )?
+                }
+                <$T as CType>::c_define_self(definer)?;
+                definer.define_once("SAFER_FFI_ALIGNAS", &mut |definer| {
+                    definer.set_symbol_kind(
+                        "SAFER_FFI_ALIGNAS",
+                        crate::headers::SymbolKind::Macro,
+                    );
+                    definer.out().write_all(
+                        context::context().c_dialect.alignas_macro().as_bytes()
+                    )
+                })?;
+                definer.define_once(me, &mut |definer| {
+                    write!(definer.out(),
+                        concat!(
+                            "typedef struct {{\n",
+                            "    SAFER_FFI_ALIGNAS(", stringify!($align), ") ",
+                            "{};\n",
+                            "}} {};\n\n",
+                        ),
+                        <$T as CType>::c_var(
+                            concat!("lanes[", stringify!($N), "]"),
+                        ),
+                        crate::headers::__type_name__(&me),
+                    )
+                })
+            }
+
+            fn c_var_fmt (
+                fmt: &'_ mut fmt::Formatter<'_>,
+                var_name: &'_ str,
+            ) -> fmt::Result
+            {
+                write!(fmt,
+                    "{}{sep}{}",
+                    crate::headers::__type_name__(&stringify!($name)),
+                    var_name,
+                    sep = if var_name.is_empty() { "" } else { " " },
+                )
+            }
+        } type OPAQUE_KIND = OpaqueKind::Concrete; }
+
+        from_CType_impl_ReprC! { $name }
+    )*
+)}
+
+simd_types! {
+    /// Four `f32` lanes, with the layout of `__m128`.
+    f32x4: [f32; 4], align(16) => "__m128" in "xmmintrin.h",
+
+    /// Eight `f32` lanes, with the layout of `__m256`.
+    f32x8: [f32; 8], align(32) => "__m256" in "immintrin.h",
+
+    /// Two `f64` lanes, with the layout of `__m128d`.
+    f64x2: [f64; 2], align(16) => "__m128d" in "emmintrin.h",
+
+    /// Four `f64` lanes, with the layout of `__m256d`.
+    f64x4: [f64; 4], align(32) => "__m256d" in "immintrin.h",
+
+    /// Four `i32` lanes, with the layout of `__m128i`.
+    i32x4: [i32; 4], align(16) => "__m128i" in "emmintrin.h",
+
+    /// Four `u32` lanes, with the layout of `__m128i`.
+    u32x4: [u32; 4], align(16) => "__m128i" in "emmintrin.h",
+}
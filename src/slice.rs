@@ -199,6 +199,30 @@ cfg_alloc! {
         }
     }
 
+    impl<T> From<rust::Vec<T>>
+        for slice_boxed<T>
+    {
+        /// Shrinks the excess capacity, if any.
+        #[inline]
+        fn from (vec: rust::Vec<T>)
+          -> Self
+        {
+            vec.into_boxed_slice().into()
+        }
+    }
+
+    impl<T> From<slice_boxed<T>>
+        for rust::Vec<T>
+    {
+        #[inline]
+        fn from (it: slice_boxed<T>)
+          -> rust::Vec<T>
+        {
+            let boxed_slice: rust::Box<[T]> = it.into();
+            boxed_slice.into()
+        }
+    }
+
     impl<T> Drop
         for slice_boxed<T>
     {
@@ -559,13 +583,97 @@ impl<'lt, T : 'lt> From<slice_mut<'lt, T>>
     }
 }
 
+/// `From<&[T; N]>` conversions (for `N <= 32`).
+macro_rules! impl_From_array_refs {(
+    $($N:literal)*
+) => ($(
+    impl<'lt, T : 'lt> From<&'lt [T; $N]>
+        for slice_ref<'lt, T>
+    {
+        #[inline]
+        fn from (array: &'lt [T; $N])
+          -> slice_ref<'lt, T>
+        {
+            (&array[..]).into()
+        }
+    }
+
+    impl<'lt, T : 'lt> From<&'lt mut [T; $N]>
+        for slice_mut<'lt, T>
+    {
+        #[inline]
+        fn from (array: &'lt mut [T; $N])
+          -> slice_mut<'lt, T>
+        {
+            (&mut array[..]).into()
+        }
+    }
+)*)}
+
+impl_From_array_refs! {
+    0 1 2 3 4 5 6 7 8 9
+    10 11 12 13 14 15 16 17 18 19
+    20 21 22 23 24 25 26 27 28 29
+    30 31 32
+}
+
 /// Extra traits for these `#[repr(C)]` slices.
 const _: () = {
     use ::core::{
+        borrow::Borrow,
         hash::{Hash, Hasher},
         cmp::Ordering,
     };
 
+    /// `AsRef<[T]>`, `Borrow<[T]>` and `PartialEq<[T]>`, for parity with the
+    /// Rust slice types.
+    macro_rules! impl_slice_traits {(
+        $(
+            $(@for[$lt:lifetime])? $Slice:ty
+        ),* $(,)?
+    ) => ($(
+        impl<$($lt,)? T> AsRef<[T]>
+            for $Slice
+        {
+            #[inline]
+            fn as_ref (self: &'_ Self)
+              -> &'_ [T]
+            {
+                &self[..]
+            }
+        }
+        impl<$($lt,)? T> Borrow<[T]>
+            for $Slice
+        {
+            #[inline]
+            fn borrow (self: &'_ Self)
+              -> &'_ [T]
+            {
+                &self[..]
+            }
+        }
+        impl<$($lt,)? T : PartialEq> PartialEq<[T]>
+            for $Slice
+        {
+            #[inline]
+            fn eq (self: &'_ Self, other: &'_ [T])
+              -> bool
+            {
+                self[..] == *other
+            }
+        }
+    )*)}
+
+    impl_slice_traits! {
+        @for['lt] slice_ref<'lt, T>,
+        @for['lt] slice_mut<'lt, T>,
+    }
+    cfg_alloc! {
+        impl_slice_traits! {
+            slice_boxed<T>,
+        }
+    }
+
     impl<T : Ord> Ord
         for slice_ref<'_, T>
     {
@@ -0,0 +1,322 @@
+//! Byte stream handles, bridging C to [`io::Read`] / [`io::Write`] impls.
+//!
+//! Rather than inventing a streaming protocol per project, the Rust side
+//! wraps its reader (resp. writer) in a [`Reader`] (resp. [`Writer`]) handle,
+//! and the C side drives it through the `read` (resp. `write` / `flush`) and
+//! `close` functions exported by the [`ffi_export_stream!`] macro.
+//!
+//! These functions return the number of bytes read / written, or a negative
+//! [`StreamError`] code. In particular, a non-blocking stream which is not
+//! ready yet (back-pressure) yields [`StreamError::WouldBlock`], signaling
+//! that the operation should be retried later.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ::safer_ffi::{prelude::*, stream::Reader};
+//!
+//! /// Streams the given bytes back.
+//! #[ffi_export]
+//! fn echo_reader (bytes: c_slice::Ref<'_, u8>) -> repr_c::Box<Reader>
+//! {
+//!     repr_c::Box::new(Reader::new(::std::io::Cursor::new(bytes.to_vec())))
+//! }
+//!
+//! ::safer_ffi::ffi_export_stream! {
+//!     Reader => {
+//!         read: echo_read,
+//!         close: echo_close,
+//!     }
+//! }
+//! ```
+//!
+//! which, C-wise, is used as:
+//!
+//! ```C
+//! Reader_t * reader = echo_reader((slice_ref_uint8_t) { .ptr = data, .len = len });
+//! uint8_t buf[256];
+//! ssize_t n;
+//! while ((n = echo_read(reader, (slice_mut_uint8_t) { .ptr = buf, .len = sizeof(buf) })) > 0) {
+//!     fwrite(buf, 1, n, stdout);
+//! }
+//! echo_close(reader);
+//! ```
+
+use_prelude!();
+
+ReprC! {
+    /// Negative return codes of the exported stream functions.
+    #[repr(i8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub
+    enum StreamError {
+        /// Any other I/O error.
+        Other = -1,
+        /// The stream is not ready yet: retry later.
+        WouldBlock = -2,
+        /// The other end of the stream has been closed.
+        BrokenPipe = -3,
+        /// The data is invalid for this stream.
+        InvalidData = -4,
+    }
+}
+
+impl StreamError {
+    pub
+    fn from_io (err: &'_ io::Error)
+      -> StreamError
+    {
+        match err.kind() {
+            | io::ErrorKind::WouldBlock => StreamError::WouldBlock,
+            | io::ErrorKind::BrokenPipe => StreamError::BrokenPipe,
+            | io::ErrorKind::InvalidData => StreamError::InvalidData,
+            | _ => StreamError::Other,
+        }
+    }
+
+    /// `Ok(n)` becomes `n`, and an `Err`or, its (negative) code.
+    pub
+    fn encode (result: io::Result<usize>)
+      -> isize
+    {
+        match result {
+            | Ok(n) => n as isize,
+            | Err(ref err) => StreamError::from_io(err) as isize,
+        }
+    }
+}
+
+impl fmt::Display
+    for StreamError
+{
+    fn fmt (self: &'_ Self, fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        fmt.write_str(match *self {
+            | StreamError::Other => "I/O error",
+            | StreamError::WouldBlock => "operation would block",
+            | StreamError::BrokenPipe => "broken pipe",
+            | StreamError::InvalidData => "invalid data",
+        })
+    }
+}
+
+impl ::std::error::Error
+    for StreamError
+{}
+
+/// Retries the operation when it is `Interrupted`.
+fn retrying<T> (mut op: impl FnMut() -> io::Result<T>)
+  -> io::Result<T>
+{
+    loop {
+        match op() {
+            | Err(ref err) if err.kind() == io::ErrorKind::Interrupted => {},
+            | result => return result,
+        }
+    }
+}
+
+ReprC! {
+    #[ReprC::opaque("Reader")]
+    /// Handle to a byte stream to be read from C.
+    ///
+    /// It is an opaque type from the point of view of C.
+    pub
+    struct Reader {
+        inner: rust::Box<dyn io::Read + Send>,
+    }
+}
+
+impl Reader {
+    pub
+    fn new (reader: impl 'static + Send + io::Read)
+      -> Reader
+    {
+        Reader { inner: rust::Box::new(reader) }
+    }
+
+    /// Reads into `buf`, returning the number of bytes read (`0` at the end
+    /// of the stream), or a (negative) [`StreamError`] code.
+    pub
+    fn read_encoded (self: &'_ mut Reader, buf: &'_ mut [u8])
+      -> isize
+    {
+        StreamError::encode(retrying(|| self.inner.read(buf)))
+    }
+}
+
+impl fmt::Debug
+    for Reader
+{
+    fn fmt (self: &'_ Self, fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        fmt .debug_struct("Reader")
+            .finish()
+    }
+}
+
+ReprC! {
+    #[ReprC::opaque("Writer")]
+    /// Handle to a byte stream to be written to from C.
+    ///
+    /// It is an opaque type from the point of view of C.
+    pub
+    struct Writer {
+        inner: rust::Box<dyn io::Write + Send>,
+    }
+}
+
+impl Writer {
+    pub
+    fn new (writer: impl 'static + Send + io::Write)
+      -> Writer
+    {
+        Writer { inner: rust::Box::new(writer) }
+    }
+
+    /// Writes (some of) `buf`, returning the number of bytes written, or a
+    /// (negative) [`StreamError`] code.
+    pub
+    fn write_encoded (self: &'_ mut Writer, buf: &'_ [u8])
+      -> isize
+    {
+        StreamError::encode(retrying(|| self.inner.write(buf)))
+    }
+
+    /// Flushes the stream, returning `0`, or a (negative) [`StreamError`]
+    /// code.
+    pub
+    fn flush_encoded (self: &'_ mut Writer)
+      -> isize
+    {
+        StreamError::encode(retrying(|| self.inner.flush()).map(|()| 0))
+    }
+}
+
+impl fmt::Debug
+    for Writer
+{
+    fn fmt (self: &'_ Self, fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        fmt .debug_struct("Writer")
+            .finish()
+    }
+}
+
+/// Exports the functions operating on a [`Reader`][`crate::stream::Reader`]
+/// or a [`Writer`][`crate::stream::Writer`].
+///
+/// ```rust,ignore
+/// ::safer_ffi::ffi_export_stream! {
+///     Reader => {
+///         read: my_read,
+///         close: my_reader_close,
+///     }
+/// }
+///
+/// ::safer_ffi::ffi_export_stream! {
+///     Writer => {
+///         write: my_write,
+///         flush: my_flush,
+///         close: my_writer_close,
+///     }
+/// }
+/// ```
+///
+/// exports:
+///
+/// ```C
+/// ssize_t my_read (Reader_t * reader, slice_mut_uint8_t buf);
+///
+/// void my_reader_close (Reader_t * reader);
+///
+/// ssize_t my_write (Writer_t * writer, slice_ref_uint8_t buf);
+///
+/// ssize_t my_flush (Writer_t * writer);
+///
+/// ssize_t my_writer_close (Writer_t * writer);
+/// ```
+///
+///   - `read` returns the number of bytes read (`0` at the end of the
+///     stream), `write`, the number of bytes written (which may be less than
+///     `buf.len`), and `flush`, `0`;
+///
+///   - `close` destroys the handle (`NULL` is allowed), after flushing it in
+///     the case of a `Writer`;
+///
+///   - on error, they return a negative
+///     [`StreamError`][`crate::stream::StreamError`] code.
+#[macro_export]
+macro_rules! ffi_export_stream {
+    (
+        Reader => {
+            read: $read:ident,
+            close: $close:ident $(,)?
+        } $(;)?
+    ) => (
+        #[$crate::ffi_export]
+        /// Reads into `buf`, returning the number of bytes read (`0` at the
+        /// end of the stream), or a negative error code.
+        pub
+        fn $read (
+            reader: &'_ mut $crate::stream::Reader,
+            buf: $crate::slice::slice_mut<'_, u8>,
+        ) -> isize
+        {
+            reader.read_encoded(buf.as_slice())
+        }
+
+        #[$crate::ffi_export]
+        /// Destroys the reader.
+        pub
+        fn $close (reader: Option<$crate::boxed::Box<$crate::stream::Reader>>)
+        {
+            $crate::core::mem::drop(reader)
+        }
+    );
+
+    (
+        Writer => {
+            write: $write:ident,
+            flush: $flush:ident,
+            close: $close:ident $(,)?
+        } $(;)?
+    ) => (
+        #[$crate::ffi_export]
+        /// Writes (some of) `buf`, returning the number of bytes written, or a
+        /// negative error code.
+        pub
+        fn $write (
+            writer: &'_ mut $crate::stream::Writer,
+            buf: $crate::slice::slice_ref<'_, u8>,
+        ) -> isize
+        {
+            writer.write_encoded(buf.as_slice())
+        }
+
+        #[$crate::ffi_export]
+        /// Flushes the writer, returning `0`, or a negative error code.
+        pub
+        fn $flush (writer: &'_ mut $crate::stream::Writer)
+          -> isize
+        {
+            writer.flush_encoded()
+        }
+
+        #[$crate::ffi_export]
+        /// Flushes and destroys the writer, returning `0`, or a negative
+        /// error code.
+        pub
+        fn $close (writer: Option<$crate::boxed::Box<$crate::stream::Writer>>)
+          -> isize
+        {
+            match writer {
+                | Some(mut writer) => writer.flush_encoded(),
+                | None => 0,
+            }
+        }
+    );
+}
@@ -40,6 +40,19 @@ cfg_alloc! {
         }
     }
 
+    impl TryFrom<rust::Vec<u8>>
+        for String
+    {
+        type Error = ::alloc::string::FromUtf8Error;
+
+        #[inline]
+        fn try_from (bytes: rust::Vec<u8>)
+          -> Result<String, ::alloc::string::FromUtf8Error>
+        {
+            rust::String::from_utf8(bytes).map(String::from)
+        }
+    }
+
     impl Deref
         for String
     {
@@ -48,11 +61,102 @@ cfg_alloc! {
         fn deref (self: &'_ Self) -> &'_ Self::Target
         {
             unsafe {
-                ::core::str::from_utf8_unchecked(&* self.0)
+                ::core::str::from_utf8_unchecked(&self.0)
             }
         }
     }
 
+    /// Extra traits, for parity with [`rust::String`].
+    const _: () = {
+        use ::core::{
+            borrow::Borrow,
+            hash::{Hash, Hasher},
+        };
+
+        impl AsRef<str>
+            for String
+        {
+            #[inline]
+            fn as_ref (self: &'_ String)
+              -> &'_ str
+            {
+                self
+            }
+        }
+        impl AsRef<[u8]>
+            for String
+        {
+            #[inline]
+            fn as_ref (self: &'_ String)
+              -> &'_ [u8]
+            {
+                self.as_bytes()
+            }
+        }
+        impl Borrow<str>
+            for String
+        {
+            #[inline]
+            fn borrow (self: &'_ String)
+              -> &'_ str
+            {
+                self
+            }
+        }
+        impl Eq
+            for String
+        {}
+        impl PartialEq
+            for String
+        {
+            #[inline]
+            fn eq (self: &'_ String, other: &'_ String)
+              -> bool
+            {
+                **self == **other
+            }
+        }
+        impl PartialEq<str>
+            for String
+        {
+            #[inline]
+            fn eq (self: &'_ String, other: &'_ str)
+              -> bool
+            {
+                **self == *other
+            }
+        }
+        impl Hash
+            for String
+        {
+            #[inline]
+            fn hash<H : Hasher> (self: &'_ String, hasher: &'_ mut H)
+            {
+                (**self).hash(hasher)
+            }
+        }
+        impl Clone
+            for String
+        {
+            #[inline]
+            fn clone (self: &'_ String)
+              -> String
+            {
+                Self(self.0.clone())
+            }
+        }
+        impl Default
+            for String
+        {
+            #[inline]
+            fn default ()
+              -> String
+            {
+                String::EMPTY
+            }
+        }
+    };
+
     impl fmt::Debug
         for String
     {
@@ -67,6 +171,18 @@ cfg_alloc! {
         pub
         const EMPTY: Self = Self(Vec::EMPTY);
 
+        /// Infallible conversion from bytes, which replaces invalid UTF-8
+        /// sequences with `U+FFFD REPLACEMENT CHARACTER`.
+        #[inline]
+        pub
+        fn from_utf8_lossy (bytes: &'_ [u8])
+          -> String
+        {
+            rust::String::from_utf8_lossy(bytes)
+                .into_owned()
+                .into()
+        }
+
         pub
         fn with_rust_mut<R> (
             self: &'_ mut String,
@@ -0,0 +1,507 @@
+//! Global state meant to be accessed from `#[ffi_export]`ed functions.
+//!
+//! The classic `lazy_static!` + `Mutex` + `.unwrap()` combo is a recurring
+//! source of panics within exported functions (which then abort the process):
+//! a panicking initializer, a poisoned lock, or a C callback re-entering the
+//! library while the lock is held, or while the lazy value is being
+//! initialized (deadlock).
+//!
+//! The [`FfiLazy`] and [`FfiMutex`] statics provided here turn these
+//! situations into an [`AccessError`], which can then be forwarded to C as an
+//! error code. Only re-entry from the very same thread is detected, though: a
+//! callback handing the work over to another thread, which then blocks on the
+//! lock, still deadlocks.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ::safer_ffi::{prelude::*, sync::{AccessError, FfiLazy, FfiMutex}};
+//!
+//! static COUNTER: FfiMutex<u64> = FfiMutex::new(0);
+//!
+//! static GREETING: FfiLazy<String> = FfiLazy::new(|| {
+//!     ::std::env::var("GREETING").unwrap_or_else(|_| "Hello".into())
+//! });
+//!
+//! /// Returns the new value of the counter, or a negative error code.
+//! #[ffi_export]
+//! fn increment () -> i64
+//! {
+//!     match COUNTER.lock() {
+//!         | Ok(mut counter) => {
+//!             *counter += 1;
+//!             *counter as i64
+//!         },
+//!         | Err(err) => err as i64,
+//!     }
+//! }
+//!
+//! /// Returns the length of the greeting, or a negative error code.
+//! #[ffi_export]
+//! fn greeting_len () -> i64
+//! {
+//!     GREETING.get().map_or_else(|err| err as i64, |it| it.len() as i64)
+//! }
+//! ```
+//!
+//! # Exported initialization
+//!
+//! A library needing some global initialization often exports a
+//! `mylib_init()` function, which C hosts end up calling from several
+//! threads, or several times. The [`ffi_once!`] macro exports such a
+//! function, which runs its body at most once, and which is safe to call
+//! concurrently:
+//!
+//! ```rust
+//! use ::safer_ffi::prelude::*;
+//!
+//! ::safer_ffi::ffi_once! {
+//!     /// Initializes the library.
+//!     pub
+//!     fn mylib_try_init ()
+//!     {
+//!         // ...
+//!     }
+//! }
+//!
+//! # fn main () {
+//! assert!(mylib_try_init());
+//! assert!(!mylib_try_init());
+//! # }
+//! ```
+
+use_prelude!();
+use ::core::{
+    cell::UnsafeCell,
+    ops,
+    sync::atomic::{self, AtomicBool, AtomicUsize},
+};
+use ::std::{
+    panic,
+    sync::{Condvar, Mutex, Once, PoisonError},
+    thread,
+};
+
+ReprC! {
+    /// Error when accessing an [`FfiLazy`] or an [`FfiMutex`].
+    ///
+    /// Its values are negative, so that they can double as error codes of
+    /// functions otherwise returning non-negative integers.
+    #[repr(i32)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub
+    enum AccessError {
+        /// The initializer of the lazy value panicked.
+        InitPanicked = -1,
+        /// A previous holder of the lock panicked.
+        Poisoned = -2,
+        /// The lock is already held by the current thread, or the lazy value
+        /// is already being initialized by it.
+        Reentrant = -3,
+    }
+}
+
+impl fmt::Display
+    for AccessError
+{
+    fn fmt (self: &'_ Self, fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        fmt.write_str(match *self {
+            | AccessError::InitPanicked => "lazy initializer panicked",
+            | AccessError::Poisoned => "lock poisoned by a panic",
+            | AccessError::Reentrant => {
+                "lock already held, or lazy value being initialized, by this thread"
+            },
+        })
+    }
+}
+
+impl ::std::error::Error
+    for AccessError
+{}
+
+/// A lazily initialized value, usable as a `static`.
+///
+/// Contrary to `lazy_static!`:
+///
+///   - a panicking initializer does not make every subsequent access panic:
+///     they get an [`AccessError::InitPanicked`] instead;
+///
+///   - accessing the value from within its own initializer (_e.g._, when it
+///     calls back into C, which re-enters the library) fails with
+///     [`AccessError::Reentrant`] rather than deadlocking.
+pub
+struct FfiLazy<T, F = fn() -> T> {
+    once: Once,
+    /// The `thread_token()` of the thread running `init`, or `0`.
+    initializer: AtomicUsize,
+    init: F,
+    value: UnsafeCell<Option<T>>,
+}
+
+unsafe // Safety: `value` is only written to once, within `once`.
+impl<T, F> Sync
+    for FfiLazy<T, F>
+where
+    T : Send + Sync,
+    F : Send,
+{}
+
+impl<T, F> FfiLazy<T, F> {
+    /// The `init`-ializer is only called on the first access.
+    pub
+    const
+    fn new (init: F)
+      -> Self
+    {
+        FfiLazy {
+            once: Once::new(),
+            initializer: AtomicUsize::new(0),
+            init,
+            value: UnsafeCell::new(None),
+        }
+    }
+}
+
+impl<T, F> FfiLazy<T, F>
+where
+    F : Fn() -> T,
+{
+    /// Initializes the value if needed, and returns a reference to it.
+    pub
+    fn get (self: &'_ Self)
+      -> Result<&'_ T, AccessError>
+    {
+        if  self.once.is_completed().not()
+        &&  self.initializer.load(atomic::Ordering::Relaxed) == thread_token()
+        {
+            return Err(AccessError::Reentrant);
+        }
+        self.once.call_once(|| {
+            self.initializer.store(thread_token(), atomic::Ordering::Relaxed);
+            if let Ok(value) = panic::catch_unwind(
+                panic::AssertUnwindSafe(&self.init)
+            )
+            {
+                unsafe {
+                    // Safety: unique access within `call_once`.
+                    *self.value.get() = Some(value);
+                }
+            }
+            self.initializer.store(0, atomic::Ordering::Relaxed);
+        });
+        unsafe {
+            // Safety: `once` has completed: no more writes.
+            (*self.value.get()).as_ref()
+        }.ok_or(AccessError::InitPanicked)
+    }
+}
+
+impl<T : fmt::Debug, F> fmt::Debug
+    for FfiLazy<T, F>
+{
+    fn fmt (self: &'_ Self, fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        let value = if self.once.is_completed() {
+            unsafe {
+                // Safety: `once` has completed: no more writes.
+                (*self.value.get()).as_ref()
+            }
+        } else {
+            None
+        };
+        fmt .debug_struct("FfiLazy")
+            .field("value", &value)
+            .finish()
+    }
+}
+
+/// A mutual exclusion lock, usable as a `static`.
+///
+///   - a panic while the lock is held poisons it: subsequent
+///     [`.lock()`][`FfiMutex::lock`]s fail with [`AccessError::Poisoned`]
+///     (until [`.clear_poison()`][`FfiMutex::clear_poison`] is called);
+///
+///   - trying to lock it while the current thread already holds it (_e.g._,
+///     when a C callback re-enters the library) fails with
+///     [`AccessError::Reentrant`] rather than deadlocking.
+///
+/// The lock is fair: it is handed over to the waiting threads in the order
+/// they asked for it, which block (rather than spin) meanwhile.
+pub
+struct FfiMutex<T> {
+    /// The `thread_token()` of the current holder, or `0`.
+    owner: AtomicUsize,
+    /// Ticket lock: the ticket handed to the next `.lock()`…
+    next_ticket: AtomicUsize,
+    /// … and the one currently allowed to hold the lock.
+    now_serving: AtomicUsize,
+    poisoned: AtomicBool,
+    value: UnsafeCell<T>,
+}
+
+unsafe // Safety: `value` is only accessed while holding the lock.
+impl<T : Send> Sync
+    for FfiMutex<T>
+{}
+
+/// Non-zero identifier of the current thread, unique among the live threads.
+// `const { … }` `thread_local!` initializers require Rust 1.59.
+#[allow(clippy::missing_const_for_thread_local)]
+fn thread_token ()
+  -> usize
+{
+    ::std::thread_local! {
+        static TOKEN: u8 = 0;
+    }
+    TOKEN.with(|it| it as *const u8 as usize)
+}
+
+impl<T> FfiMutex<T> {
+    /// An unlocked mutex holding `value`.
+    pub
+    const
+    fn new (value: T)
+      -> Self
+    {
+        FfiMutex {
+            owner: AtomicUsize::new(0),
+            next_ticket: AtomicUsize::new(0),
+            now_serving: AtomicUsize::new(0),
+            poisoned: AtomicBool::new(false),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Acquires the lock, waiting for it if need be.
+    pub
+    fn lock (self: &'_ Self)
+      -> Result<FfiMutexGuard<'_, T>, AccessError>
+    {
+        let me = thread_token();
+        if self.owner.load(atomic::Ordering::Relaxed) == me {
+            return Err(AccessError::Reentrant);
+        }
+        let ticket = self.next_ticket.fetch_add(1, atomic::Ordering::Relaxed);
+        self.wait_for(ticket);
+        self.owner.store(me, atomic::Ordering::Relaxed);
+        let guard = FfiMutexGuard { mutex: self, _not_send: PhantomData };
+        if self.poisoned.load(atomic::Ordering::Relaxed) {
+            return Err(AccessError::Poisoned);
+        }
+        Ok(guard)
+    }
+
+    /// Blocks until `ticket` is being served.
+    fn wait_for (self: &'_ Self, ticket: usize)
+    {
+        let is_served = || {
+            self.now_serving.load(atomic::Ordering::SeqCst) == ticket
+        };
+        // Short critical sections are the norm: give them a chance to end
+        // before resorting to blocking.
+        for _ in 0 .. 16 {
+            if is_served() {
+                return;
+            }
+            thread::yield_now();
+        }
+        let parking = Parking::get();
+        parking.waiters.fetch_add(1, atomic::Ordering::SeqCst);
+        let mut guard =
+            parking.mutex.lock().unwrap_or_else(PoisonError::into_inner)
+        ;
+        while is_served().not() {
+            guard =
+                parking.condvar.wait(guard)
+                    .unwrap_or_else(PoisonError::into_inner)
+            ;
+        }
+        drop(guard);
+        parking.waiters.fetch_sub(1, atomic::Ordering::SeqCst);
+    }
+
+    /// Hands the lock over to the next ticket.
+    fn unlock (self: &'_ Self)
+    {
+        self.owner.store(0, atomic::Ordering::Relaxed);
+        self.now_serving.fetch_add(1, atomic::Ordering::SeqCst);
+        let parking = Parking::get();
+        if parking.waiters.load(atomic::Ordering::SeqCst) != 0 {
+            // Taking the lock ensures that no waiter is in between its check
+            // and its `.wait()`, where it would miss the notification.
+            drop(parking.mutex.lock());
+            parking.condvar.notify_all();
+        }
+    }
+
+    /// Clears the poisoning caused by a panic while the lock was held.
+    pub
+    fn clear_poison (self: &'_ Self)
+    {
+        self.poisoned.store(false, atomic::Ordering::Relaxed);
+    }
+}
+
+impl<T> fmt::Debug
+    for FfiMutex<T>
+{
+    fn fmt (self: &'_ Self, fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        fmt .debug_struct("FfiMutex")
+            .field("locked", &(self.owner.load(atomic::Ordering::Relaxed) != 0))
+            .field("poisoned", &self.poisoned.load(atomic::Ordering::Relaxed))
+            .finish()
+    }
+}
+
+/// Unlocks the [`FfiMutex`] when dropped.
+pub
+struct FfiMutexGuard<'lock, T> {
+    mutex: &'lock FfiMutex<T>,
+    /// The lock must be released by the thread that acquired it.
+    _not_send: PhantomData<*const ()>,
+}
+
+unsafe // Safety: same as `&'lock T`.
+impl<T : Sync> Sync
+    for FfiMutexGuard<'_, T>
+{}
+
+impl<T> ops::Deref
+    for FfiMutexGuard<'_, T>
+{
+    type Target = T;
+
+    #[inline]
+    fn deref (self: &'_ Self)
+      -> &'_ T
+    {
+        unsafe {
+            // Safety: we hold the lock.
+            &*self.mutex.value.get()
+        }
+    }
+}
+
+impl<T> ops::DerefMut
+    for FfiMutexGuard<'_, T>
+{
+    #[inline]
+    fn deref_mut (self: &'_ mut Self)
+      -> &'_ mut T
+    {
+        unsafe {
+            // Safety: we hold the lock.
+            &mut *self.mutex.value.get()
+        }
+    }
+}
+
+impl<T> Drop
+    for FfiMutexGuard<'_, T>
+{
+    fn drop (self: &'_ mut Self)
+    {
+        if thread::panicking() {
+            self.mutex.poisoned.store(true, atomic::Ordering::Relaxed);
+        }
+        self.mutex.unlock();
+    }
+}
+
+/// Where the threads waiting for an [`FfiMutex`] block: shared by all of them,
+/// since `Mutex::new()` and `Condvar::new()` are not `const fn`s.
+struct Parking {
+    /// How many threads are (about to be) waiting on `condvar`.
+    waiters: AtomicUsize,
+    mutex: Mutex<()>,
+    condvar: Condvar,
+}
+
+impl Parking {
+    fn get ()
+      -> &'static Parking
+    {
+        static PARKING: FfiLazy<Parking> = FfiLazy::new(|| Parking {
+            waiters: AtomicUsize::new(0),
+            mutex: Mutex::new(()),
+            condvar: Condvar::new(),
+        });
+        match PARKING.get() {
+            | Ok(it) => it,
+            | Err(_) => unreachable!("Parking initializer does not panic"),
+        }
+    }
+}
+
+impl<T : fmt::Debug> fmt::Debug
+    for FfiMutexGuard<'_, T>
+{
+    fn fmt (self: &'_ Self, fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        T::fmt(self, fmt)
+    }
+}
+
+#[doc(hidden)] /** Not part of the public API **/ pub
+fn __once__ (once: &'_ Once, init: impl FnOnce())
+  -> bool
+{
+    let mut ran = false;
+    once.call_once(|| {
+        ran = panic::catch_unwind(panic::AssertUnwindSafe(init)).is_ok();
+    });
+    ran
+}
+
+/// Exports a function running its body at most once, which C may call
+/// concurrently from several threads, and as many times as it wants.
+///
+/// ```rust,ignore
+/// ::safer_ffi::ffi_once! {
+///     /// Initializes the library.
+///     pub
+///     fn mylib_try_init ()
+///     {
+///         // ...
+///     }
+/// }
+/// ```
+///
+/// exports:
+///
+/// ```C
+/// bool mylib_try_init (void);
+/// ```
+///
+/// which returns whether this very call ran the initialization: concurrent
+/// calls wait for the one running it to complete, and then return `false`,
+/// as do the subsequent ones.
+///
+/// A panicking body is not run again either: the call running it then
+/// returns `false` as well.
+#[macro_export]
+macro_rules! ffi_once {(
+    $(#[$($attr:tt)*])*
+    $pub:vis
+    fn $fname:ident () $body:block
+) => (
+    #[$crate::ffi_export]
+    $(#[$($attr)*])*
+    ///
+    /// Safe to call concurrently, and more than once: only the first call
+    /// runs the initialization (the concurrent ones waiting for it to
+    /// complete). Returns whether this call ran it (successfully).
+    $pub
+    fn $fname ()
+      -> bool
+    {
+        static ONCE: $crate::std::sync::Once = $crate::std::sync::Once::new();
+        $crate::sync::__once__(&ONCE, || $body)
+    }
+)}
@@ -0,0 +1,100 @@
+//! Bounded-latency exported functions.
+//!
+//! See the `timeout_arg` option of [`#[ffi_export]`][`crate::ffi_export`].
+
+use_prelude!();
+use ::std::time::{Duration, Instant};
+
+ReprC! {
+    /// Status code of a function exported with a timeout.
+    #[repr(u8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub
+    enum TimeoutStatus {
+        /// The function completed within the allotted time.
+        Ok = 0,
+        /// The allotted time elapsed before the function could complete.
+        TimedOut = 1,
+    }
+}
+
+/// Return types of functions exported with a timeout, with a value to signal
+/// that the allotted time elapsed.
+pub
+trait TimedOut {
+    /// The value returned when the allotted time elapses.
+    fn timed_out ()
+      -> Self
+    ;
+}
+
+impl TimedOut
+    for TimeoutStatus
+{
+    #[inline]
+    fn timed_out ()
+      -> TimeoutStatus
+    {
+        TimeoutStatus::TimedOut
+    }
+}
+
+impl<T> TimedOut
+    for Option<T>
+{
+    #[inline]
+    fn timed_out ()
+      -> Option<T>
+    {
+        None
+    }
+}
+
+/// Cooperative cancellation token of a function exported with a timeout.
+///
+/// Once the deadline is reached, the body should stop as soon as possible,
+/// returning, for instance, [`TimedOut::timed_out()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub
+struct Deadline {
+    at: Option<Instant>,
+}
+
+impl Deadline {
+    /// A deadline `timeout_ms` milliseconds from now, with `0` meaning
+    /// "no timeout".
+    pub
+    fn after_ms (timeout_ms: u32)
+      -> Deadline
+    {
+        Deadline {
+            at: if timeout_ms == 0 {
+                None
+            } else {
+                Some(Instant::now() + Duration::from_millis(timeout_ms.into()))
+            },
+        }
+    }
+
+    /// Whether the deadline has been reached.
+    pub
+    fn expired (self: &'_ Self)
+      -> bool
+    {
+        match self.at {
+            | Some(at) => Instant::now() >= at,
+            | None => false,
+        }
+    }
+
+    /// The time left before the deadline (`None` when there is no timeout).
+    pub
+    fn remaining (self: &'_ Self)
+      -> Option<Duration>
+    {
+        self.at.map(|at| {
+            let now = Instant::now();
+            if now < at { at - now } else { Duration::from_secs(0) }
+        })
+    }
+}
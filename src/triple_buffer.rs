@@ -0,0 +1,302 @@
+//! Lock-free exchange of the latest state between a writer and a reader.
+//!
+//! Audio and video pipelines typically have a producer and a consumer running
+//! at different rates (_e.g._, a UI thread tweaking parameters, and an audio
+//! thread reading them at each callback), where the consumer only cares about
+//! the latest state, and neither side may ever block.
+//!
+//! A [`TripleBuffer`] holds three copies of the state: the one being written,
+//! the one being read, and the latest published one, which the writer and the
+//! reader swap theirs with, atomically. Writing thus never waits for the
+//! reader, reading never waits for the writer, and neither allocates.
+//!
+//! There can only be one writer and one reader at a time: a
+//! [`write`][`TripleBuffer::write`] (resp. [`read`][`TripleBuffer::read`])
+//! concurrent with another one fails (rather than blocking).
+//!
+//! The `new / write / read / free` functions are generated, for a given type
+//! of state, with the [`ffi_export_triple_buffer!`] macro.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ::safer_ffi::{prelude::*, triple_buffer::TripleBuffer};
+//!
+//! #[derive_ReprC]
+//! #[repr(C)]
+//! #[derive(Debug, Clone, Copy, PartialEq)]
+//! pub
+//! struct Params {
+//!     gain: f32,
+//!     pan: f32,
+//! }
+//!
+//! ::safer_ffi::ffi_export_triple_buffer! {
+//!     Params => {
+//!         new: params_new,
+//!         write: params_write,
+//!         read: params_read,
+//!         free: params_free,
+//!     }
+//! }
+//!
+//! # fn main () {
+//! let params = TripleBuffer::new(Params { gain: 1.0, pan: 0.0 });
+//! params.write(Params { gain: 0.5, pan: 0.0 }).unwrap();
+//! params.write(Params { gain: 0.25, pan: 0.0 }).unwrap();
+//! // Only the latest state is seen.
+//! assert_eq!(params.read().unwrap().gain, 0.25);
+//! assert!(!params.has_update());
+//! # }
+//! ```
+//!
+//! which, C-wise, is used as:
+//!
+//! ```C
+//! /* UI thread */
+//! Params_t params = { .gain = 0.5, .pan = 0.0 };
+//! params_write(shared, params);
+//!
+//! /* Audio thread */
+//! Params_t current;
+//! params_read(shared, &current);
+//! ```
+
+use_prelude!();
+use ::core::{
+    cell::UnsafeCell,
+    sync::atomic::{self, AtomicBool, AtomicU8},
+};
+
+/// Set in `latest` when its slot has been written since the
+/// last read.
+const DIRTY: u8 = 0b100;
+
+/// Masks the slot index out of `latest`.
+const INDEX: u8 = 0b011;
+
+ReprC! {
+    #[ReprC::opaque(
+        format!("TripleBuffer_{}", <T::CLayout as CType>::c_short_name())
+    )]
+    /// Lock-free single-writer single-reader exchange of the latest `T`.
+    ///
+    /// It is an opaque type from the point of view of C.
+    pub
+    struct TripleBuffer[T]
+    where {
+        T : ReprC,
+    }
+    {
+        slots: [UnsafeCell<T>; 3],
+        /// The index of the latest published slot, and whether it is
+        /// [`DIRTY`].
+        latest: AtomicU8,
+        /// The index of the slot owned by the writer.
+        write_index: AtomicU8,
+        /// The index of the slot owned by the reader.
+        read_index: AtomicU8,
+        writing: AtomicBool,
+        reading: AtomicBool,
+    }
+}
+
+unsafe // Safety: each slot is only accessed by its owner (writer or reader).
+impl<T : ReprC + Send> Sync
+    for TripleBuffer<T>
+{}
+
+impl<T : ReprC + Clone> TripleBuffer<T> {
+    /// Creates a triple buffer whose (already published) state is `initial`.
+    pub
+    fn new (initial: T)
+      -> TripleBuffer<T>
+    {
+        TripleBuffer {
+            slots: [
+                UnsafeCell::new(initial.clone()),
+                UnsafeCell::new(initial.clone()),
+                UnsafeCell::new(initial),
+            ],
+            latest: AtomicU8::new(0),
+            write_index: AtomicU8::new(1),
+            read_index: AtomicU8::new(2),
+            writing: AtomicBool::new(false),
+            reading: AtomicBool::new(false),
+        }
+    }
+
+    /// Publishes `value` as the latest state.
+    ///
+    /// Gives `value` back if another write is in progress.
+    pub
+    fn write (self: &'_ Self, value: T)
+      -> Result<(), T>
+    {
+        if self.writing.swap(true, atomic::Ordering::Acquire) {
+            return Err(value);
+        }
+        let index = self.write_index.load(atomic::Ordering::Relaxed);
+        unsafe {
+            // Safety: the writer has exclusive access to its slot.
+            *self.slots[usize::from(index)].get() = value;
+        }
+        let previous = self.latest.swap(index | DIRTY, atomic::Ordering::AcqRel);
+        self.write_index.store(previous & INDEX, atomic::Ordering::Relaxed);
+        self.writing.store(false, atomic::Ordering::Release);
+        Ok(())
+    }
+
+    /// The latest published state, or `None` if another read is in
+    /// progress.
+    pub
+    fn read (self: &'_ Self)
+      -> Option<T>
+    {
+        if self.reading.swap(true, atomic::Ordering::Acquire) {
+            return None;
+        }
+        let mut index = self.read_index.load(atomic::Ordering::Relaxed);
+        if self.has_update() {
+            let previous = self.latest.swap(index, atomic::Ordering::AcqRel);
+            index = previous & INDEX;
+            self.read_index.store(index, atomic::Ordering::Relaxed);
+        }
+        let value = unsafe {
+            // Safety: the reader has exclusive access to its slot.
+            (*self.slots[usize::from(index)].get()).clone()
+        };
+        self.reading.store(false, atomic::Ordering::Release);
+        Some(value)
+    }
+}
+
+impl<T : ReprC> TripleBuffer<T> {
+    /// Whether a state has been published since the last
+    /// [`read`][`TripleBuffer::read`].
+    #[inline]
+    pub
+    fn has_update (self: &'_ Self)
+      -> bool
+    {
+        self.latest.load(atomic::Ordering::Relaxed) & DIRTY != 0
+    }
+}
+
+impl<T : ReprC> fmt::Debug
+    for TripleBuffer<T>
+{
+    fn fmt (self: &'_ Self, fmt: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        fmt .debug_struct("TripleBuffer")
+            .field("has_update", &self.has_update())
+            .finish()
+    }
+}
+
+/// Exports the `new`, `write`, `read` and `free` functions of a
+/// [`TripleBuffer`][`crate::triple_buffer::TripleBuffer`]`<T>`.
+///
+/// ```rust,ignore
+/// ::safer_ffi::ffi_export_triple_buffer! {
+///     T => {
+///         new: my_state_new,
+///         write: my_state_write,
+///         read: my_state_read,
+///         free: my_state_free,
+///     }
+/// }
+/// ```
+///
+/// exports:
+///
+/// ```C
+/// TripleBuffer_T_t * my_state_new (T_t initial);
+///
+/// bool my_state_write (TripleBuffer_T_t const * buffer, T_t value);
+///
+/// bool my_state_read (TripleBuffer_T_t const * buffer, T_t * out);
+///
+/// void my_state_free (TripleBuffer_T_t * buffer);
+/// ```
+///
+///   - `write` publishes `value`, and returns `false` (doing nothing) if
+///     another write is in progress;
+///
+///   - `read` writes the latest state to `out`, and returns `false` (leaving
+///     `out` untouched) if another read is in progress. It is an `unsafe fn`
+///     on the Rust side: `out` must be `NULL` or valid for a write;
+///
+///   - `free` destroys the buffer (`NULL` is allowed).
+#[macro_export]
+macro_rules! ffi_export_triple_buffer {(
+    $T:ty => {
+        new: $new:ident,
+        write: $write:ident,
+        read: $read:ident,
+        free: $free:ident $(,)?
+    } $(;)?
+) => (
+    #[$crate::ffi_export]
+    /// Creates a triple buffer, whose state is `initial`.
+    pub
+    fn $new (initial: $T)
+      -> $crate::boxed::Box<$crate::triple_buffer::TripleBuffer<$T>>
+    {
+        $crate::boxed::Box::new($crate::triple_buffer::TripleBuffer::new(initial))
+    }
+
+    #[$crate::ffi_export]
+    /// Publishes `value` as the latest state.
+    ///
+    /// Returns `false`, doing nothing, if another write is in progress.
+    pub
+    fn $write (
+        buffer: &'_ $crate::triple_buffer::TripleBuffer<$T>,
+        value: $T,
+    ) -> bool
+    {
+        buffer.write(value).is_ok()
+    }
+
+    #[$crate::ffi_export]
+    /// Writes the latest state to `out`.
+    ///
+    /// Returns `false`, leaving `out` untouched, if another read is in
+    /// progress (or if `out` is `NULL`).
+    ///
+    /// # Safety
+    ///
+    /// `out` must be `NULL` or valid for (and properly aligned for) a write
+    /// of the state.
+    pub
+    unsafe
+    fn $read (
+        buffer: &'_ $crate::triple_buffer::TripleBuffer<$T>,
+        out: *mut $T,
+    ) -> bool
+    {
+        if out.is_null() {
+            return false;
+        }
+        match buffer.read() {
+            | Some(value) => {
+                // Safety: non-null out-pointer, valid as per the contract.
+                out.write(value);
+                true
+            },
+            | None => false,
+        }
+    }
+
+    #[$crate::ffi_export]
+    /// Destroys the triple buffer.
+    pub
+    fn $free (
+        buffer: Option<$crate::boxed::Box<$crate::triple_buffer::TripleBuffer<$T>>>,
+    )
+    {
+        $crate::core::mem::drop(buffer)
+    }
+)}
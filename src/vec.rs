@@ -183,6 +183,175 @@ impl<T> Vec<T> {
     }
 }
 
+impl<T> Vec<T> {
+    /// Removes the elements within the given `range`, and returns them.
+    ///
+    /// Contrary to [`rust::Vec::drain`], the elements are removed eagerly
+    /// (the returned iterator owns them).
+    pub
+    fn drain (
+        self: &'_ mut Self,
+        range: impl ::core::ops::RangeBounds<usize>,
+    ) -> ::alloc::vec::IntoIter<T>
+    {
+        self.with_rust_mut(|v| v.drain(range).collect::<rust::Vec<T>>())
+            .into_iter()
+    }
+
+    /// Clones and appends all the elements of the `slice` (which can come from
+    /// C).
+    pub
+    fn extend_from_slice (self: &'_ mut Self, slice: slice_ref<'_, T>)
+    where
+        T : Clone,
+    {
+        self.with_rust_mut(|v| v.extend_from_slice(slice.as_slice()))
+    }
+}
+
+impl<T> IntoIterator
+    for Vec<T>
+{
+    type Item = T;
+    type IntoIter = ::alloc::vec::IntoIter<T>;
+
+    #[inline]
+    fn into_iter (self: Vec<T>)
+      -> ::alloc::vec::IntoIter<T>
+    {
+        <Self as Into<rust::Vec<T>>>::into(self).into_iter()
+    }
+}
+
+impl<'vec, T> IntoIterator
+    for &'vec Vec<T>
+{
+    type Item = &'vec T;
+    type IntoIter = slice::Iter<'vec, T>;
+
+    #[inline]
+    fn into_iter (self: &'vec Vec<T>)
+      -> slice::Iter<'vec, T>
+    {
+        self.iter()
+    }
+}
+
+impl<'vec, T> IntoIterator
+    for &'vec mut Vec<T>
+{
+    type Item = &'vec mut T;
+    type IntoIter = slice::IterMut<'vec, T>;
+
+    #[inline]
+    fn into_iter (self: &'vec mut Vec<T>)
+      -> slice::IterMut<'vec, T>
+    {
+        self.iter_mut()
+    }
+}
+
+/// Extra traits, for parity with [`rust::Vec`].
+const _: () = {
+    use ::core::{
+        borrow::{Borrow, BorrowMut},
+        hash::{Hash, Hasher},
+    };
+
+    impl<T> AsRef<[T]>
+        for Vec<T>
+    {
+        #[inline]
+        fn as_ref (self: &'_ Vec<T>)
+          -> &'_ [T]
+        {
+            self
+        }
+    }
+    impl<T> AsMut<[T]>
+        for Vec<T>
+    {
+        #[inline]
+        fn as_mut (self: &'_ mut Vec<T>)
+          -> &'_ mut [T]
+        {
+            self
+        }
+    }
+    impl<T> Borrow<[T]>
+        for Vec<T>
+    {
+        #[inline]
+        fn borrow (self: &'_ Vec<T>)
+          -> &'_ [T]
+        {
+            self
+        }
+    }
+    impl<T> BorrowMut<[T]>
+        for Vec<T>
+    {
+        #[inline]
+        fn borrow_mut (self: &'_ mut Vec<T>)
+          -> &'_ mut [T]
+        {
+            self
+        }
+    }
+    impl<T : Eq> Eq
+        for Vec<T>
+    {}
+    impl<T : PartialEq> PartialEq
+        for Vec<T>
+    {
+        #[inline]
+        fn eq (self: &'_ Vec<T>, other: &'_ Vec<T>)
+          -> bool
+        {
+            self[..] == other[..]
+        }
+    }
+    impl<T : PartialEq> PartialEq<[T]>
+        for Vec<T>
+    {
+        #[inline]
+        fn eq (self: &'_ Vec<T>, other: &'_ [T])
+          -> bool
+        {
+            self[..] == *other
+        }
+    }
+    impl<T : Hash> Hash
+        for Vec<T>
+    {
+        #[inline]
+        fn hash<H : Hasher> (self: &'_ Vec<T>, hasher: &'_ mut H)
+        {
+            self[..].hash(hasher)
+        }
+    }
+    impl<T : Clone> Clone
+        for Vec<T>
+    {
+        #[inline]
+        fn clone (self: &'_ Vec<T>)
+          -> Vec<T>
+        {
+            self[..].to_vec().into()
+        }
+    }
+    impl<T> Default
+        for Vec<T>
+    {
+        #[inline]
+        fn default ()
+          -> Vec<T>
+        {
+            Vec::EMPTY
+        }
+    }
+};
+
 impl<T : fmt::Debug + ReprC> fmt::Debug
     for Vec<T>
 {
@@ -197,3 +366,50 @@ impl<T : fmt::Debug + ReprC> fmt::Debug
 macro_rules! c_vec { [$($input:tt)*] => (
     $crate::Vec::from($crate::std::vec![ $($input)* ])
 )}
+
+/// Exports `push` and `get` functions for a
+/// [`repr_c::Vec`][`crate::Vec`]`<Item>`, so that C can populate and inspect
+/// it.
+///
+/// ```rust,ignore
+/// ::safer_ffi::ffi_export_vec_helpers! {
+///     Item => {
+///         push: items_push,
+///         get: items_get,
+///     }
+/// }
+/// ```
+///
+/// exports:
+///
+/// ```C
+/// void items_push (Vec_Item_t * vec, Item_t value);
+///
+/// Item_t const * items_get (Vec_Item_t const * vec, size_t index);
+/// ```
+///
+/// where `get` returns `NULL` when the `index` is out of bounds.
+#[macro_export]
+macro_rules! ffi_export_vec_helpers {(
+    $Item:ty => {
+        push: $push:ident,
+        get: $get:ident $(,)?
+    } $(;)?
+) => (
+    #[$crate::ffi_export]
+    /// Appends `value` to the back of the `vec`.
+    pub
+    fn $push (vec: &'_ mut $crate::Vec<$Item>, value: $Item)
+    {
+        vec.with_rust_mut(|v| v.push(value))
+    }
+
+    #[$crate::ffi_export]
+    /// Returns a pointer to the element at `index`, or `NULL` if out of bounds.
+    pub
+    fn $get<'vec> (vec: &'vec $crate::Vec<$Item>, index: usize)
+      -> $crate::core::option::Option<&'vec $Item>
+    {
+        vec.get(index)
+    }
+)}
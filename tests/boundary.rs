@@ -0,0 +1,118 @@
+#![cfg(feature = "proc_macros")]
+
+use ::std::{
+    mem,
+    ops::Not as _,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use ::safer_ffi::{
+    boundary::{self, BoundaryPolicy},
+    bounded::Bounded,
+    layout::ReprC,
+    prelude::*,
+};
+
+::safer_ffi::bounds! {
+    pub Percent: u8 = [1, 100];
+}
+
+static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+#[ffi_export]
+fn double_percent (percent: Bounded<Percent>)
+  -> u32
+{
+    CALLS.fetch_add(1, Ordering::SeqCst);
+    u32::from(percent.get()) * 2
+}
+
+#[ffi_export]
+fn boxed_percent (percent: Bounded<Percent>)
+  -> Option<repr_c::Box<u8>>
+{
+    CALLS.fetch_add(1, Ordering::SeqCst);
+    Some(Box::new(percent.get()).into())
+}
+
+#[ffi_export]
+fn negate (b: bool)
+  -> bool
+{
+    CALLS.fetch_add(1, Ordering::SeqCst);
+    b.not()
+}
+
+#[ffi_export]
+fn touch (b: bool)
+{
+    CALLS.fetch_add(1, Ordering::SeqCst);
+    let _ = b;
+}
+
+/// The raw (C) `Bounded<Percent>`, which may be out of range.
+fn raw_percent (value: u8)
+  -> <Bounded<Percent> as ReprC>::CLayout
+{
+    unsafe { mem::transmute(value) }
+}
+
+/// The raw (C) `bool`, which may be neither `0` nor `1`.
+fn raw_bool (value: u8)
+  -> <bool as ReprC>::CLayout
+{
+    unsafe { mem::transmute(value) }
+}
+
+fn calls ()
+  -> usize
+{
+    CALLS.load(Ordering::SeqCst)
+}
+
+/// A single test, since the policy is process-wide.
+#[test]
+fn policies ()
+{
+    let double_percent = ::safer_ffi::c_fn!(double_percent);
+    let boxed_percent = ::safer_ffi::c_fn!(boxed_percent);
+    let negate = ::safer_ffi::c_fn!(negate);
+    let touch = ::safer_ffi::c_fn!(touch);
+
+    for &policy in &[BoundaryPolicy::ReturnError, BoundaryPolicy::LogAndClamp] {
+        boundary::set_policy(policy);
+        assert_eq!(boundary::policy(), policy);
+
+        // Valid arguments are passed through.
+        let before = calls();
+        assert_eq!(double_percent(raw_percent(21)), 42);
+        assert_eq!(boxed_percent(raw_percent(1)).map(|it| *it), Some(1));
+        assert!(negate(raw_bool(0)));
+        touch(raw_bool(1));
+        assert_eq!(calls(), before + 4);
+
+        // Invalid arguments which cannot be clamped: the body is not run,
+        // and the all-zeros value is returned.
+        let before = calls();
+        assert!(negate(raw_bool(2)).not());
+        touch(raw_bool(0xff));
+        assert_eq!(calls(), before);
+    }
+
+    boundary::set_policy(BoundaryPolicy::ReturnError);
+    let before = calls();
+    assert_eq!(double_percent(raw_percent(0)), 0);
+    assert_eq!(double_percent(raw_percent(101)), 0);
+    assert!(boxed_percent(raw_percent(0xff)).is_none());
+    assert_eq!(calls(), before);
+
+    // Out-of-range `Bounded` integers are clamped to the nearest bound.
+    boundary::set_policy(BoundaryPolicy::LogAndClamp);
+    let before = calls();
+    assert_eq!(double_percent(raw_percent(0)), 2);
+    assert_eq!(double_percent(raw_percent(101)), 200);
+    assert_eq!(boxed_percent(raw_percent(0xff)).map(|it| *it), Some(100));
+    assert_eq!(calls(), before + 3);
+
+    boundary::set_policy(BoundaryPolicy::Abort);
+    assert_eq!(boundary::policy(), BoundaryPolicy::Abort);
+}
@@ -0,0 +1,179 @@
+#![cfg(feature = "proc_macros")]
+
+#![allow(unused_imports)]
+
+use ::std::{
+    mem::MaybeUninit,
+    ptr,
+};
+use ::safer_ffi::prelude::*;
+
+#[derive_ReprC]
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub
+enum Mode {
+    Fast = 42,
+    Accurate,
+}
+
+#[derive_ReprC(default_init)]
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+pub
+struct Settings {
+    pub mode: Mode,
+    pub ratio: f32,
+    #[c_default = "NULL"]
+    pub name: Option<char_p::Ref<'static>>,
+}
+
+impl Default for Settings {
+    fn default () -> Self
+    {
+        Settings { mode: Mode::Accurate, ratio: 0.5, name: None }
+    }
+}
+
+#[test]
+fn default_init ()
+{
+    let mut out = MaybeUninit::<Settings>::uninit();
+    let out = unsafe {
+        settings_init(out.as_mut_ptr());
+        out.assume_init()
+    };
+    assert_eq!(out, Settings::default());
+    // `NULL` is a no-op.
+    unsafe { settings_init(ptr::null_mut()) };
+}
+
+#[derive_ReprC(struct_size)]
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+pub
+struct DecoderConfig {
+    pub verbose: bool,
+    pub threads: u32,
+}
+
+impl Default for DecoderConfig {
+    fn default () -> Self
+    {
+        DecoderConfig {
+            struct_size: Self::STRUCT_SIZE,
+            verbose: true,
+            threads: 4,
+        }
+    }
+}
+
+/// An older version of `DecoderConfig`, without `threads`.
+#[repr(C)]
+struct DecoderConfigV1 {
+    struct_size: usize,
+    verbose: bool,
+}
+
+#[test]
+fn struct_size_init ()
+{
+    // Older caller: only its own fields are written.
+    let mut v1 = [0xff_u8; 24];
+    let v1_size = ::std::mem::size_of::<DecoderConfigV1>();
+    unsafe {
+        decoder_config_init(v1.as_mut_ptr() as *mut DecoderConfig, v1_size);
+        let v1 = &*(v1.as_ptr() as *const DecoderConfigV1);
+        assert_eq!(v1.struct_size, v1_size);
+        assert!(v1.verbose);
+    }
+    assert!(v1[v1_size ..].iter().all(|&b| b == 0xff));
+
+    // Newer caller: the extra trailing bytes are zeroed.
+    let size = DecoderConfig::STRUCT_SIZE;
+    let mut v3 = vec![0xff_u8; size + 8];
+    unsafe {
+        decoder_config_init(v3.as_mut_ptr() as *mut DecoderConfig, size + 8);
+        let v3 = (v3.as_ptr() as *const DecoderConfig).read_unaligned();
+        assert_eq!(v3.struct_size, size + 8);
+        assert!(v3.verbose);
+        assert_eq!(v3.threads, 4);
+    }
+    assert!(v3[size ..].iter().all(|&b| b == 0));
+
+    unsafe { decoder_config_init(ptr::null_mut(), size) };
+}
+
+#[test]
+fn struct_size_from_versioned_ptr ()
+{
+    // A caller only knowing about `struct_size` and `verbose` (up to the
+    // padding before `threads`, which is thus not read).
+    let usize_size = ::std::mem::size_of::<usize>();
+    let mut bytes = [0xff_u8; 32];
+    bytes[.. usize_size].copy_from_slice(&(usize_size + 1).to_ne_bytes());
+    bytes[usize_size] = false as u8;
+    let config = unsafe {
+        DecoderConfig::from_versioned_ptr(bytes.as_ptr() as *const DecoderConfig)
+    };
+    assert_eq!(config, Some(DecoderConfig {
+        struct_size: DecoderConfig::STRUCT_SIZE,
+        verbose: false,
+        threads: 4,
+    }));
+    assert_eq!(unsafe { DecoderConfig::from_versioned_ptr(ptr::null()) }, None);
+}
+
+#[derive_ReprC(struct_size, default_init)]
+#[repr(C)]
+#[derive(Default)]
+pub
+struct Limits {
+    pub max: u32,
+}
+
+#[cfg(feature = "headers")]
+fn headers ()
+  -> String
+{
+    let mut out = Vec::new();
+    ::safer_ffi::headers::builder()
+        .to_writer(&mut out)
+        .generate()
+        .unwrap()
+    ;
+    String::from_utf8(out).unwrap()
+}
+
+#[cfg(feature = "headers")]
+#[test]
+fn default_init_headers ()
+{
+    let out = headers();
+    assert!(out.contains("\
+        #define SETTINGS_DEFAULT { \
+            /* .mode = */ MODE_ACCURATE, \
+            /* .ratio = */ 0.5f, \
+            /* .name = */ NULL \
+        }\n\
+    "), "{}", out);
+    assert!(out.contains("void settings_init (\n    Settings_t * out);"), "{}", out);
+}
+
+#[cfg(feature = "headers")]
+#[test]
+fn struct_size_default_init_headers ()
+{
+    let out = headers();
+    assert!(out.contains("\
+        #define LIMITS_DEFAULT { \
+            /* .struct_size = */ sizeof(Limits_t), \
+            /* .max = */ 0u \
+        }\n\
+    "), "{}", out);
+    assert!(out.contains("\
+        void limits_init (\n    \
+            Limits_t * out,\n    \
+            size_t struct_size);\
+    "), "{}", out);
+}
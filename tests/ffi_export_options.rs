@@ -0,0 +1,136 @@
+#![cfg(feature = "proc_macros")]
+
+#![allow(unused_imports)]
+
+use ::std::{
+    ops::Not as _,
+    os::raw::c_void,
+    ptr,
+};
+use ::safer_ffi::{
+    prelude::*,
+    progress::ProgressCallback,
+    timeout::{TimedOut, TimeoutStatus},
+};
+
+unsafe extern "C"
+fn record (ctx: *mut c_void, percent: u8, _stage: char_p::Raw)
+  -> bool
+{
+    let reports = &mut *(ctx as *mut Vec<u8>);
+    reports.push(percent);
+    percent < 50
+}
+
+extern "C"
+fn double (x: u8)
+  -> u8
+{
+    x.wrapping_mul(2)
+}
+
+#[ffi_export(progress)]
+/// `fn` pointers in the signature are left untouched.
+fn apply_all (
+    xs: c_slice::Ref<'_, u8>,
+    f: Option<extern "C" fn(u8) -> u8>,
+) -> Option<extern "C" fn(u8) -> u8>
+{
+    let f = f?;
+    let len = xs.len().max(1);
+    for (i, &x) in xs.as_slice().iter().enumerate() {
+        let percent = ((i + 1) * 100 / len) as u8;
+        if progress.report(percent, c!("applying")).not() {
+            return None;
+        }
+        f(x);
+    }
+    Some(f)
+}
+
+#[test]
+fn progress_with_fn_ptrs ()
+{
+    let mut reports = Vec::<u8>::new();
+    let progress = ProgressCallback {
+        ctx: &mut reports as *mut Vec<u8> as *mut c_void,
+        report: Some(record),
+    };
+    let xs = [1, 2, 3, 4];
+    // Cancelled once `record` returns `false` (at `50`%).
+    assert!(apply_all((&xs[..]).into(), Some(double), progress).is_none());
+    assert_eq!(reports, [25, 50]);
+
+    let no_progress = ProgressCallback {
+        ctx: ptr::null_mut(),
+        report: None,
+    };
+    let f = apply_all((&xs[..]).into(), Some(double), no_progress).unwrap();
+    assert_eq!(f(21), 42);
+    assert!(apply_all((&xs[..]).into(), None, no_progress).is_none());
+}
+
+#[ffi_export(timeout_arg)]
+/// Borrowed parameters are fine: the body runs on the caller's thread.
+fn count_spaces (s: char_p::Ref<'_>, spins: c_slice::Ref<'_, u32>)
+  -> Option<repr_c::Box<usize>>
+{
+    for &spin in spins.as_slice() {
+        if deadline.expired() {
+            return TimedOut::timed_out();
+        }
+        ::std::thread::sleep(::std::time::Duration::from_millis(spin.into()));
+    }
+    Some(Box::new(s.to_str().matches(' ').count()).into())
+}
+
+#[ffi_export(timeout_arg)]
+fn wait_ms (ms: u64)
+  -> TimeoutStatus
+{
+    let start = ::std::time::Instant::now();
+    while (start.elapsed().as_millis() as u64) < ms {
+        if deadline.expired() {
+            return TimeoutStatus::TimedOut;
+        }
+        ::std::thread::yield_now();
+    }
+    TimeoutStatus::Ok
+}
+
+#[test]
+fn timeout ()
+{
+    let s = c!("a b c");
+    let spins = [0, 0];
+    assert_eq!(
+        count_spaces(s, (&spins[..]).into(), 1_000).map(|it| *it),
+        Some(2),
+    );
+    let spins = [20, 20];
+    assert!(count_spaces(s, (&spins[..]).into(), 1).is_none());
+    // `0` means no timeout.
+    assert!(count_spaces(s, (&spins[..]).into(), 0).is_some());
+
+    assert_eq!(wait_ms(0, 0), TimeoutStatus::Ok);
+    assert_eq!(wait_ms(1, 1_000), TimeoutStatus::Ok);
+    assert_eq!(wait_ms(1_000, 5), TimeoutStatus::TimedOut);
+}
+
+#[cfg(feature = "headers")]
+#[test]
+fn progress_headers ()
+{
+    let mut out = Vec::new();
+    ::safer_ffi::headers::builder()
+        .to_writer(&mut out)
+        .generate()
+        .unwrap()
+    ;
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.contains("\
+        slice_ref_uint8_t xs,\n    \
+        uint8_t (*f)(uint8_t),\n    \
+        ProgressCallback_t progress);\
+    "), "{}", out);
+}
@@ -0,0 +1,53 @@
+#![cfg(feature = "proc_macros")]
+
+use ::std::{
+    mem::MaybeUninit,
+    ops::Not as _,
+    ptr,
+};
+use ::safer_ffi::{
+    prelude::*,
+    generator::Generator,
+};
+
+#[ffi_export]
+fn countdown (from: u32)
+  -> repr_c::Box<Generator<u32>>
+{
+    repr_c::Box::new(Generator::new(move |yielder| {
+        for n in (0 .. from).rev() {
+            if yielder.yield_(n).is_err() {
+                return;
+            }
+        }
+    }))
+}
+
+::safer_ffi::ffi_export_generator! {
+    u32 => {
+        next: countdown_next,
+        free: countdown_free,
+    }
+}
+
+#[test]
+fn next ()
+{
+    let mut it = countdown(3);
+    let mut out = MaybeUninit::<u32>::uninit();
+    let mut yielded = vec![];
+    while unsafe { countdown_next(&mut it, out.as_mut_ptr()) } {
+        yielded.push(unsafe { out.assume_init() });
+    }
+    assert_eq!(yielded, [2, 1, 0]);
+    // Exhausted.
+    assert!(unsafe { countdown_next(&mut it, out.as_mut_ptr()) }.not());
+    countdown_free(Some(it));
+
+    // A `NULL` out-pointer does not advance the generator.
+    let mut it = countdown(1);
+    assert!(unsafe { countdown_next(&mut it, ptr::null_mut()) }.not());
+    assert!(unsafe { countdown_next(&mut it, out.as_mut_ptr()) });
+    assert_eq!(unsafe { out.assume_init() }, 0);
+    countdown_free(Some(it));
+}
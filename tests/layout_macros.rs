@@ -360,3 +360,77 @@ fn generate_headers ()
         }
     }
 })}
+
+#[derive_ReprC]
+#[repr(C)]
+pub
+struct Unsorted {
+    zeta: u8,
+    alpha: u8,
+    mu: u8,
+}
+
+#[derive_ReprC]
+#[repr(i8)]
+pub
+enum Shuffled {
+    Zeta = 5,
+    Alpha = -3,
+    Mu, // = -2
+    Beta = 1,
+}
+
+#[ffi_export]
+fn take_unsorted (_u: Unsorted, _s: Shuffled)
+{}
+
+#[cfg(feature = "headers")]
+#[test]
+fn declaration_order ()
+  -> io::Result<()>
+{Ok({
+    use ::safer_ffi::headers::VariantOrder;
+
+    let generate = |variant_order| -> io::Result<String> {
+        let mut out = Vec::new();
+        ::safer_ffi::headers::builder()
+            .with_filter(&|name| name == "take_unsorted")
+            .with_variant_order(variant_order)
+            .to_writer(&mut out)
+            .generate()?
+        ;
+        Ok(String::from_utf8(out).unwrap())
+    };
+    let assert_in_order = |header: &str, items: &[&str]| {
+        let positions: Vec<usize> =
+            items
+                .iter()
+                .map(|item| header.find(item).unwrap_or_else(|| {
+                    panic!("`{}` is missing from:\n{}", item, header)
+                }))
+                .collect()
+        ;
+        assert!(
+            positions.windows(2).all(|it| it[0] < it[1]),
+            "{:?} are not in order in:\n{}", items, header,
+        );
+    };
+
+    let header = generate(VariantOrder::Declaration)?;
+    assert_in_order(&header, &["zeta;", "alpha;", "mu;"]);
+    assert_in_order(&header, &[
+        "SHUFFLED_ZETA = 5,",
+        "SHUFFLED_ALPHA = -3,",
+        "SHUFFLED_MU,",
+        "SHUFFLED_BETA = 1,",
+    ]);
+
+    let header = generate(VariantOrder::Value)?;
+    assert_in_order(&header, &["zeta;", "alpha;", "mu;"]);
+    assert_in_order(&header, &[
+        "SHUFFLED_ALPHA = -3,",
+        "SHUFFLED_MU = -2,",
+        "SHUFFLED_BETA = 1,",
+        "SHUFFLED_ZETA = 5,",
+    ]);
+})}
@@ -0,0 +1,115 @@
+#![cfg(feature = "proc_macros")]
+
+use ::std::{
+    panic,
+    sync::{Arc, atomic::{AtomicUsize, Ordering}},
+    thread,
+    time::Duration,
+};
+use ::safer_ffi::sync::{AccessError, FfiLazy, FfiMutex};
+
+#[test]
+fn mutex_poisoning ()
+{
+    static M: FfiMutex<u32> = FfiMutex::new(0);
+    let _ = panic::catch_unwind(|| {
+        let mut guard = M.lock().unwrap();
+        *guard += 1;
+        panic!("while holding the lock");
+    });
+    assert_eq!(M.lock().err(), Some(AccessError::Poisoned));
+    // Failing with `Poisoned` does not keep the lock held.
+    assert_eq!(M.lock().err(), Some(AccessError::Poisoned));
+    M.clear_poison();
+    assert_eq!(*M.lock().unwrap(), 1);
+}
+
+#[test]
+fn mutex_reentrancy ()
+{
+    static M: FfiMutex<()> = FfiMutex::new(());
+    let guard = M.lock().unwrap();
+    assert_eq!(M.lock().err(), Some(AccessError::Reentrant));
+    // Other threads just wait for it.
+    let waiter = thread::spawn(|| M.lock().is_ok());
+    thread::sleep(Duration::from_millis(50));
+    drop(guard);
+    assert!(waiter.join().unwrap());
+    assert!(M.lock().is_ok());
+}
+
+#[test]
+fn mutex_contention ()
+{
+    static M: FfiMutex<u64> = FfiMutex::new(0);
+    let threads =
+        (0 .. 8)
+            .map(|_| thread::spawn(|| {
+                for _ in 0 .. 1_000 {
+                    *M.lock().unwrap() += 1;
+                }
+            }))
+            .collect::<Vec<_>>()
+    ;
+    for thread in threads {
+        thread.join().unwrap();
+    }
+    assert_eq!(*M.lock().unwrap(), 8_000);
+}
+
+#[test]
+fn lazy_init_panicked ()
+{
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+    static L: FfiLazy<u32> = FfiLazy::new(|| {
+        CALLS.fetch_add(1, Ordering::SeqCst);
+        panic!("initializer");
+    });
+    assert_eq!(L.get().err(), Some(AccessError::InitPanicked));
+    assert_eq!(L.get().err(), Some(AccessError::InitPanicked));
+    // The initializer is not run again.
+    assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn lazy_reentrancy ()
+{
+    static INNER: FfiMutex<Option<AccessError>> = FfiMutex::new(None);
+    static L: FfiLazy<u32> = FfiLazy::new(|| {
+        // _e.g._, a C callback calling back into the library.
+        *INNER.lock().unwrap() = L.get().err();
+        42
+    });
+    assert_eq!(L.get(), Ok(&42));
+    assert_eq!(*INNER.lock().unwrap(), Some(AccessError::Reentrant));
+    assert_eq!(L.get(), Ok(&42));
+}
+
+#[test]
+fn lazy_concurrent_init ()
+{
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+    static L: FfiLazy<u32> = FfiLazy::new(|| {
+        CALLS.fetch_add(1, Ordering::SeqCst);
+        thread::sleep(Duration::from_millis(50));
+        42
+    });
+    let barrier = Arc::new(::std::sync::Barrier::new(4));
+    let threads =
+        (0 .. 4)
+            .map(|_| {
+                let barrier = Arc::clone(&barrier);
+                thread::spawn(move || {
+                    barrier.wait();
+                    // Concurrent accesses wait for the initializer rather
+                    // than being deemed re-entrant.
+                    L.get()
+                })
+            })
+            .collect::<Vec<_>>()
+    ;
+    for thread in threads {
+        assert_eq!(thread.join().unwrap(), Ok(&42));
+    }
+    assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+}